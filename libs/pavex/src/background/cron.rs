@@ -0,0 +1,284 @@
+use std::fmt;
+use std::str::FromStr;
+
+use jiff::Timestamp;
+use jiff::civil::DateTime;
+use jiff::tz::TimeZone;
+
+/// A schedule expressed using the standard five-field cron syntax,
+/// interpreted in UTC.
+///
+/// The fields are, in order: minute (0-59), hour (0-23), day of the month (1-31),
+/// month (1-12) and day of the week (0-7, where both 0 and 7 stand for Sunday).
+/// Each field can be:
+///
+/// - `*`, to match any value;
+/// - a value, e.g. `5`;
+/// - a range, e.g. `1-5`;
+/// - a step, e.g. `*/15` or `10-50/20`;
+/// - a comma-separated list of the above, e.g. `0,30` or `1-5,10`.
+///
+/// If both the day of the month and the day of the week are restricted (i.e. not `*`),
+/// the schedule matches days that satisfy **either** of them, following the convention of
+/// the classic cron implementation.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::background::CronSchedule;
+///
+/// // Every day at 03:30 UTC.
+/// let schedule: CronSchedule = "30 3 * * *".parse().unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CronSchedule {
+    source: String,
+    minutes: Field,
+    hours: Field,
+    days_of_month: Field,
+    months: Field,
+    days_of_week: Field,
+}
+
+/// The error returned by [`CronSchedule::parse`] when the cron expression is invalid.
+#[derive(Debug, thiserror::Error)]
+#[error("`{expression}` is not a valid cron expression: {reason}")]
+pub struct InvalidCronExpression {
+    expression: String,
+    reason: String,
+}
+
+/// The set of values matched by one of the fields of a cron expression,
+/// as a bitmask.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Field {
+    mask: u64,
+    is_wildcard: bool,
+}
+
+impl Field {
+    fn parse(s: &str, name: &str, min: u8, max: u8) -> Result<Self, String> {
+        let mut mask = 0u64;
+        for item in s.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => {
+                    let step: u8 = step.parse().map_err(|_| {
+                        format!("`{step}` is not a valid step for the {name} field")
+                    })?;
+                    if step == 0 {
+                        return Err(format!("the step for the {name} field can't be zero"));
+                    }
+                    (range, step)
+                }
+                None => (item, 1),
+            };
+            let parse_value = |v: &str| -> Result<u8, String> {
+                let value: u8 = v
+                    .parse()
+                    .map_err(|_| format!("`{v}` is not a valid value for the {name} field"))?;
+                if value < min || value > max {
+                    return Err(format!(
+                        "{value} is out of range for the {name} field ({min}-{max})"
+                    ));
+                }
+                Ok(value)
+            };
+            let (start, end) = match range {
+                "*" => (min, max),
+                _ => match range.split_once('-') {
+                    Some((start, end)) => {
+                        let (start, end) = (parse_value(start)?, parse_value(end)?);
+                        if start > end {
+                            return Err(format!("`{range}` is an empty range"));
+                        }
+                        (start, end)
+                    }
+                    // `5/15` is a shorthand for `5-<max>/15`.
+                    None if step != 1 => (parse_value(range)?, max),
+                    None => {
+                        let value = parse_value(range)?;
+                        (value, value)
+                    }
+                },
+            };
+            for value in (start..=end).step_by(step as usize) {
+                mask |= 1 << value;
+            }
+        }
+        Ok(Self {
+            mask,
+            is_wildcard: s == "*",
+        })
+    }
+
+    fn contains(&self, value: i8) -> bool {
+        self.mask & (1 << value) != 0
+    }
+}
+
+impl CronSchedule {
+    /// Parse a cron expression.
+    pub fn parse(expression: &str) -> Result<Self, InvalidCronExpression> {
+        let error = |reason: String| InvalidCronExpression {
+            expression: expression.to_owned(),
+            reason,
+        };
+        let fields: Vec<_> = expression.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(error(format!(
+                "expected 5 space-separated fields, found {}",
+                fields.len()
+            )));
+        };
+        let mut days_of_week =
+            Field::parse(days_of_week, "day of the week", 0, 7).map_err(error)?;
+        // Both 0 and 7 stand for Sunday.
+        if days_of_week.contains(7) {
+            days_of_week.mask |= 1;
+        }
+        Ok(Self {
+            source: expression.to_owned(),
+            minutes: Field::parse(minutes, "minute", 0, 59).map_err(error)?,
+            hours: Field::parse(hours, "hour", 0, 23).map_err(error)?,
+            days_of_month: Field::parse(days_of_month, "day of the month", 1, 31).map_err(error)?,
+            months: Field::parse(months, "month", 1, 12).map_err(error)?,
+            days_of_week,
+        })
+    }
+
+    /// The first instant strictly after `after` that matches the schedule.
+    ///
+    /// It returns `None` if the schedule never matches (e.g. `0 0 31 2 *`, the 31st of February).
+    pub fn next_after(&self, after: Timestamp) -> Option<Timestamp> {
+        let start = after.to_zoned(TimeZone::UTC).datetime();
+        // Start from the beginning of the following minute.
+        let mut dt = start
+            .date()
+            .at(start.hour(), start.minute(), 0, 0)
+            .checked_add(jiff::Span::new().minutes(1))
+            .ok()?;
+        // Any satisfiable schedule matches at least once every 4 years.
+        let limit = start.year().saturating_add(5);
+        while dt.year() <= limit {
+            if !self.months.contains(dt.month()) {
+                dt = dt
+                    .date()
+                    .first_of_month()
+                    .checked_add(jiff::Span::new().months(1))
+                    .ok()?
+                    .at(0, 0, 0, 0);
+            } else if !self.matches_day(dt) {
+                dt = dt.date().tomorrow().ok()?.at(0, 0, 0, 0);
+            } else if !self.hours.contains(dt.hour()) {
+                dt = dt
+                    .date()
+                    .at(dt.hour(), 0, 0, 0)
+                    .checked_add(jiff::Span::new().hours(1))
+                    .ok()?;
+            } else if !self.minutes.contains(dt.minute()) {
+                dt = dt.checked_add(jiff::Span::new().minutes(1)).ok()?;
+            } else {
+                return dt.to_zoned(TimeZone::UTC).ok().map(|z| z.timestamp());
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, dt: DateTime) -> bool {
+        let day_of_month = self.days_of_month.contains(dt.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(dt.weekday().to_sunday_zero_offset());
+        match (
+            self.days_of_month.is_wildcard,
+            self.days_of_week.is_wildcard,
+        ) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = InvalidCronExpression;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CronSchedule;
+    use jiff::Timestamp;
+
+    fn next(expression: &str, after: &str) -> Option<String> {
+        let schedule = CronSchedule::parse(expression).unwrap();
+        let after: Timestamp = after.parse().unwrap();
+        schedule.next_after(after).map(|t| t.to_string())
+    }
+
+    #[test]
+    fn next_occurrence() {
+        assert_eq!(
+            next("*/15 * * * *", "2025-01-01T10:07:30Z").unwrap(),
+            "2025-01-01T10:15:00Z"
+        );
+        // Strictly after.
+        assert_eq!(
+            next("30 3 * * *", "2025-01-01T03:30:00Z").unwrap(),
+            "2025-01-02T03:30:00Z"
+        );
+        // Mondays at midnight, across a month boundary.
+        assert_eq!(
+            next("0 0 * * 1", "2025-01-31T12:00:00Z").unwrap(),
+            "2025-02-03T00:00:00Z"
+        );
+        // Sunday can be either 0 or 7.
+        assert_eq!(
+            next("0 12 * * 7", "2025-01-01T00:00:00Z").unwrap(),
+            "2025-01-05T12:00:00Z"
+        );
+        // Either the day of the month or the day of the week.
+        assert_eq!(
+            next("0 0 15 * 1", "2025-01-07T00:00:00Z").unwrap(),
+            "2025-01-13T00:00:00Z"
+        );
+        // Leap days.
+        assert_eq!(
+            next("0 0 29 2 *", "2025-01-01T00:00:00Z").unwrap(),
+            "2028-02-29T00:00:00Z"
+        );
+        // Never.
+        assert_eq!(next("0 0 31 2 *", "2025-01-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for (expression, reason) in [
+            ("* * * *", "expected 5 space-separated fields, found 4"),
+            (
+                "60 * * * *",
+                "60 is out of range for the minute field (0-59)",
+            ),
+            ("*/0 * * * *", "the step for the minute field can't be zero"),
+            ("* 5-2 * * *", "`5-2` is an empty range"),
+            (
+                "* * * jan *",
+                "`jan` is not a valid value for the month field",
+            ),
+        ] {
+            let e = CronSchedule::parse(expression).unwrap_err();
+            assert_eq!(
+                e.to_string(),
+                format!("`{expression}` is not a valid cron expression: {reason}")
+            );
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Run tasks in the background, next to the HTTP server.
+//!
+//! Check out [`Blueprint::background_task`] and [`Blueprint::cron`] for an introduction
+//! to background tasks in Pavex.
+//!
+//! [`Blueprint::background_task`]: crate::blueprint::Blueprint::background_task
+//! [`Blueprint::cron`]: crate::blueprint::Blueprint::cron
+mod cron;
+mod runner;
+mod signal;
+
+pub use cron::{CronSchedule, InvalidCronExpression};
+pub use runner::BackgroundTasks;
+#[doc(hidden)]
+pub use runner::Schedule;
+pub use signal::ShutdownSignal;
+
+/// Log the failure of a background task.
+///
+/// Used by the code generated by Pavex if there are no error observers
+/// in scope for the failing task.
+#[doc(hidden)]
+pub fn log_task_error(task: &'static str, e: &(dyn std::error::Error + 'static)) {
+    tracing::error!(
+        background_task.name = task,
+        error.msg = %e,
+        error.details = ?e,
+        "A background task failed"
+    );
+}
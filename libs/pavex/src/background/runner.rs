@@ -0,0 +1,206 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tracing::Instrument;
+
+use super::{CronSchedule, ShutdownSignal};
+
+/// When a background task should be invoked.
+///
+/// Used by the code generated by Pavex.
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub enum Schedule {
+    /// Invoke the task once, as soon as it's spawned.
+    ///
+    /// If `cancel_on_shutdown` is `true`, the task is dropped when the application
+    /// starts shutting down. Otherwise, it's awaited.
+    Once { cancel_on_shutdown: bool },
+    /// Invoke the task as soon as it's spawned, and then periodically.
+    Every(Duration),
+    /// Invoke the task according to a cron schedule.
+    Cron(CronSchedule),
+}
+
+/// The background tasks of an application.
+///
+/// It's returned by the `ApplicationState::start_background_tasks` method in the code
+/// generated by Pavex.
+/// The generated `run` function takes care of starting the background tasks for you,
+/// and it stops them when the server shuts down.
+///
+/// # Shutdown
+///
+/// When [`BackgroundTasks::shutdown`] is called:
+///
+/// - periodic and scheduled tasks are no longer invoked. Runs that are in progress
+///   are awaited.
+/// - long-running tasks are cancelled, unless they take a [`ShutdownSignal`] as input:
+///   in that case, they're awaited.
+#[must_use = "Background tasks are cancelled when `BackgroundTasks` is dropped"]
+pub struct BackgroundTasks {
+    shutdown: watch::Sender<bool>,
+    tasks: JoinSet<()>,
+}
+
+impl Default for BackgroundTasks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackgroundTasks {
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        let (shutdown, _) = watch::channel(false);
+        Self {
+            shutdown,
+            tasks: JoinSet::new(),
+        }
+    }
+
+    /// Spawn a background task on the current Tokio runtime.
+    ///
+    /// `task` is invoked every time the task is due to run, according to `schedule`.
+    ///
+    /// Used by the code generated by Pavex.
+    #[doc(hidden)]
+    pub fn spawn<F, Fut>(&mut self, name: &'static str, schedule: Schedule, mut task: F)
+    where
+        F: FnMut(ShutdownSignal) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let signal = ShutdownSignal::new(self.shutdown.subscribe());
+        let mut run = move |signal: ShutdownSignal| {
+            let span = tracing::info_span!("Run background task", background_task.name = name);
+            task(signal).instrument(span)
+        };
+        self.tasks.spawn(async move {
+            match schedule {
+                Schedule::Once { cancel_on_shutdown } => {
+                    if cancel_on_shutdown {
+                        tokio::select! {
+                            _ = run(signal.clone()) => {}
+                            _ = signal.triggered() => {}
+                        }
+                    } else {
+                        run(signal).await;
+                    }
+                }
+                Schedule::Every(period) => {
+                    let mut interval = tokio::time::interval(period);
+                    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                    loop {
+                        tokio::select! {
+                            _ = interval.tick() => {}
+                            _ = signal.triggered() => break,
+                        }
+                        run(signal.clone()).await;
+                    }
+                }
+                Schedule::Cron(schedule) => loop {
+                    let now = jiff::Timestamp::now();
+                    let Some(next) = schedule.next_after(now) else {
+                        tracing::warn!(
+                            background_task.name = name,
+                            "The cron schedule `{schedule}` never matches. The task won't be invoked"
+                        );
+                        break;
+                    };
+                    let delay = Duration::try_from(next.duration_since(now)).unwrap_or_default();
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = signal.triggered() => break,
+                    }
+                    run(signal.clone()).await;
+                },
+            }
+        });
+    }
+
+    /// The number of background tasks that are still running.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if there are no background tasks running.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Stop all background tasks.
+    ///
+    /// It waits for in-progress runs to complete, as detailed in the
+    /// [type-level documentation](BackgroundTasks#shutdown).
+    pub async fn shutdown(mut self) {
+        let _ = self.shutdown.send(true);
+        while let Some(outcome) = self.tasks.join_next().await {
+            if let Err(e) = outcome {
+                if e.is_panic() {
+                    tracing::error!(
+                        error.msg = %e,
+                        error.details = ?e,
+                        "A background task panicked"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::{BackgroundTasks, Schedule};
+
+    #[tokio::test]
+    async fn shutdown_respects_the_task_preferences() {
+        let completed = Arc::new(AtomicUsize::new(0));
+        let mut tasks = BackgroundTasks::new();
+        // Never completes: it must be cancelled.
+        tasks.spawn(
+            "forever",
+            Schedule::Once {
+                cancel_on_shutdown: true,
+            },
+            |_| std::future::pending(),
+        );
+        // Stops when the shutdown signal fires: it must be awaited.
+        let c = completed.clone();
+        tasks.spawn(
+            "graceful",
+            Schedule::Once {
+                cancel_on_shutdown: false,
+            },
+            move |signal| {
+                let c = c.clone();
+                async move {
+                    signal.triggered().await;
+                    c.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+        );
+        // Runs immediately, then never again.
+        let c = completed.clone();
+        tasks.spawn(
+            "periodic",
+            Schedule::Every(Duration::from_secs(3600)),
+            move |_| {
+                let c = c.clone();
+                async move {
+                    c.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(tasks.len(), 3);
+
+        tasks.shutdown().await;
+        assert_eq!(completed.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,46 @@
+use tokio::sync::watch;
+
+/// A signal that fires when the application starts shutting down.
+///
+/// Take it as input in a long-running [background task](crate::blueprint::Blueprint::background_task)
+/// to stop gracefully: the task will be awaited, rather than cancelled, when the application
+/// shuts down.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::background::ShutdownSignal;
+/// use std::time::Duration;
+///
+/// # pub struct Queue;
+/// # impl Queue { pub async fn process_next(&self) {} pub async fn flush(&self) {} }
+/// pub async fn process_queue(queue: &Queue, shutdown: ShutdownSignal) {
+///     while !shutdown.is_triggered() {
+///         queue.process_next().await;
+///     }
+///     queue.flush().await;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ShutdownSignal(watch::Receiver<bool>);
+
+impl ShutdownSignal {
+    pub(super) fn new(receiver: watch::Receiver<bool>) -> Self {
+        Self(receiver)
+    }
+
+    /// Returns `true` if the application has started shutting down.
+    pub fn is_triggered(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Wait until the application starts shutting down.
+    ///
+    /// It returns immediately if the shutdown has already started.
+    pub async fn triggered(&self) {
+        let mut receiver = self.0.clone();
+        // An error means that the sender has been dropped: there is nobody
+        // left to wait for.
+        let _ = receiver.wait_for(|triggered| *triggered).await;
+    }
+}
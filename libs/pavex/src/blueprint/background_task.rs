@@ -0,0 +1,100 @@
+//! Register tasks to be run in the background, next to the HTTP server.
+use std::time::Duration;
+
+use crate::blueprint::Blueprint;
+use crate::blueprint::conversions::raw_identifiers2callable;
+use crate::blueprint::reflection::RawIdentifiers;
+use pavex_bp_schema::{
+    BackgroundTask as BackgroundTaskSchema, Blueprint as BlueprintSchema, Callable, Component,
+    TaskSchedule,
+};
+
+/// The type returned by [`Blueprint::background_task`] and [`Blueprint::cron`].
+///
+/// It allows you to further configure the behaviour of the registered task.
+pub struct RegisteredBackgroundTask<'a> {
+    pub(crate) blueprint: &'a mut BlueprintSchema,
+    /// The index of the registered task in the blueprint's `components` vector.
+    pub(crate) component_id: usize,
+}
+
+impl RegisteredBackgroundTask<'_> {
+    /// Invoke the task periodically, rather than once.
+    ///
+    /// The task is invoked as soon as the application starts, and then again every
+    /// `period`. If a run takes longer than `period`, the next one starts as soon as the
+    /// previous one completes—runs never overlap.
+    ///
+    /// It replaces the schedule that the task was registered with.
+    pub fn every(mut self, period: Duration) -> Self {
+        self.task().schedule = TaskSchedule::Every(period);
+        self
+    }
+
+    fn task(&mut self) -> &mut BackgroundTaskSchema {
+        let component = &mut self.blueprint.components[self.component_id];
+        let Component::BackgroundTask(t) = component else {
+            unreachable!("The component should be a background task")
+        };
+        t
+    }
+}
+
+/// A background task that has been configured but has not yet been registered with a [`Blueprint`].
+///
+/// # Guide
+///
+/// Check out [`Blueprint::background_task`] and [`Blueprint::cron`] for an introduction
+/// to background tasks in Pavex.
+///
+/// # Use cases
+///
+/// [`BackgroundTask`] is primarily used by
+/// [kits](https://pavex.dev/docs/guide/dependency_injection/kits)
+/// to allow users to customize (or disable!)
+/// the bundled tasks **before** registering them with a [`Blueprint`].
+#[derive(Clone, Debug)]
+pub struct BackgroundTask {
+    pub(in crate::blueprint) callable: Callable,
+    pub(in crate::blueprint) schedule: TaskSchedule,
+}
+
+impl BackgroundTask {
+    /// Create a new (unregistered) background task, invoked once when the application starts.
+    ///
+    /// Check out the documentation of [`Blueprint::background_task`] for more details.
+    #[track_caller]
+    pub fn new(callable: RawIdentifiers) -> Self {
+        Self {
+            callable: raw_identifiers2callable(callable),
+            schedule: TaskSchedule::Once,
+        }
+    }
+
+    /// Create a new (unregistered) background task, invoked according to a cron schedule.
+    ///
+    /// Check out the documentation of [`Blueprint::cron`] for more details.
+    #[track_caller]
+    pub fn cron(schedule: &str, callable: RawIdentifiers) -> Self {
+        Self {
+            callable: raw_identifiers2callable(callable),
+            schedule: TaskSchedule::Cron(schedule.to_owned()),
+        }
+    }
+
+    /// Invoke the task periodically, rather than once.
+    ///
+    /// Check out [`RegisteredBackgroundTask::every`] for more details.
+    pub fn every(mut self, period: Duration) -> Self {
+        self.schedule = TaskSchedule::Every(period);
+        self
+    }
+
+    /// Register this task with a [`Blueprint`].
+    ///
+    /// Check out the documentation of [`Blueprint::background_task`] and [`Blueprint::cron`]
+    /// for more details.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredBackgroundTask {
+        bp.register_background_task(self)
+    }
+}
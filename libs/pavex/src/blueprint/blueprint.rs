@@ -1,13 +1,16 @@
+use crate::blueprint::background_task::RegisteredBackgroundTask;
 use crate::blueprint::conversions::{
-    cloning2cloning, lifecycle2lifecycle, method_guard2method_guard, raw_identifiers2callable,
-    raw_identifiers2type,
+    cloning2cloning, condition2condition, lifecycle2lifecycle, method_guard2method_guard,
+    raw_identifiers2callable, raw_identifiers2type, sources2import,
 };
 use crate::blueprint::error_observer::RegisteredErrorObserver;
+use crate::blueprint::lifecycle_hook::RegisteredLifecycleHook;
 use crate::blueprint::prebuilt::RegisteredPrebuiltType;
 use crate::blueprint::router::RegisteredFallback;
 use pavex_bp_schema::{
-    Blueprint as BlueprintSchema, ConfigType, Constructor, Fallback, NestedBlueprint,
-    PostProcessingMiddleware, PreProcessingMiddleware, PrebuiltType, Route, WrappingMiddleware,
+    BackgroundTask, Blueprint as BlueprintSchema, ConfigType, Constructor, Fallback, LifecycleHook,
+    LifecyclePhase, NestedBlueprint, PostProcessingMiddleware, PreProcessingMiddleware,
+    PrebuiltType, Route, TaskSchedule, WrappingMiddleware,
 };
 use pavex_reflection::Location;
 
@@ -18,7 +21,7 @@ use super::middleware::{
     RegisteredWrappingMiddleware,
 };
 use super::nesting::NestingConditions;
-use super::reflection::RawIdentifiers;
+use super::reflection::{RawIdentifiers, Sources};
 use super::router::{MethodGuard, RegisteredRoute};
 
 /// The starting point for building an application with Pavex.
@@ -106,6 +109,7 @@ impl Blueprint {
             method_guard: method_guard2method_guard(method_guard),
             request_handler: raw_identifiers2callable(callable),
             error_handler: None,
+            condition: None,
         };
         let component_id = self.push_component(registered_route);
         RegisteredRoute {
@@ -120,6 +124,7 @@ impl Blueprint {
             method_guard: method_guard2method_guard(r.method_guard),
             error_handler: r.error_handler,
             request_handler: r.callable,
+            condition: r.condition.map(condition2condition),
         };
         let component_id = self.push_component(r);
         RegisteredRoute {
@@ -128,6 +133,61 @@ impl Blueprint {
         }
     }
 
+    #[track_caller]
+    /// Register all the components defined in the given modules (and their submodules)
+    /// that have been annotated with one of Pavex's attribute macros—e.g.
+    /// [`#[pavex::get]`](macro@crate::get) or [`#[pavex::request_scoped]`](macro@crate::request_scoped).
+    ///
+    /// Pavex relies on the annotations to determine how each component should be registered
+    /// (its lifecycle, the path of a route, etc.), so there is no need to reference the
+    /// annotated items by path with [`f!`](crate::f): moving them around won't break
+    /// your `Blueprint`, as long as they stay within the imported modules.
+    ///
+    /// You can import components from your dependencies too, e.g. `bp.import(from![my_dependency])`.
+    /// If two imported crates provide a constructor for the same type, Pavex will report
+    /// an error rather than picking one of them: register the constructor you want to use
+    /// explicitly, after the imports, to resolve the conflict.
+    ///
+    /// Functions annotated with [`#[pavex::error_handler]`](macro@crate::error_handler) become
+    /// the default error handler for the error type they take as input: they are used for every
+    /// fallible component that returns that exact error type and doesn't specify an error
+    /// handler of its own, no matter where it was registered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::blueprint::Blueprint;
+    /// use pavex::from;
+    /// use pavex::response::Response;
+    ///
+    /// mod routes {
+    ///     use pavex::response::Response;
+    ///
+    ///     #[pavex::get(path = "/")]
+    ///     pub fn index() -> Response {
+    ///         Response::ok()
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.import(from![crate::routes]);
+    /// # }
+    /// ```
+    ///
+    /// # Limitations
+    ///
+    /// Pavex discovers annotated methods on inherent `impl` blocks, but it doesn't
+    /// look for annotated methods inside trait implementations.
+    /// Imported routes are registered after the other imported components, so they are
+    /// wrapped by the middlewares and error observers imported alongside them.
+    /// Middlewares and error observers are registered in the order in which they
+    /// are discovered: if their relative order matters, register them explicitly
+    /// via [`Blueprint::wrap`], [`Blueprint::pre_process`], etc.
+    pub fn import(&mut self, sources: Sources) {
+        self.push_component(sources2import(sources));
+    }
+
     #[track_caller]
     /// Register a type to be used as input parameter to the (generated) `ApplicationState::new`
     /// method.
@@ -185,6 +245,7 @@ impl Blueprint {
             key: key.to_owned(),
             cloning_strategy: None,
             default_if_missing: None,
+            reloadable: None,
         };
         let component_id = self.push_component(registered);
         RegisteredConfigType {
@@ -202,6 +263,7 @@ impl Blueprint {
             key: i.key,
             cloning_strategy: i.cloning_strategy.map(cloning2cloning),
             default_if_missing: i.default_if_missing,
+            reloadable: i.reloadable,
         };
         let component_id = self.push_component(i);
         RegisteredConfigType {
@@ -250,6 +312,7 @@ impl Blueprint {
             cloning_strategy: None,
             error_handler: None,
             lints: Default::default(),
+            condition: None,
         };
         let component_id = self.push_component(registered_constructor);
         RegisteredConstructor {
@@ -358,6 +421,7 @@ impl Blueprint {
             cloning_strategy: constructor.cloning_strategy.map(cloning2cloning),
             error_handler: constructor.error_handler,
             lints: constructor.lints,
+            condition: constructor.condition.map(condition2condition),
         };
         let component_id = self.push_component(constructor);
         RegisteredConstructor {
@@ -906,6 +970,242 @@ impl Blueprint {
         }
     }
 
+    #[track_caller]
+    /// Register a hook to be invoked after the application state has been built,
+    /// before the application starts serving requests.
+    ///
+    /// Use startup hooks to run warmup tasks (e.g. priming a cache) or to verify that
+    /// your dependencies are reachable (e.g. pinging the database).
+    ///
+    /// # Inputs and outputs
+    ///
+    /// Startup hooks can only take singletons as inputs—i.e. types built by a
+    /// singleton constructor, prebuilt types and configuration types.
+    /// Owned inputs are cloned, so they must be marked as `CloneIfNecessary`;
+    /// prefer taking a shared reference (e.g. `&DbPool`).
+    ///
+    /// A hook must return either `()` or `Result<(), E>`.
+    /// If a hook fails (or [times out](RegisteredLifecycleHook::timeout)),
+    /// `ApplicationState::new` returns an error and the remaining hooks are skipped.
+    ///
+    /// # Ordering
+    ///
+    /// Hooks are invoked one at a time.
+    /// If a hook takes as input a singleton that depends, directly or indirectly,
+    /// on a singleton used by another hook, it will be invoked after that other hook.
+    /// Otherwise, hooks are invoked in the order they were registered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::Blueprint;
+    /// use std::time::Duration;
+    ///
+    /// # pub struct DbPool;
+    /// # impl DbPool { pub async fn ping(&self) -> Result<(), std::io::Error> { Ok(()) } }
+    /// pub async fn check_db(pool: &DbPool) -> Result<(), std::io::Error> {
+    ///     pool.ping().await
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.on_startup(f!(crate::check_db))
+    ///     .timeout(Duration::from_secs(5));
+    /// # }
+    /// ```
+    pub fn on_startup(&mut self, callable: RawIdentifiers) -> RegisteredLifecycleHook {
+        self.register_lifecycle_hook(super::lifecycle_hook::LifecycleHook {
+            callable: raw_identifiers2callable(callable),
+            phase: LifecyclePhase::Startup,
+            timeout: None,
+        })
+    }
+
+    #[track_caller]
+    /// Register a hook to be invoked after the server has stopped serving requests,
+    /// e.g. to flush buffered telemetry or to close connection pools.
+    ///
+    /// Shutdown hooks are invoked when [`ServerHandle::shutdown`] is called on the
+    /// server started via the generated `run` function, before `shutdown` completes.
+    /// You can also invoke them manually, via the generated `ApplicationState::shutdown`.
+    ///
+    /// # Inputs and outputs
+    ///
+    /// The same constraints of [startup hooks](Self::on_startup) apply:
+    /// shutdown hooks can only take singletons as inputs and they must return
+    /// either `()` or `Result<(), E>`.
+    /// If a hook fails (or [times out](RegisteredLifecycleHook::timeout)), the error is logged
+    /// and the remaining hooks are still invoked.
+    ///
+    /// # Ordering
+    ///
+    /// Shutdown hooks are invoked in the reverse order of startup: if a hook takes as input
+    /// a singleton that depends on a singleton used by another hook, it will be invoked
+    /// **before** that other hook.
+    /// Otherwise, hooks are invoked in the order they were registered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::Blueprint;
+    /// use std::time::Duration;
+    ///
+    /// # pub struct KafkaProducer;
+    /// # impl KafkaProducer { pub async fn flush(&self) {} }
+    /// pub async fn flush_producer(producer: &KafkaProducer) {
+    ///     producer.flush().await
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.on_shutdown(f!(crate::flush_producer))
+    ///     .timeout(Duration::from_secs(10));
+    /// # }
+    /// ```
+    ///
+    /// [`ServerHandle::shutdown`]: crate::server::ServerHandle::shutdown
+    pub fn on_shutdown(&mut self, callable: RawIdentifiers) -> RegisteredLifecycleHook {
+        self.register_lifecycle_hook(super::lifecycle_hook::LifecycleHook {
+            callable: raw_identifiers2callable(callable),
+            phase: LifecyclePhase::Shutdown,
+            timeout: None,
+        })
+    }
+
+    pub(super) fn register_lifecycle_hook(
+        &mut self,
+        h: super::lifecycle_hook::LifecycleHook,
+    ) -> RegisteredLifecycleHook {
+        let h = LifecycleHook {
+            hook: h.callable,
+            phase: h.phase,
+            timeout: h.timeout,
+        };
+        let component_id = self.push_component(h);
+        RegisteredLifecycleHook {
+            component_id,
+            blueprint: &mut self.schema,
+        }
+    }
+
+    #[track_caller]
+    /// Register a task to be run in the background, next to the HTTP server.
+    ///
+    /// The task is invoked once, when the application starts.
+    /// Use [`every`](RegisteredBackgroundTask::every) to invoke it periodically, or
+    /// register it via [`Blueprint::cron`] to invoke it according to a cron schedule.
+    ///
+    /// Background tasks are started by the generated `run` function, and they're stopped
+    /// when the server shuts down—before the [shutdown hooks](Self::on_shutdown) are invoked.
+    /// If you're not using `run`, start them via the generated
+    /// `ApplicationState::start_background_tasks` method.
+    ///
+    /// # Inputs and outputs
+    ///
+    /// Background tasks can only take singletons as inputs—i.e. types built by a
+    /// singleton constructor, prebuilt types and configuration types.
+    /// Owned inputs are cloned, so they must be marked as `CloneIfNecessary`;
+    /// prefer taking a shared reference (e.g. `&DbPool`).
+    /// A task can also take a [`ShutdownSignal`] as input, to learn when the
+    /// application starts shutting down.
+    ///
+    /// A task must return either `()` or `Result<(), E>`.
+    /// Errors are reported to the [error observers](Self::error_observer) that are in scope
+    /// for the task, i.e. the ones registered before the task against the same blueprint
+    /// or one of its parents.
+    /// Error observers can only take singletons as inputs (alongside `&pavex::Error`)
+    /// when invoked for a background task: the ones that require request-scoped
+    /// components are skipped, and Pavex emits a warning.
+    /// If there are no error observers in scope, errors are logged.
+    ///
+    /// # Shutdown
+    ///
+    /// When the application shuts down, periodic and scheduled tasks are no longer invoked,
+    /// but in-progress runs are awaited.
+    /// Long-running tasks are cancelled, unless they take a [`ShutdownSignal`] as input:
+    /// in that case, they're awaited.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::Blueprint;
+    /// use std::time::Duration;
+    ///
+    /// # pub struct Cache;
+    /// # impl Cache { pub async fn refresh(&self) -> Result<(), std::io::Error> { Ok(()) } }
+    /// pub async fn warm_cache(cache: &Cache) -> Result<(), std::io::Error> {
+    ///     cache.refresh().await
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.background_task(f!(crate::warm_cache))
+    ///     .every(Duration::from_secs(60 * 5));
+    /// # }
+    /// ```
+    ///
+    /// [`ShutdownSignal`]: crate::background::ShutdownSignal
+    pub fn background_task(&mut self, callable: RawIdentifiers) -> RegisteredBackgroundTask {
+        self.register_background_task(super::background_task::BackgroundTask {
+            callable: raw_identifiers2callable(callable),
+            schedule: TaskSchedule::Once,
+        })
+    }
+
+    #[track_caller]
+    /// Register a task to be run in the background according to a cron schedule.
+    ///
+    /// `schedule` uses the standard five-field cron syntax and it's interpreted in UTC.
+    /// Check out [`CronSchedule`] for the details of the supported syntax.
+    /// Pavex verifies that the expression is valid when generating the server SDK.
+    ///
+    /// All the considerations in [`Blueprint::background_task`] apply to scheduled tasks too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::Blueprint;
+    ///
+    /// # pub struct SessionStore;
+    /// # impl SessionStore { pub async fn delete_expired(&self) -> Result<(), std::io::Error> { Ok(()) } }
+    /// pub async fn delete_expired_sessions(store: &SessionStore) -> Result<(), std::io::Error> {
+    ///     store.delete_expired().await
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// // Every day at 03:30 UTC.
+    /// bp.cron("30 3 * * *", f!(crate::delete_expired_sessions));
+    /// # }
+    /// ```
+    ///
+    /// [`CronSchedule`]: crate::background::CronSchedule
+    pub fn cron(&mut self, schedule: &str, callable: RawIdentifiers) -> RegisteredBackgroundTask {
+        self.register_background_task(super::background_task::BackgroundTask {
+            callable: raw_identifiers2callable(callable),
+            schedule: TaskSchedule::Cron(schedule.to_owned()),
+        })
+    }
+
+    pub(super) fn register_background_task(
+        &mut self,
+        t: super::background_task::BackgroundTask,
+    ) -> RegisteredBackgroundTask {
+        let t = BackgroundTask {
+            task: t.callable,
+            schedule: t.schedule,
+        };
+        let component_id = self.push_component(t);
+        RegisteredBackgroundTask {
+            component_id,
+            blueprint: &mut self.schema,
+        }
+    }
+
     /// Register a component and return its id (i.e. its index in the `components` vector).
     pub fn push_component(&mut self, component: impl Into<pavex_bp_schema::Component>) -> usize {
         let id = self.schema.components.len();
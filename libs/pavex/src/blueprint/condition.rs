@@ -0,0 +1,110 @@
+//! Include components in the generated server SDK only if a compile-time condition holds.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pavex::blueprint::{Blueprint, condition::Condition};
+//! use pavex::f;
+//! # pub struct PaymentsClient;
+//! # pub fn payments_client() -> PaymentsClient { todo!() }
+//! # pub fn fake_payments_client() -> PaymentsClient { todo!() }
+//!
+//! # fn main() {
+//! let mut bp = Blueprint::new();
+//! let fake = Condition::feature("fake-payments");
+//! bp.singleton(f!(crate::payments_client))
+//!     .only_if(!fake.clone());
+//! bp.singleton(f!(crate::fake_payments_client))
+//!     .only_if(fake);
+//! # }
+//! ```
+//!
+//! Pavex generates the code for both variants of the application and guards each of them
+//! with the appropriate `#[cfg(...)]` attributes. The `fake-payments` feature is added to
+//! the manifest of the generated server SDK crate.
+//!
+//! # Limitations
+//!
+//! Only constructors and routes can be registered conditionally, via
+//! [`RegisteredConstructor::only_if`] and [`RegisteredRoute::only_if`].
+//!
+//! Pavex analyses your application once for every possible combination of the features
+//! and profiles mentioned in your conditions: `2^features × (profiles + 1)` variants.
+//! Code generation gets slower as that number grows, so it is capped at 32 variants—e.g.
+//! 5 features and no profiles, or 3 features and 3 profiles.
+//! Pavex reports an error if your blueprint goes over the limit.
+//!
+//! [`RegisteredConstructor::only_if`]: crate::blueprint::constructor::RegisteredConstructor::only_if
+//! [`RegisteredRoute::only_if`]: crate::blueprint::router::RegisteredRoute::only_if
+
+/// A compile-time condition attached to a component registration.
+///
+/// Conditions are evaluated when the generated server SDK is compiled, via `#[cfg(...)]`
+/// attributes. Check out the [module-level documentation](self) for an example.
+///
+/// Conditions can be combined via [`Condition::all`], [`Condition::any`] and negated
+/// using the `!` operator.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Condition {
+    /// A cargo feature of the generated server SDK crate is enabled.
+    Feature(String),
+    /// The server SDK is compiled for a named profile.
+    ///
+    /// Profiles are selected at compile-time by setting the `pavex_profile` cfg, e.g.
+    /// `RUSTFLAGS='--cfg pavex_profile="test"' cargo build`.
+    Profile(String),
+    /// The inner condition doesn't hold.
+    Not(Box<Condition>),
+    /// All the inner conditions hold.
+    All(Vec<Condition>),
+    /// At least one of the inner conditions holds.
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    /// The condition holds if the cargo feature with the given name is enabled
+    /// for the generated server SDK crate.
+    pub fn feature(name: impl Into<String>) -> Self {
+        Self::Feature(name.into())
+    }
+
+    /// The condition holds if the generated server SDK is compiled for the given profile.
+    ///
+    /// Check out [`Condition::Profile`] for more details on how to select a profile.
+    pub fn profile(name: impl Into<String>) -> Self {
+        Self::Profile(name.into())
+    }
+
+    /// The condition holds if all the given conditions hold.
+    pub fn all(conditions: impl IntoIterator<Item = Condition>) -> Self {
+        Self::All(conditions.into_iter().collect())
+    }
+
+    /// The condition holds if at least one of the given conditions holds.
+    pub fn any(conditions: impl IntoIterator<Item = Condition>) -> Self {
+        Self::Any(conditions.into_iter().collect())
+    }
+
+    /// Combine two conditions, requiring both of them to hold.
+    pub(in crate::blueprint) fn and(self, other: Condition) -> Self {
+        match self {
+            Self::All(mut conditions) => {
+                conditions.push(other);
+                Self::All(conditions)
+            }
+            c => Self::All(vec![c, other]),
+        }
+    }
+}
+
+impl std::ops::Not for Condition {
+    type Output = Condition;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Self::Not(c) => *c,
+            c => Self::Not(Box::new(c)),
+        }
+    }
+}
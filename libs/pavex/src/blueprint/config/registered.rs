@@ -58,6 +58,39 @@ impl RegisteredConfigType<'_> {
         self
     }
 
+    /// Allow the configuration values for this type to be updated while the application
+    /// is running, without restarting the server.
+    ///
+    /// # Reading reloadable values
+    ///
+    /// Pavex stores a [`Reloadable`] handle in the application state, rather than the
+    /// configuration value itself.
+    /// Request-scoped and transient components can keep asking for the configuration type
+    /// (e.g. `&LogConfig`): they'll receive a copy of the most recent values, cloned at the
+    /// beginning of each request.
+    /// Singletons can't, since they're built once and would never observe an update:
+    /// Pavex will reject them at compile-time. They should take the handle as input
+    /// (e.g. `&Reloadable<LogConfig>`) and read from it whenever they need the current values.
+    ///
+    /// The handle is always stored in the application state, even if no component uses it.
+    /// Reloadable configuration types must therefore be `Send` and `Sync`.
+    ///
+    /// # Updating values
+    ///
+    /// Pavex generates a `ConfigReloader` type in the server SDK, accessible via
+    /// `ApplicationState::config_reloader`.
+    /// Pass a new `ApplicationConfig` to `ConfigReloader::reload` to update
+    /// the values of all reloadable configuration types.
+    /// [`ConfigLoader::watch`] can take care of loading a new `ApplicationConfig`
+    /// when the configuration files change or when the process receives a `SIGHUP` signal.
+    ///
+    /// [`Reloadable`]: crate::config::Reloadable
+    /// [`ConfigLoader::watch`]: crate::config::ConfigLoader::watch
+    pub fn reloadable(mut self) -> Self {
+        self.config().reloadable = Some(true);
+        self
+    }
+
     fn config(&mut self) -> &mut ConfigType {
         let component = &mut self.blueprint.components[self.component_id];
         let Component::ConfigType(s) = component else {
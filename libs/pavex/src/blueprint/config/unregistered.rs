@@ -24,6 +24,7 @@ pub struct ConfigType {
     pub(in crate::blueprint) key: String,
     pub(in crate::blueprint) cloning_strategy: Option<CloningStrategy>,
     pub(in crate::blueprint) default_if_missing: Option<bool>,
+    pub(in crate::blueprint) reloadable: Option<bool>,
 }
 
 impl ConfigType {
@@ -38,6 +39,7 @@ impl ConfigType {
             key: key.into(),
             cloning_strategy: None,
             default_if_missing: None,
+            reloadable: None,
         }
     }
 
@@ -78,6 +80,15 @@ impl ConfigType {
         self
     }
 
+    /// Allow the configuration values for this type to be updated while the application
+    /// is running, without restarting the server.
+    ///
+    /// Check out [`RegisteredConfigType::reloadable`] for more details.
+    pub fn reloadable(mut self) -> Self {
+        self.reloadable = Some(true);
+        self
+    }
+
     /// Register this configuration type with a [`Blueprint`].
     ///
     /// Check out the documentation of [`Blueprint::config`] for more details.
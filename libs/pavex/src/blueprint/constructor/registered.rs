@@ -1,5 +1,8 @@
+use crate::blueprint::condition::Condition;
 use crate::blueprint::constructor::CloningStrategy;
-use crate::blueprint::conversions::{cloning2cloning, lint2lint, raw_identifiers2callable};
+use crate::blueprint::conversions::{
+    cloning2cloning, condition2condition, lint2lint, raw_identifiers2callable,
+};
 use crate::blueprint::linter::Lint;
 use crate::blueprint::reflection::RawIdentifiers;
 use pavex_bp_schema::{Blueprint as BlueprintSchema, LintSetting};
@@ -104,6 +107,55 @@ impl RegisteredConstructor<'_> {
         self
     }
 
+    /// Only include this constructor in the generated code if the given condition holds.
+    ///
+    /// Pavex generates a variant of the server SDK for each combination of the conditions
+    /// used in your [`Blueprint`], guarded by the appropriate `#[cfg(...)]` attributes.
+    /// It's a convenient way to swap the implementation of a type depending on a cargo feature
+    /// of the server SDK crate—e.g. a test double for a third-party client.
+    ///
+    /// If a condition has already been set for this constructor, the new condition
+    /// must hold as well.
+    ///
+    /// Each distinct feature or profile multiplies the work Pavex has to do at code generation
+    /// time: check out the [`condition`](crate::blueprint::condition) module for the limits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::{Blueprint, condition::Condition};
+    /// # pub struct PaymentsClient;
+    ///
+    /// fn payments_client() -> PaymentsClient {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// fn fake_payments_client() -> PaymentsClient {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// let fake = Condition::feature("fake-payments");
+    /// bp.singleton(f!(crate::payments_client)).only_if(!fake.clone());
+    /// bp.singleton(f!(crate::fake_payments_client)).only_if(fake);
+    /// # }
+    /// ```
+    ///
+    /// [`Blueprint`]: crate::blueprint::Blueprint
+    pub fn only_if(mut self, condition: Condition) -> Self {
+        let condition = condition2condition(condition);
+        let slot = &mut self.constructor().condition;
+        *slot = Some(match slot.take() {
+            Some(existing) => pavex_bp_schema::Condition::All(vec![existing, condition]),
+            None => condition,
+        });
+        self
+    }
+
     fn constructor(&mut self) -> &mut Constructor {
         let component = &mut self.blueprint.components[self.component_id];
         let Component::Constructor(c) = component else {
@@ -1,4 +1,5 @@
 use crate::blueprint::Blueprint;
+use crate::blueprint::condition::Condition;
 use crate::blueprint::constructor::{CloningStrategy, Lifecycle, RegisteredConstructor};
 use crate::blueprint::conversions::{lint2lint, raw_identifiers2callable};
 use crate::blueprint::linter::Lint;
@@ -26,6 +27,7 @@ pub struct Constructor {
     pub(in crate::blueprint) cloning_strategy: Option<CloningStrategy>,
     pub(in crate::blueprint) error_handler: Option<Callable>,
     pub(in crate::blueprint) lints: BTreeMap<pavex_bp_schema::Lint, LintSetting>,
+    pub(in crate::blueprint) condition: Option<Condition>,
 }
 
 impl Constructor {
@@ -41,6 +43,7 @@ impl Constructor {
             cloning_strategy: None,
             error_handler: None,
             lints: Default::default(),
+            condition: None,
         }
     }
 
@@ -111,6 +114,17 @@ impl Constructor {
         self
     }
 
+    /// Only include this constructor in the generated code if the given condition holds.
+    ///
+    /// Check out the documentation of [`RegisteredConstructor::only_if`] for more details.
+    pub fn only_if(mut self, condition: Condition) -> Self {
+        self.condition = Some(match self.condition {
+            Some(existing) => existing.and(condition),
+            None => condition,
+        });
+        self
+    }
+
     /// Register this constructor with a [`Blueprint`].
     ///
     /// Check out the documentation of [`Blueprint::constructor`] for more details.
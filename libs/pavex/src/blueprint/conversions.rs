@@ -1,9 +1,10 @@
 //! Conversions between `pavex_bp_schema` and `pavex_bp` types.
+use crate::blueprint::condition::Condition;
 use crate::blueprint::constructor::{CloningStrategy, Lifecycle};
 use crate::blueprint::linter::Lint;
-use crate::blueprint::reflection::RawIdentifiers;
+use crate::blueprint::reflection::{RawIdentifiers, Sources};
 use crate::router::AllowedMethods;
-use pavex_bp_schema::{Callable, Location, Type};
+use pavex_bp_schema::{Callable, Import, Location, Type};
 use pavex_reflection::RegisteredAt;
 
 #[track_caller]
@@ -49,6 +50,32 @@ pub(super) fn raw_identifiers2type(callable: RawIdentifiers) -> Type {
     }
 }
 
+pub(super) fn condition2condition(condition: Condition) -> pavex_bp_schema::Condition {
+    match condition {
+        Condition::Feature(name) => pavex_bp_schema::Condition::Feature(name),
+        Condition::Profile(name) => pavex_bp_schema::Condition::Profile(name),
+        Condition::Not(c) => pavex_bp_schema::Condition::Not(Box::new(condition2condition(*c))),
+        Condition::All(c) => {
+            pavex_bp_schema::Condition::All(c.into_iter().map(condition2condition).collect())
+        }
+        Condition::Any(c) => {
+            pavex_bp_schema::Condition::Any(c.into_iter().map(condition2condition).collect())
+        }
+    }
+}
+
+#[track_caller]
+pub(super) fn sources2import(sources: Sources) -> Import {
+    Import {
+        sources: sources.paths.iter().map(|p| (*p).to_owned()).collect(),
+        registered_at: RegisteredAt {
+            crate_name: sources.crate_name.to_owned(),
+            module_path: sources.module_path.to_owned(),
+        },
+        location: Location::caller(),
+    }
+}
+
 pub(super) fn lifecycle2lifecycle(lifecycle: Lifecycle) -> pavex_bp_schema::Lifecycle {
     match lifecycle {
         Lifecycle::RequestScoped => pavex_bp_schema::Lifecycle::RequestScoped,
@@ -0,0 +1,103 @@
+//! Register hooks to be invoked when the application starts up or shuts down.
+use std::time::Duration;
+
+use crate::blueprint::Blueprint;
+use crate::blueprint::conversions::raw_identifiers2callable;
+use crate::blueprint::reflection::RawIdentifiers;
+use pavex_bp_schema::{
+    Blueprint as BlueprintSchema, Callable, Component, LifecycleHook as LifecycleHookSchema,
+    LifecyclePhase,
+};
+
+/// The type returned by [`Blueprint::on_startup`] and [`Blueprint::on_shutdown`].
+///
+/// It allows you to further configure the behaviour of the registered hook.
+pub struct RegisteredLifecycleHook<'a> {
+    pub(crate) blueprint: &'a mut BlueprintSchema,
+    /// The index of the registered hook in the blueprint's `components` vector.
+    pub(crate) component_id: usize,
+}
+
+impl RegisteredLifecycleHook<'_> {
+    /// Set the maximum amount of time the hook is allowed to run for.
+    ///
+    /// If the hook doesn't complete in time, it's cancelled and the
+    /// application reports a `HookTimeout` error, as if the hook had failed.
+    ///
+    /// The timeout can only interrupt the hook at an `.await` point: it has no
+    /// effect on synchronous hooks.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.hook().timeout = Some(timeout);
+        self
+    }
+
+    fn hook(&mut self) -> &mut LifecycleHookSchema {
+        let component = &mut self.blueprint.components[self.component_id];
+        let Component::LifecycleHook(h) = component else {
+            unreachable!("The component should be a lifecycle hook")
+        };
+        h
+    }
+}
+
+/// A lifecycle hook that has been configured but has not yet been registered with a [`Blueprint`].
+///
+/// # Guide
+///
+/// Check out [`Blueprint::on_startup`] and [`Blueprint::on_shutdown`] for an introduction
+/// to lifecycle hooks in Pavex.
+///
+/// # Use cases
+///
+/// [`LifecycleHook`] is primarily used by
+/// [kits](https://pavex.dev/docs/guide/dependency_injection/kits)
+/// to allow users to customize (or disable!)
+/// the bundled hooks **before** registering them with a [`Blueprint`].
+#[derive(Clone, Debug)]
+pub struct LifecycleHook {
+    pub(in crate::blueprint) callable: Callable,
+    pub(in crate::blueprint) phase: LifecyclePhase,
+    pub(in crate::blueprint) timeout: Option<Duration>,
+}
+
+impl LifecycleHook {
+    /// Create a new (unregistered) startup hook.
+    ///
+    /// Check out the documentation of [`Blueprint::on_startup`] for more details.
+    #[track_caller]
+    pub fn startup(callable: RawIdentifiers) -> Self {
+        Self {
+            callable: raw_identifiers2callable(callable),
+            phase: LifecyclePhase::Startup,
+            timeout: None,
+        }
+    }
+
+    /// Create a new (unregistered) shutdown hook.
+    ///
+    /// Check out the documentation of [`Blueprint::on_shutdown`] for more details.
+    #[track_caller]
+    pub fn shutdown(callable: RawIdentifiers) -> Self {
+        Self {
+            callable: raw_identifiers2callable(callable),
+            phase: LifecyclePhase::Shutdown,
+            timeout: None,
+        }
+    }
+
+    /// Set the maximum amount of time the hook is allowed to run for.
+    ///
+    /// Check out [`RegisteredLifecycleHook::timeout`] for more details.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Register this hook with a [`Blueprint`].
+    ///
+    /// Check out the documentation of [`Blueprint::on_startup`] and [`Blueprint::on_shutdown`]
+    /// for more details.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredLifecycleHook {
+        bp.register_lifecycle_hook(self)
+    }
+}
@@ -4,12 +4,15 @@
 //! Pavex guide for more details on the role of [`Blueprint`] in Pavex applications.
 pub use blueprint::Blueprint;
 
+pub mod background_task;
 #[allow(clippy::module_inception)]
 mod blueprint;
+pub mod condition;
 pub mod config;
 pub mod constructor;
 mod conversions;
 pub mod error_observer;
+pub mod lifecycle_hook;
 pub mod linter;
 pub mod middleware;
 pub mod nesting;
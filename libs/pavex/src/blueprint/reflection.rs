@@ -97,3 +97,61 @@ macro_rules! t {
         }
     }};
 }
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone)]
+/// An implementation detail of the builder.
+/// You must use the [`from!`] macro wherever an instance of `Sources` is needed.
+///
+/// [`from!`]: crate::from
+pub struct Sources {
+    #[doc(hidden)]
+    pub paths: &'static [&'static str],
+    #[doc(hidden)]
+    pub crate_name: &'static str,
+    #[doc(hidden)]
+    pub module_path: &'static str,
+}
+
+#[macro_export]
+/// Specify the modules that [`Blueprint::import`] should scan for annotated components.
+///
+/// Each path must point to a module, either in the current crate (e.g. `crate::routes`),
+/// relative to the module where `from!` is invoked (e.g. `self::routes` or `super::routes`)
+/// or in one of your dependencies (e.g. `my_dependency` or `my_dependency::routes`).
+/// Submodules are scanned as well.
+/// Only public items are imported from dependencies.
+///
+/// Paths are checked at compile time: `from!` won't compile if one of them
+/// doesn't point to an existing item.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::from;
+///
+/// # mod routes {}
+/// # mod middlewares {}
+/// # fn main() {
+/// let mut bp = Blueprint::new();
+/// bp.import(from![crate::routes, crate::middlewares]);
+/// # }
+/// ```
+///
+/// [`Blueprint::import`]: crate::blueprint::Blueprint::import
+macro_rules! from {
+    ($($($segment:ident)::+),+ $(,)?) => {{
+        // Make sure that every path points to an item that actually exists,
+        // so that typos are caught by `rustc` rather than by `pavexc`.
+        $(
+            #[allow(unused_imports)]
+            use $($segment)::+ as _;
+        )+
+
+        $crate::blueprint::reflection::Sources {
+            paths: &[$(stringify!($($segment)::+)),+],
+            crate_name: ::std::env!("CARGO_PKG_NAME", "Failed to load the CARGO_PKG_NAME environment variable. Are you using a custom build system?"),
+            module_path: module_path!(),
+        }
+    }};
+}
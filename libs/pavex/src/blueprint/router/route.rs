@@ -1,5 +1,6 @@
 use crate::blueprint::Blueprint;
-use crate::blueprint::conversions::raw_identifiers2callable;
+use crate::blueprint::condition::Condition;
+use crate::blueprint::conversions::{condition2condition, raw_identifiers2callable};
 use crate::blueprint::reflection::RawIdentifiers;
 use crate::blueprint::router::MethodGuard;
 use pavex_bp_schema::{Blueprint as BlueprintSchema, Callable, Component};
@@ -61,6 +62,44 @@ impl RegisteredRoute<'_> {
         self
     }
 
+    /// Only include this route in the generated code if the given condition holds.
+    ///
+    /// If a condition has already been set for this route, the new condition must hold as well.
+    ///
+    /// Each distinct feature or profile multiplies the work Pavex has to do at code generation
+    /// time: check out the [`condition`](crate::blueprint::condition) module for the limits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::{Blueprint, condition::Condition, router::GET};
+    /// use pavex::response::Response;
+    ///
+    /// fn debug_info() -> Response {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.route(GET, "/debug", f!(crate::debug_info))
+    ///     .only_if(Condition::profile("dev"));
+    /// # }
+    /// ```
+    ///
+    /// Check out [`RegisteredConstructor::only_if`](crate::blueprint::constructor::RegisteredConstructor::only_if)
+    /// for more details on conditional registration.
+    pub fn only_if(mut self, condition: Condition) -> Self {
+        let condition = condition2condition(condition);
+        let slot = &mut self.route().condition;
+        *slot = Some(match slot.take() {
+            Some(existing) => pavex_bp_schema::Condition::All(vec![existing, condition]),
+            None => condition,
+        });
+        self
+    }
+
     fn route(&mut self) -> &mut pavex_bp_schema::Route {
         let component = &mut self.blueprint.components[self.component_id];
         let Component::Route(c) = component else {
@@ -89,6 +128,7 @@ pub struct Route {
     pub(in crate::blueprint) path: String,
     pub(in crate::blueprint) callable: Callable,
     pub(in crate::blueprint) error_handler: Option<Callable>,
+    pub(in crate::blueprint) condition: Option<Condition>,
 }
 
 impl Route {
@@ -101,6 +141,7 @@ impl Route {
         Self {
             callable: raw_identifiers2callable(callable),
             error_handler: None,
+            condition: None,
             method_guard,
             path: path.to_owned(),
         }
@@ -115,6 +156,17 @@ impl Route {
         self
     }
 
+    /// Only include this route in the generated code if the given condition holds.
+    ///
+    /// Check out the documentation of [`RegisteredRoute::only_if`] for more details.
+    pub fn only_if(mut self, condition: Condition) -> Self {
+        self.condition = Some(match self.condition {
+            Some(existing) => existing.and(condition),
+            None => condition,
+        });
+        self
+    }
+
     /// Register this route with a [`Blueprint`].
     ///
     /// Check out the documentation of [`Blueprint::route`] for more details.
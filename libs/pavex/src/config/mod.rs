@@ -1,19 +1,36 @@
 //! Utilities to load the hierarchical configuration for a Pavex application.
 //!
 //! [`ConfigLoader`] is the key type in this module.
+//! Wrap sensitive values in [`Secret`] to keep them out of your logs.
 //!
 //! # Guide
 //!
 //! Check out [the guide](https://pavex.dev/docs/guide/configuration/)
 //! for a thorough introduction to Pavex configuration system.
-use std::{path::PathBuf, str::FromStr};
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 
 use anyhow::Context;
 use figment::{
     Figment,
-    providers::{Env, Format, Yaml},
+    providers::{Env, Format, Json, Toml, Yaml},
 };
 use serde::de::DeserializeOwned;
+use sources::{ConfigSource, DotEnv, SecretsDir, SharedProvider};
+
+mod reloadable;
+mod secret;
+mod sources;
+mod watch;
+
+pub use reloadable::Reloadable;
+pub use secret::Secret;
+pub use watch::ConfigWatcher;
+
+/// Re-export of [`figment`], the library used by [`ConfigLoader`] under the hood.
+///
+/// Implement [`figment::Provider`] to plug a custom source into [`ConfigLoader`]
+/// via [`ConfigLoader::provider`].
+pub use figment;
 
 #[derive(Clone, Debug)]
 /// A utility to load hierarchical configuration in a Pavex application.
@@ -47,6 +64,8 @@ use serde::de::DeserializeOwned;
 pub struct ConfigLoader<Profile> {
     configuration_dir: Option<PathBuf>,
     profile: Option<Profile>,
+    sources: Vec<ConfigSource>,
+    dotenv_file: Option<PathBuf>,
 }
 
 /// A macro to derive an implementation of the [`ConfigProfile`] trait.
@@ -202,6 +221,8 @@ where
         Self {
             configuration_dir: None,
             profile: None,
+            sources: Vec::new(),
+            dotenv_file: None,
         }
     }
 
@@ -235,40 +256,240 @@ where
         self
     }
 
-    /// Load the configuration for the application by merging together three sources:
+    /// Load configuration values from a TOML file.
+    ///
+    /// Relative paths are resolved relative to the [configuration directory](Self::configuration_dir).
+    /// The file is skipped if it doesn't exist.
+    ///
+    /// Check out [`ConfigLoader::load`] to understand how this source is ranked against the others.
+    pub fn toml_file<P>(mut self, path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.sources.push(ConfigSource::Toml(path.into()));
+        self
+    }
+
+    /// Load configuration values from a JSON file.
+    ///
+    /// Relative paths are resolved relative to the [configuration directory](Self::configuration_dir).
+    /// The file is skipped if it doesn't exist.
+    ///
+    /// Check out [`ConfigLoader::load`] to understand how this source is ranked against the others.
+    pub fn json_file<P>(mut self, path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.sources.push(ConfigSource::Json(path.into()));
+        self
+    }
+
+    /// Load configuration values from a directory of files, one file per value.
+    ///
+    /// This is the layout used by most platforms to expose secrets to an application,
+    /// e.g. `/run/secrets` for Docker secrets or a Kubernetes secret mounted as a volume.
+    ///
+    /// The name of each file is used as key, following the same conventions used for environment variables,
+    /// minus the `PX_` prefix: `__` separates nested keys and names are case-insensitive.
+    /// E.g. the contents of `/run/secrets/database__password` are used as the value of `database.password`.
+    /// Trailing newlines are stripped.
+    /// Hidden files (i.e. starting with `.`) and sub-directories are ignored.
+    ///
+    /// The directory is skipped if it doesn't exist.
+    ///
+    /// Check out [`ConfigLoader::load`] to understand how this source is ranked against the others.
+    ///
+    /// # Values are always strings
+    ///
+    /// Unlike environment variables, the contents of each file are **not** parsed:
+    /// they're always interpreted as strings, even if they look like a number or a boolean.
+    /// A password such as `12345` is loaded as-is, but you can't use a secrets directory to set
+    /// fields of a different type—e.g. a `u16` port or a `bool` flag—since deserialization would fail.
+    /// Set those values using a different source, e.g. your YAML files or environment variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use pavex::config::ConfigLoader;
+    /// # #[derive(pavex::config::ConfigProfile)]
+    /// # pub enum Profile { Dev, Prod }
+    /// # #[derive(serde::Deserialize)]
+    /// # pub struct Config;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let config: Config = ConfigLoader::<Profile>::new()
+    ///     .secrets_dir("/run/secrets")
+    ///     .load()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn secrets_dir<Dir>(mut self, dir: Dir) -> Self
+    where
+        Dir: Into<PathBuf>,
+    {
+        self.sources.push(ConfigSource::SecretsDir(dir.into()));
+        self
+    }
+
+    /// Load configuration values from a custom [`figment::Provider`].
+    ///
+    /// Check out [`ConfigLoader::load`] to understand how this source is ranked against the others.
+    pub fn provider<P>(mut self, provider: P) -> Self
+    where
+        P: figment::Provider + Send + Sync + 'static,
+    {
+        self.sources
+            .push(ConfigSource::Custom(SharedProvider(Arc::new(provider))));
+        self
+    }
+
+    /// Load `PX_`-prefixed variables from a `.env` file, as if they were environment variables.
+    ///
+    /// This is primarily useful for local development.
+    /// Variables that are actually set in the environment take precedence
+    /// over the ones defined in the `.env` file.
+    /// Relative paths are resolved relative to the current working directory.
+    /// The file is skipped if it doesn't exist.
+    ///
+    /// The `.env` file is **not** used to determine the [profile](ConfigProfile).
+    pub fn dotenv_file<P>(mut self, path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.dotenv_file = Some(path.into());
+        self
+    }
+
+    /// Load the configuration for the application by merging together the following sources:
     ///
     /// 1. Environment variables (`PX_*`)
-    /// 2. Profile-specific configuration file (`{configuration_dir}/{profile}.yml`)
-    /// 3. Base configuration file (`{configuration_dir}/base.yml`)
+    /// 2. `PX_*` variables in the `.env` file, if one was specified via [`ConfigLoader::dotenv_file`]
+    /// 3. Additional sources specified via [`ConfigLoader::toml_file`], [`ConfigLoader::json_file`],
+    ///    [`ConfigLoader::secrets_dir`] and [`ConfigLoader::provider`]
+    /// 4. Profile-specific configuration file (`{configuration_dir}/{profile}.yml`)
+    /// 5. Base configuration file (`{configuration_dir}/base.yml`)
     ///
     /// The list above is ordered by precedence: environment variables take precedence
-    /// over profile-specific configuration files, which in turn take precedence
-    /// over the base configuration file.
+    /// over the `.env` file, which in turn takes precedence over additional sources, and so on.
+    /// Additional sources are ranked according to the order they were added in:
+    /// sources added later take precedence over sources added earlier.
     ///
     /// # Guide
     ///
     /// Check out [the guide](https://pavex.dev/docs/guide/configuration/loading/)
     /// for an overview of Pavex's configuration hierarchy, as well as a detailed
     /// explanation of the naming convention used for environment variables.
+    ///
+    /// # Validation
+    ///
+    /// Pavex generates a JSON Schema for your application configuration
+    /// (`configuration/schema.json`, in the directory of your server SDK crate).
+    /// Run `pavex config check <configuration_dir> --profile <profile>` to validate
+    /// the configuration for a profile against it, using the same sources listed above.
     pub fn load<Config>(self) -> Result<Config, errors::ConfigLoadError>
     where
         Config: DeserializeOwned,
     {
-        let profile = match self.profile {
+        let loaded_profile;
+        let profile = match &self.profile {
             Some(profile) => profile,
-            None => Profile::load().map_err(|e| errors::ConfigLoadError(e.into()))?,
+            None => {
+                loaded_profile = Profile::load().map_err(|e| errors::ConfigLoadError(e.into()))?;
+                &loaded_profile
+            }
         };
-        let configuration_dir = self
-            .configuration_dir
-            .unwrap_or_else(|| PathBuf::from("configuration"));
+        let configuration_dir = self.resolved_configuration_dir();
+        let sources = self
+            .sources
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
         let span = tracing::info_span!(
             "Loading configuration",
             configuration.directory = %configuration_dir.display(),
             configuration.profile = %profile.as_ref(),
+            configuration.sources = %sources,
+            configuration.dotenv_file = self.dotenv_file.as_ref().map(|p| tracing::field::display(p.display())),
         );
         let _guard = span.enter();
-        // Load configuration files from the specified directory
-        // and return a `Config` instance.
+        let configuration: Config = self
+            .figment_for(profile)
+            .extract()
+            .context("Failed to load hierarchical configuration")
+            .map_err(errors::ConfigLoadError)?;
+        Ok(configuration)
+    }
+
+    /// Reload the configuration whenever one of the configuration files changes
+    /// or the process receives a `SIGHUP` signal (on Unix).
+    ///
+    /// The configuration is loaded using the same sources and precedence rules of
+    /// [`ConfigLoader::load`], then passed to `on_reload`.
+    /// If the configuration can't be loaded, the error is logged and `on_reload`
+    /// is not invoked: the application keeps running with the previous values.
+    ///
+    /// Configuration files, `.env` files and secret directories are checked for changes
+    /// every second. Custom [providers](ConfigLoader::provider) are only reloaded
+    /// on `SIGHUP`.
+    ///
+    /// The watcher stops when the returned [`ConfigWatcher`] is dropped.
+    ///
+    /// # Example
+    ///
+    /// Combine `watch` with the `ConfigReloader` generated by Pavex to update
+    /// the values of [reloadable configuration types](crate::blueprint::config::RegisteredConfigType::reloadable):
+    ///
+    /// ```rust,ignore
+    /// use pavex::config::ConfigLoader;
+    /// use server_sdk::{ApplicationConfig, ApplicationState};
+    ///
+    /// let config: ApplicationConfig = ConfigLoader::<Profile>::new().load()?;
+    /// let state = ApplicationState::new(config).await?;
+    /// let reloader = state.config_reloader();
+    /// let _watcher = ConfigLoader::<Profile>::new()
+    ///     .watch(move |config: ApplicationConfig| reloader.reload(config));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `watch` panics if it's not invoked from within a `tokio` runtime.
+    pub fn watch<Config, F>(self, on_reload: F) -> ConfigWatcher
+    where
+        Config: DeserializeOwned,
+        F: FnMut(Config) + Send + 'static,
+        Profile: Clone + Send + 'static,
+    {
+        ConfigWatcher::spawn(self, on_reload)
+    }
+
+    /// Combine all the configuration sources into a [`Figment`](figment::Figment),
+    /// without extracting the configuration out of it.
+    ///
+    /// The sources and their precedence are the same ones used by [`ConfigLoader::load`],
+    /// as well as the rules to determine the profile.
+    /// Use this method if you need to know where each configuration value comes from,
+    /// e.g. to validate the configuration and point at the offending source when something's wrong.
+    pub fn figment(&self) -> Result<Figment, errors::ConfigLoadError> {
+        let figment = match &self.profile {
+            Some(profile) => self.figment_for(profile),
+            None => {
+                let profile = Profile::load().map_err(|e| errors::ConfigLoadError(e.into()))?;
+                self.figment_for(&profile)
+            }
+        };
+        Ok(figment)
+    }
+
+    fn resolved_configuration_dir(&self) -> PathBuf {
+        self.configuration_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("configuration"))
+    }
+
+    /// Combine all the configuration sources, ordered by precedence.
+    fn figment_for(&self, profile: &Profile) -> Figment {
+        let configuration_dir = self.resolved_configuration_dir();
         let base_filepath = configuration_dir.join("base.yml");
         let profile_filepath = configuration_dir.join(format!("{}.yml", profile.as_ref()));
 
@@ -279,16 +500,49 @@ where
             // to allow users to set `#[serde(deny_unknown_fields)]` on their configuration type.
             // Without this `ignore`, `serde` would complain about `PX_PROFILE` being unknown.
             .ignore(&[PROFILE_ENV_VAR.strip_prefix(prefix).unwrap()]);
-        let figment = Figment::new()
+        let mut figment = Figment::new()
             .merge(Yaml::file(base_filepath))
-            .merge(Yaml::file(profile_filepath))
-            .merge(env_source);
+            .merge(Yaml::file(profile_filepath));
+        for source in &self.sources {
+            figment = match source {
+                ConfigSource::Toml(path) => figment.merge(Toml::file(configuration_dir.join(path))),
+                ConfigSource::Json(path) => figment.merge(Json::file(configuration_dir.join(path))),
+                ConfigSource::SecretsDir(dir) => figment.merge(SecretsDir(dir.to_owned())),
+                ConfigSource::Custom(provider) => figment.merge(provider.clone()),
+            };
+        }
+        if let Some(path) = &self.dotenv_file {
+            figment = figment.merge(DotEnv {
+                path: path.to_owned(),
+                prefix,
+                ignore: PROFILE_ENV_VAR.strip_prefix(prefix).unwrap(),
+            });
+        }
+        figment.merge(env_source)
+    }
 
-        let configuration: Config = figment
-            .extract()
-            .context("Failed to load hierarchical configuration")
-            .map_err(errors::ConfigLoadError)?;
-        Ok(configuration)
+    /// The files and directories that should be monitored for changes.
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        let configuration_dir = self.resolved_configuration_dir();
+        let mut paths = vec![configuration_dir.join("base.yml")];
+        match &self.profile {
+            Some(profile) => {
+                paths.push(configuration_dir.join(format!("{}.yml", profile.as_ref())))
+            }
+            // We don't know which profile will be selected: watch the whole directory.
+            None => paths.push(configuration_dir.clone()),
+        }
+        for source in &self.sources {
+            match source {
+                ConfigSource::Toml(path) | ConfigSource::Json(path) => {
+                    paths.push(configuration_dir.join(path))
+                }
+                ConfigSource::SecretsDir(dir) => paths.push(dir.to_owned()),
+                ConfigSource::Custom(_) => {}
+            }
+        }
+        paths.extend(self.dotenv_file.clone());
+        paths
     }
 }
 
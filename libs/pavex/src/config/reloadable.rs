@@ -0,0 +1,73 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+
+/// A handle to the current values of a reloadable configuration type.
+///
+/// Pavex stores a `Reloadable<T>` in the application state for every configuration type
+/// registered via [`RegisteredConfigType::reloadable`].
+/// All clones of a handle share the same underlying value: when it's updated
+/// (see [`Reloadable::update`]), every clone observes the new values.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::config::Reloadable;
+///
+/// #[derive(Debug, Clone, serde::Deserialize)]
+/// pub struct LogConfig {
+///     pub level: String,
+/// }
+///
+/// # fn main() {
+/// let handle = Reloadable::new(LogConfig { level: "info".into() });
+/// let clone = handle.clone();
+/// handle.update(LogConfig { level: "debug".into() });
+/// assert_eq!(clone.current().level, "debug");
+/// # }
+/// ```
+///
+/// [`RegisteredConfigType::reloadable`]: crate::blueprint::config::RegisteredConfigType::reloadable
+pub struct Reloadable<T>(Arc<RwLock<Arc<T>>>);
+
+impl<T> Reloadable<T> {
+    /// Create a new handle, initialised with the given value.
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(value))))
+    }
+
+    /// Get a reference-counted snapshot of the current values.
+    ///
+    /// The snapshot is not affected by later updates.
+    pub fn snapshot(&self) -> Arc<T> {
+        // The lock is only held to swap or clone an `Arc`: it can't be poisoned
+        // by a panic that left the value in an inconsistent state.
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the current values.
+    pub fn update(&self, value: T) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(value);
+    }
+}
+
+impl<T: Clone> Reloadable<T> {
+    /// Get a copy of the current values.
+    ///
+    /// Pavex uses this method to inject reloadable configuration types
+    /// into request-scoped and transient components.
+    pub fn current(&self) -> T {
+        T::clone(&self.snapshot())
+    }
+}
+
+impl<T> Clone for Reloadable<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Debug> Debug for Reloadable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Reloadable").field(&self.snapshot()).finish()
+    }
+}
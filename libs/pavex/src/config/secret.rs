@@ -0,0 +1,104 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use serde::{Deserialize, Deserializer};
+use zeroize::Zeroize;
+
+/// A configuration value that must not be leaked, e.g. a password or an API key.
+///
+/// `Secret<T>` deserializes exactly like `T`, from any configuration source,
+/// but its [`Debug`] and [`Display`] representations never include the wrapped value.
+/// This keeps it out of your logs, including `tracing` fields (`?config`, `%config`)
+/// and the `Debug` representation of the `ApplicationConfig` generated by Pavex.
+///
+/// The wrapped value is zeroed out in memory when the secret is dropped.
+/// `T` must implement [`zeroize::Zeroize`], which is already the case for `String`,
+/// `Vec<u8>` and integer types.
+///
+/// Pavex recognises `Secret` fields when exporting the JSON Schema for your configuration:
+/// they are marked with `"writeOnly": true` and `"x-pavex-secret": true`.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::config::Secret;
+///
+/// #[derive(Debug, Clone, serde::Deserialize)]
+/// pub struct DatabaseConfig {
+///     pub username: String,
+///     pub password: Secret<String>,
+/// }
+///
+/// # fn main() {
+/// let config: DatabaseConfig =
+///     serde_json::from_str(r#"{"username": "app", "password": "hunter2"}"#).unwrap();
+/// assert_eq!(
+///     format!("{config:?}"),
+///     r#"DatabaseConfig { username: "app", password: Secret([REDACTED]) }"#
+/// );
+/// // Access the underlying value explicitly, when you actually need it.
+/// assert_eq!(config.password.expose_secret(), "hunter2");
+/// # }
+/// ```
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    /// Wrap a value to prevent it from being leaked.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Get a reference to the wrapped value.
+    ///
+    /// Be careful with what you do with it!
+    pub fn expose_secret(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Secret<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Zeroize + Default> Default for Secret<T> {
+    fn default() -> Self {
+        Self(T::default())
+    }
+}
+
+impl<T: Zeroize> Debug for Secret<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl<T: Zeroize> Display for Secret<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Secret<T>
+where
+    T: Zeroize + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Self)
+    }
+}
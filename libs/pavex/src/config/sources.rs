@@ -0,0 +1,289 @@
+//! Additional configuration sources, on top of the YAML files and the environment variables
+//! that [`ConfigLoader`](super::ConfigLoader) always loads.
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use figment::value::{Dict, Map, Value};
+use figment::{Error, Metadata, Profile, Provider};
+
+/// A configuration source registered via one of [`ConfigLoader`](super::ConfigLoader)'s
+/// builder methods.
+#[derive(Clone)]
+pub(super) enum ConfigSource {
+    Toml(PathBuf),
+    Json(PathBuf),
+    SecretsDir(PathBuf),
+    Custom(SharedProvider),
+}
+
+impl std::fmt::Debug for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Toml(path) => f.debug_tuple("Toml").field(path).finish(),
+            ConfigSource::Json(path) => f.debug_tuple("Json").field(path).finish(),
+            ConfigSource::SecretsDir(path) => f.debug_tuple("SecretsDir").field(path).finish(),
+            ConfigSource::Custom(provider) => f
+                .debug_tuple("Custom")
+                .field(&provider.metadata().name)
+                .finish(),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Toml(path) | ConfigSource::Json(path) => {
+                write!(f, "{}", path.display())
+            }
+            ConfigSource::SecretsDir(path) => write!(f, "secrets:{}", path.display()),
+            ConfigSource::Custom(provider) => write!(f, "{}", provider.metadata().name),
+        }
+    }
+}
+
+/// A type-erased, cheaply clonable [`Provider`].
+#[derive(Clone)]
+pub(super) struct SharedProvider(pub(super) Arc<dyn Provider + Send + Sync>);
+
+impl Provider for SharedProvider {
+    fn metadata(&self) -> Metadata {
+        self.0.metadata()
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        self.0.data()
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.0.profile()
+    }
+}
+
+/// Every file in the directory is a configuration value: the file name is the key,
+/// the file contents are the value.
+///
+/// File names follow the same conventions used for environment variables, minus the prefix:
+/// they're lowercased and `__` is used to separate nested keys.
+/// E.g. the contents of `database__password` become the value of `database.password`.
+pub(super) struct SecretsDir(pub(super) PathBuf);
+
+impl Provider for SecretsDir {
+    fn metadata(&self) -> Metadata {
+        Metadata::named(format!("secrets directory `{}`", self.0.display()))
+            .interpolater(|_: &Profile, keys: &[&str]| keys.join("__").to_ascii_lowercase())
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let mut dict = Dict::new();
+        let entries = match fs_err::read_dir(&self.0) {
+            Ok(entries) => entries,
+            // Secrets may not be mounted at all, e.g. when running locally.
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Profile::Default.collect(dict));
+            }
+            Err(e) => return Err(e.to_string().into()),
+        };
+        for entry in entries {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let Some(name) = entry.file_name().to_str().map(ToOwned::to_owned) else {
+                continue;
+            };
+            // Skip hidden files, including the `..data` directory and the symlinks
+            // that Kubernetes creates when mounting secrets as volumes.
+            if name.starts_with('.') {
+                continue;
+            }
+            // `metadata` follows symlinks, unlike `entry.file_type()`.
+            let is_file = fs_err::metadata(entry.path())
+                .map(|m| m.is_file())
+                .unwrap_or(false);
+            if !is_file {
+                continue;
+            }
+            let contents = fs_err::read_to_string(entry.path()).map_err(|e| e.to_string())?;
+            let value = contents.trim_end_matches(['\n', '\r']).to_owned();
+            let keys: Vec<_> = name
+                .to_ascii_lowercase()
+                .split("__")
+                .map(String::from)
+                .collect();
+            insert(&mut dict, &keys, Value::from(value));
+        }
+        Ok(Profile::Default.collect(dict))
+    }
+}
+
+/// The `PX_`-prefixed entries in a `.env` file, interpreted as if they were
+/// environment variables.
+pub(super) struct DotEnv {
+    pub(super) path: PathBuf,
+    pub(super) prefix: &'static str,
+    pub(super) ignore: &'static str,
+}
+
+impl Provider for DotEnv {
+    fn metadata(&self) -> Metadata {
+        let prefix = self.prefix;
+        Metadata::from(
+            format!("`{prefix}` variable(s) in `.env` file"),
+            self.path.as_path(),
+        )
+        .interpolater(move |_: &Profile, keys: &[&str]| {
+            format!("{prefix}{}", keys.join("__").to_ascii_uppercase())
+        })
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let mut dict = Dict::new();
+        let entries = match dotenvy::from_path_iter(&self.path) {
+            Ok(entries) => entries,
+            Err(e) if e.not_found() => return Ok(Profile::Default.collect(dict)),
+            Err(e) => {
+                return Err(format!("Failed to read `{}`: {e}", self.path.display()).into());
+            }
+        };
+        for entry in entries {
+            let (key, value) =
+                entry.map_err(|e| format!("Failed to parse `{}`: {e}", self.path.display()))?;
+            let Some(key) = strip_prefix_ignore_case(&key, self.prefix) else {
+                continue;
+            };
+            if key.eq_ignore_ascii_case(self.ignore) {
+                continue;
+            }
+            let keys: Vec<_> = key
+                .to_ascii_lowercase()
+                .split("__")
+                .map(String::from)
+                .collect();
+            // Values are parsed using the same rules applied to environment variables.
+            insert(&mut dict, &keys, value.parse().expect("infallible"));
+        }
+        Ok(Profile::Default.collect(dict))
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &s[prefix.len()..])
+}
+
+/// Insert a value at the given (nested) key path, creating intermediate dictionaries
+/// as needed.
+fn insert(dict: &mut Dict, keys: &[String], value: Value) {
+    let Some((first, rest)) = keys.split_first() else {
+        return;
+    };
+    if first.is_empty() {
+        return;
+    }
+    if rest.is_empty() {
+        dict.insert(first.to_owned(), value);
+        return;
+    }
+    let entry = dict
+        .entry(first.to_owned())
+        .or_insert_with(|| Value::from(Dict::new()));
+    if !matches!(entry, Value::Dict(..)) {
+        *entry = Value::from(Dict::new());
+    }
+    if let Value::Dict(_, nested) = entry {
+        insert(nested, rest, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DotEnv, SecretsDir};
+    use figment::Figment;
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Config {
+        database: Database,
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Database {
+        password: String,
+        port: u16,
+    }
+
+    #[test]
+    fn secrets_are_read_from_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("database__password"), "hunter2\n").unwrap();
+        std::fs::write(dir.path().join(".hidden"), "ignored").unwrap();
+        std::fs::create_dir(dir.path().join("..data")).unwrap();
+
+        let config: Config = Figment::new()
+            .merge(("database.port", 5432))
+            .merge(SecretsDir(dir.path().to_owned()))
+            .extract()
+            .unwrap();
+        assert_eq!(config.database.password, "hunter2");
+        assert_eq!(config.database.port, 5432);
+    }
+
+    #[test]
+    fn secrets_are_never_parsed() {
+        #[derive(serde::Deserialize)]
+        struct Port {
+            #[allow(dead_code)]
+            port: u16,
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("database__password"), "12345").unwrap();
+        std::fs::write(dir.path().join("port"), "5432").unwrap();
+
+        let figment = Figment::new()
+            .merge(("database.port", 5432))
+            .merge(SecretsDir(dir.path().to_owned()));
+        let config: Config = figment.extract().unwrap();
+        assert_eq!(config.database.password, "12345");
+        // Only string fields can be set via a secrets directory.
+        assert!(figment.extract::<Port>().is_err());
+    }
+
+    #[test]
+    fn a_missing_secrets_dir_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: Config = Figment::new()
+            .merge(("database", serde_json::json!({"password": "a", "port": 1})))
+            .merge(SecretsDir(dir.path().join("missing")))
+            .extract()
+            .unwrap();
+        assert_eq!(config.database.port, 1);
+    }
+
+    #[test]
+    fn only_prefixed_dotenv_entries_are_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            "PX_DATABASE__PASSWORD=secret\nPX_DATABASE__PORT=5433\nPX_PROFILE=dev\nOTHER=1\n",
+        )
+        .unwrap();
+
+        let provider = DotEnv {
+            path,
+            prefix: "PX_",
+            ignore: "PROFILE",
+        };
+        let data = figment::Provider::data(&provider).unwrap();
+        let dict = &data[&figment::Profile::Default];
+        assert_eq!(dict.keys().collect::<Vec<_>>(), vec!["database"]);
+
+        let config: Config = Figment::new().merge(provider).extract().unwrap();
+        assert_eq!(
+            config.database,
+            Database {
+                password: "secret".into(),
+                port: 5433
+            }
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::de::DeserializeOwned;
+use tokio::task::JoinHandle;
+
+use super::{ConfigLoader, ConfigProfile};
+
+/// How often configuration files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A background task that reloads the application configuration when it changes.
+///
+/// Returned by [`ConfigLoader::watch`]. The task is stopped when the watcher is dropped.
+#[must_use = "The configuration is no longer watched once the `ConfigWatcher` is dropped"]
+pub struct ConfigWatcher {
+    task: JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+    pub(super) fn spawn<Profile, Config, F>(loader: ConfigLoader<Profile>, mut on_reload: F) -> Self
+    where
+        Profile: ConfigProfile + Clone + Send + 'static,
+        Config: DeserializeOwned,
+        F: FnMut(Config) + Send + 'static,
+    {
+        let paths = loader.watched_paths();
+        let task = tokio::spawn(async move {
+            let mut fingerprint = Fingerprint::compute(&paths);
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut sighup = Sighup::new();
+            loop {
+                let trigger = tokio::select! {
+                    _ = interval.tick() => {
+                        let current = Fingerprint::compute(&paths);
+                        if current == fingerprint {
+                            continue;
+                        }
+                        fingerprint = current;
+                        "file change"
+                    }
+                    _ = sighup.recv() => "SIGHUP",
+                };
+                let span = tracing::info_span!(
+                    "Reloading configuration",
+                    configuration.reload_trigger = trigger
+                );
+                let _guard = span.enter();
+                match loader.clone().load::<Config>() {
+                    Ok(config) => {
+                        on_reload(config);
+                        tracing::info!("Configuration reloaded");
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            error.msg = %e,
+                            error.details = ?e,
+                            "Failed to reload configuration. The previous values will be kept"
+                        );
+                    }
+                }
+            }
+        });
+        Self { task }
+    }
+
+    /// Stop watching for changes.
+    ///
+    /// Equivalent to dropping the watcher.
+    pub fn stop(self) {}
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The last modification time of every watched file, used to detect changes.
+///
+/// Directories are expanded to include the modification time of the files they contain.
+#[derive(PartialEq, Eq)]
+struct Fingerprint(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Fingerprint {
+    fn compute(paths: &[PathBuf]) -> Self {
+        let mut entries = Vec::new();
+        for path in paths {
+            entries.push((path.to_owned(), modified(path)));
+            if path.is_dir() {
+                let Ok(children) = fs_err::read_dir(path) else {
+                    continue;
+                };
+                let mut children: Vec<_> = children
+                    .filter_map(|c| c.ok())
+                    .map(|c| c.path())
+                    .filter(|p| p.is_file())
+                    .map(|p| {
+                        let modified = modified(&p);
+                        (p, modified)
+                    })
+                    .collect();
+                children.sort();
+                entries.extend(children);
+            }
+        }
+        Self(entries)
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    // `metadata` follows symlinks, which is what we want for secrets
+    // mounted as volumes (e.g. in Kubernetes).
+    fs_err::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// A stream of `SIGHUP` signals. It never yields on platforms without `SIGHUP`.
+struct Sighup {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Sighup {
+    fn new() -> Self {
+        #[cfg(unix)]
+        {
+            let signal =
+                tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()).ok();
+            if signal.is_none() {
+                tracing::warn!(
+                    "Failed to install a `SIGHUP` handler: configuration will only be reloaded on file changes"
+                );
+            }
+            Self { signal }
+        }
+        #[cfg(not(unix))]
+        Self {}
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            if signal.recv().await.is_some() {
+                return;
+            }
+        }
+        std::future::pending::<()>().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fingerprint;
+
+    #[test]
+    fn fingerprint_changes_when_a_file_in_a_watched_directory_is_added() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = vec![dir.path().join("base.yml"), dir.path().to_owned()];
+        let before = Fingerprint::compute(&paths);
+        std::fs::write(dir.path().join("prod.yml"), "a: 1").unwrap();
+        let after = Fingerprint::compute(&paths);
+        assert!(before != after);
+        assert!(after == Fingerprint::compute(&paths));
+    }
+}
@@ -94,3 +94,69 @@ impl InjectResponseCookiesError {
         Response::internal_server_error()
     }
 }
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`validate_prefix`](super::validate_prefix) when a cookie
+/// doesn't satisfy the requirements of its name prefix.
+pub enum CookiePrefixError {
+    #[error("The `{cookie_name}` cookie must be marked as `Secure`, since its name starts with `{}`", prefix.as_str())]
+    NotSecure {
+        /// The name of the offending cookie.
+        cookie_name: String,
+        /// The prefix of the cookie name.
+        prefix: super::CookiePrefix,
+    },
+    #[error(
+        "The `{cookie_name}` cookie can't have a `Domain` attribute, since its name starts with `__Host-`"
+    )]
+    HasDomain {
+        /// The name of the offending cookie.
+        cookie_name: String,
+    },
+    #[error(
+        "The `{cookie_name}` cookie must have its `Path` set to `/`, since its name starts with `__Host-`"
+    )]
+    InvalidPath {
+        /// The name of the offending cookie.
+        cookie_name: String,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned when retrieving a cookie from [`SignedCookies`](super::SignedCookies)
+/// or [`EncryptedCookies`](super::EncryptedCookies).
+pub enum GetCookieError {
+    /// None of the configured keys could verify or decrypt the cookie value.
+    #[error(transparent)]
+    Crypto(CryptoError),
+    /// The cookie value was verified, but it couldn't be deserialized into the expected type.
+    #[error("Failed to deserialize the value of the `{cookie_name}` cookie")]
+    Deserialization {
+        /// The name of the cookie.
+        cookie_name: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error(transparent)]
+    Unexpected(UnexpectedError),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned when inserting a cookie via [`SignedCookies`](super::SignedCookies)
+/// or [`EncryptedCookies`](super::EncryptedCookies).
+pub enum InsertCookieError {
+    /// The cookie doesn't satisfy the requirements of its name prefix.
+    #[error(transparent)]
+    InvalidPrefix(#[from] CookiePrefixError),
+    /// The value couldn't be serialized.
+    #[error("Failed to serialize the value of the `{cookie_name}` cookie")]
+    Serialization {
+        /// The name of the cookie.
+        cookie_name: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
@@ -0,0 +1,384 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::config::{CryptoAlgorithm, CryptoRule, FallbackConfig};
+use super::errors::{GetCookieError, InsertCookieError, ProcessIncomingError};
+use super::{
+    Key, Processor, ProcessorConfig, RequestCookies, ResponseCookie, ResponseCookies,
+    validate_prefix,
+};
+use crate::error::UnexpectedError;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[non_exhaustive]
+/// Configure the keys used by [`SignedCookies`] and [`EncryptedCookies`].
+///
+/// # Key rotation
+///
+/// To rotate keys without invalidating the cookies that clients already hold:
+///
+/// 1. Set [`key`](Self::key) to a new key, and move the old one to
+///    [`fallback_keys`](Self::fallback_keys)
+/// 2. Wait for the expiration of all cookies signed or encrypted with the old key
+/// 3. Remove the old key from [`fallback_keys`](Self::fallback_keys)
+pub struct CookieJarConfig {
+    /// The key used to sign and encrypt cookies.
+    ///
+    /// It must be at least 32 bytes long and it should be generated using
+    /// a cryptographically secure random number generator.
+    ///
+    /// There is no default key: it must always be specified.
+    pub key: Key,
+    /// Keys that are no longer used to sign or encrypt cookies, but are still
+    /// accepted when verifying or decrypting them.
+    ///
+    /// They are tried in order, after [`key`](Self::key).
+    #[serde(default)]
+    pub fallback_keys: Vec<Key>,
+}
+
+#[derive(Debug, Clone)]
+/// Typed access to cookies whose values are signed, using HMAC-SHA256.
+///
+/// Signing guarantees the **integrity** of the value: the client can read it,
+/// but it can't tamper with it.
+/// Use [`EncryptedCookies`] if the value must be kept confidential.
+///
+/// Values are serialized as JSON before being signed.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::cookie::{RequestCookies, ResponseCookie, ResponseCookies, SignedCookies};
+/// use pavex::cookie::errors::{GetCookieError, InsertCookieError};
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// pub struct Preferences {
+///     pub dark_mode: bool,
+/// }
+///
+/// pub fn get_preferences(
+///     signed: &SignedCookies,
+///     request_cookies: &RequestCookies,
+/// ) -> Result<Option<Preferences>, GetCookieError> {
+///     signed.get::<Preferences>(request_cookies, "__Host-preferences")
+/// }
+///
+/// pub fn set_preferences(
+///     signed: &SignedCookies,
+///     response_cookies: &mut ResponseCookies,
+/// ) -> Result<(), InsertCookieError> {
+///     // The value of the cookie will be replaced by the signed value.
+///     let cookie = ResponseCookie::new("__Host-preferences", "")
+///         .set_secure(true)
+///         .set_path("/");
+///     signed.insert(response_cookies, cookie, &Preferences { dark_mode: true })
+/// }
+/// ```
+pub struct SignedCookies(Jar);
+
+impl SignedCookies {
+    /// Build a new [`SignedCookies`] using the keys in [`CookieJarConfig`].
+    pub fn new(config: &CookieJarConfig) -> Self {
+        Self(Jar::new(config, CryptoAlgorithm::Signing))
+    }
+
+    /// Retrieve the request cookie named `name`, verify its signature and
+    /// deserialize its value.
+    ///
+    /// It returns `Ok(None)` if there is no cookie with that name.
+    /// If the client sent multiple cookies with the same name, the first one
+    /// that can be verified is returned.
+    pub fn get<T: DeserializeOwned>(
+        &self,
+        request_cookies: &RequestCookies<'_>,
+        name: &str,
+    ) -> Result<Option<T>, GetCookieError> {
+        self.0.get(request_cookies, name)
+    }
+
+    /// Serialize `value`, sign it and insert it into `response_cookies`
+    /// as the value of `cookie`.
+    ///
+    /// All other attributes of `cookie` (path, domain, etc.) are preserved.
+    /// It fails if `cookie` doesn't satisfy the requirements of its
+    /// [name prefix](super::CookiePrefix).
+    pub fn insert<T: Serialize + ?Sized>(
+        &self,
+        response_cookies: &mut ResponseCookies,
+        cookie: ResponseCookie<'static>,
+        value: &T,
+    ) -> Result<(), InsertCookieError> {
+        self.0.insert(response_cookies, cookie, value)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Typed access to cookies whose values are encrypted, using AEAD-AES-256-GCM-SIV.
+///
+/// Encryption guarantees the **confidentiality** and the **integrity** of the value:
+/// the client can neither read it nor tamper with it.
+///
+/// It exposes the same API as [`SignedCookies`]—check out its documentation
+/// for an example.
+pub struct EncryptedCookies(Jar);
+
+impl EncryptedCookies {
+    /// Build a new [`EncryptedCookies`] using the keys in [`CookieJarConfig`].
+    pub fn new(config: &CookieJarConfig) -> Self {
+        Self(Jar::new(config, CryptoAlgorithm::Encryption))
+    }
+
+    /// Retrieve the request cookie named `name`, decrypt it and
+    /// deserialize its value.
+    ///
+    /// It returns `Ok(None)` if there is no cookie with that name.
+    /// If the client sent multiple cookies with the same name, the first one
+    /// that can be decrypted is returned.
+    pub fn get<T: DeserializeOwned>(
+        &self,
+        request_cookies: &RequestCookies<'_>,
+        name: &str,
+    ) -> Result<Option<T>, GetCookieError> {
+        self.0.get(request_cookies, name)
+    }
+
+    /// Serialize `value`, encrypt it and insert it into `response_cookies`
+    /// as the value of `cookie`.
+    ///
+    /// All other attributes of `cookie` (path, domain, etc.) are preserved.
+    /// It fails if `cookie` doesn't satisfy the requirements of its
+    /// [name prefix](super::CookiePrefix).
+    pub fn insert<T: Serialize + ?Sized>(
+        &self,
+        response_cookies: &mut ResponseCookies,
+        cookie: ResponseCookie<'static>,
+        value: &T,
+    ) -> Result<(), InsertCookieError> {
+        self.0.insert(response_cookies, cookie, value)
+    }
+}
+
+/// The logic shared by [`SignedCookies`] and [`EncryptedCookies`].
+///
+/// The cryptographic heavy lifting is delegated to a [`Processor`] with a single
+/// rule, scoped to the cookie being processed.
+#[derive(Debug, Clone)]
+struct Jar {
+    algorithm: CryptoAlgorithm,
+    key: Key,
+    fallback_keys: Vec<Key>,
+}
+
+impl Jar {
+    fn new(config: &CookieJarConfig, algorithm: CryptoAlgorithm) -> Self {
+        Self {
+            algorithm,
+            key: config.key.clone(),
+            fallback_keys: config.fallback_keys.clone(),
+        }
+    }
+
+    fn processor(&self, name: &str) -> Processor {
+        let fallbacks = self
+            .fallback_keys
+            .iter()
+            .map(|key| FallbackConfig {
+                key: key.clone(),
+                algorithm: self.algorithm,
+            })
+            .collect();
+        let mut config = ProcessorConfig::default();
+        // Percent-encoding is taken care of by the application-wide processor,
+        // when the `Set-Cookie` header is built.
+        config.percent_encode = false;
+        config.crypto_rules.push(CryptoRule {
+            cookie_names: vec![name.to_owned()],
+            algorithm: self.algorithm,
+            key: self.key.clone(),
+            fallbacks,
+        });
+        config.into()
+    }
+
+    fn get<T: DeserializeOwned>(
+        &self,
+        request_cookies: &RequestCookies<'_>,
+        name: &str,
+    ) -> Result<Option<T>, GetCookieError> {
+        let Some(cookies) = request_cookies.get_all(name) else {
+            return Ok(None);
+        };
+        let processor = self.processor(name);
+        let mut error = None;
+        for value in cookies.values() {
+            match processor.process_incoming(name, value) {
+                Ok(cookie) => {
+                    return serde_json::from_str(cookie.value()).map(Some).map_err(|e| {
+                        GetCookieError::Deserialization {
+                            cookie_name: name.to_owned(),
+                            source: e,
+                        }
+                    });
+                }
+                Err(e) => {
+                    // We only want to keep the first error.
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        match error {
+            None => Ok(None),
+            Some(ProcessIncomingError::Crypto(e)) => Err(GetCookieError::Crypto(e)),
+            Some(e) => Err(GetCookieError::Unexpected(UnexpectedError::new(e))),
+        }
+    }
+
+    fn insert<T: Serialize + ?Sized>(
+        &self,
+        response_cookies: &mut ResponseCookies,
+        cookie: ResponseCookie<'static>,
+        value: &T,
+    ) -> Result<(), InsertCookieError> {
+        validate_prefix(&cookie)?;
+        let value = serde_json::to_string(value).map_err(|e| InsertCookieError::Serialization {
+            cookie_name: cookie.name().to_owned(),
+            source: e,
+        })?;
+        let cookie = self
+            .processor(cookie.name())
+            .process_outgoing(cookie.set_value(value));
+        response_cookies.insert(cookie);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CookieJarConfig, EncryptedCookies, SignedCookies};
+    use crate::cookie::errors::{GetCookieError, InsertCookieError};
+    use crate::cookie::{Key, RequestCookie, RequestCookies, ResponseCookie, ResponseCookies};
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Preferences {
+        dark_mode: bool,
+    }
+
+    fn config(key: &Key, fallback_keys: &[Key]) -> CookieJarConfig {
+        CookieJarConfig {
+            key: key.clone(),
+            fallback_keys: fallback_keys.to_vec(),
+        }
+    }
+
+    /// Turn the response cookies into the request cookies a client would send back.
+    fn roundtrip(response_cookies: &ResponseCookies) -> RequestCookies<'static> {
+        let mut request_cookies = RequestCookies::new();
+        for cookie in response_cookies.iter() {
+            request_cookies.append(RequestCookie::new(
+                cookie.name().to_owned(),
+                cookie.value().to_owned(),
+            ));
+        }
+        request_cookies
+    }
+
+    #[test]
+    fn signed_values_roundtrip() {
+        let signed = SignedCookies::new(&config(&Key::generate(), &[]));
+        let mut response_cookies = ResponseCookies::new();
+        let prefs = Preferences { dark_mode: true };
+        signed
+            .insert(
+                &mut response_cookies,
+                ResponseCookie::new("prefs", ""),
+                &prefs,
+            )
+            .unwrap();
+
+        let request_cookies = roundtrip(&response_cookies);
+        let retrieved = signed
+            .get::<Preferences>(&request_cookies, "prefs")
+            .unwrap();
+        assert_eq!(retrieved, Some(prefs));
+        assert_eq!(
+            signed
+                .get::<Preferences>(&request_cookies, "other")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn encrypted_values_are_not_readable() {
+        let encrypted = EncryptedCookies::new(&config(&Key::generate(), &[]));
+        let mut response_cookies = ResponseCookies::new();
+        encrypted
+            .insert(
+                &mut response_cookies,
+                ResponseCookie::new("id", ""),
+                "secret",
+            )
+            .unwrap();
+        let value = response_cookies.get("id").unwrap().value();
+        assert!(!value.contains("secret"));
+
+        let request_cookies = roundtrip(&response_cookies);
+        let retrieved = encrypted.get::<String>(&request_cookies, "id").unwrap();
+        assert_eq!(retrieved.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn tampered_values_are_rejected() {
+        let signed = SignedCookies::new(&config(&Key::generate(), &[]));
+        let mut request_cookies = RequestCookies::new();
+        request_cookies.append(RequestCookie::new("prefs", r#"{"dark_mode":true}"#));
+        assert!(matches!(
+            signed.get::<Preferences>(&request_cookies, "prefs"),
+            Err(GetCookieError::Crypto(_))
+        ));
+    }
+
+    #[test]
+    fn fallback_keys_are_accepted_when_reading() {
+        let (old, new) = (Key::generate(), Key::generate());
+        let before = SignedCookies::new(&config(&old, &[]));
+        let mut response_cookies = ResponseCookies::new();
+        before
+            .insert(&mut response_cookies, ResponseCookie::new("n", ""), &1)
+            .unwrap();
+        let request_cookies = roundtrip(&response_cookies);
+
+        let rotated = SignedCookies::new(&config(&new, &[old]));
+        assert_eq!(rotated.get::<u8>(&request_cookies, "n").unwrap(), Some(1));
+
+        // The fallback is never used to sign.
+        let mut response_cookies = ResponseCookies::new();
+        rotated
+            .insert(&mut response_cookies, ResponseCookie::new("n", ""), &2)
+            .unwrap();
+        let request_cookies = roundtrip(&response_cookies);
+        let after = SignedCookies::new(&config(&new, &[]));
+        assert_eq!(after.get::<u8>(&request_cookies, "n").unwrap(), Some(2));
+        assert!(before.get::<u8>(&request_cookies, "n").is_err());
+    }
+
+    #[test]
+    fn prefixed_cookies_are_validated() {
+        let signed = SignedCookies::new(&config(&Key::generate(), &[]));
+        let mut response_cookies = ResponseCookies::new();
+        let outcome = signed.insert(
+            &mut response_cookies,
+            ResponseCookie::new("__Host-prefs", ""),
+            &1,
+        );
+        assert!(matches!(outcome, Err(InsertCookieError::InvalidPrefix(_))));
+        assert!(response_cookies.get("__Host-prefs").is_none());
+    }
+
+    #[test]
+    fn a_key_is_required() {
+        let e = serde_json::from_str::<CookieJarConfig>("{}").unwrap_err();
+        assert!(e.to_string().contains("missing field `key`"), "{e}");
+    }
+}
@@ -24,6 +24,34 @@ use crate::{f, t};
 /// let mut bp = Blueprint::new();
 /// let kit = CookieKit::new().register(&mut bp);
 /// ```
+///
+/// # Signed and encrypted cookies
+///
+/// [`SignedCookies`] and [`EncryptedCookies`] are not registered by default.
+/// Opt in via [`CookieKit::with_cookie_jars`]:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::cookie::CookieKit;
+///
+/// let mut bp = Blueprint::new();
+/// let kit = CookieKit::new().with_cookie_jars().register(&mut bp);
+/// ```
+///
+/// Their keys are loaded from the `cookie_jars` configuration key, as a [`CookieJarConfig`].
+/// There is no default value: the application fails to load its configuration if
+/// `cookie_jars.key` is missing.
+///
+/// ```yaml
+/// cookie_jars:
+///   # At least 32 bytes, generated using a cryptographically secure RNG.
+///   key: "<your secret key>"
+///   fallback_keys: []
+/// ```
+///
+/// [`SignedCookies`]: super::SignedCookies
+/// [`EncryptedCookies`]: super::EncryptedCookies
+/// [`CookieJarConfig`]: super::CookieJarConfig
 pub struct CookieKit {
     /// The constructor for [`RequestCookies`].
     ///
@@ -63,6 +91,30 @@ pub struct CookieKit {
     /// [`InjectResponseCookiesError::into_response`]: super::errors::InjectResponseCookiesError::into_response
     /// [`inject_response_cookies`]: super::inject_response_cookies
     pub response_cookie_injector: Option<PostProcessingMiddleware>,
+    /// The constructor for [`SignedCookies`].
+    ///
+    /// It's not registered by default.
+    /// [`CookieKit::with_cookie_jars`] sets it to [`SignedCookies::new`].
+    ///
+    /// [`SignedCookies`]: super::SignedCookies
+    /// [`SignedCookies::new`]: super::SignedCookies::new
+    pub signed_cookies: Option<Constructor>,
+    /// The constructor for [`EncryptedCookies`].
+    ///
+    /// It's not registered by default.
+    /// [`CookieKit::with_cookie_jars`] sets it to [`EncryptedCookies::new`].
+    ///
+    /// [`EncryptedCookies`]: super::EncryptedCookies
+    /// [`EncryptedCookies::new`]: super::EncryptedCookies::new
+    pub encrypted_cookies: Option<Constructor>,
+    /// Register [`CookieJarConfig`] as a configuration type.
+    ///
+    /// It's not registered by default.
+    /// [`CookieKit::with_cookie_jars`] registers it under the `cookie_jars` configuration key,
+    /// with no default value.
+    ///
+    /// [`CookieJarConfig`]: super::CookieJarConfig
+    pub jar_config: Option<ConfigType>,
 }
 
 impl Default for CookieKit {
@@ -94,9 +146,30 @@ impl CookieKit {
             response_cookie_injector: Some(response_cookie_injector),
             processor: Some(processor),
             processor_config: Some(processor_config),
+            signed_cookies: None,
+            encrypted_cookies: None,
+            jar_config: None,
         }
     }
 
+    /// Register [`SignedCookies`] and [`EncryptedCookies`], alongside their
+    /// configuration.
+    ///
+    /// The `cookie_jars` configuration key becomes required: check out
+    /// [`CookieJarConfig`] for the available options.
+    ///
+    /// [`SignedCookies`]: super::SignedCookies
+    /// [`EncryptedCookies`]: super::EncryptedCookies
+    /// [`CookieJarConfig`]: super::CookieJarConfig
+    pub fn with_cookie_jars(mut self) -> Self {
+        self.signed_cookies =
+            Some(Constructor::singleton(f!(super::SignedCookies::new)).ignore(Lint::Unused));
+        self.encrypted_cookies =
+            Some(Constructor::singleton(f!(super::EncryptedCookies::new)).ignore(Lint::Unused));
+        self.jar_config = Some(ConfigType::new("cookie_jars", t!(super::CookieJarConfig)));
+        self
+    }
+
     #[doc(hidden)]
     #[deprecated(note = "This call is no longer necessary. \
         The cookie processor configuration will automatically use its default values if left unspecified.")]
@@ -123,6 +196,15 @@ impl CookieKit {
         if let Some(processor_config) = self.processor_config {
             processor_config.register(bp);
         }
+        if let Some(signed_cookies) = self.signed_cookies {
+            signed_cookies.register(bp);
+        }
+        if let Some(encrypted_cookies) = self.encrypted_cookies {
+            encrypted_cookies.register(bp);
+        }
+        if let Some(jar_config) = self.jar_config {
+            jar_config.register(bp);
+        }
         RegisteredCookieKit {}
     }
 }
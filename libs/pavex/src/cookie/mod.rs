@@ -5,6 +5,18 @@
 //! Check out the ["Cookies"](https://pavex.dev/guide/cookies/)
 //! section of Pavex's guide for a thorough introduction to cookies.
 //!
+//! # Signed and encrypted cookies
+//!
+//! [`SignedCookies`] and [`EncryptedCookies`] give you typed access to cookies
+//! whose values must not be tampered with or read by the client.
+//! Values are serialized via `serde`, then signed or encrypted with the key
+//! in [`CookieJarConfig`]. Check out [`CookieJarConfig`] for key rotation.
+//! Use [`CookieKit::with_cookie_jars`] to register them.
+//!
+//! Cookies whose name starts with `__Host-` or `__Secure-` are checked against
+//! the requirements of their [prefix](CookiePrefix) before being inserted—see
+//! [`validate_prefix`].
+//!
 //! # Implementation details
 //!
 //! Most types and functions are re-exports of the
@@ -24,8 +36,14 @@ pub mod response;
 mod components;
 pub use components::{extract_request_cookies, inject_response_cookies};
 
+mod jars;
+pub use jars::{CookieJarConfig, EncryptedCookies, SignedCookies};
+
 mod kit;
 pub use kit::CookieKit;
 
+mod prefix;
+pub use prefix::{CookiePrefix, validate_prefix};
+
 mod response_cookies;
 pub use response_cookies::ResponseCookies;
@@ -0,0 +1,137 @@
+use super::ResponseCookie;
+use super::errors::CookiePrefixError;
+
+/// A cookie name prefix that browsers attach additional requirements to.
+///
+/// Check out [MDN](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Set-Cookie#cookie_prefixes)
+/// for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CookiePrefix {
+    /// `__Host-`.
+    ///
+    /// The cookie must be `Secure`, it must have its `Path` set to `/`
+    /// and it can't have a `Domain` attribute.
+    Host,
+    /// `__Secure-`.
+    ///
+    /// The cookie must be `Secure`.
+    Secure,
+}
+
+impl CookiePrefix {
+    /// Determine which prefix, if any, the cookie name starts with.
+    ///
+    /// Prefixes are matched case-insensitively, just like browsers do.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::cookie::CookiePrefix;
+    ///
+    /// assert_eq!(CookiePrefix::of("__Host-id"), Some(CookiePrefix::Host));
+    /// assert_eq!(CookiePrefix::of("__secure-id"), Some(CookiePrefix::Secure));
+    /// assert_eq!(CookiePrefix::of("id"), None);
+    /// ```
+    pub fn of(name: &str) -> Option<Self> {
+        [CookiePrefix::Host, CookiePrefix::Secure]
+            .into_iter()
+            .find(|prefix| {
+                let prefix = prefix.as_str();
+                name.get(..prefix.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+            })
+    }
+
+    /// The prefix, as it appears in the cookie name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CookiePrefix::Host => "__Host-",
+            CookiePrefix::Secure => "__Secure-",
+        }
+    }
+}
+
+/// Verify that a cookie satisfies the requirements of its name prefix, if any.
+///
+/// Browsers silently drop prefixed cookies that don't meet the requirements
+/// of their prefix. This check lets you catch the mistake on the server side.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::cookie::{ResponseCookie, validate_prefix};
+///
+/// let cookie = ResponseCookie::new("__Host-id", "value").set_secure(true);
+/// // `__Host-` cookies must have their path set to `/`.
+/// assert!(validate_prefix(&cookie).is_err());
+/// assert!(validate_prefix(&cookie.set_path("/")).is_ok());
+///
+/// // Cookies without a prefix are always valid.
+/// assert!(validate_prefix(&ResponseCookie::new("id", "value")).is_ok());
+/// ```
+pub fn validate_prefix(cookie: &ResponseCookie<'_>) -> Result<(), CookiePrefixError> {
+    let Some(prefix) = CookiePrefix::of(cookie.name()) else {
+        return Ok(());
+    };
+    if cookie.secure() != Some(true) {
+        return Err(CookiePrefixError::NotSecure {
+            cookie_name: cookie.name().to_owned(),
+            prefix,
+        });
+    }
+    if prefix == CookiePrefix::Host {
+        if cookie.domain().is_some() {
+            return Err(CookiePrefixError::HasDomain {
+                cookie_name: cookie.name().to_owned(),
+            });
+        }
+        if cookie.path() != Some("/") {
+            return Err(CookiePrefixError::InvalidPath {
+                cookie_name: cookie.name().to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CookiePrefix, validate_prefix};
+    use crate::cookie::ResponseCookie;
+    use crate::cookie::errors::CookiePrefixError;
+
+    #[test]
+    fn prefixes_are_matched_case_insensitively() {
+        assert_eq!(CookiePrefix::of("__HOST-id"), Some(CookiePrefix::Host));
+        assert_eq!(CookiePrefix::of("__Secure-id"), Some(CookiePrefix::Secure));
+        assert_eq!(CookiePrefix::of("__Host"), None);
+        assert_eq!(CookiePrefix::of("my__Host-id"), None);
+    }
+
+    #[test]
+    fn secure_prefix_requires_the_secure_attribute() {
+        let cookie = ResponseCookie::new("__Secure-id", "value");
+        assert!(matches!(
+            validate_prefix(&cookie),
+            Err(CookiePrefixError::NotSecure {
+                prefix: CookiePrefix::Secure,
+                ..
+            })
+        ));
+        let cookie = cookie.set_secure(true).set_domain("example.com");
+        assert!(validate_prefix(&cookie).is_ok());
+    }
+
+    #[test]
+    fn host_prefix_forbids_domain() {
+        let cookie = ResponseCookie::new("__Host-id", "value")
+            .set_secure(true)
+            .set_path("/")
+            .set_domain("example.com");
+        assert!(matches!(
+            validate_prefix(&cookie),
+            Err(CookiePrefixError::HasDomain { .. })
+        ));
+    }
+}
@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use std::pin::Pin;
+
+use futures_util::future::join_all;
+
+use super::report::{CheckReport, HealthStatus};
+
+/// A check that must pass for the application to be considered ready
+/// to accept traffic—e.g. a ping against a database.
+///
+/// Register your checks with [`HealthChecks::register`].
+///
+/// # Implementations
+///
+/// `HealthCheck` is implemented for all async closures returning a `Result`:
+///
+/// ```rust
+/// use pavex::health::HealthChecks;
+///
+/// let checks = HealthChecks::new().register("always-ok", || async { Ok::<_, std::io::Error>(()) });
+/// ```
+///
+/// Implement it manually for your own types:
+///
+/// ```rust
+/// use std::{future::Future, pin::Pin};
+/// use pavex::health::HealthCheck;
+///
+/// struct Upstream;
+///
+/// impl HealthCheck for Upstream {
+///     fn check(&self) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send + '_>> {
+///         Box::pin(async move {
+///             // [...]
+///             Ok(())
+///         })
+///     }
+/// }
+/// ```
+pub trait HealthCheck: Send + Sync + 'static {
+    /// Run the check.
+    ///
+    /// The check fails if it returns an error.
+    fn check(&self) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send + '_>>;
+}
+
+impl<F, Fut, E> HealthCheck for F
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send,
+    E: Into<anyhow::Error>,
+{
+    fn check(&self) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send + '_>> {
+        Box::pin(async move { (self)().await.map_err(Into::into) })
+    }
+}
+
+/// The set of [`HealthCheck`]s that are run by the readiness probe.
+///
+/// Checks are run concurrently, each one with a [timeout](HealthChecks::timeout).
+#[derive(Clone)]
+pub struct HealthChecks {
+    checks: Vec<(Cow<'static, str>, Arc<dyn HealthCheck>)>,
+    timeout: Duration,
+}
+
+impl fmt::Debug for HealthChecks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthChecks")
+            .field(
+                "checks",
+                &self.checks.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl Default for HealthChecks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthChecks {
+    /// The default timeout for each check.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Create an empty set of checks, using [the default timeout](Self::DEFAULT_TIMEOUT).
+    pub fn new() -> Self {
+        Self {
+            checks: Vec::new(),
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Add a check to the set.
+    ///
+    /// `name` is used to identify the check in the [`HealthReport`](super::HealthReport).
+    pub fn register<C: HealthCheck>(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        check: C,
+    ) -> Self {
+        self.checks.push((name.into(), Arc::new(check)));
+        self
+    }
+
+    /// Set the timeout for each check.
+    ///
+    /// A check that doesn't complete within the timeout is considered failed.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The number of registered checks.
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+
+    /// Returns `true` if no check has been registered.
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Run all checks concurrently and collect their outcomes, in registration order.
+    pub async fn run(&self) -> Vec<CheckReport> {
+        join_all(self.checks.iter().map(|(name, check)| async move {
+            let start = Instant::now();
+            let outcome = tokio::time::timeout(self.timeout, check.check()).await;
+            let latency = start.elapsed();
+            let error = match outcome {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => {
+                    tracing::warn!(
+                        health_check.name = %name,
+                        error.msg = %e,
+                        error.details = ?e,
+                        "Health check failed"
+                    );
+                    Some(e.to_string())
+                }
+                Err(_) => {
+                    tracing::warn!(health_check.name = %name, "Health check timed out");
+                    Some("The check timed out".to_owned())
+                }
+            };
+            CheckReport {
+                name: name.to_string(),
+                status: if error.is_none() {
+                    HealthStatus::Pass
+                } else {
+                    HealthStatus::Fail
+                },
+                latency,
+                error,
+            }
+        }))
+        .await
+    }
+}
@@ -0,0 +1,115 @@
+use crate::blueprint::Blueprint;
+use crate::blueprint::constructor::Constructor;
+use crate::blueprint::linter::Lint;
+use crate::blueprint::router::GET;
+use crate::f;
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// A collection of components to expose liveness and readiness probes.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::health::HealthKit;
+///
+/// let mut bp = Blueprint::new();
+/// let kit = HealthKit::new().register(&mut bp);
+/// ```
+///
+/// # Readiness checks
+///
+/// By default, the readiness probe doesn't run any [`HealthCheck`].
+/// Provide your own constructor for [`HealthChecks`] to register your checks:
+///
+/// ```rust
+/// use pavex::blueprint::{Blueprint, constructor::Constructor};
+/// use pavex::health::{HealthChecks, HealthKit};
+/// use pavex::f;
+///
+/// pub fn health_checks() -> HealthChecks {
+///     HealthChecks::new().register("upstream", || async {
+///         // [...]
+///         Ok::<_, std::io::Error>(())
+///     })
+/// }
+///
+/// let mut bp = Blueprint::new();
+/// let mut kit = HealthKit::new();
+/// kit.health_checks = Some(Constructor::singleton(f!(crate::health_checks)));
+/// kit.register(&mut bp);
+/// ```
+///
+/// [`HealthCheck`]: super::HealthCheck
+/// [`HealthChecks`]: super::HealthChecks
+pub struct HealthKit {
+    /// The path of the liveness probe.
+    ///
+    /// By default, it's `/health/live`.
+    /// The probe is handled by [`liveness_probe`](super::liveness_probe).
+    pub liveness_path: Option<String>,
+    /// The path of the readiness probe.
+    ///
+    /// By default, it's `/health/ready`.
+    /// The probe is handled by [`readiness_probe`](super::readiness_probe).
+    pub readiness_path: Option<String>,
+    /// The constructor for [`Readiness`].
+    ///
+    /// By default, it uses [`Readiness::new`].
+    ///
+    /// [`Readiness`]: super::Readiness
+    /// [`Readiness::new`]: super::Readiness::new
+    pub readiness: Option<Constructor>,
+    /// The constructor for [`HealthChecks`].
+    ///
+    /// By default, it uses [`HealthChecks::new`], i.e. no checks are run.
+    ///
+    /// [`HealthChecks`]: super::HealthChecks
+    /// [`HealthChecks::new`]: super::HealthChecks::new
+    pub health_checks: Option<Constructor>,
+}
+
+impl Default for HealthKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthKit {
+    /// Create a new [`HealthKit`] with all the bundled routes and constructors.
+    pub fn new() -> Self {
+        Self {
+            liveness_path: Some("/health/live".into()),
+            readiness_path: Some("/health/ready".into()),
+            readiness: Some(Constructor::singleton(f!(super::Readiness::new)).ignore(Lint::Unused)),
+            health_checks: Some(
+                Constructor::singleton(f!(super::HealthChecks::new)).ignore(Lint::Unused),
+            ),
+        }
+    }
+
+    /// Register all the bundled routes and constructors with a [`Blueprint`].
+    ///
+    /// If a component is set to `None` it will not be registered.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredHealthKit {
+        if let Some(liveness_path) = self.liveness_path {
+            bp.route(GET, &liveness_path, f!(super::liveness_probe));
+        }
+        if let Some(readiness_path) = self.readiness_path {
+            bp.route(GET, &readiness_path, f!(super::readiness_probe));
+        }
+        if let Some(readiness) = self.readiness {
+            readiness.register(bp);
+        }
+        if let Some(health_checks) = self.health_checks {
+            health_checks.register(bp);
+        }
+        RegisteredHealthKit {}
+    }
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The type returned by [`HealthKit::register`].
+pub struct RegisteredHealthKit {}
@@ -0,0 +1,44 @@
+//! Liveness and readiness probes for your application.
+//!
+//! # Guide
+//!
+//! Use [`HealthKit`] to register `/health/live` and `/health/ready` against your
+//! [`Blueprint`](crate::blueprint::Blueprint):
+//!
+//! - the liveness probe always succeeds, as long as the server is able to process requests;
+//! - the readiness probe runs the [`HealthChecks`] registered by your application
+//!   and fails if any of them fails, or if the server has started shutting down.
+//!
+//! Both probes return a JSON [`HealthReport`].
+//!
+//! # Readiness checks
+//!
+//! Readiness checks are types that implement the [`HealthCheck`] trait—e.g. a ping
+//! against the database your application relies on.
+//! They are collected in a [`HealthChecks`] singleton: provide your own constructor
+//! to register the checks you care about.
+//!
+//! ```rust
+//! use pavex::health::HealthChecks;
+//!
+//! # #[derive(Clone)] struct DbPool;
+//! # impl DbPool { async fn ping(&self) -> Result<(), std::io::Error> { Ok(()) } }
+//! pub fn health_checks(pool: &DbPool) -> HealthChecks {
+//!     let pool = pool.clone();
+//!     HealthChecks::new().register("database", move || {
+//!         let pool = pool.clone();
+//!         async move { pool.ping().await }
+//!     })
+//! }
+//! ```
+mod check;
+mod kit;
+mod probes;
+mod readiness;
+mod report;
+
+pub use check::{HealthCheck, HealthChecks};
+pub use kit::{HealthKit, RegisteredHealthKit};
+pub use probes::{liveness_probe, readiness_probe};
+pub use readiness::Readiness;
+pub use report::{CheckReport, HealthReport, HealthStatus};
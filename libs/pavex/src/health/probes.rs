@@ -0,0 +1,77 @@
+use crate::http::StatusCode;
+use crate::response::Response;
+use crate::response::body::Json;
+
+use super::{HealthChecks, HealthReport, Readiness};
+
+/// The liveness probe.
+///
+/// It always returns a `200 OK` response: if the server is able to invoke it,
+/// the application is alive.
+pub fn liveness_probe() -> Response {
+    respond(HealthReport::new(Vec::new(), false))
+}
+
+/// The readiness probe.
+///
+/// It returns `200 OK` if all [`HealthChecks`] passed, `503 Service Unavailable`
+/// otherwise.
+/// It fails without running any check if the application is [draining](Readiness::is_draining).
+pub async fn readiness_probe(readiness: &Readiness, checks: &HealthChecks) -> Response {
+    let report = if readiness.is_draining() {
+        HealthReport::new(Vec::new(), true)
+    } else {
+        HealthReport::new(checks.run().await, false)
+    };
+    respond(report)
+}
+
+fn respond(report: HealthReport) -> Response {
+    let status = if report.status.is_pass() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = Json::new(&report).expect("Failed to serialize the health report");
+    Response::new(status).set_typed_body(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+
+    use super::*;
+
+    async fn body(response: Response) -> serde_json::Value {
+        let (_, body) = response.into_parts();
+        let bytes = body.collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn readiness_reflects_checks_and_draining() {
+        let readiness = Readiness::new();
+        let checks = HealthChecks::new()
+            .register("ok", || async { Ok::<_, std::io::Error>(()) })
+            .register("ko", || async { Err(std::io::Error::other("unreachable")) });
+
+        let response = readiness_probe(&readiness, &checks).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let report = body(response).await;
+        assert_eq!(report["status"], "fail");
+        assert_eq!(report["checks"][0]["status"], "pass");
+        assert_eq!(report["checks"][1]["status"], "fail");
+        assert_eq!(report["checks"][1]["error"], "unreachable");
+        assert!(report["checks"][0]["latency_ms"].is_f64());
+
+        let checks = HealthChecks::new().register("ok", || async { Ok::<_, std::io::Error>(()) });
+        let response = readiness_probe(&readiness, &checks).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        readiness.mark_draining();
+        let response = readiness_probe(&readiness, &checks).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let report = body(response).await;
+        assert_eq!(report["draining"], true);
+    }
+}
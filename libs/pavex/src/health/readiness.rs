@@ -0,0 +1,38 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Tracks whether the application is ready to accept new traffic.
+///
+/// The readiness probe fails as soon as [`Readiness::mark_draining`] has been called.
+///
+/// # Shutdown
+///
+/// The server generated by Pavex calls [`Readiness::mark_draining`] for you
+/// as soon as [`ServerHandle::shutdown`] is invoked, before in-flight requests
+/// have been drained: orchestrators (e.g. Kubernetes) can stop routing new
+/// traffic to the instance while it's shutting down.
+///
+/// [`ServerHandle::shutdown`]: crate::server::ServerHandle::shutdown
+#[derive(Clone, Debug, Default)]
+pub struct Readiness {
+    draining: Arc<AtomicBool>,
+}
+
+impl Readiness {
+    /// Create a new [`Readiness`] tracker, for an application that is not draining.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the application as draining: the readiness probe will fail from now on.
+    ///
+    /// All clones of this [`Readiness`] instance are affected.
+    pub fn mark_draining(&self) {
+        self.draining.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if [`Readiness::mark_draining`] has been called.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+/// The outcome of a health check, or of a set of health checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// The check succeeded.
+    Pass,
+    /// The check failed.
+    Fail,
+}
+
+impl HealthStatus {
+    /// Returns `true` if the status is [`HealthStatus::Pass`].
+    pub fn is_pass(&self) -> bool {
+        matches!(self, HealthStatus::Pass)
+    }
+}
+
+/// The JSON report returned by the liveness and readiness probes.
+///
+/// # Example
+///
+/// ```json
+/// {
+///   "status": "fail",
+///   "checks": [
+///     { "name": "postgres", "status": "pass", "latency_ms": 1.42 },
+///     { "name": "redis", "status": "fail", "latency_ms": 5000.0, "error": "The check timed out" }
+///   ]
+/// }
+/// ```
+#[derive(Clone, Debug, serde::Serialize)]
+#[non_exhaustive]
+pub struct HealthReport {
+    /// [`HealthStatus::Pass`] if all checks passed and the application isn't draining.
+    pub status: HealthStatus,
+    /// Set to `true` if the application has started shutting down.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub draining: bool,
+    /// The outcome of each check, in registration order.
+    pub checks: Vec<CheckReport>,
+}
+
+/// The outcome of a single [`HealthCheck`](super::HealthCheck).
+#[derive(Clone, Debug, serde::Serialize)]
+#[non_exhaustive]
+pub struct CheckReport {
+    /// The name the check was registered with.
+    pub name: String,
+    /// Whether the check passed.
+    pub status: HealthStatus,
+    /// How long the check took to complete.
+    #[serde(rename = "latency_ms", serialize_with = "as_millis")]
+    pub latency: Duration,
+    /// The error returned by the check, if it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HealthReport {
+    pub(super) fn new(checks: Vec<CheckReport>, draining: bool) -> Self {
+        let status = if !draining && checks.iter().all(|c| c.status.is_pass()) {
+            HealthStatus::Pass
+        } else {
+            HealthStatus::Fail
+        };
+        Self {
+            status,
+            draining,
+            checks,
+        }
+    }
+}
+
+fn as_millis<S: serde::Serializer>(latency: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(latency.as_secs_f64() * 1000.0)
+}
@@ -10,7 +10,13 @@
 //! to get you up and running with the framework in no time.
 
 pub use error::error_::Error;
+pub use pavex_macros::{
+    constructor, delete, error_handler, error_observer, get, head, options, patch, post,
+    post_process, pre_process, put, request_scoped, route, singleton, transient, wrap,
+};
 
+#[cfg(feature = "background")]
+pub mod background;
 pub mod blueprint;
 #[cfg(feature = "config")]
 pub mod config;
@@ -18,8 +24,10 @@ pub mod connection;
 #[cfg(feature = "cookie")]
 pub mod cookie;
 pub mod error;
+pub mod health;
 pub mod http;
 pub mod kit;
+pub mod lifecycle;
 pub mod middleware;
 pub mod request;
 pub mod response;
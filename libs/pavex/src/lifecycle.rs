@@ -0,0 +1,96 @@
+//! Support for the hooks invoked when the application starts up or shuts down.
+//!
+//! Check out [`Blueprint::on_startup`] and [`Blueprint::on_shutdown`] for more details.
+//!
+//! [`Blueprint::on_startup`]: crate::blueprint::Blueprint::on_startup
+//! [`Blueprint::on_shutdown`]: crate::blueprint::Blueprint::on_shutdown
+use std::future::IntoFuture;
+use std::time::Duration;
+
+use tracing::Instrument;
+
+/// The error returned when a lifecycle hook doesn't complete within the
+/// [timeout](crate::blueprint::lifecycle_hook::RegisteredLifecycleHook::timeout)
+/// it was registered with.
+#[derive(Debug, thiserror::Error)]
+#[error("`{hook}` didn't complete within {timeout:?}")]
+pub struct HookTimeoutError {
+    hook: &'static str,
+    timeout: Duration,
+}
+
+impl HookTimeoutError {
+    /// The path of the hook that timed out.
+    pub fn hook(&self) -> &'static str {
+        self.hook
+    }
+
+    /// The timeout that was exceeded.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+/// Invoke a lifecycle hook.
+///
+/// Used by the code generated by Pavex.
+#[doc(hidden)]
+pub async fn run_hook<F>(hook: &'static str, future: F) -> F::Output
+where
+    F: IntoFuture,
+{
+    let span = tracing::info_span!("Run lifecycle hook", lifecycle_hook.name = hook);
+    future.into_future().instrument(span).await
+}
+
+/// Invoke a lifecycle hook, cancelling it if it doesn't complete within `timeout`.
+///
+/// Used by the code generated by Pavex.
+#[doc(hidden)]
+pub async fn run_hook_with_timeout<F>(
+    hook: &'static str,
+    timeout: Duration,
+    future: F,
+) -> Result<F::Output, HookTimeoutError>
+where
+    F: IntoFuture,
+{
+    tokio::time::timeout(timeout, run_hook(hook, future))
+        .await
+        .map_err(|_| HookTimeoutError { hook, timeout })
+}
+
+/// Log the failure of a shutdown hook.
+///
+/// Used by the code generated by Pavex: a failing shutdown hook doesn't prevent
+/// the remaining hooks from running.
+#[doc(hidden)]
+pub fn log_shutdown_error(hook: &'static str, e: &(dyn std::error::Error + 'static)) {
+    tracing::error!(
+        lifecycle_hook.name = hook,
+        error.msg = %e,
+        error.details = ?e,
+        "A shutdown hook failed"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_hook_with_timeout;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn hooks_that_exceed_their_timeout_are_cancelled() {
+        let outcome = run_hook_with_timeout("app::slow", Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        })
+        .await;
+        let e = outcome.unwrap_err();
+        assert_eq!(e.hook(), "app::slow");
+        assert_eq!(e.to_string(), "`app::slow` didn't complete within 10ms");
+
+        let outcome =
+            run_hook_with_timeout("app::fast", Duration::from_secs(10), async { 42 }).await;
+        assert_eq!(outcome.unwrap(), 42);
+    }
+}
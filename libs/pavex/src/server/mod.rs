@@ -34,6 +34,7 @@ pub use incoming::IncomingStream;
 pub use server::Server;
 pub use server_handle::ServerHandle;
 pub use shutdown_mode::ShutdownMode;
+pub use stats::ServerStats;
 
 mod configuration;
 mod incoming;
@@ -41,4 +42,5 @@ mod incoming;
 mod server;
 mod server_handle;
 mod shutdown_mode;
+mod stats;
 mod worker;
@@ -1,9 +1,11 @@
 use std::future::Future;
 use std::net::SocketAddr;
+use std::pin::Pin;
 
 use crate::connection::ConnectionInfo;
 use crate::server::configuration::ServerConfiguration;
 use crate::server::server_handle::ServerHandle;
+use crate::server::stats::ServerStats;
 
 use super::IncomingStream;
 
@@ -57,8 +59,17 @@ use super::IncomingStream;
 pub struct Server {
     config: ServerConfiguration,
     incoming: Vec<IncomingStream>,
+    drain_hooks: Vec<DrainHook>,
+    shutdown_hooks: Vec<ShutdownHook>,
+    stats: ServerStats,
 }
 
+/// A callback to be invoked as soon as the server starts shutting down.
+pub(super) type DrainHook = Box<dyn FnOnce() + Send>;
+
+/// A callback to be invoked once the server has shut down.
+pub(super) type ShutdownHook = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()>>> + Send>;
+
 impl Default for Server {
     fn default() -> Self {
         Self::new()
@@ -71,6 +82,9 @@ impl Server {
         Self {
             config: ServerConfiguration::default(),
             incoming: Vec::new(),
+            drain_hooks: Vec::new(),
+            shutdown_hooks: Vec::new(),
+            stats: ServerStats::default(),
         }
     }
 
@@ -91,6 +105,15 @@ impl Server {
         &self.config
     }
 
+    /// Get a handle to the live statistics of this [`Server`]—e.g. the number of
+    /// open connections.
+    ///
+    /// The handle can be retrieved before calling [`Server::serve`]: it'll reflect the
+    /// state of the server once it starts.
+    pub fn stats(&self) -> ServerStats {
+        self.stats.clone()
+    }
+
     /// Bind the server to the given address: the server will accept incoming connections from this
     /// address when started.
     /// Binding an address may fail (e.g. if the address is already in use), therefore this method
@@ -229,6 +252,83 @@ impl Server {
         self
     }
 
+    /// Register a callback to be invoked as soon as [`ServerHandle::shutdown`] is called,
+    /// before in-flight requests are drained.
+    ///
+    /// Callbacks are invoked in the order they were registered.
+    ///
+    /// The code generated by Pavex uses this method to mark the application as
+    /// [draining](crate::health::Readiness::mark_draining), thus failing the readiness probe
+    /// registered by [`HealthKit`](crate::health::HealthKit).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::SocketAddr;
+    /// use pavex::server::Server;
+    ///
+    /// # async fn t() -> std::io::Result<()> {
+    /// let addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+    ///
+    /// Server::new()
+    ///     .bind(addr)
+    ///     .await?
+    ///     .on_drain_start(|| {
+    ///         println!("The server is shutting down");
+    ///     })
+    ///     # ;
+    ///     // [...]
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_drain_start<F>(mut self, callback: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.drain_hooks.push(Box::new(callback));
+        self
+    }
+
+    /// Register a callback to be invoked once the server has shut down,
+    /// after all workers have stopped processing requests.
+    ///
+    /// [`ServerHandle::shutdown`] won't complete until all callbacks have completed.
+    /// Callbacks are invoked one at a time, in the order they were registered.
+    ///
+    /// The code generated by Pavex uses this method to invoke the shutdown hooks
+    /// registered via [`Blueprint::on_shutdown`](crate::blueprint::Blueprint::on_shutdown).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::SocketAddr;
+    /// use pavex::server::Server;
+    ///
+    /// # async fn t() -> std::io::Result<()> {
+    /// let addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+    ///
+    /// Server::new()
+    ///     .bind(addr)
+    ///     .await?
+    ///     .on_shutdown(|| async {
+    ///         println!("The server has shut down");
+    ///     })
+    ///     # ;
+    ///     // [...]
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_shutdown<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        self.shutdown_hooks.push(Box::new(move || {
+            Box::pin(callback()) as Pin<Box<dyn Future<Output = ()>>>
+        }));
+        self
+    }
+
     /// Start listening for incoming connections.
     ///
     /// You must specify:
@@ -294,6 +394,9 @@ impl Server {
             self.incoming,
             handler,
             application_state,
+            self.drain_hooks,
+            self.shutdown_hooks,
+            self.stats,
         ))
     }
 }
@@ -13,8 +13,10 @@ use tracing_log_error::log_error;
 
 use crate::connection::ConnectionInfo;
 use crate::server::configuration::ServerConfiguration;
+use crate::server::stats::ServerStats;
 use crate::server::worker::{ConnectionMessage, Worker, WorkerHandle};
 
+use super::server::{DrainHook, ShutdownHook};
 use super::{IncomingStream, ShutdownMode};
 
 /// A handle to a running [`Server`](super::Server).
@@ -57,13 +59,25 @@ impl ServerHandle {
             ApplicationState,
         ) -> HandlerFuture,
         application_state: ApplicationState,
+        drain_hooks: Vec<DrainHook>,
+        shutdown_hooks: Vec<ShutdownHook>,
+        stats: ServerStats,
     ) -> Self
     where
         HandlerFuture: Future<Output = crate::response::Response> + 'static,
         ApplicationState: Clone + Send + Sync + 'static,
     {
         let (command_outbox, command_inbox) = tokio::sync::mpsc::channel(32);
-        let acceptor = Acceptor::new(config, incoming, handler, application_state, command_inbox);
+        let acceptor = Acceptor::new(
+            config,
+            incoming,
+            handler,
+            application_state,
+            drain_hooks,
+            shutdown_hooks,
+            stats,
+            command_inbox,
+        );
         let _ = acceptor.spawn();
         Self { command_outbox }
     }
@@ -120,6 +134,9 @@ struct Acceptor<HandlerFuture, ApplicationState> {
         ApplicationState,
     ) -> HandlerFuture,
     application_state: ApplicationState,
+    drain_hooks: Vec<DrainHook>,
+    shutdown_hooks: Vec<ShutdownHook>,
+    stats: ServerStats,
     // We use a `fn() -> HandlerFuture` instead of a `HandlerFuture` because we need `Acceptor`
     // to be `Send` and `Sync`. That wouldn't work with `PhantomData<HandlerFuture>`.
     // In the end, we just need to stash the generic type *somewhere*.
@@ -136,6 +153,7 @@ where
     HandlerFuture: Future<Output = crate::response::Response> + 'static,
     ApplicationState: Clone + Send + Sync + 'static,
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         config: ServerConfiguration,
         incoming: Vec<IncomingStream>,
@@ -145,15 +163,24 @@ where
             ApplicationState,
         ) -> HandlerFuture,
         application_state: ApplicationState,
+        drain_hooks: Vec<DrainHook>,
+        shutdown_hooks: Vec<ShutdownHook>,
+        stats: ServerStats,
         command_inbox: tokio::sync::mpsc::Receiver<ServerCommand>,
     ) -> Self {
         // TODO: make this configurable
         let max_queue_length = 15;
         let n_workers = config.n_workers.get();
         let mut worker_handles = Vec::with_capacity(n_workers);
+        stats.init_workers(n_workers);
         for i in 0..n_workers {
-            let (worker, handle) =
-                Worker::new(i, max_queue_length, handler, application_state.clone());
+            let (worker, handle) = Worker::new(
+                i,
+                max_queue_length,
+                handler,
+                application_state.clone(),
+                stats.clone(),
+            );
             worker_handles.push(handle);
             // TODO: should we panic here?
             worker.spawn().expect("Failed to spawn worker thread");
@@ -168,6 +195,9 @@ where
             handler_output_future: Default::default(),
             next_worker: 0,
             application_state,
+            drain_hooks,
+            shutdown_hooks,
+            stats,
         }
     }
 
@@ -179,6 +209,7 @@ where
         /// If accepting a certain connection fails, log the error and keep trying with the next connection.
         async fn accept_connection(
             incoming: IncomingStream,
+            stats: ServerStats,
         ) -> (IncomingStream, TcpStream, SocketAddr) {
             #[allow(deprecated)]
             // This has been inlined from `tokio`'s codebase, since it's not public API.
@@ -200,6 +231,7 @@ where
                 match incoming.accept().await {
                     Ok((connection, remote_peer)) => return (incoming, connection, remote_peer),
                     Err(e) => {
+                        stats.record_accept_error();
                         if is_rt_shutdown_err(&e) {
                             log_error!(e, level: tracing::Level::DEBUG, "Failed to accept connection");
                         } else {
@@ -220,6 +252,9 @@ where
             max_queue_length,
             handler,
             application_state,
+            drain_hooks,
+            shutdown_hooks,
+            stats,
             handler_output_future: _,
         } = self;
 
@@ -227,7 +262,7 @@ where
 
         let mut incoming_join_set = JoinSet::new();
         for incoming in incoming.into_iter() {
-            incoming_join_set.spawn(accept_connection(incoming));
+            incoming_join_set.spawn(accept_connection(incoming, stats.clone()));
         }
 
         let error = 'event_loop: loop {
@@ -246,6 +281,8 @@ where
                             mode,
                             incoming_join_set,
                             worker_handles,
+                            drain_hooks,
+                            shutdown_hooks,
                         )
                         .await;
                         return;
@@ -274,7 +311,7 @@ where
                         }
                     };
                     // Re-spawn the task to keep accepting connections from the same socket.
-                    incoming_join_set.spawn(accept_connection(incoming));
+                    incoming_join_set.spawn(accept_connection(incoming, stats.clone()));
 
                     // A flag to track if the connection has been successfully sent to a worker.
                     let mut has_been_handled = false;
@@ -318,6 +355,7 @@ where
                                 max_queue_length,
                                 handler,
                                 application_state.clone(),
+                                stats.clone(),
                             );
                             // TODO: what if we fail to spawn the worker thread? We don't want to panic here!
                             worker.spawn().expect("Failed to spawn worker thread");
@@ -375,7 +413,13 @@ where
         mode: ShutdownMode,
         incoming_join_set: JoinSet<(IncomingStream, TcpStream, SocketAddr)>,
         worker_handles: Vec<WorkerHandle>,
+        drain_hooks: Vec<DrainHook>,
+        shutdown_hooks: Vec<ShutdownHook>,
     ) {
+        for hook in drain_hooks {
+            hook();
+        }
+
         // This drops the `JoinSet`, which will cause all the tasks that are still running to
         // be cancelled.
         // It will in turn cause the `Incoming` to be dropped, which will cause the `TcpListener`
@@ -402,6 +446,11 @@ where
             .await;
         }
 
+        // All workers have stopped: we can now safely run the shutdown hooks.
+        for hook in shutdown_hooks {
+            hook().await;
+        }
+
         // Notify the caller that the server has shut down.
         let _ = completion_notifier.send(());
     }
@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Live statistics about a [`Server`](super::Server).
+///
+/// Retrieve it via [`Server::stats`](super::Server::stats).
+/// All clones share the same underlying counters, which are updated by the server
+/// while it's running.
+///
+/// # Use cases
+///
+/// `ServerStats` is primarily meant for telemetry: check out `pavex_metrics` to expose
+/// these statistics as Prometheus metrics.
+#[derive(Clone, Debug, Default)]
+pub struct ServerStats {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    accept_errors: AtomicU64,
+    /// The number of open connections for each worker, indexed by worker id.
+    /// It's initialized when the server starts.
+    open_connections: OnceLock<Box<[AtomicUsize]>>,
+}
+
+impl ServerStats {
+    /// The number of open connections handled by each worker, indexed by worker id.
+    ///
+    /// It's empty if the server hasn't started yet.
+    pub fn open_connections(&self) -> Vec<usize> {
+        self.inner
+            .open_connections
+            .get()
+            .map(|workers| workers.iter().map(|n| n.load(Ordering::Relaxed)).collect())
+            .unwrap_or_default()
+    }
+
+    /// The number of times the server failed to accept an incoming connection.
+    pub fn accept_errors(&self) -> u64 {
+        self.inner.accept_errors.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn init_workers(&self, n_workers: usize) {
+        let _ = self
+            .inner
+            .open_connections
+            .set((0..n_workers).map(|_| AtomicUsize::new(0)).collect());
+    }
+
+    pub(super) fn record_accept_error(&self) {
+        self.inner.accept_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment the number of open connections for the given worker.
+    /// The count is decremented when the returned guard is dropped.
+    pub(super) fn track_connection(&self, worker_id: usize) -> ConnectionGuard {
+        if let Some(n) = self.worker(worker_id) {
+            n.fetch_add(1, Ordering::Relaxed);
+        }
+        ConnectionGuard {
+            stats: self.clone(),
+            worker_id,
+        }
+    }
+
+    fn worker(&self, worker_id: usize) -> Option<&AtomicUsize> {
+        self.inner.open_connections.get()?.get(worker_id)
+    }
+}
+
+pub(super) struct ConnectionGuard {
+    stats: ServerStats,
+    worker_id: usize,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        if let Some(n) = self.stats.worker(self.worker_id) {
+            n.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
@@ -12,6 +12,7 @@ use tracing_log_error::log_error;
 
 use crate::connection::ConnectionInfo;
 use crate::server::ShutdownMode;
+use crate::server::stats::{ConnectionGuard, ServerStats};
 
 pub(super) struct ConnectionMessage {
     pub(super) connection: TcpStream,
@@ -85,6 +86,7 @@ pub(super) struct Worker<HandlerFuture, ApplicationState> {
     application_state: ApplicationState,
     id: usize,
     shutdown_coordinator: GracefulShutdown,
+    stats: ServerStats,
 }
 
 impl<HandlerFuture, ApplicationState> Worker<HandlerFuture, ApplicationState>
@@ -105,6 +107,7 @@ where
             ApplicationState,
         ) -> HandlerFuture,
         application_state: ApplicationState,
+        stats: ServerStats,
     ) -> (Self, WorkerHandle) {
         let (connection_outbox, connection_inbox) = tokio::sync::mpsc::channel(max_queue_length);
         let (shutdown_outbox, shutdown_inbox) = tokio::sync::mpsc::unbounded_channel();
@@ -115,6 +118,7 @@ where
             application_state,
             id,
             shutdown_coordinator: GracefulShutdown::new(),
+            stats,
         };
         let handle = WorkerHandle {
             connection_outbox,
@@ -156,6 +160,7 @@ where
             application_state,
             id,
             shutdown_coordinator,
+            stats,
         } = self;
         'event_loop: loop {
             let message =
@@ -168,6 +173,7 @@ where
                         handler,
                         application_state.clone(),
                         &shutdown_coordinator,
+                        stats.track_connection(id),
                     );
                 }
                 WorkerInboxMessage::Shutdown(shutdown) => {
@@ -187,6 +193,7 @@ where
                                     handler,
                                     application_state.clone(),
                                     &shutdown_coordinator,
+                                    stats.track_connection(id),
                                 );
                             }
 
@@ -213,6 +220,7 @@ where
         ) -> HandlerFuture,
         application_state: ApplicationState,
         shutdown_coordinator: &GracefulShutdown,
+        connection_guard: ConnectionGuard,
     ) {
         let ConnectionMessage {
             connection,
@@ -236,6 +244,8 @@ where
         let connection_future =
             shutdown_coordinator.watch(builder.serve_connection(connection, handler).into_owned());
         tokio::task::spawn_local(async move {
+            // Keep the connection tracked until it's closed.
+            let _connection_guard = connection_guard;
             if let Err(e) = connection_future.await {
                 log_error!(*e, level: tracing::Level::WARN, "Failed to serve an incoming connection");
             }
@@ -223,3 +223,49 @@ async fn graceful_but_too_fast() {
         "The server was supposed to shutdown forcefully the slow request, but it waited instead"
     );
 }
+
+#[tokio::test]
+async fn drain_hooks_run_before_shutdown_hooks() {
+    let (incoming, _) = test_incoming().await;
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let (e1, e2) = (events.clone(), events.clone());
+    let server_handle = Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .on_shutdown(move || async move { e1.lock().unwrap().push("shutdown") })
+        .on_drain_start(move || e2.lock().unwrap().push("drain"))
+        .serve(test_handler, ());
+
+    server_handle
+        .shutdown(ShutdownMode::Graceful {
+            timeout: Duration::from_secs(1),
+        })
+        .await;
+
+    assert_eq!(*events.lock().unwrap(), vec!["drain", "shutdown"]);
+}
+
+#[tokio::test]
+async fn stats_track_open_connections() {
+    let (incoming, addr) = test_incoming().await;
+    let delay = Duration::from_millis(100);
+    let (mut has_started, state) = SlowHandlerState::new(delay);
+
+    let server = Server::new()
+        .set_config(test_server_config())
+        .listen(incoming);
+    let stats = server.stats();
+    assert!(stats.open_connections().is_empty());
+    let _server_handle = server.serve(slow_handler, state);
+    assert_eq!(stats.open_connections(), vec![0]);
+
+    let get_response = tokio::task::spawn(async move {
+        let url = format!("http://localhost:{}", addr.port());
+        reqwest::get(url).await.unwrap().error_for_status().unwrap();
+    });
+    has_started.recv().await.unwrap();
+    assert_eq!(stats.open_connections(), vec![1]);
+    get_response.await.unwrap();
+    assert_eq!(stats.accept_errors(), 0);
+}
@@ -28,6 +28,15 @@ pub enum Component {
     ErrorObserver(ErrorObserver),
     PrebuiltType(PrebuiltType),
     ConfigType(ConfigType),
+    Import(Import),
+    LifecycleHook(LifecycleHook),
+    BackgroundTask(BackgroundTask),
+}
+
+impl From<Import> for Component {
+    fn from(i: Import) -> Self {
+        Self::Import(i)
+    }
 }
 
 impl From<PrebuiltType> for Component {
@@ -90,6 +99,18 @@ impl From<ErrorObserver> for Component {
     }
 }
 
+impl From<LifecycleHook> for Component {
+    fn from(h: LifecycleHook) -> Self {
+        Self::LifecycleHook(h)
+    }
+}
+
+impl From<BackgroundTask> for Component {
+    fn from(t: BackgroundTask) -> Self {
+        Self::BackgroundTask(t)
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 /// A route registered against a `Blueprint` via `Blueprint::route`.
 pub struct Route {
@@ -101,6 +122,8 @@ pub struct Route {
     pub request_handler: Callable,
     /// The callable in charge of processing errors returned by the request handler, if any.
     pub error_handler: Option<Callable>,
+    /// The route is only included in the generated code if this condition holds.
+    pub condition: Option<Condition>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -121,6 +144,72 @@ pub struct ErrorObserver {
     pub error_observer: Callable,
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+/// A callable registered against a `Blueprint` via `Blueprint::on_startup` or
+/// `Blueprint::on_shutdown`, to be invoked when the application starts or shuts down.
+pub struct LifecycleHook {
+    /// The callable to be invoked.
+    pub hook: Callable,
+    /// When the hook should be invoked.
+    pub phase: LifecyclePhase,
+    /// How long the hook is allowed to run for, if there is a limit.
+    pub timeout: Option<std::time::Duration>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The moment in the lifecycle of an application when a [`LifecycleHook`] is invoked.
+pub enum LifecyclePhase {
+    /// After the application state has been built, before serving requests.
+    Startup,
+    /// After the server has stopped serving requests.
+    Shutdown,
+}
+
+impl fmt::Display for LifecyclePhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecyclePhase::Startup => write!(f, "startup"),
+            LifecyclePhase::Shutdown => write!(f, "shutdown"),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+/// A callable registered against a `Blueprint` via `Blueprint::background_task` or
+/// `Blueprint::cron`, to be invoked in the background while the application is running.
+pub struct BackgroundTask {
+    /// The callable to be invoked.
+    pub task: Callable,
+    /// When the task should be invoked.
+    pub schedule: TaskSchedule,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+/// When a [`BackgroundTask`] should be invoked.
+pub enum TaskSchedule {
+    /// Once, when the application starts.
+    Once,
+    /// When the application starts, and then periodically.
+    Every(std::time::Duration),
+    /// According to a cron expression, e.g. `0 3 * * *`.
+    Cron(String),
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+/// A group of modules registered against a `Blueprint` via `Blueprint::import`.
+///
+/// All components annotated with one of Pavex's attribute macros (e.g. `#[pavex::get]`)
+/// in those modules (and their submodules) will be registered against the `Blueprint`.
+pub struct Import {
+    /// The paths of the modules to be scanned, as they were written by the user.
+    pub sources: Vec<String>,
+    /// Information on the module where the import was registered,
+    /// used to resolve relative paths (e.g. `crate::...` or `super::...`).
+    pub registered_at: RegisteredAt,
+    /// The location where the import was registered against the `Blueprint`.
+    pub location: Location,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 /// A type registered against a `Blueprint` via `Blueprint::prebuilt` to
 /// be added as an input parameter to `ApplicationState::new`.
@@ -144,6 +233,9 @@ pub struct ConfigType {
     /// Whether to use `Default::default` to generate default configuration
     /// values if the user hasn't specified any.
     pub default_if_missing: Option<bool>,
+    /// Whether the configuration values can be updated while the application is running.
+    #[serde(default)]
+    pub reloadable: Option<bool>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -159,6 +251,8 @@ pub struct Constructor {
     pub error_handler: Option<Callable>,
     /// Lint settings for this constructor.
     pub lints: BTreeMap<Lint, LintSetting>,
+    /// The constructor is only included in the generated code if this condition holds.
+    pub condition: Option<Condition>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -277,6 +371,23 @@ pub enum MethodGuard {
     Some(BTreeSet<String>),
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
+/// A compile-time condition attached to a component registration.
+///
+/// It's evaluated when compiling the generated server SDK, via `#[cfg(...)]` attributes.
+pub enum Condition {
+    /// A cargo feature of the generated server SDK crate is enabled.
+    Feature(String),
+    /// The server SDK is compiled with `--cfg pavex_profile="<name>"`.
+    Profile(String),
+    /// The inner condition doesn't hold.
+    Not(Box<Condition>),
+    /// All the inner conditions hold.
+    All(Vec<Condition>),
+    /// At least one of the inner conditions holds.
+    Any(Vec<Condition>),
+}
+
 #[derive(
     Debug, Clone, Copy, Eq, Ord, PartialOrd, PartialEq, Hash, serde::Serialize, serde::Deserialize,
 )]
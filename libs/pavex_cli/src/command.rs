@@ -100,6 +100,46 @@ impl From<Color> for pavexc_cli_client::config::Color {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub enum MessageFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+impl Display for MessageFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageFormat::Human => write!(f, "human"),
+            MessageFormat::Json => write!(f, "json"),
+            MessageFormat::Sarif => write!(f, "sarif"),
+        }
+    }
+}
+
+impl FromStr for MessageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            "sarif" => Ok(MessageFormat::Sarif),
+            s => Err(anyhow::anyhow!("Invalid message format: {}", s)),
+        }
+    }
+}
+
+impl From<MessageFormat> for pavexc_cli_client::config::MessageFormat {
+    fn from(value: MessageFormat) -> Self {
+        match value {
+            MessageFormat::Human => pavexc_cli_client::config::MessageFormat::Human,
+            MessageFormat::Json => pavexc_cli_client::config::MessageFormat::Json,
+            MessageFormat::Sarif => pavexc_cli_client::config::MessageFormat::Sarif,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Command {
     /// Generate the server SDK code for an application blueprint.
@@ -122,6 +162,37 @@ pub enum Command {
         /// it is interpreted as relative to the root of the current workspace.
         #[clap(short, long, value_parser)]
         output: PathBuf,
+        /// The format used to report diagnostics: human, json, sarif.
+        ///
+        /// `json` emits one JSON object per line on stdout, using the same `compiler-message`
+        /// envelope emitted by `cargo --message-format json`.
+        /// `sarif` emits a single SARIF v2.1.0 log on stdout.
+        #[clap(long, env = "PAVEX_MESSAGE_FORMAT", default_value_t = MessageFormat::Human)]
+        message_format: MessageFormat,
+    },
+    /// Watch the current workspace and restart the server on every change.
+    ///
+    /// Every time a local crate changes, the server SDK is regenerated and the
+    /// server binary is rebuilt (via `cargo px build`) and relaunched.
+    /// If the build fails, the diagnostics are printed and the last successfully
+    /// built server is kept running.
+    Dev {
+        /// The binary target that should be launched.
+        ///
+        /// If not provided, Pavex will launch the only binary target in the workspace
+        /// that isn't used to generate a server SDK.
+        #[clap(long, value_parser)]
+        bin: Option<String>,
+        /// How often, in milliseconds, Pavex should check for changes.
+        #[clap(long, value_parser, default_value_t = 500)]
+        poll_interval: u64,
+        /// How long, in seconds, Pavex should wait for the server to shut down
+        /// gracefully before killing it.
+        #[clap(long, value_parser, default_value_t = 10)]
+        shutdown_timeout: u64,
+        /// Arguments to pass to the server binary.
+        #[arg(last = true)]
+        args: Vec<String>,
     },
     /// Scaffold a new Pavex project at <PATH>.
     New {
@@ -137,6 +208,11 @@ pub enum Command {
         #[clap(short, long, value_parser, default_value = "api")]
         template: TemplateName,
     },
+    /// Inspect and validate the configuration of your application.
+    Config {
+        #[clap(subcommand)]
+        command: ConfigCommands,
+    },
     /// Modify the installation of the Pavex CLI.
     #[command(name = "self")]
     Self_ {
@@ -150,12 +226,64 @@ impl Command {
     pub(crate) fn needs_activation_key(&self) -> bool {
         match self {
             Command::Generate { check, .. } => !check,
+            Command::Dev { .. } => true,
             Command::New { .. } => true,
+            Command::Config { .. } => false,
             Command::Self_ { .. } => false,
         }
     }
 }
 
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Load the configuration for a profile and validate it against the schema of your application.
+    ///
+    /// Configuration values are loaded via `ConfigLoader`, using the same sources and precedence
+    /// rules of `ConfigLoader::load`: `base.yml`, `<profile>.yml`, the additional sources specified
+    /// via the options below and `PX_`-prefixed environment variables.
+    /// Custom `figment` providers can't be replicated by this command: values set exclusively
+    /// via a custom provider will be reported as missing.
+    ///
+    /// Type mismatches, missing required keys and unknown keys are reported, pointing
+    /// at the offending location in your configuration sources.
+    Check {
+        /// The directory that contains the configuration files.
+        #[arg(index = 1)]
+        dir: PathBuf,
+        /// The profile whose configuration should be checked, e.g. `prod`.
+        #[clap(long, env = "PX_PROFILE")]
+        profile: String,
+        /// The path to the JSON Schema generated by Pavex for your application configuration.
+        ///
+        /// If not provided, Pavex will look for `configuration/schema.json` in the directory
+        /// of the server SDK crate in the current workspace.
+        #[clap(long, value_parser)]
+        schema: Option<PathBuf>,
+        /// An additional TOML file, as in `ConfigLoader::toml_file`.
+        /// Relative paths are resolved relative to the configuration directory.
+        ///
+        /// Can be repeated. Later files take precedence over earlier ones.
+        #[clap(long = "toml-file", value_parser)]
+        toml_files: Vec<PathBuf>,
+        /// An additional JSON file, as in `ConfigLoader::json_file`.
+        /// Relative paths are resolved relative to the configuration directory.
+        ///
+        /// Can be repeated. Later files take precedence over earlier ones,
+        /// as well as over TOML files.
+        #[clap(long = "json-file", value_parser)]
+        json_files: Vec<PathBuf>,
+        /// A secrets directory, as in `ConfigLoader::secrets_dir`.
+        ///
+        /// Can be repeated. Later directories take precedence over earlier ones,
+        /// as well as over TOML and JSON files.
+        #[clap(long = "secrets-dir", value_parser)]
+        secrets_dirs: Vec<PathBuf>,
+        /// A `.env` file, as in `ConfigLoader::dotenv_file`.
+        #[clap(long, value_parser)]
+        dotenv_file: Option<PathBuf>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum SelfCommands {
     /// Download and install a newer version of Pavex CLI, if available.
@@ -0,0 +1,781 @@
+//! The machinery behind `pavex config check`: load the configuration for a profile
+//! via `pavex::config::ConfigLoader`, using the same sources (and the same precedence rules)
+//! of `ConfigLoader::load`, then validate the merged configuration against the JSON Schema
+//! that Pavex generates for `ApplicationConfig`.
+use anyhow::Context;
+use guppy::graph::PackageGraph;
+use miette::{LabeledSpan, MietteDiagnostic, NamedSource, Severity};
+use pavex::config::figment::value::{Num, Value};
+use pavex::config::figment::{self, Figment, Metadata};
+use pavex::config::{ConfigLoader, ConfigProfile};
+use serde_json::Value as Schema;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+
+/// The location of the JSON Schema, relative to the root directory of the server SDK crate.
+const SCHEMA_PATH: &str = "configuration/schema.json";
+/// Keep in sync with `pavex::config::ConfigLoader::load`.
+const ENV_PREFIX: &str = "PX_";
+/// The name `figment` assigns to the `PX_`-prefixed environment variables source.
+const ENV_SOURCE_NAME: &str = "`PX_` environment variable(s)";
+/// Keep in sync with the metadata of `pavex::config::sources::SecretsDir`.
+const SECRETS_DIR_SOURCE_NAME: &str = "secrets directory";
+/// The keyword used by Pavex to mark `pavex::config::Secret` fields in the schema.
+const SECRET_KEYWORD: &str = "x-pavex-secret";
+
+/// Options for `pavex config check`.
+pub struct CheckOptions {
+    /// The directory that contains the configuration files.
+    pub configuration_dir: PathBuf,
+    /// The profile whose configuration should be checked.
+    pub profile: String,
+    /// The path to the JSON Schema for `ApplicationConfig`.
+    pub schema: PathBuf,
+    /// Additional TOML files, as in `ConfigLoader::toml_file`.
+    pub toml_files: Vec<PathBuf>,
+    /// Additional JSON files, as in `ConfigLoader::json_file`.
+    pub json_files: Vec<PathBuf>,
+    /// Secrets directories, as in `ConfigLoader::secrets_dir`.
+    pub secrets_dirs: Vec<PathBuf>,
+    /// A `.env` file, as in `ConfigLoader::dotenv_file`.
+    pub dotenv_file: Option<PathBuf>,
+}
+
+/// The outcome of `pavex config check`.
+pub struct CheckReport {
+    /// All the issues that have been detected, ready to be printed.
+    pub issues: Vec<miette::Report>,
+    pub n_errors: usize,
+    pub n_warnings: usize,
+}
+
+/// `pavex config check` doesn't know the profile type of the application it's checking:
+/// we accept any profile name and let the user tell us which one they want.
+struct ProfileName(String);
+
+impl FromStr for ProfileName {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl AsRef<str> for ProfileName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl ConfigProfile for ProfileName {}
+
+/// Find the JSON Schema generated by Pavex in the current workspace.
+///
+/// We look for it in the directory of every server SDK crate—i.e. crates with
+/// a `[package.metadata.px.generate]` section in their manifest.
+pub fn default_schema_path(package_graph: &PackageGraph) -> Result<PathBuf, anyhow::Error> {
+    let candidates: Vec<PathBuf> = package_graph
+        .workspace()
+        .iter()
+        .filter(|p| !p.metadata_table()["px"]["generate"].is_null())
+        .filter_map(|p| {
+            let path = p.manifest_path().parent()?.as_std_path().join(SCHEMA_PATH);
+            path.exists().then_some(path)
+        })
+        .collect();
+    match candidates.as_slice() {
+        [path] => Ok(path.to_owned()),
+        [] => Err(anyhow::anyhow!(
+            "I couldn't find the configuration schema for your application.\n\
+            Pavex generates it at `{SCHEMA_PATH}`, inside the directory of your server SDK crate: \
+            have you generated the server SDK with a recent version of Pavex?\n\
+            You can also specify the path to the schema using `--schema`."
+        )),
+        _ => Err(anyhow::anyhow!(
+            "There are multiple server SDK crates in the current workspace, each with its own configuration schema: {}.\n\
+            Use `--schema` to specify which one should be used.",
+            candidates
+                .iter()
+                .map(|c| format!("`{}`", c.display()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Load the configuration for the specified profile and validate it against the schema.
+pub fn check(options: &CheckOptions) -> Result<CheckReport, anyhow::Error> {
+    let schema: Schema = {
+        let raw = fs_err::read_to_string(&options.schema)
+            .context("Failed to read the configuration schema")?;
+        serde_json::from_str(&raw).context("Failed to parse the configuration schema")?
+    };
+
+    let mut loader = ConfigLoader::<ProfileName>::new()
+        .configuration_dir(&options.configuration_dir)
+        .profile(ProfileName(options.profile.clone()));
+    // `ConfigLoader` resolves the paths of configuration files relative to the configuration
+    // directory, while secrets directories and `.env` files are resolved relative to the
+    // current working directory. We follow the same rules to check if they exist.
+    let mut expected_paths = vec![
+        options.configuration_dir.join("base.yml"),
+        options
+            .configuration_dir
+            .join(format!("{}.yml", options.profile)),
+    ];
+    for path in &options.toml_files {
+        loader = loader.toml_file(path);
+        expected_paths.push(options.configuration_dir.join(path));
+    }
+    for path in &options.json_files {
+        loader = loader.json_file(path);
+        expected_paths.push(options.configuration_dir.join(path));
+    }
+    for dir in &options.secrets_dirs {
+        loader = loader.secrets_dir(dir);
+        expected_paths.push(dir.to_owned());
+    }
+    if let Some(path) = &options.dotenv_file {
+        loader = loader.dotenv_file(path);
+        expected_paths.push(path.to_owned());
+    }
+
+    let mut checker = Checker {
+        figment: loader.figment()?,
+        schema: &schema,
+        files: HashMap::new(),
+        in_secret: false,
+        report: CheckReport {
+            issues: Vec::new(),
+            n_errors: 0,
+            n_warnings: 0,
+        },
+    };
+
+    for path in expected_paths {
+        if !path.exists() {
+            checker.push(
+                MietteDiagnostic::new(format!(
+                    "`{}` doesn't exist, it won't contribute any configuration value.",
+                    path.display()
+                ))
+                .with_severity(Severity::Warning),
+                None,
+            );
+        }
+    }
+
+    let merged: Value = match checker.figment.extract() {
+        Ok(merged) => merged,
+        Err(e) => {
+            for e in e {
+                checker.push(
+                    MietteDiagnostic::new(format!("Failed to load the configuration: {e}")),
+                    None,
+                );
+            }
+            return Ok(checker.report);
+        }
+    };
+    checker.check_value(&merged, &schema, &mut Vec::new());
+    Ok(checker.report)
+}
+
+struct Checker<'a> {
+    figment: Figment,
+    schema: &'a Schema,
+    /// The parsed YAML files, keyed by path. `None` if the file couldn't be parsed.
+    files: HashMap<PathBuf, Option<SourceFile>>,
+    report: CheckReport,
+    /// Set when checking a value wrapped in `pavex::config::Secret`: we must not
+    /// show its contents in our diagnostics.
+    in_secret: bool,
+}
+
+impl<'a> Checker<'a> {
+    fn check_value(&mut self, value: &Value, schema: &'a Schema, path: &mut Vec<String>) {
+        let is_secret = schema.get(SECRET_KEYWORD) == Some(&Schema::Bool(true));
+        let in_secret = self.in_secret;
+        self.in_secret |= is_secret;
+        self._check_value(value, schema, path);
+        self.in_secret = in_secret;
+    }
+
+    fn _check_value(&mut self, value: &Value, schema: &'a Schema, path: &mut Vec<String>) {
+        let schema = self.resolve(schema);
+
+        if let Some(Schema::Array(variants)) = schema.get("anyOf") {
+            if matches!(value, Value::Empty(..))
+                && variants
+                    .iter()
+                    .any(|v| v.get("type") == Some(&"null".into()))
+            {
+                return;
+            }
+            if let Some(variant) = variants
+                .iter()
+                .find(|v| v.get("type") != Some(&"null".into()))
+            {
+                self.check_value(value, variant, path);
+            }
+            return;
+        }
+
+        let actual = kind(value);
+        if let Some(expected) = schema.get("type").and_then(Schema::as_str) {
+            let compatible = expected == actual || (expected == "number" && actual == "integer");
+            if !compatible {
+                let mut message = format!(
+                    "{} has the wrong type: expected {}, found {actual}.",
+                    display_path(path),
+                    with_article(expected)
+                );
+                if actual == "string"
+                    && self
+                        .metadata(value)
+                        .is_some_and(|m| m.name.starts_with(SECRETS_DIR_SOURCE_NAME))
+                {
+                    message.push_str(
+                        " Values loaded from a secrets directory are always interpreted as strings.",
+                    );
+                }
+                self.error(message, Some(value), path, Span::Value);
+                return;
+            }
+        }
+
+        if let Some(Schema::Array(allowed)) = schema.get("enum") {
+            let is_allowed = match value {
+                Value::String(_, s) => allowed.iter().any(|a| a.as_str() == Some(s.as_str())),
+                _ => false,
+            };
+            if !is_allowed {
+                let allowed: Vec<_> = allowed
+                    .iter()
+                    .map(|a| format!("`{}`", display(a)))
+                    .collect();
+                self.error(
+                    format!(
+                        "{} has an invalid value. Expected one of {}.",
+                        display_path(path),
+                        allowed.join(", ")
+                    ),
+                    Some(value),
+                    path,
+                    Span::Value,
+                );
+                return;
+            }
+        }
+
+        if let (Some(minimum), Value::Num(_, n)) =
+            (schema.get("minimum").and_then(Schema::as_f64), value)
+        {
+            if n.to_f64().is_some_and(|n| n < minimum) {
+                self.error(
+                    format!(
+                        "{} is out of range: it must be greater than or equal to {minimum}.",
+                        display_path(path)
+                    ),
+                    Some(value),
+                    path,
+                    Span::Value,
+                );
+            }
+        }
+
+        match value {
+            Value::Dict(_, dict) => {
+                let properties = schema.get("properties").and_then(Schema::as_object);
+                let additional = schema.get("additionalProperties");
+                for (key, field) in dict {
+                    path.push(key.to_owned());
+                    match (properties.and_then(|p| p.get(key)), additional) {
+                        (Some(field_schema), _) => self.check_value(field, field_schema, path),
+                        (None, Some(Schema::Bool(false))) => self.error(
+                            format!("{} is not a valid configuration key.", display_path(path)),
+                            Some(field),
+                            path,
+                            Span::Key,
+                        ),
+                        (None, Some(field_schema @ Schema::Object(_))) => {
+                            self.check_value(field, field_schema, path)
+                        }
+                        (None, _) if properties.is_some() => self.warning(
+                            format!(
+                                "{} is not a known configuration key, it will be ignored.",
+                                display_path(path)
+                            ),
+                            Some(field),
+                            path,
+                        ),
+                        (None, _) => {}
+                    }
+                    path.pop();
+                }
+                let required = schema
+                    .get("required")
+                    .and_then(Schema::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Schema::as_str);
+                for key in required {
+                    if !dict.contains_key(key) {
+                        path.push(key.to_owned());
+                        let message = format!(
+                            "{} is missing. It's required, but it isn't set by any configuration source.",
+                            display_path(path)
+                        );
+                        path.pop();
+                        self.error(message, Some(value), path, Span::Key);
+                    }
+                }
+            }
+            Value::Array(_, items) => {
+                let prefix_items = schema.get("prefixItems").and_then(Schema::as_array);
+                let items_schema = schema.get("items");
+                for (i, item) in items.iter().enumerate() {
+                    let item_schema = prefix_items.and_then(|p| p.get(i)).or(items_schema);
+                    if let Some(item_schema) = item_schema {
+                        path.push(i.to_string());
+                        self.check_value(item, item_schema, path);
+                        path.pop();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Follow `$ref`s to the definitions section of the schema.
+    fn resolve(&self, schema: &'a Schema) -> &'a Schema {
+        let mut schema = schema;
+        // Guard against reference cycles.
+        for _ in 0..32 {
+            let Some(reference) = schema.get("$ref").and_then(Schema::as_str) else {
+                break;
+            };
+            let Some(definition) = reference
+                .strip_prefix("#/$defs/")
+                .and_then(|name| self.schema.get("$defs")?.get(name))
+            else {
+                break;
+            };
+            schema = definition;
+        }
+        schema
+    }
+
+    fn error(&mut self, message: String, value: Option<&Value>, path: &[String], span: Span) {
+        let diagnostic = MietteDiagnostic::new(message);
+        let location = value.and_then(|v| self.locate(v, path, span));
+        self.push(diagnostic, location);
+    }
+
+    fn warning(&mut self, message: String, value: Option<&Value>, path: &[String]) {
+        let diagnostic = MietteDiagnostic::new(message).with_severity(Severity::Warning);
+        let location = value.and_then(|v| self.locate(v, path, Span::Key));
+        self.push(diagnostic, location);
+    }
+
+    fn push(&mut self, diagnostic: MietteDiagnostic, location: Option<Location>) {
+        match diagnostic.severity {
+            Some(Severity::Warning) => self.report.n_warnings += 1,
+            _ => self.report.n_errors += 1,
+        }
+        let report = match location {
+            Some(Location::File {
+                path,
+                contents,
+                offset,
+                len,
+            }) => miette::Report::new(
+                diagnostic.with_label(LabeledSpan::underline(offset..offset + len)),
+            )
+            .with_source_code(NamedSource::new(path.display().to_string(), contents)),
+            Some(Location::Source { key, source }) => miette::Report::new(
+                diagnostic.with_help(format!("The value comes from `{key}` ({source}).")),
+            ),
+            Some(Location::RedactedFile(path)) => {
+                miette::Report::new(diagnostic.with_help(format!(
+                    "The value comes from `{}`. It's a secret, so it won't be displayed here.",
+                    path.display()
+                )))
+            }
+            None => miette::Report::new(diagnostic),
+        };
+        self.report.issues.push(report);
+    }
+
+    fn metadata(&self, value: &Value) -> Option<&Metadata> {
+        self.figment.get_metadata(value.tag())
+    }
+
+    /// Determine where a value has been set, using the metadata tracked by `figment`.
+    fn locate(&mut self, value: &Value, path: &[String], span: Span) -> Option<Location> {
+        let metadata = self.figment.get_metadata(value.tag())?;
+        if let Some(figment::Source::File(filepath)) = &metadata.source {
+            if self.in_secret {
+                return Some(Location::RedactedFile(filepath.to_owned()));
+            }
+            // We can only point at the offending snippet for YAML files
+            // (and JSON files, since JSON is a subset of YAML).
+            let is_yaml = filepath
+                .extension()
+                .is_some_and(|e| e == "yml" || e == "yaml" || e == "json");
+            if is_yaml {
+                let file = self
+                    .files
+                    .entry(filepath.to_owned())
+                    .or_insert_with(|| SourceFile::parse(filepath))
+                    .as_ref();
+                if let Some(file) = file {
+                    let locations = file.locations.get(path)?;
+                    let (offset, len) = match span {
+                        Span::Key => locations.key.or(locations.value)?,
+                        Span::Value => locations.value.or(locations.key)?,
+                    };
+                    return Some(Location::File {
+                        path: filepath.to_owned(),
+                        contents: file.contents.clone(),
+                        offset,
+                        len,
+                    });
+                }
+            }
+        }
+        if path.is_empty() {
+            return None;
+        }
+        let key = if metadata.name == ENV_SOURCE_NAME {
+            // Keep in sync with the key splitting rules of the `Env` provider.
+            format!("{ENV_PREFIX}{}", path.join("__").to_uppercase())
+        } else {
+            metadata.interpolate(&figment::Profile::Default, path)
+        };
+        Some(Location::Source {
+            key,
+            source: metadata.name.to_string(),
+        })
+    }
+}
+
+enum Location {
+    File {
+        path: PathBuf,
+        contents: String,
+        offset: usize,
+        len: usize,
+    },
+    /// A source without a file we can point into, e.g. environment variables
+    /// or a secrets directory.
+    Source {
+        /// How the key is spelled in the source, e.g. `PX_SERVER__PORT`.
+        key: String,
+        /// The name of the source.
+        source: String,
+    },
+    /// A file location for a secret value: we don't show the offending snippet.
+    RedactedFile(PathBuf),
+}
+
+/// Whether a diagnostic should point at the key or at the value of an entry.
+#[derive(Clone, Copy)]
+enum Span {
+    Key,
+    Value,
+}
+
+/// A YAML configuration file, with the position of every key and value.
+struct SourceFile {
+    contents: String,
+    locations: HashMap<Vec<String>, Locations>,
+}
+
+#[derive(Default)]
+struct Locations {
+    /// Byte offset and length of the key.
+    key: Option<(usize, usize)>,
+    /// Byte offset and length of the value. For collections, only the opening token.
+    value: Option<(usize, usize)>,
+}
+
+impl SourceFile {
+    fn parse(path: &Path) -> Option<Self> {
+        let contents = fs_err::read_to_string(path).ok()?;
+        let mut collector = LocationCollector::default();
+        Parser::new_from_str(&contents)
+            .load(&mut collector, false)
+            .ok()?;
+        Some(Self {
+            contents,
+            locations: collector.locations,
+        })
+    }
+}
+
+enum Frame {
+    Mapping {
+        path: Vec<String>,
+        /// The key of the entry whose value we're waiting for.
+        key: Option<String>,
+    },
+    Sequence {
+        path: Vec<String>,
+        index: usize,
+    },
+}
+
+#[derive(Default)]
+struct LocationCollector {
+    stack: Vec<Frame>,
+    locations: HashMap<Vec<String>, Locations>,
+}
+
+impl LocationCollector {
+    /// The path of the value that's about to start, if we're not parsing a key.
+    fn next_value_path(&mut self) -> Option<Vec<String>> {
+        match self.stack.last_mut() {
+            None => Some(Vec::new()),
+            Some(Frame::Mapping { path, key }) => {
+                let key = key.take()?;
+                let mut path = path.clone();
+                path.push(key);
+                Some(path)
+            }
+            Some(Frame::Sequence { path, index }) => {
+                let mut path = path.clone();
+                path.push(index.to_string());
+                *index += 1;
+                Some(path)
+            }
+        }
+    }
+}
+
+impl MarkedEventReceiver for LocationCollector {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, style, ..) => {
+                // Quoted scalars: include the quotes in the span.
+                let len = match style {
+                    TScalarStyle::SingleQuoted | TScalarStyle::DoubleQuoted => value.len() + 2,
+                    _ => value.len().max(1),
+                };
+                if let Some(Frame::Mapping {
+                    path,
+                    key: key @ None,
+                }) = self.stack.last_mut()
+                {
+                    // This scalar is a key.
+                    let mut key_path = path.clone();
+                    key_path.push(value.clone());
+                    self.locations.entry(key_path).or_default().key = Some((mark.index(), len));
+                    *key = Some(value);
+                    return;
+                }
+                if let Some(path) = self.next_value_path() {
+                    self.locations.entry(path).or_default().value = Some((mark.index(), len));
+                }
+            }
+            Event::Alias(_) => {
+                if let Some(path) = self.next_value_path() {
+                    self.locations.entry(path).or_default().value = Some((mark.index(), 1));
+                }
+            }
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                let path = self.next_value_path().unwrap_or_default();
+                self.locations.entry(path.clone()).or_default().value = Some((mark.index(), 1));
+                self.stack
+                    .push(if matches!(event, Event::MappingStart(..)) {
+                        Frame::Mapping { path, key: None }
+                    } else {
+                        Frame::Sequence { path, index: 0 }
+                    });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::String(..) | Value::Char(..) => "string",
+        Value::Bool(..) => "boolean",
+        Value::Num(_, Num::F32(_) | Num::F64(_)) => "number",
+        Value::Num(..) => "integer",
+        Value::Empty(..) => "null",
+        Value::Dict(..) => "object",
+        Value::Array(..) => "array",
+    }
+}
+
+fn with_article(kind: &str) -> String {
+    match kind {
+        "integer" | "object" | "array" => format!("an {kind}"),
+        "null" => "null".to_owned(),
+        _ => format!("a {kind}"),
+    }
+}
+
+fn display(value: &Schema) -> String {
+    match value {
+        Schema::String(s) => s.to_owned(),
+        v => v.to_string(),
+    }
+}
+
+fn display_path(path: &[String]) -> String {
+    if path.is_empty() {
+        "The configuration".to_owned()
+    } else {
+        format!("`{}`", path.join("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CheckOptions, CheckReport, check};
+    use std::path::Path;
+
+    /// The schema Pavex generates for an application with two configuration entries,
+    /// `server` and `database`.
+    const SCHEMA: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/config_schema.json"
+    );
+
+    fn check_files(files: &[(&str, &str)]) -> CheckReport {
+        check_with(files, |_, _| {})
+    }
+
+    /// Write `files` to a temporary configuration directory, then customize the options
+    /// before running the check.
+    fn check_with(
+        files: &[(&str, &str)],
+        customize: impl FnOnce(&Path, &mut CheckOptions),
+    ) -> CheckReport {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in files {
+            let path = dir.path().join(name);
+            fs_err::create_dir_all(path.parent().unwrap()).unwrap();
+            fs_err::write(path, contents).unwrap();
+        }
+        let mut options = CheckOptions {
+            configuration_dir: dir.path().to_owned(),
+            profile: "dev".into(),
+            schema: Path::new(SCHEMA).to_owned(),
+            toml_files: vec![],
+            json_files: vec![],
+            secrets_dirs: vec![],
+            dotenv_file: None,
+        };
+        customize(dir.path(), &mut options);
+        check(&options).unwrap()
+    }
+
+    fn messages(report: &CheckReport) -> Vec<String> {
+        report.issues.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn a_valid_configuration_passes() {
+        let report = check_files(&[
+            (
+                "base.yml",
+                "server:\n  ip: 0.0.0.0\n  port: 8000\ndatabase:\n  max_connections: 10\n",
+            ),
+            ("dev.yml", "database:\n  url: postgres://localhost:5432\n"),
+        ]);
+        assert_eq!(report.n_errors, 0, "{:?}", messages(&report));
+        assert_eq!(report.n_warnings, 0, "{:?}", messages(&report));
+    }
+
+    #[test]
+    fn a_missing_required_key_is_an_error() {
+        let report = check_files(&[
+            ("base.yml", "server:\n  ip: 0.0.0.0\n"),
+            ("dev.yml", "database:\n  url: postgres://localhost:5432\n"),
+        ]);
+        assert_eq!(report.n_errors, 1);
+        let messages = messages(&report);
+        assert!(
+            messages[0].starts_with("`server.port` is missing."),
+            "{messages:?}"
+        );
+    }
+
+    #[test]
+    fn a_value_of_the_wrong_type_is_an_error() {
+        let report = check_files(&[
+            ("base.yml", "server:\n  ip: 0.0.0.0\n  port: eighty\n"),
+            ("dev.yml", "database:\n  url: postgres://localhost:5432\n"),
+        ]);
+        assert_eq!(report.n_errors, 1);
+        let messages = messages(&report);
+        assert!(
+            messages[0].starts_with(
+                "`server.port` has the wrong type: expected an integer, found string."
+            ),
+            "{messages:?}"
+        );
+    }
+
+    #[test]
+    fn a_missing_profile_file_is_a_warning() {
+        let report = check_files(&[(
+            "base.yml",
+            "server:\n  ip: 0.0.0.0\n  port: 8000\ndatabase:\n  url: postgres://localhost:5432\n",
+        )]);
+        assert_eq!(report.n_errors, 0, "{:?}", messages(&report));
+        assert_eq!(report.n_warnings, 1);
+        assert!(messages(&report)[0].ends_with("it won't contribute any configuration value."));
+    }
+
+    #[test]
+    fn additional_sources_are_loaded() {
+        let report = check_with(
+            &[
+                ("base.yml", "server:\n  ip: 0.0.0.0\n"),
+                ("dev.yml", ""),
+                ("extra.toml", "[server]\nport = 8000\n"),
+                ("secrets/database__url", "postgres://localhost:5432\n"),
+            ],
+            |dir, options| {
+                options.toml_files.push("extra.toml".into());
+                options.secrets_dirs.push(dir.join("secrets"));
+            },
+        );
+        assert_eq!(report.n_errors, 0, "{:?}", messages(&report));
+        assert_eq!(report.n_warnings, 0, "{:?}", messages(&report));
+    }
+
+    #[test]
+    fn values_from_a_secrets_dir_are_strings() {
+        let report = check_with(
+            &[
+                ("base.yml", "server:\n  ip: 0.0.0.0\n"),
+                ("dev.yml", "database:\n  url: postgres://localhost:5432\n"),
+                ("secrets/server__port", "8000\n"),
+            ],
+            |dir, options| options.secrets_dirs.push(dir.join("secrets")),
+        );
+        assert_eq!(report.n_errors, 1);
+        let messages = messages(&report);
+        assert!(
+            messages[0].ends_with(
+                "Values loaded from a secrets directory are always interpreted as strings."
+            ),
+            "{messages:?}"
+        );
+        let help = report.issues[0].help().unwrap().to_string();
+        assert!(help.starts_with("The value comes from `server__port` (secrets directory"));
+    }
+}
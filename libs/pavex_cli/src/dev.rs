@@ -0,0 +1,385 @@
+//! The machinery behind `pavex dev`: watch the blueprint crate (and the local crates
+//! it depends on), regenerate the server SDK on change, rebuild the server binary
+//! and restart it.
+use anyhow::Context;
+use guppy::graph::{BuildTargetId, PackageGraph, PackageMetadata};
+use pavex_cli_shell::{SHELL, ShellExt};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitCode, ExitStatus, Stdio};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Options for `pavex dev`.
+pub struct DevOptions {
+    /// The binary target to launch after every successful build.
+    ///
+    /// If `None`, Pavex will look for the only binary target in the workspace that
+    /// isn't used to generate a server SDK.
+    pub bin: Option<String>,
+    /// How often the watched directories should be scanned for changes.
+    pub poll_interval: Duration,
+    /// How long to wait for the server to shut down gracefully before killing it.
+    pub shutdown_timeout: Duration,
+    /// Extra arguments to pass to the server binary.
+    pub args: Vec<String>,
+    /// Environment variables to set when invoking `cargo px build`.
+    pub envs: Vec<(String, String)>,
+}
+
+/// Regenerate, rebuild and restart the server every time one of the watched crates changes.
+///
+/// The function only returns if the initial setup fails: the watch loop runs until the process
+/// is interrupted.
+pub fn dev(package_graph: &PackageGraph, options: DevOptions) -> Result<ExitCode, anyhow::Error> {
+    let workspace = package_graph.workspace();
+    let workspace_root = workspace.root().as_std_path().to_owned();
+    let bin = match options.bin {
+        Some(bin) => bin,
+        None => default_binary(package_graph)?,
+    };
+    let binary_path = workspace
+        .target_directory()
+        .as_std_path()
+        .join("debug")
+        .join(format!("{bin}{}", std::env::consts::EXE_SUFFIX));
+    let watched = WatchedDirs::new(package_graph);
+    for dir in &watched.dirs {
+        tracing::debug!(dir = %dir.display(), "Watching for changes");
+    }
+
+    let mut server = Server {
+        binary_path,
+        args: options.args,
+        working_dir: workspace_root.clone(),
+        shutdown_timeout: options.shutdown_timeout,
+        child: None,
+    };
+    let mut snapshot = watched.snapshot();
+    rebuild(&workspace_root, &bin, &options.envs, &mut server);
+    SHELL.status(
+        "Watching",
+        format!("{} crate(s) for changes", watched.dirs.len()),
+    );
+    loop {
+        std::thread::sleep(options.poll_interval);
+        server.check_exit();
+
+        let mut current = watched.snapshot();
+        if current == snapshot {
+            continue;
+        }
+        // Wait for the file system to settle down—editors often perform
+        // multiple writes in quick succession when saving.
+        loop {
+            std::thread::sleep(options.poll_interval);
+            let next = watched.snapshot();
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        snapshot = current;
+        rebuild(&workspace_root, &bin, &options.envs, &mut server);
+    }
+}
+
+/// Regenerate the server SDK and rebuild the server binary via `cargo px build`.
+///
+/// Diagnostics (including the ones emitted by `pavexc` while generating the SDK) are
+/// streamed to the terminal.
+/// The running server is only replaced if the build succeeds.
+fn rebuild(workspace_root: &Path, bin: &str, envs: &[(String, String)], server: &mut Server) {
+    SHELL.status("Building", format!("`{bin}`"));
+    let mut cmd = Command::new("cargo");
+    cmd.arg("px")
+        .arg("build")
+        .arg("--bin")
+        .arg(bin)
+        .current_dir(workspace_root)
+        .envs(envs.iter().map(|(k, v)| (k, v)))
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+    let succeeded = match cmd.status() {
+        Ok(status) => status.success(),
+        Err(e) => {
+            SHELL.error(format!("Failed to invoke `cargo px build`: {e}"));
+            false
+        }
+    };
+    if !succeeded {
+        if server.child.is_some() {
+            SHELL.warn("The build failed. The last successfully built server is still running.");
+        } else {
+            SHELL.warn("The build failed. Waiting for changes...");
+        }
+        return;
+    }
+    if let Err(e) = server.restart() {
+        SHELL.error(format!("{e:?}"));
+    }
+}
+
+/// The directories of all local crates that can affect the generated server SDK
+/// or the server binary.
+struct WatchedDirs {
+    dirs: BTreeSet<PathBuf>,
+}
+
+impl WatchedDirs {
+    fn new(package_graph: &PackageGraph) -> Self {
+        let dirs = package_graph
+            .packages()
+            .filter(|p| p.source().is_local())
+            // The server SDK is regenerated on every change: watching it would
+            // trigger an endless rebuild loop.
+            .filter(|p| !is_server_sdk(p))
+            .filter_map(|p| p.manifest_path().parent())
+            .map(|dir| dir.as_std_path().to_owned())
+            .collect();
+        Self { dirs }
+    }
+
+    /// Record the last modification time of every file in the watched directories.
+    fn snapshot(&self) -> BTreeMap<PathBuf, SystemTime> {
+        let mut snapshot = BTreeMap::new();
+        for dir in &self.dirs {
+            let walker = walkdir::WalkDir::new(dir)
+                .into_iter()
+                .filter_entry(|e| !is_ignored(e.path(), e.depth()));
+            for entry in walker.filter_map(Result::ok) {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                if let Some(modified) = entry.metadata().ok().and_then(|m| m.modified().ok()) {
+                    snapshot.insert(entry.into_path(), modified);
+                }
+            }
+        }
+        snapshot
+    }
+}
+
+/// Skip build artifacts and hidden directories (e.g. `.git`).
+fn is_ignored(path: &Path, depth: usize) -> bool {
+    if depth == 0 {
+        return false;
+    }
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    name == "target" || name.starts_with('.')
+}
+
+/// A server SDK crate is generated by `cargo px`, as specified in its
+/// `package.metadata.px.generate` section.
+fn is_server_sdk(package: &PackageMetadata) -> bool {
+    !package.metadata_table()["px"]["generate"].is_null()
+}
+
+/// Find the only binary target in the workspace that isn't a server SDK generator.
+fn default_binary(package_graph: &PackageGraph) -> Result<String, anyhow::Error> {
+    let workspace = package_graph.workspace();
+    let generators: BTreeSet<&str> = workspace
+        .iter()
+        .filter_map(|p| p.metadata_table()["px"]["generate"]["generator_name"].as_str())
+        .collect();
+    let candidates: Vec<&str> = workspace
+        .iter()
+        .flat_map(|p| p.build_targets())
+        .filter_map(|t| match t.id() {
+            BuildTargetId::Binary(name) => Some(name),
+            _ => None,
+        })
+        .filter(|name| !generators.contains(name))
+        .collect();
+    match candidates.as_slice() {
+        [bin] => Ok((*bin).to_owned()),
+        [] => Err(anyhow::anyhow!(
+            "There are no binary targets in the current workspace, apart from the server SDK generators.\n\
+            Which binary should I launch?"
+        )),
+        _ => Err(anyhow::anyhow!(
+            "There are multiple binary targets in the current workspace: {}.\n\
+            Use `--bin` to specify which one should be launched.",
+            candidates
+                .iter()
+                .map(|c| format!("`{c}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// The server process launched by `pavex dev`.
+struct Server {
+    binary_path: PathBuf,
+    args: Vec<String>,
+    working_dir: PathBuf,
+    shutdown_timeout: Duration,
+    child: Option<Child>,
+}
+
+impl Server {
+    /// Stop the running server, if there is one, and launch the freshly built binary.
+    fn restart(&mut self) -> Result<(), anyhow::Error> {
+        self.stop();
+        SHELL.status("Running", format!("`{}`", self.binary_path.display()));
+        let child = Command::new(&self.binary_path)
+            .args(&self.args)
+            .current_dir(&self.working_dir)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to launch `{}`", self.binary_path.display()))?;
+        self.child = Some(child);
+        Ok(())
+    }
+
+    /// Stop the running server, if there is one.
+    fn stop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            shut_down(&mut child, self.shutdown_timeout);
+        }
+    }
+
+    /// Report if the server exited on its own (e.g. it panicked at startup).
+    fn check_exit(&mut self) {
+        let Some(child) = &mut self.child else {
+            return;
+        };
+        if let Ok(Some(status)) = child.try_wait() {
+            SHELL.warn(format!(
+                "The server exited ({status}). Waiting for changes..."
+            ));
+            self.child = None;
+        }
+    }
+}
+
+/// Ask the server to shut down, giving it a chance to drain in-flight requests
+/// and run its shutdown hooks.
+///
+/// The server is killed if it's still running after `timeout`.
+fn shut_down(child: &mut Child, timeout: Duration) -> Option<ExitStatus> {
+    if interrupt(child) {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            match child.try_wait() {
+                Ok(Some(status)) => return Some(status),
+                Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+                Err(_) => break,
+            }
+        }
+        SHELL.warn(format!(
+            "The server didn't shut down within {}s. Killing it.",
+            timeout.as_secs_f32()
+        ));
+    }
+    let _ = child.kill();
+    child.wait().ok()
+}
+
+/// Send `SIGINT` to the server, the same signal it would get if you pressed Ctrl+C.
+///
+/// It returns `false` if the signal couldn't be delivered.
+#[cfg(unix)]
+fn interrupt(child: &Child) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(child.id()) else {
+        return false;
+    };
+    // SAFETY: `kill` has no memory-safety preconditions.
+    unsafe { libc::kill(pid, libc::SIGINT) == 0 }
+}
+
+/// There is no portable way to deliver Ctrl+C to a single child process on Windows:
+/// the server is killed right away.
+#[cfg(not(unix))]
+fn interrupt(_child: &Child) -> bool {
+    false
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WatchedDirs, is_ignored, shut_down};
+    use std::collections::BTreeSet;
+    use std::path::Path;
+    use std::time::Duration;
+
+    #[test]
+    fn build_artifacts_and_hidden_directories_are_ignored() {
+        assert!(is_ignored(Path::new("app/target"), 1));
+        assert!(is_ignored(Path::new("app/.git"), 1));
+        assert!(is_ignored(Path::new("app/src/.swp"), 2));
+        assert!(!is_ignored(Path::new("app/src"), 1));
+        assert!(!is_ignored(Path::new("app/src/target.rs"), 2));
+        // The watched directory itself is never ignored, whatever its name.
+        assert!(!is_ignored(Path::new(".hidden"), 0));
+    }
+
+    #[test]
+    fn snapshot_tracks_source_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for path in ["src/lib.rs", "target/debug/app", ".git/HEAD", "Cargo.toml"] {
+            let path = root.join(path);
+            fs_err::create_dir_all(path.parent().unwrap()).unwrap();
+            fs_err::write(path, "").unwrap();
+        }
+        let watched = WatchedDirs {
+            dirs: BTreeSet::from([root.to_owned()]),
+        };
+
+        let snapshot = watched.snapshot();
+        let files: BTreeSet<_> = snapshot
+            .keys()
+            .map(|p| p.strip_prefix(root).unwrap().to_owned())
+            .collect();
+        assert_eq!(
+            files,
+            BTreeSet::from(["Cargo.toml".into(), "src/lib.rs".into()])
+        );
+
+        // New files are picked up.
+        fs_err::write(root.join("src/main.rs"), "").unwrap();
+        assert_ne!(watched.snapshot(), snapshot);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn servers_are_given_a_chance_to_shut_down_gracefully() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::Command;
+
+        let mut child = Command::new("sh")
+            .args(["-c", "trap 'exit 7' INT; while true; do sleep 0.05; done"])
+            .spawn()
+            .unwrap();
+        // Give the shell time to install the trap.
+        std::thread::sleep(Duration::from_millis(200));
+        let status = shut_down(&mut child, Duration::from_secs(5)).unwrap();
+        // The server exited on its own terms, rather than being killed.
+        assert_eq!(status.code(), Some(7));
+        assert_eq!(status.signal(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn servers_are_killed_if_they_dont_shut_down_in_time() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::Command;
+
+        let mut child = Command::new("sh")
+            .args(["-c", "trap '' INT; while true; do sleep 0.05; done"])
+            .spawn()
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        let status = shut_down(&mut child, Duration::from_millis(300)).unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGKILL));
+    }
+}
@@ -2,6 +2,8 @@ pub mod activation;
 pub mod cargo_install;
 pub mod cli_kind;
 pub mod command;
+pub mod config_check;
+pub mod dev;
 pub mod env;
 pub mod locator;
 pub mod package_graph;
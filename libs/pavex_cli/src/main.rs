@@ -4,6 +4,7 @@ use pavex_cli_shell::{SHELL, ShellExt, try_init_shell};
 use std::io::{ErrorKind, IsTerminal};
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::time::Duration;
 use tracing_log_error::log_error;
 
 use clap::Parser;
@@ -15,7 +16,9 @@ use pavex_cli::activation::{
 };
 use pavex_cli::cargo_install::{GitSourceRevision, Source, cargo_install};
 use pavex_cli::cli_kind::CliKind;
-use pavex_cli::command::{Cli, Color, Command, SelfCommands};
+use pavex_cli::command::{Cli, Color, Command, ConfigCommands, MessageFormat, SelfCommands};
+use pavex_cli::config_check::CheckOptions;
+use pavex_cli::dev::DevOptions;
 use pavex_cli::locator::PavexLocator;
 use pavex_cli::package_graph::compute_package_graph;
 use pavex_cli::pavexc::{get_or_install_from_graph, get_or_install_from_version};
@@ -76,18 +79,63 @@ fn _main(cli: Cli) -> Result<ExitCode, miette::Error> {
             .map_err(anyhow2miette)?;
         background_token_refresh(&claims, &key_set, activation_key, &locator);
     }
+    let pavex_envs = pavex_envs(&cli);
     match cli.command {
         Command::Generate {
             blueprint,
             diagnostics,
             check,
             output,
-        } => {
-            generate(client, &locator, blueprint, diagnostics, output, check).map_err(anyhow2miette)
-        }
+            message_format,
+        } => generate(
+            client,
+            &locator,
+            blueprint,
+            diagnostics,
+            output,
+            check,
+            message_format,
+        )
+        .map_err(anyhow2miette),
+        Command::Dev {
+            bin,
+            poll_interval,
+            shutdown_timeout,
+            args,
+        } => dev(
+            &locator,
+            DevOptions {
+                bin,
+                poll_interval: Duration::from_millis(poll_interval),
+                shutdown_timeout: Duration::from_secs(shutdown_timeout),
+                args,
+                envs: pavex_envs,
+            },
+        )
+        .map_err(anyhow2miette),
         Command::New { path, template } => {
             scaffold_project(client, &locator, path, template).map_err(anyhow2miette)
         }
+        Command::Config { command } => match command {
+            ConfigCommands::Check {
+                dir,
+                profile,
+                schema,
+                toml_files,
+                json_files,
+                secrets_dirs,
+                dotenv_file,
+            } => config_check(
+                dir,
+                profile,
+                schema,
+                toml_files,
+                json_files,
+                secrets_dirs,
+                dotenv_file,
+            )
+            .map_err(anyhow2miette),
+        },
         Command::Self_ { command } => {
             // You should always be able to run `self` commands, even if Pavex has
             // not been activated yet.
@@ -145,6 +193,64 @@ fn pavexc_client(cli: &Cli) -> Client {
     client
 }
 
+/// `pavex dev` generates the server SDK via `cargo px`, which invokes `pavex generate`
+/// from the blueprint binary: we propagate our options to the nested invocation
+/// via environment variables.
+fn pavex_envs(cli: &Cli) -> Vec<(String, String)> {
+    let mut envs = vec![("PAVEX_COLOR".to_owned(), cli.color.to_string())];
+    if cli.debug {
+        envs.push(("PAVEX_DEBUG".to_owned(), "true".to_owned()));
+    }
+    envs
+}
+
+#[tracing::instrument("Check configuration")]
+fn config_check(
+    configuration_dir: PathBuf,
+    profile: String,
+    schema: Option<PathBuf>,
+    toml_files: Vec<PathBuf>,
+    json_files: Vec<PathBuf>,
+    secrets_dirs: Vec<PathBuf>,
+    dotenv_file: Option<PathBuf>,
+) -> Result<ExitCode, anyhow::Error> {
+    let schema = match schema {
+        Some(schema) => schema,
+        None => {
+            let package_graph = compute_package_graph()
+                .context("Failed to compute package graph for the current workspace")?;
+            pavex_cli::config_check::default_schema_path(&package_graph)?
+        }
+    };
+    let report = pavex_cli::config_check::check(&CheckOptions {
+        configuration_dir,
+        profile: profile.clone(),
+        schema,
+        toml_files,
+        json_files,
+        secrets_dirs,
+        dotenv_file,
+    })?;
+    for issue in &report.issues {
+        eprintln!("{issue:?}");
+    }
+    if report.n_errors > 0 {
+        SHELL.error(format!(
+            "The configuration for the `{profile}` profile is invalid: {} error(s), {} warning(s)",
+            report.n_errors, report.n_warnings
+        ));
+        return Ok(ExitCode::FAILURE);
+    }
+    SHELL.status(
+        "Checked",
+        format!(
+            "the configuration for the `{profile}` profile ({} warning(s))",
+            report.n_warnings
+        ),
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
 #[tracing::instrument("Generate server sdk", skip(client, locator))]
 fn generate(
     mut client: Client,
@@ -153,6 +259,7 @@ fn generate(
     diagnostics: Option<PathBuf>,
     output: PathBuf,
     check: bool,
+    message_format: MessageFormat,
 ) -> Result<ExitCode, anyhow::Error> {
     let pavexc_cli_path = if let Some(pavexc_override) = pavex_cli::env::pavexc_override() {
         pavexc_override
@@ -173,6 +280,7 @@ fn generate(
     if check {
         cmd = cmd.check();
     }
+    cmd = cmd.message_format(message_format.into());
 
     match cmd.execute() {
         Ok(()) => Ok(ExitCode::SUCCESS),
@@ -181,6 +289,18 @@ fn generate(
     }
 }
 
+#[tracing::instrument("Watch and rebuild", skip(locator, options))]
+fn dev(locator: &PavexLocator, options: DevOptions) -> Result<ExitCode, anyhow::Error> {
+    let package_graph = compute_package_graph()
+        .context("Failed to compute package graph for the current workspace")?;
+    // Make sure that the right `pavexc` binary is installed before entering the watch loop,
+    // rather than failing on the first build.
+    if pavex_cli::env::pavexc_override().is_none() {
+        get_or_install_from_graph(locator, &package_graph)?;
+    }
+    pavex_cli::dev::dev(&package_graph, options)
+}
+
 #[tracing::instrument("Scaffold new project", skip(client, locator))]
 fn scaffold_project(
     mut client: Client,
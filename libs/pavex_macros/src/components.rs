@@ -0,0 +1,351 @@
+//! Attribute macros to annotate components (constructors, routes, middlewares, etc.)
+//! so that they can be registered via `Blueprint::import`.
+//!
+//! The macros don't generate any code: they validate their arguments and then attach
+//! a normalized `#[diagnostic::pavex::<kind>(...)]` attribute to the annotated function.
+//! `pavexc` looks for those attributes in the JSON documentation of the imported modules.
+//! The attribute lives under the `diagnostic` namespace since it's the only tool namespace
+//! that the compiler accepts without requiring a `#![register_tool]` declaration.
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Error, Expr, ExprLit, ImplItemFn, Lit, Meta, Token};
+
+/// The HTTP methods that `ANY` expands to.
+/// It must match the set of well-known methods used by `pavex::blueprint::router::ANY`.
+const WELL_KNOWN_METHODS: [&str; 9] = [
+    "CONNECT", "DELETE", "GET", "HEAD", "OPTIONS", "PATCH", "POST", "PUT", "TRACE",
+];
+
+#[derive(Clone, Copy)]
+pub(super) enum Kind {
+    /// `#[pavex::constructor]`, with an optional lifecycle preset by one of its shorthands
+    /// (e.g. `#[pavex::singleton]`).
+    Constructor(Option<&'static str>),
+    /// `#[pavex::route]`, with an optional method preset by one of its shorthands
+    /// (e.g. `#[pavex::get]`).
+    Route(Option<&'static str>),
+    Wrap,
+    PreProcess,
+    PostProcess,
+    ErrorObserver,
+    ErrorHandler,
+}
+
+impl Kind {
+    fn name(&self) -> &'static str {
+        match self {
+            Kind::Constructor(None) => "constructor",
+            Kind::Constructor(Some(lifecycle)) => lifecycle,
+            Kind::Route(None) => "route",
+            Kind::Route(Some("GET")) => "get",
+            Kind::Route(Some("POST")) => "post",
+            Kind::Route(Some("PUT")) => "put",
+            Kind::Route(Some("PATCH")) => "patch",
+            Kind::Route(Some("DELETE")) => "delete",
+            Kind::Route(Some("HEAD")) => "head",
+            Kind::Route(Some("OPTIONS")) => "options",
+            Kind::Route(Some(_)) => "route",
+            Kind::Wrap => "wrap",
+            Kind::PreProcess => "pre_process",
+            Kind::PostProcess => "post_process",
+            Kind::ErrorObserver => "error_observer",
+            Kind::ErrorHandler => "error_handler",
+        }
+    }
+
+    /// The name of the normalized attribute, i.e. `#[diagnostic::pavex::<marker>]`.
+    fn marker(&self) -> &'static str {
+        match self {
+            Kind::Constructor(_) => "constructor",
+            Kind::Route(_) => "route",
+            Kind::Wrap => "wrap",
+            Kind::PreProcess => "pre_process",
+            Kind::PostProcess => "post_process",
+            Kind::ErrorObserver => "error_observer",
+            Kind::ErrorHandler => "error_handler",
+        }
+    }
+
+    /// The arguments accepted by the macro, used in error messages.
+    fn usage(&self) -> String {
+        let name = self.name();
+        match self {
+            Kind::Constructor(None) => format!(
+                "#[pavex::{name}(request_scoped, clone_if_necessary, error_handler = crate::path::to::handler)]`.\n\
+                The lifecycle (`singleton`, `request_scoped` or `transient`) is required, \
+                all other arguments are optional"
+            ),
+            Kind::Constructor(Some(_)) => format!(
+                "#[pavex::{name}(clone_if_necessary, error_handler = crate::path::to::handler)]`.\n\
+                All arguments are optional"
+            ),
+            Kind::Route(None) => format!(
+                "#[pavex::{name}(method = GET, path = \"/\", error_handler = crate::path::to::handler)]`.\n\
+                `method` accepts a single method (e.g. `GET`), a list of methods (e.g. `[GET, POST]`) or `ANY`. \
+                `error_handler` is optional"
+            ),
+            Kind::Route(Some(_)) => format!(
+                "#[pavex::{name}(path = \"/\", error_handler = crate::path::to::handler)]`.\n\
+                `error_handler` is optional"
+            ),
+            Kind::Wrap | Kind::PreProcess | Kind::PostProcess => format!(
+                "#[pavex::{name}(error_handler = crate::path::to::handler)]`.\n\
+                `error_handler` is optional"
+            ),
+            Kind::ErrorObserver | Kind::ErrorHandler => {
+                format!("#[pavex::{name}]`. It doesn't accept any argument")
+            }
+        }
+    }
+}
+
+/// The normalized arguments, in the order they should be emitted.
+#[derive(Default)]
+struct Properties {
+    lifecycle: Option<String>,
+    cloning_strategy: Option<String>,
+    method: Option<String>,
+    path: Option<String>,
+    error_handler: Option<String>,
+}
+
+pub(super) fn annotate(kind: Kind, metadata: TokenStream, input: TokenStream) -> TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+    let properties = syn::parse::<ImplItemFn>(input.clone().into())
+        .map_err(|e| {
+            Error::new(
+                e.span(),
+                format!(
+                    "`#[pavex::{}]` can only be applied to functions and methods.",
+                    kind.name()
+                ),
+            )
+        })
+        .and_then(|_| parse_properties(kind, metadata));
+    let properties = match properties {
+        Ok(p) => p,
+        Err(e) => {
+            // We emit both the error AND the original item, to avoid spurious
+            // "cannot find function" errors at the call sites.
+            let e = e.to_compile_error();
+            return quote! { #e #input }.into();
+        }
+    };
+
+    let marker = syn::Ident::new(kind.marker(), Span::call_site());
+    let mut args = Vec::new();
+    for (key, value) in [
+        ("lifecycle", properties.lifecycle),
+        ("cloning_strategy", properties.cloning_strategy),
+        ("method", properties.method),
+        ("path", properties.path),
+        ("error_handler", properties.error_handler),
+    ] {
+        if let Some(value) = value {
+            let key = syn::Ident::new(key, Span::call_site());
+            args.push(quote! { #key = #value });
+        }
+    }
+    quote! {
+        #[diagnostic::pavex::#marker(#(#args),*)]
+        #input
+    }
+    .into()
+}
+
+fn parse_properties(kind: Kind, metadata: TokenStream) -> Result<Properties, Error> {
+    let metas =
+        syn::parse::Parser::parse(Punctuated::<Meta, Token![,]>::parse_terminated, metadata)?;
+    let invalid = |span: Span| {
+        Error::new(
+            span,
+            format!(
+                "Invalid arguments. The expected syntax is `{}.",
+                kind.usage()
+            ),
+        )
+    };
+    let mut properties = Properties::default();
+    if let Kind::Constructor(Some(lifecycle)) = kind {
+        properties.lifecycle = Some(lifecycle.to_owned());
+    }
+    if let Kind::Route(Some(method)) = kind {
+        properties.method = Some(method.to_owned());
+    }
+
+    for meta in metas {
+        let span = meta.span();
+        let Some(key) = meta.path().get_ident().map(|i| i.to_string()) else {
+            return Err(invalid(span));
+        };
+        match (kind, key.as_str(), &meta) {
+            (
+                Kind::Constructor(None),
+                "singleton" | "request_scoped" | "transient",
+                Meta::Path(_),
+            ) => {
+                set_once(&mut properties.lifecycle, key, span, "lifecycle")?;
+            }
+            (Kind::Constructor(_), "clone_if_necessary" | "never_clone", Meta::Path(_)) => {
+                set_once(
+                    &mut properties.cloning_strategy,
+                    key,
+                    span,
+                    "cloning strategy",
+                )?;
+            }
+            (Kind::Route(None), "method", Meta::NameValue(nv)) => {
+                let methods = parse_methods(&nv.value)?;
+                set_once(&mut properties.method, methods, span, "method")?;
+            }
+            (Kind::Route(_), "path", Meta::NameValue(nv)) => {
+                let path = parse_route_path(&nv.value)?;
+                set_once(&mut properties.path, path, span, "path")?;
+            }
+            (
+                Kind::Constructor(_)
+                | Kind::Route(_)
+                | Kind::Wrap
+                | Kind::PreProcess
+                | Kind::PostProcess,
+                "error_handler",
+                Meta::NameValue(nv),
+            ) => {
+                let Expr::Path(path) = &nv.value else {
+                    return Err(Error::new_spanned(
+                        &nv.value,
+                        "The error handler must be a path to a function or a method, \
+                        e.g. `crate::errors::handle_error`.",
+                    ));
+                };
+                let path = quote! { #path }.to_string();
+                set_once(&mut properties.error_handler, path, span, "error handler")?;
+            }
+            _ => return Err(invalid(span)),
+        }
+    }
+
+    if let Kind::Constructor(None) = kind {
+        if properties.lifecycle.is_none() {
+            return Err(Error::new(
+                Span::call_site(),
+                "You must specify the lifecycle of the constructor: `singleton`, `request_scoped` or `transient`.\n\
+                E.g. `#[pavex::constructor(request_scoped)]`.",
+            ));
+        }
+    }
+    if let Kind::Route(preset) = kind {
+        if properties.method.is_none() {
+            return Err(Error::new(
+                Span::call_site(),
+                "You must specify the HTTP method(s) that the route should match.\n\
+                E.g. `#[pavex::route(method = GET, path = \"/\")]`.",
+            ));
+        }
+        if properties.path.is_none() {
+            let name = Kind::Route(preset).name();
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "You must specify the path of the route.\nE.g. `#[pavex::{name}(path = \"/\")]`."
+                ),
+            ));
+        }
+    }
+    Ok(properties)
+}
+
+fn set_once(slot: &mut Option<String>, value: String, span: Span, what: &str) -> Result<(), Error> {
+    if slot.is_some() {
+        return Err(Error::new(
+            span,
+            format!("The {what} can only be specified once."),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// Parse the `method` argument of `#[pavex::route]`, returning a comma-separated
+/// list of HTTP methods—or `*` for `ANY_WITH_EXTENSIONS`.
+fn parse_methods(value: &Expr) -> Result<String, Error> {
+    let elements: Vec<&Expr> = match value {
+        Expr::Array(array) => array.elems.iter().collect(),
+        e => vec![e],
+    };
+    if elements.is_empty() {
+        return Err(Error::new_spanned(
+            value,
+            "You must specify at least one HTTP method.",
+        ));
+    }
+    let mut methods = Vec::new();
+    for element in elements {
+        let method = match element {
+            Expr::Path(p) => p.path.get_ident().map(|i| i.to_string()),
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(s), ..
+            }) => Some(s.value()),
+            _ => None,
+        };
+        let Some(method) = method else {
+            return Err(Error::new_spanned(
+                element,
+                "Invalid HTTP method. Expected a method name, e.g. `GET` or `\"PURGE\"` for custom methods.",
+            ));
+        };
+        match method.as_str() {
+            "ANY" => methods.extend(WELL_KNOWN_METHODS.iter().map(|m| m.to_string())),
+            "ANY_WITH_EXTENSIONS" => return Ok("*".to_owned()),
+            m if WELL_KNOWN_METHODS.contains(&m) => methods.push(method),
+            m if matches!(element, Expr::Lit(_))
+                && !m.is_empty()
+                && m.bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)) =>
+            {
+                methods.push(method)
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    element,
+                    format!(
+                        "`{method}` is not a well-known HTTP method. Expected one of {}, `ANY` or `ANY_WITH_EXTENSIONS`.\n\
+                        Use a string literal (e.g. `\"PURGE\"`) for custom methods.",
+                        WELL_KNOWN_METHODS
+                            .iter()
+                            .map(|m| format!("`{m}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+    methods.sort();
+    methods.dedup();
+    Ok(methods.join(","))
+}
+
+fn parse_route_path(value: &Expr) -> Result<String, Error> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(path),
+        ..
+    }) = value
+    else {
+        return Err(Error::new_spanned(
+            value,
+            "The route path must be a string literal, e.g. `\"/users/{id}\"`.",
+        ));
+    };
+    let path = path.value();
+    if !path.is_empty() && !path.starts_with('/') {
+        return Err(Error::new_spanned(
+            value,
+            "Route paths must either be empty or begin with a forward slash, `/`.",
+        ));
+    }
+    Ok(path)
+}
@@ -1,5 +1,7 @@
+use components::Kind;
 use proc_macro::TokenStream;
 
+mod components;
 mod config_profile;
 mod path_params;
 
@@ -13,3 +15,135 @@ pub fn PathParams(_metadata: TokenStream, input: TokenStream) -> TokenStream {
 pub fn derive_config_profile(input: TokenStream) -> TokenStream {
     config_profile::derive_config_profile(input)
 }
+
+/// Mark a function (or a method) as a constructor.
+///
+/// The lifecycle is required: `#[pavex::constructor(request_scoped)]`.
+/// Check out [`Blueprint::import`](https://docs.rs/pavex/latest/pavex/blueprint/struct.Blueprint.html#method.import)
+/// for more details on how annotated components are registered.
+#[proc_macro_attribute]
+pub fn constructor(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Constructor(None), metadata, input)
+}
+
+/// Mark a function (or a method) as a singleton constructor.
+///
+/// A shorthand for `#[pavex::constructor(singleton)]`.
+#[proc_macro_attribute]
+pub fn singleton(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Constructor(Some("singleton")), metadata, input)
+}
+
+/// Mark a function (or a method) as a request-scoped constructor.
+///
+/// A shorthand for `#[pavex::constructor(request_scoped)]`.
+#[proc_macro_attribute]
+pub fn request_scoped(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Constructor(Some("request_scoped")), metadata, input)
+}
+
+/// Mark a function (or a method) as a transient constructor.
+///
+/// A shorthand for `#[pavex::constructor(transient)]`.
+#[proc_macro_attribute]
+pub fn transient(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Constructor(Some("transient")), metadata, input)
+}
+
+/// Mark a function (or a method) as a request handler.
+///
+/// E.g. `#[pavex::route(method = [GET, HEAD], path = "/")]`.
+#[proc_macro_attribute]
+pub fn route(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Route(None), metadata, input)
+}
+
+/// Mark a function (or a method) as the request handler for `GET` requests.
+///
+/// A shorthand for `#[pavex::route(method = GET, path = "...")]`.
+#[proc_macro_attribute]
+pub fn get(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Route(Some("GET")), metadata, input)
+}
+
+/// Mark a function (or a method) as the request handler for `POST` requests.
+///
+/// A shorthand for `#[pavex::route(method = POST, path = "...")]`.
+#[proc_macro_attribute]
+pub fn post(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Route(Some("POST")), metadata, input)
+}
+
+/// Mark a function (or a method) as the request handler for `PUT` requests.
+///
+/// A shorthand for `#[pavex::route(method = PUT, path = "...")]`.
+#[proc_macro_attribute]
+pub fn put(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Route(Some("PUT")), metadata, input)
+}
+
+/// Mark a function (or a method) as the request handler for `PATCH` requests.
+///
+/// A shorthand for `#[pavex::route(method = PATCH, path = "...")]`.
+#[proc_macro_attribute]
+pub fn patch(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Route(Some("PATCH")), metadata, input)
+}
+
+/// Mark a function (or a method) as the request handler for `DELETE` requests.
+///
+/// A shorthand for `#[pavex::route(method = DELETE, path = "...")]`.
+#[proc_macro_attribute]
+pub fn delete(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Route(Some("DELETE")), metadata, input)
+}
+
+/// Mark a function (or a method) as the request handler for `HEAD` requests.
+///
+/// A shorthand for `#[pavex::route(method = HEAD, path = "...")]`.
+#[proc_macro_attribute]
+pub fn head(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Route(Some("HEAD")), metadata, input)
+}
+
+/// Mark a function (or a method) as the request handler for `OPTIONS` requests.
+///
+/// A shorthand for `#[pavex::route(method = OPTIONS, path = "...")]`.
+#[proc_macro_attribute]
+pub fn options(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Route(Some("OPTIONS")), metadata, input)
+}
+
+/// Mark a function (or a method) as a wrapping middleware.
+#[proc_macro_attribute]
+pub fn wrap(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::Wrap, metadata, input)
+}
+
+/// Mark a function (or a method) as a pre-processing middleware.
+#[proc_macro_attribute]
+pub fn pre_process(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::PreProcess, metadata, input)
+}
+
+/// Mark a function (or a method) as a post-processing middleware.
+#[proc_macro_attribute]
+pub fn post_process(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::PostProcess, metadata, input)
+}
+
+/// Mark a function (or a method) as an error observer.
+#[proc_macro_attribute]
+pub fn error_observer(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::ErrorObserver, metadata, input)
+}
+
+/// Mark a function (or a method) as the default error handler for the error type
+/// it takes as input.
+///
+/// When imported, it is used for every fallible component that returns that error type
+/// and doesn't specify an error handler of its own.
+#[proc_macro_attribute]
+pub fn error_handler(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    components::annotate(Kind::ErrorHandler, metadata, input)
+}
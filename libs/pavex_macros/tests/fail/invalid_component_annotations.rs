@@ -0,0 +1,31 @@
+use pavex::response::Response;
+
+#[pavex_macros::constructor]
+pub fn missing_lifecycle() -> u64 {
+    0
+}
+
+#[pavex_macros::constructor(scoped)]
+pub fn unknown_lifecycle() -> u64 {
+    0
+}
+
+#[pavex_macros::get(path = "users")]
+pub fn relative_path() -> Response {
+    Response::ok()
+}
+
+#[pavex_macros::route(method = FETCH, path = "/")]
+pub fn unknown_method() -> Response {
+    Response::ok()
+}
+
+#[pavex_macros::error_handler(request_scoped)]
+pub fn handler_with_arguments(_e: &pavex::Error) -> Response {
+    Response::internal_server_error()
+}
+
+#[pavex_macros::request_scoped]
+pub struct NotAFunction;
+
+fn main() {}
@@ -0,0 +1,56 @@
+use pavex::response::Response;
+
+pub struct Client;
+
+impl Client {
+    #[pavex_macros::singleton(never_clone)]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Debug)]
+pub struct Error;
+
+#[pavex_macros::error_handler]
+pub fn handle_error(_e: &Error) -> Response {
+    Response::internal_server_error()
+}
+
+#[pavex_macros::constructor(request_scoped, error_handler = crate::handle_error)]
+pub fn fallible() -> Result<u64, Error> {
+    Ok(0)
+}
+
+#[pavex_macros::get(path = "/")]
+pub fn index() -> Response {
+    Response::ok()
+}
+
+#[pavex_macros::route(method = [GET, "PURGE"], path = "/cache")]
+pub fn purge() -> Response {
+    Response::ok()
+}
+
+#[pavex_macros::route(method = ANY, path = "/any")]
+pub fn any() -> Response {
+    Response::ok()
+}
+
+#[pavex_macros::wrap]
+pub async fn timeout<C>(next: pavex::middleware::Next<C>) -> Response
+where
+    C: std::future::IntoFuture<Output = Response>,
+{
+    next.await
+}
+
+#[pavex_macros::post_process]
+pub fn log(response: Response) -> Response {
+    response
+}
+
+#[pavex_macros::error_observer]
+pub fn observe(_e: &pavex::Error) {}
+
+fn main() {}
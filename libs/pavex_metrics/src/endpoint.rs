@@ -0,0 +1,35 @@
+use pavex::http::HeaderValue;
+use pavex::http::header::CONTENT_TYPE;
+use pavex::response::Response;
+
+use crate::MetricsRegistry;
+
+/// The content type of the OpenMetrics text format.
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// A request handler that exposes all the metrics in [`MetricsRegistry`]
+/// using the OpenMetrics text format.
+///
+/// # Registration
+///
+/// [`MetricsKit`](crate::MetricsKit) registers it for you, on `GET /metrics`.
+/// Alternatively, use [`Blueprint::route`] to register it manually:
+///
+/// ```rust
+/// use pavex::blueprint::{Blueprint, router::GET};
+/// use pavex::f;
+///
+/// let mut bp = Blueprint::new();
+/// bp.route(GET, "/internal/metrics", f!(pavex_metrics::metrics_endpoint));
+/// ```
+///
+/// [`Blueprint::route`]: pavex::blueprint::Blueprint::route
+pub fn metrics_endpoint(registry: &MetricsRegistry) -> Response {
+    match registry.encode() {
+        Ok(body) => Response::ok().set_typed_body(body).insert_header(
+            CONTENT_TYPE,
+            HeaderValue::from_static(OPENMETRICS_CONTENT_TYPE),
+        ),
+        Err(_) => Response::internal_server_error(),
+    }
+}
@@ -0,0 +1,101 @@
+use pavex::blueprint::Blueprint;
+use pavex::blueprint::constructor::Constructor;
+use pavex::blueprint::linter::Lint;
+use pavex::blueprint::middleware::WrappingMiddleware;
+use pavex::blueprint::router::GET;
+use pavex::f;
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// A collection of components to record HTTP metrics and expose them
+/// in the OpenMetrics text format.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex_metrics::MetricsKit;
+///
+/// let mut bp = Blueprint::new();
+/// // Register the kit before your other middlewares, to measure
+/// // the entire request processing pipeline.
+/// MetricsKit::new().register(&mut bp);
+/// ```
+///
+/// # Bring your own registry
+///
+/// By default, [`MetricsRegistry`] is built by [`MetricsRegistry::new`].
+/// Register it as a prebuilt type if you want to build it yourself—e.g. to expose
+/// the statistics collected by the [`Server`](pavex::server::Server) via
+/// [`MetricsRegistry::register_server_stats`]:
+///
+/// ```rust
+/// use pavex::blueprint::{Blueprint, prebuilt::PrebuiltType};
+/// use pavex::t;
+/// use pavex_metrics::MetricsKit;
+///
+/// let mut bp = Blueprint::new();
+/// let mut kit = MetricsKit::new();
+/// kit.registry = None;
+/// kit.register(&mut bp);
+/// PrebuiltType::new(t!(pavex_metrics::MetricsRegistry)).register(&mut bp);
+/// ```
+///
+/// [`MetricsRegistry`]: crate::MetricsRegistry
+/// [`MetricsRegistry::new`]: crate::MetricsRegistry::new
+/// [`MetricsRegistry::register_server_stats`]: crate::MetricsRegistry::register_server_stats
+pub struct MetricsKit {
+    /// A wrapping middleware to record HTTP metrics for every incoming request.
+    ///
+    /// By default, it's set to [`record_http_metrics`](crate::record_http_metrics).
+    pub middleware: Option<WrappingMiddleware>,
+    /// The path of the endpoint that exposes the metrics.
+    ///
+    /// By default, it's `/metrics`.
+    /// Requests are handled by [`metrics_endpoint`](crate::metrics_endpoint).
+    pub endpoint_path: Option<String>,
+    /// The constructor for [`MetricsRegistry`](crate::MetricsRegistry).
+    ///
+    /// By default, it uses [`MetricsRegistry::new`](crate::MetricsRegistry::new).
+    pub registry: Option<Constructor>,
+}
+
+impl Default for MetricsKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsKit {
+    /// Create a new [`MetricsKit`] with all the bundled components.
+    pub fn new() -> Self {
+        Self {
+            middleware: Some(WrappingMiddleware::new(f!(crate::record_http_metrics))),
+            endpoint_path: Some("/metrics".into()),
+            registry: Some(
+                Constructor::singleton(f!(crate::MetricsRegistry::new)).ignore(Lint::Unused),
+            ),
+        }
+    }
+
+    /// Register all the bundled components with a [`Blueprint`].
+    ///
+    /// If a component is set to `None` it will not be registered.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredMetricsKit {
+        if let Some(middleware) = self.middleware {
+            middleware.register(bp);
+        }
+        if let Some(endpoint_path) = self.endpoint_path {
+            bp.route(GET, &endpoint_path, f!(crate::metrics_endpoint));
+        }
+        if let Some(registry) = self.registry {
+            registry.register(bp);
+        }
+        RegisteredMetricsKit {}
+    }
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The type returned by [`MetricsKit::register`].
+pub struct RegisteredMetricsKit {}
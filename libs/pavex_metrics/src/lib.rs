@@ -0,0 +1,51 @@
+//! Prometheus/OpenMetrics metrics for applications built with the [Pavex] framework.
+//!
+//! # Overview
+//!
+//! Register [`MetricsKit`] against your [`Blueprint`](pavex::blueprint::Blueprint) to:
+//!
+//! - record HTTP metrics for every incoming request, via the [`record_http_metrics`] middleware;
+//! - expose all metrics in the OpenMetrics text format, via the [`metrics_endpoint`] route;
+//! - register [`MetricsRegistry`], the registry that holds all the metrics.
+//!
+//! # HTTP metrics
+//!
+//! | Name | Type | Labels |
+//! |------|------|--------|
+//! | `http_server_requests_total` | Counter | `method`, `route`, `status` |
+//! | `http_server_request_duration_seconds` | Histogram | `method`, `route`, `status` |
+//! | `http_server_active_requests` | Gauge | `method`, `route` |
+//!
+//! The `route` label is set to the [`MatchedPathPattern`](pavex::request::path::MatchedPathPattern)
+//! of the incoming request, to keep the cardinality of the metrics under control.
+//! It's set to `*` for requests that didn't match any route.
+//!
+//! # Server metrics
+//!
+//! Call [`MetricsRegistry::register_server_stats`] to expose the statistics collected by
+//! the [`Server`](pavex::server::Server):
+//!
+//! | Name | Type | Labels |
+//! |------|------|--------|
+//! | `pavex_server_open_connections` | Gauge | `worker` |
+//! | `pavex_server_accept_errors_total` | Counter | |
+//!
+//! # Custom metrics
+//!
+//! [`MetricsRegistry`] is built on top of [`prometheus_client`]'s [`Registry`].
+//! You can bring your own registry, with your application-specific metrics, via
+//! [`MetricsRegistry::from_registry`].
+//!
+//! [Pavex]: https://pavex.dev
+//! [`Registry`]: prometheus_client::registry::Registry
+mod endpoint;
+mod kit;
+mod mw;
+mod registry;
+mod server;
+
+pub use endpoint::metrics_endpoint;
+pub use kit::{MetricsKit, RegisteredMetricsKit};
+pub use mw::record_http_metrics;
+pub use prometheus_client;
+pub use registry::MetricsRegistry;
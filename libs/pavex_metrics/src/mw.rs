@@ -0,0 +1,165 @@
+use std::future::IntoFuture;
+use std::time::Instant;
+
+use pavex::middleware::Next;
+use pavex::request::RequestHead;
+use pavex::request::path::MatchedPathPattern;
+use pavex::response::Response;
+use prometheus_client::metrics::gauge::Gauge;
+
+use crate::MetricsRegistry;
+use crate::registry::{ActiveRequestLabels, HttpLabels};
+
+/// A middleware to record HTTP metrics for every incoming request.
+///
+/// Check out the [crate-level documentation](crate#http-metrics) for the list of
+/// metrics it records.
+///
+/// # Registration
+///
+/// [`MetricsKit`](crate::MetricsKit) registers it for you.
+/// Alternatively, use [`Blueprint::wrap`] to register it manually:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::f;
+///
+/// let mut bp = Blueprint::new();
+/// bp.wrap(f!(pavex_metrics::record_http_metrics));
+/// ```
+///
+/// Register it before your other middlewares to measure the entire request processing pipeline.
+///
+/// [`Blueprint::wrap`]: pavex::blueprint::Blueprint::wrap
+pub async fn record_http_metrics<C>(
+    registry: &MetricsRegistry,
+    request_head: &RequestHead,
+    matched_path: MatchedPathPattern,
+    next: Next<C>,
+) -> Response
+where
+    C: IntoFuture<Output = Response>,
+{
+    let method = request_head.method.as_str().to_owned();
+    let route = matched_path.inner();
+    let active = ActiveRequestGuard::new(
+        registry
+            .http
+            .active
+            .get_or_create(&ActiveRequestLabels {
+                method: method.clone(),
+                route,
+            })
+            .clone(),
+    );
+    let start = Instant::now();
+    let response = next.into_future().await;
+    let elapsed = start.elapsed();
+    drop(active);
+
+    let labels = HttpLabels {
+        method,
+        route,
+        status: response.status().as_u16(),
+    };
+    registry.http.requests.get_or_create(&labels).inc();
+    registry
+        .http
+        .duration
+        .get_or_create(&labels)
+        .observe(elapsed.as_secs_f64());
+    response
+}
+
+/// Increments the active requests gauge when created and decrements it when dropped.
+///
+/// The request future may be dropped before it completes (e.g. the client disconnected,
+/// or the server is shutting down): a guard keeps the gauge accurate in that case too.
+struct ActiveRequestGuard(Gauge);
+
+impl ActiveRequestGuard {
+    fn new(gauge: Gauge) -> Self {
+        gauge.inc();
+        Self(gauge)
+    }
+}
+
+impl Drop for ActiveRequestGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pavex::http::{HeaderMap, Method, StatusCode, Version};
+    use pavex::middleware::Next;
+    use pavex::request::RequestHead;
+    use pavex::request::path::MatchedPathPattern;
+    use pavex::response::Response;
+
+    use super::record_http_metrics;
+    use crate::MetricsRegistry;
+
+    fn request_head() -> RequestHead {
+        RequestHead {
+            method: Method::POST,
+            target: "/users/42".parse().unwrap(),
+            version: Version::HTTP_11,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_are_labelled_by_method_route_and_status() {
+        let registry = MetricsRegistry::new();
+        let request_head = request_head();
+        let next = Next::new(async { Response::new(StatusCode::CREATED) });
+        let response = record_http_metrics(
+            &registry,
+            &request_head,
+            MatchedPathPattern::new("/users/{id}"),
+            next,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let encoded = registry.encode().unwrap();
+        assert!(encoded.contains(
+            r#"http_server_requests_total{method="POST",route="/users/{id}",status="201"} 1"#
+        ));
+        assert!(encoded.contains(
+            r#"http_server_request_duration_seconds_count{method="POST",route="/users/{id}",status="201"} 1"#
+        ));
+        assert!(
+            encoded.contains(r#"http_server_active_requests{method="POST",route="/users/{id}"} 0"#)
+        );
+    }
+
+    #[tokio::test]
+    async fn active_requests_are_decremented_if_the_request_is_cancelled() {
+        let registry = MetricsRegistry::new();
+        let request_head = request_head();
+        let next = Next::new(std::future::pending::<Response>());
+        let outcome = tokio::time::timeout(
+            Duration::from_millis(10),
+            record_http_metrics(
+                &registry,
+                &request_head,
+                MatchedPathPattern::new("/users/{id}"),
+                next,
+            ),
+        )
+        .await;
+        assert!(outcome.is_err());
+
+        let encoded = registry.encode().unwrap();
+        assert!(
+            encoded.contains(r#"http_server_active_requests{method="POST",route="/users/{id}"} 0"#)
+        );
+        // The request never completed: it must not be counted as processed.
+        assert!(!encoded.contains("http_server_requests_total{"));
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use pavex::server::ServerStats;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::Histogram;
+use prometheus_client::registry::Registry;
+
+use crate::server::ServerStatsCollector;
+
+/// The registry holding all the metrics exposed by your application.
+///
+/// It's cheap to clone: all clones share the same underlying registry.
+///
+/// # Bring your own registry
+///
+/// [`MetricsRegistry::new`] starts from an empty [`Registry`].
+/// Use [`MetricsRegistry::from_registry`] if you have already registered your own
+/// metrics with a [`Registry`], or use [`MetricsRegistry::with_registry`] to register
+/// them later on.
+#[derive(Clone)]
+pub struct MetricsRegistry {
+    registry: Arc<RwLock<Registry>>,
+    pub(crate) http: HttpMetrics,
+}
+
+impl fmt::Debug for MetricsRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsRegistry").finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct HttpMetrics {
+    pub(crate) requests: Family<HttpLabels, Counter>,
+    pub(crate) duration: Family<HttpLabels, Histogram, fn() -> Histogram>,
+    pub(crate) active: Family<ActiveRequestLabels, Gauge>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub(crate) struct HttpLabels {
+    pub(crate) method: String,
+    pub(crate) route: &'static str,
+    pub(crate) status: u16,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub(crate) struct ActiveRequestLabels {
+    pub(crate) method: String,
+    pub(crate) route: &'static str,
+}
+
+/// The buckets of the request duration histogram, in seconds.
+const DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsRegistry {
+    /// Create a new [`MetricsRegistry`], starting from an empty [`Registry`].
+    pub fn new() -> Self {
+        Self::from_registry(Registry::default())
+    }
+
+    /// Create a new [`MetricsRegistry`] on top of an existing [`Registry`].
+    ///
+    /// The HTTP metrics are added to `registry`, alongside the metrics
+    /// you've already registered.
+    pub fn from_registry(mut registry: Registry) -> Self {
+        let http = HttpMetrics {
+            requests: Family::default(),
+            duration: Family::new_with_constructor(|| Histogram::new(DURATION_BUCKETS)),
+            active: Family::default(),
+        };
+        registry.register(
+            "http_server_requests",
+            "The number of HTTP requests processed by the server",
+            http.requests.clone(),
+        );
+        registry.register(
+            "http_server_request_duration_seconds",
+            "How long it took to process HTTP requests",
+            http.duration.clone(),
+        );
+        registry.register(
+            "http_server_active_requests",
+            "The number of HTTP requests currently being processed by the server",
+            http.active.clone(),
+        );
+        Self {
+            registry: Arc::new(RwLock::new(registry)),
+            http,
+        }
+    }
+
+    /// Expose the statistics collected by a [`Server`](pavex::server::Server).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::server::Server;
+    /// use pavex_metrics::MetricsRegistry;
+    ///
+    /// let registry = MetricsRegistry::new();
+    /// let server = Server::new();
+    /// registry.register_server_stats(server.stats());
+    /// ```
+    pub fn register_server_stats(&self, stats: ServerStats) {
+        self.with_registry(|registry| {
+            registry.register_collector(Box::new(ServerStatsCollector::new(stats)))
+        });
+    }
+
+    /// Get mutable access to the underlying [`Registry`]—e.g. to register your own metrics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex_metrics::MetricsRegistry;
+    /// use pavex_metrics::prometheus_client::metrics::counter::Counter;
+    ///
+    /// let registry = MetricsRegistry::new();
+    /// let signups = Counter::<u64>::default();
+    /// registry.with_registry(|r| r.register("signups", "The number of sign-ups", signups.clone()));
+    /// ```
+    pub fn with_registry<T>(&self, f: impl FnOnce(&mut Registry) -> T) -> T {
+        let mut registry = self
+            .registry
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        f(&mut registry)
+    }
+
+    /// Encode all metrics using the OpenMetrics text format.
+    pub fn encode(&self) -> Result<String, fmt::Error> {
+        let registry = self.registry.read().unwrap_or_else(PoisonError::into_inner);
+        let mut buffer = String::new();
+        prometheus_client::encoding::text::encode(&mut buffer, &registry)?;
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,107 @@
+use pavex::server::ServerStats;
+use prometheus_client::collector::Collector;
+use prometheus_client::encoding::{DescriptorEncoder, EncodeMetric};
+use prometheus_client::metrics::counter::ConstCounter;
+use prometheus_client::metrics::gauge::ConstGauge;
+use prometheus_client::metrics::{MetricType, TypedMetric};
+
+/// Expose [`ServerStats`] as metrics, reading their current value on every scrape.
+#[derive(Debug)]
+pub(crate) struct ServerStatsCollector {
+    stats: ServerStats,
+}
+
+impl ServerStatsCollector {
+    pub(crate) fn new(stats: ServerStats) -> Self {
+        Self { stats }
+    }
+}
+
+impl Collector for ServerStatsCollector {
+    fn encode(&self, mut encoder: DescriptorEncoder) -> Result<(), std::fmt::Error> {
+        let mut open_connections = encoder.encode_descriptor(
+            "pavex_server_open_connections",
+            "The number of open connections handled by each worker",
+            None,
+            MetricType::Gauge,
+        )?;
+        for (worker, n) in self.stats.open_connections().into_iter().enumerate() {
+            let gauge = ConstGauge::new(n as i64);
+            gauge.encode(open_connections.encode_family(&[("worker", worker)])?)?;
+        }
+
+        let accept_errors = ConstCounter::new(self.stats.accept_errors());
+        accept_errors.encode(encoder.encode_descriptor(
+            "pavex_server_accept_errors",
+            "The number of times the server failed to accept an incoming connection",
+            None,
+            ConstCounter::<u64>::TYPE,
+        )?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::Request;
+    use pavex::connection::ConnectionInfo;
+    use pavex::response::Response;
+    use pavex::server::{IncomingStream, Server, ServerConfiguration};
+
+    use crate::MetricsRegistry;
+
+    async fn handler<B>(
+        _request: Request<B>,
+        _info: Option<ConnectionInfo>,
+        _state: (),
+    ) -> Response {
+        Response::ok()
+    }
+
+    #[test]
+    fn server_stats_are_exposed_once_registered() {
+        let registry = MetricsRegistry::new();
+        registry.register_server_stats(Server::new().stats());
+
+        let encoded = registry.encode().unwrap();
+        assert!(encoded.contains("# TYPE pavex_server_open_connections gauge"));
+        assert!(encoded.contains("pavex_server_accept_errors_total 0"));
+        // The server hasn't started yet: there are no workers to report on.
+        assert!(!encoded.contains("pavex_server_open_connections{"));
+    }
+
+    #[tokio::test]
+    async fn open_connections_are_reported_for_each_worker() {
+        let incoming = IncomingStream::bind("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let addr = incoming.local_addr().unwrap();
+        let server = Server::new()
+            .set_config(ServerConfiguration::new().set_n_workers(1))
+            .listen(incoming);
+        let registry = MetricsRegistry::new();
+        registry.register_server_stats(server.stats());
+        let _handle = server.serve(handler, ());
+        assert!(
+            registry
+                .encode()
+                .unwrap()
+                .contains(r#"pavex_server_open_connections{worker="0"} 0"#)
+        );
+
+        let _connection = std::net::TcpStream::connect(addr).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !registry
+                .encode()
+                .unwrap()
+                .contains(r#"pavex_server_open_connections{worker="0"} 1"#)
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The open connection was never reported");
+    }
+}
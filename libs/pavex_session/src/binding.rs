@@ -0,0 +1,171 @@
+//! Bind sessions to the client that created them, to mitigate session hijacking.
+//!
+//! # Guide
+//!
+//! A stolen session cookie can be used from anywhere.
+//! Binding makes it harder to exploit: a [`SessionFingerprint`] of the client is stored
+//! in the server-side state, under [`BINDING_KEY`], when the session is created.
+//! Every time the server-side state is loaded, the fingerprint of the current request is
+//! compared to the stored one. If they differ, the configured
+//! [`BindingMismatchPolicy`](crate::config::BindingMismatchPolicy) is applied.
+//!
+//! Binding is disabled by default.
+//! Enable it by choosing the [signals](crate::config::BindingSignal) that make up the fingerprint:
+//!
+//! ```yaml
+//! session:
+//!   binding:
+//!     signals: ["ip_prefix", "user_agent"]
+//!     on_mismatch: "invalidate"
+//! ```
+//!
+//! # Caveats
+//!
+//! Fingerprints are a mitigation, not a guarantee: an attacker may be able to
+//! spoof the same signals as the victim.
+//! Legitimate clients may also see their fingerprint change (e.g. a browser update,
+//! or a new network), which is why the check is configurable.
+//!
+//! If your application runs behind a reverse proxy, the peer address is the proxy's.
+//! Override the [`SessionFingerprint`] constructor in [`SessionKit`](crate::SessionKit)
+//! to compute it from the address forwarded by the proxy, via [`SessionFingerprint::compute`].
+use std::net::IpAddr;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use pavex::connection::ConnectionInfo;
+use pavex::http::header::USER_AGENT;
+use pavex::request::RequestHead;
+use sha2::{Digest, Sha256};
+
+use crate::SessionConfig;
+use crate::config::{BindingSignal, SessionBindingConfig};
+
+/// The key used to store the session fingerprint in the server-side session state.
+pub const BINDING_KEY: &str = "pavex.binding";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// A fingerprint of the client that sent the current request.
+///
+/// It's empty if binding is disabled.
+/// Check out the [module documentation](self) for more details.
+pub struct SessionFingerprint {
+    #[serde(rename = "ip", default, skip_serializing_if = "Option::is_none")]
+    ip_prefix: Option<String>,
+    #[serde(rename = "ua", default, skip_serializing_if = "Option::is_none")]
+    user_agent: Option<String>,
+    #[serde(rename = "tls", default, skip_serializing_if = "Option::is_none")]
+    tls_channel: Option<String>,
+}
+
+impl SessionFingerprint {
+    /// Compute the fingerprint of the current request, using the signals enabled
+    /// in [`SessionBindingConfig`].
+    ///
+    /// The TLS channel identifier is not available: use [`SessionFingerprint::compute`]
+    /// if you want to include it.
+    pub fn extract(
+        request_head: &RequestHead,
+        connection_info: &ConnectionInfo,
+        config: &SessionConfig,
+    ) -> Self {
+        Self::compute(
+            &config.binding,
+            Some(connection_info.peer_addr().ip()),
+            request_head
+                .headers
+                .get(USER_AGENT)
+                .map(|v| v.as_bytes())
+                .or(Some(b"")),
+            None,
+        )
+    }
+
+    /// Compute a fingerprint from its raw parts.
+    ///
+    /// Signals that aren't enabled in `config` are ignored, as well as
+    /// the ones that are `None`.
+    pub fn compute(
+        config: &SessionBindingConfig,
+        peer_ip: Option<IpAddr>,
+        user_agent: Option<&[u8]>,
+        tls_channel_id: Option<&[u8]>,
+    ) -> Self {
+        let mut fingerprint = Self::default();
+        for signal in &config.signals {
+            match signal {
+                BindingSignal::IpPrefix => {
+                    fingerprint.ip_prefix = peer_ip.map(|ip| ip_prefix(ip, config));
+                }
+                BindingSignal::UserAgent => {
+                    fingerprint.user_agent = user_agent.map(hash);
+                }
+                BindingSignal::TlsChannel => {
+                    fingerprint.tls_channel = tls_channel_id.map(hash);
+                }
+            }
+        }
+        fingerprint
+    }
+
+    /// Returns `true` if the fingerprint doesn't include any signal.
+    pub fn is_empty(&self) -> bool {
+        self.ip_prefix.is_none() && self.user_agent.is_none() && self.tls_channel.is_none()
+    }
+
+    /// The signals that are present in both fingerprints, but with different values.
+    pub(crate) fn mismatches(&self, other: &Self) -> Vec<BindingSignal> {
+        let differ =
+            |a: &Option<String>, b: &Option<String>| matches!((a, b), (Some(a), Some(b)) if a != b);
+        let mut mismatches = Vec::new();
+        if differ(&self.ip_prefix, &other.ip_prefix) {
+            mismatches.push(BindingSignal::IpPrefix);
+        }
+        if differ(&self.user_agent, &other.user_agent) {
+            mismatches.push(BindingSignal::UserAgent);
+        }
+        if differ(&self.tls_channel, &other.tls_channel) {
+            mismatches.push(BindingSignal::TlsChannel);
+        }
+        mismatches
+    }
+
+    /// Fill in the signals that are missing from `self` using the values in `other`.
+    ///
+    /// It returns `true` if `self` was modified.
+    pub(crate) fn merge_missing(&mut self, other: &Self) -> bool {
+        let mut modified = false;
+        for (mine, theirs) in [
+            (&mut self.ip_prefix, &other.ip_prefix),
+            (&mut self.user_agent, &other.user_agent),
+            (&mut self.tls_channel, &other.tls_channel),
+        ] {
+            if mine.is_none() && theirs.is_some() {
+                *mine = theirs.clone();
+                modified = true;
+            }
+        }
+        modified
+    }
+}
+
+/// Mask the address to the configured prefix length, e.g. `203.0.113.0/24`.
+fn ip_prefix(ip: IpAddr, config: &SessionBindingConfig) -> String {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => {
+            let len = config.ipv4_prefix_len.min(32);
+            let mask = u32::MAX.checked_shl(32 - len as u32).unwrap_or(0);
+            let network = std::net::Ipv4Addr::from(u32::from(ip) & mask);
+            format!("{network}/{len}")
+        }
+        IpAddr::V6(ip) => {
+            let len = config.ipv6_prefix_len.min(128);
+            let mask = u128::MAX.checked_shl(128 - len as u32).unwrap_or(0);
+            let network = std::net::Ipv6Addr::from(u128::from(ip) & mask);
+            format!("{network}/{len}")
+        }
+    }
+}
+
+fn hash(value: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(value))
+}
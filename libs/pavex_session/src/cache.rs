@@ -0,0 +1,307 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+
+use crate::{
+    SessionId, State,
+    store::{
+        SessionRecord, SessionRecordRef, SessionStorageBackend,
+        errors::{
+            ChangeIdError, CreateError, DeleteByPrincipalError, DeleteError, DeleteExpiredError,
+            ListByPrincipalError, LoadError, UpdateError, UpdateTtlError,
+        },
+    },
+};
+
+/// A [`SessionStorageBackend`] that keeps recently loaded records in memory,
+/// in front of another backend.
+///
+/// Loads are served from an in-process LRU cache when possible, saving a round-trip
+/// to the inner backend.
+/// Cached records are invalidated when they're updated, deleted or assigned a new ID
+/// through this store.
+///
+/// # Consistency
+///
+/// The cache is local to the process.
+/// If you run multiple instances of your application, changes made by one instance
+/// won't invalidate the cache of the others: they may serve a stale state
+/// for up to [`CachedSessionStore::ttl`].
+/// Keep the TTL short (the default is 5 seconds), or stick to a single instance.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex_session::SessionStore;
+/// use pavex_session::store::CachedSessionStore;
+/// use pavex_session_memory_store::InMemorySessionStore;
+///
+/// let cached = CachedSessionStore::new(InMemorySessionStore::default());
+/// // Grab a handle to the counters before type-erasing the backend.
+/// let stats = cached.stats();
+/// let store = SessionStore::new(cached);
+/// assert_eq!(stats.hits(), 0);
+/// ```
+pub struct CachedSessionStore<B> {
+    inner: B,
+    cache: Mutex<LruCache<SessionId, CachedRecord>>,
+    capacity: NonZeroUsize,
+    ttl: Duration,
+    /// Incremented on every invalidation.
+    ///
+    /// It's used to avoid caching a record that was modified while
+    /// it was being loaded from the inner backend.
+    generation: AtomicU64,
+    stats: CacheStats,
+}
+
+struct CachedRecord {
+    state: State,
+    /// When the record expires in the inner backend.
+    deadline: Instant,
+    /// When the record must be evicted from the cache.
+    evict_at: Instant,
+}
+
+impl<B: std::fmt::Debug> std::fmt::Debug for CachedSessionStore<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedSessionStore")
+            .field("inner", &self.inner)
+            .field("capacity", &self.capacity)
+            .field("ttl", &self.ttl)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> CachedSessionStore<B> {
+    /// Put a cache in front of `inner`.
+    ///
+    /// It holds up to 10,000 records, for at most 5 seconds each.
+    /// Use [`capacity`](Self::capacity) and [`ttl`](Self::ttl) to change these limits.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            // We enforce the capacity ourselves, to avoid pre-allocating
+            // room for all records upfront.
+            cache: Mutex::new(LruCache::unbounded()),
+            capacity: NonZeroUsize::new(10_000).unwrap(),
+            ttl: Duration::from_secs(5),
+            generation: AtomicU64::new(0),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Set the maximum number of records kept in the cache.
+    ///
+    /// The least recently used record is evicted when the cache is full.
+    pub fn capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set for how long a record can be served from the cache
+    /// before it's loaded again from the inner backend.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// A handle to the hit/miss counters of this cache.
+    ///
+    /// The handle stays valid after the store has been wrapped in a
+    /// [`SessionStore`](crate::SessionStore).
+    pub fn stats(&self) -> CacheStats {
+        self.stats.clone()
+    }
+
+    /// Get a fresh record from the cache, if there's one.
+    fn get(&self, id: &SessionId) -> Option<SessionRecord> {
+        let now = Instant::now();
+        let mut cache = self.cache.lock().unwrap();
+        // `get` marks the record as the most recently used.
+        let record = cache.get(id)?;
+        if record.evict_at <= now {
+            cache.pop(id);
+            return None;
+        }
+        Some(SessionRecord {
+            state: record.state.clone(),
+            ttl: record.deadline - now,
+        })
+    }
+
+    /// Cache a record that was loaded from the inner backend, unless the cache
+    /// was invalidated in the meantime.
+    fn insert(&self, id: SessionId, record: &SessionRecord, generation: u64) {
+        let now = Instant::now();
+        let deadline = now + record.ttl;
+        let mut cache = self.cache.lock().unwrap();
+        if self.generation.load(Ordering::Acquire) != generation {
+            return;
+        }
+        cache.pop(&id);
+        if cache.len() >= self.capacity.get() {
+            cache.pop_lru();
+        }
+        cache.put(
+            id,
+            CachedRecord {
+                state: record.state.clone(),
+                deadline,
+                evict_at: deadline.min(now + self.ttl),
+            },
+        );
+    }
+
+    fn clear(&self) {
+        let mut cache = self.cache.lock().unwrap();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        cache.clear();
+    }
+
+    fn invalidate(&self, id: &SessionId) {
+        let mut cache = self.cache.lock().unwrap();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        cache.pop(id);
+    }
+}
+
+#[async_trait::async_trait]
+impl<B> SessionStorageBackend for CachedSessionStore<B>
+where
+    B: SessionStorageBackend,
+{
+    async fn create(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), CreateError> {
+        self.invalidate(id);
+        self.inner.create(id, record).await
+    }
+
+    async fn update(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), UpdateError> {
+        self.invalidate(id);
+        let outcome = self.inner.update(id, record).await;
+        // Invalidate again, in case the old state was cached by a concurrent load.
+        self.invalidate(id);
+        outcome
+    }
+
+    async fn update_ttl(&self, id: &SessionId, ttl: Duration) -> Result<(), UpdateTtlError> {
+        let outcome = self.inner.update_ttl(id, ttl).await;
+        let mut cache = self.cache.lock().unwrap();
+        match outcome {
+            Ok(()) => {
+                // The state is unchanged: keep serving it from the cache.
+                if let Some(record) = cache.peek_mut(id) {
+                    let deadline = Instant::now() + ttl;
+                    record.deadline = deadline;
+                    record.evict_at = record.evict_at.min(deadline);
+                }
+            }
+            Err(_) => {
+                self.generation.fetch_add(1, Ordering::AcqRel);
+                cache.pop(id);
+            }
+        }
+        outcome
+    }
+
+    async fn load(&self, session_id: &SessionId) -> Result<Option<SessionRecord>, LoadError> {
+        if let Some(record) = self.get(session_id) {
+            self.stats.0.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Some(record));
+        }
+        self.stats.0.misses.fetch_add(1, Ordering::Relaxed);
+        let generation = self.generation.load(Ordering::Acquire);
+        let record = self.inner.load(session_id).await?;
+        if let Some(record) = &record {
+            self.insert(*session_id, record, generation);
+        }
+        Ok(record)
+    }
+
+    async fn delete(&self, session_id: &SessionId) -> Result<(), DeleteError> {
+        self.invalidate(session_id);
+        let outcome = self.inner.delete(session_id).await;
+        self.invalidate(session_id);
+        outcome
+    }
+
+    async fn change_id(&self, old_id: &SessionId, new_id: &SessionId) -> Result<(), ChangeIdError> {
+        self.invalidate(old_id);
+        self.invalidate(new_id);
+        let outcome = self.inner.change_id(old_id, new_id).await;
+        self.invalidate(old_id);
+        self.invalidate(new_id);
+        outcome
+    }
+
+    async fn delete_expired(
+        &self,
+        batch_size: Option<NonZeroUsize>,
+    ) -> Result<usize, DeleteExpiredError> {
+        self.inner.delete_expired(batch_size).await
+    }
+
+    async fn list_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<Vec<SessionId>, ListByPrincipalError> {
+        self.inner.list_by_principal(principal).await
+    }
+
+    async fn delete_by_principal(&self, principal: &str) -> Result<usize, DeleteByPrincipalError> {
+        // We don't know which records are bound to the principal: drop them all.
+        self.clear();
+        let outcome = self.inner.delete_by_principal(principal).await;
+        self.clear();
+        outcome
+    }
+}
+
+/// The hit/miss counters of a [`CachedSessionStore`].
+///
+/// It's a cheap handle: cloning it doesn't reset the counters.
+#[derive(Clone, Default)]
+pub struct CacheStats(Arc<Counters>);
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheStats {
+    /// The number of loads that were served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.0.hits.load(Ordering::Relaxed)
+    }
+
+    /// The number of loads that had to go to the inner backend.
+    pub fn misses(&self) -> u64 {
+        self.0.misses.load(Ordering::Relaxed)
+    }
+}
+
+impl std::fmt::Debug for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheStats")
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
@@ -0,0 +1,135 @@
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+/// Configure how sessions are bound to the client that created them,
+/// to mitigate session hijacking.
+///
+/// When binding is enabled, a [fingerprint](crate::binding::SessionFingerprint) of the
+/// client is stored in the server-side state when the session is created.
+/// It is compared to the fingerprint of the current request every time the server-side
+/// state is loaded, and [`on_mismatch`](Self::on_mismatch) determines what happens if they differ.
+///
+/// Binding is disabled by default.
+pub struct SessionBindingConfig {
+    /// The signals that make up the fingerprint.
+    ///
+    /// Binding is disabled if the list is empty, which is the default.
+    #[serde(default)]
+    pub signals: Vec<BindingSignal>,
+    /// What to do when the fingerprint of the current request doesn't match
+    /// the one stored in the session.
+    ///
+    /// By default, it's set to [`BindingMismatchPolicy::Invalidate`].
+    #[serde(default)]
+    pub on_mismatch: BindingMismatchPolicy,
+    /// The length of the network prefix used to fingerprint IPv4 addresses,
+    /// when [`BindingSignal::IpPrefix`] is enabled.
+    ///
+    /// Comparing prefixes rather than full addresses tolerates clients that hop between
+    /// addresses of the same network (e.g. mobile carriers).
+    ///
+    /// By default, it's set to 24.
+    #[serde(default = "default_ipv4_prefix_len")]
+    pub ipv4_prefix_len: u8,
+    /// The length of the network prefix used to fingerprint IPv6 addresses,
+    /// when [`BindingSignal::IpPrefix`] is enabled.
+    ///
+    /// By default, it's set to 64.
+    #[serde(default = "default_ipv6_prefix_len")]
+    pub ipv6_prefix_len: u8,
+}
+
+impl Default for SessionBindingConfig {
+    fn default() -> Self {
+        Self {
+            signals: Vec::new(),
+            on_mismatch: BindingMismatchPolicy::default(),
+            ipv4_prefix_len: default_ipv4_prefix_len(),
+            ipv6_prefix_len: default_ipv6_prefix_len(),
+        }
+    }
+}
+
+impl SessionBindingConfig {
+    /// Returns `true` if at least one signal is enabled.
+    pub fn is_enabled(&self) -> bool {
+        !self.signals.is_empty()
+    }
+}
+
+fn default_ipv4_prefix_len() -> u8 {
+    24
+}
+
+fn default_ipv6_prefix_len() -> u8 {
+    64
+}
+
+/// A client property that can be used to fingerprint a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum BindingSignal {
+    /// The network prefix of the peer IP address.
+    ///
+    /// The prefix length is controlled by [`SessionBindingConfig::ipv4_prefix_len`]
+    /// and [`SessionBindingConfig::ipv6_prefix_len`].
+    IpPrefix,
+    /// A hash of the `User-Agent` header.
+    UserAgent,
+    /// A hash of the TLS channel identifier.
+    ///
+    /// Pavex doesn't terminate TLS, so this signal is only taken into account if you
+    /// provide the identifier yourself, via
+    /// [`SessionFingerprint::compute`](crate::binding::SessionFingerprint::compute).
+    /// It's skipped when it's not available.
+    TlsChannel,
+}
+
+/// What to do when the fingerprint of the current request doesn't match the one
+/// the session is bound to.
+///
+/// All policies, except [`BindingMismatchPolicy::Ignore`], emit a `WARN` event and
+/// expose the mismatch via [`Session::binding_mismatch`](crate::Session::binding_mismatch)
+/// for the rest of the request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum BindingMismatchPolicy {
+    /// Carry on as if the fingerprints matched.
+    Ignore,
+    /// Emit a `WARN` event, then carry on as if the fingerprints matched.
+    Log,
+    /// Invalidate the session.
+    ///
+    /// The server-side state is deleted, and the client is asked to discard its session.
+    ///
+    /// This is the default.
+    #[default]
+    Invalidate,
+    /// Keep the session state, but assign a new ID to the session and
+    /// bind it to the fingerprint of the current request.
+    ///
+    /// # Security
+    ///
+    /// This policy hands the session over to the client that **failed** the check—i.e.
+    /// the suspected hijacker, if the mismatch is caused by a stolen session ID.
+    /// The ID held by any other client, including the legitimate owner of the session,
+    /// stops working.
+    ///
+    /// Only use it if mismatches are much more likely to be caused by legitimate clients
+    /// changing their properties (e.g. moving between networks) than by hijacking attempts.
+    CycleId,
+}
+
+impl BindingMismatchPolicy {
+    /// The lowercase name of the policy, e.g. `cycle_id`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BindingMismatchPolicy::Ignore => "ignore",
+            BindingMismatchPolicy::Log => "log",
+            BindingMismatchPolicy::Invalidate => "invalidate",
+            BindingMismatchPolicy::CycleId => "cycle_id",
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use pavex::config::Secret;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+/// Configure the encryption at rest of the server-side session state.
+///
+/// Encryption is performed by [`EncryptedStorageBackend`], which wraps
+/// the storage backend of your choice.
+///
+/// # Key rotation
+///
+/// The first key in [`keys`](Self::keys) is used to encrypt new and updated records.
+/// All keys are tried, by identifier, when decrypting.
+/// To rotate keys:
+///
+/// 1. Add the new key at the top of the list, keeping the old one(s) after it.
+/// 2. Wait for the old records to be re-encrypted or to expire.
+/// 3. Remove the old key(s) from the list.
+///
+/// Records are re-encrypted when their state is updated. Extending their TTL doesn't
+/// re-encrypt them. A session that stays active without changing its state keeps
+/// using the old key. The wait is therefore bounded by the
+/// [`max_lifetime`](super::SessionStateConfig::max_lifetime) or the
+/// [`idle_timeout`](super::SessionStateConfig::idle_timeout) of your sessions, if you set
+/// either of them. Otherwise, it's unbounded. Records that still use an old key when it's
+/// removed can't be loaded anymore: they're reported as
+/// [decryption errors](crate::store::errors::LoadError::DecryptionError).
+///
+/// # Example
+///
+/// ```yaml
+/// session:
+///   state:
+///     encryption:
+///       keys:
+///         - id: "2024-10"
+///           key: "<base64-encoded 32 bytes>"
+///         - id: "2024-04"
+///           key: "<base64-encoded 32 bytes>"
+/// ```
+///
+/// [`EncryptedStorageBackend`]: crate::store::EncryptedStorageBackend
+pub struct StateEncryptionConfig {
+    /// The keys used to encrypt and decrypt the session state.
+    ///
+    /// It must contain at least one key.
+    pub keys: Vec<StateEncryptionKey>,
+    /// Reject records whose state isn't encrypted, rather than loading them as they are.
+    ///
+    /// Plaintext records are accepted by default, to allow enabling encryption on an
+    /// existing store: they're encrypted the next time they're updated.
+    /// Enable it once all plaintext records have been re-encrypted or have expired.
+    /// Like for [key rotation](Self#key-rotation), extending the TTL of a record doesn't
+    /// encrypt it: the wait is bounded by the maximum lifetime or the idle timeout of your
+    /// sessions, if you set either of them.
+    #[serde(default)]
+    pub reject_plaintext: bool,
+}
+
+impl StateEncryptionConfig {
+    /// Create a new configuration from a list of keys.
+    ///
+    /// The first key is used for encryption.
+    pub fn new(keys: Vec<StateEncryptionKey>) -> Self {
+        Self {
+            keys,
+            reject_plaintext: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+/// A key used to encrypt the server-side session state, via AES-256-GCM.
+pub struct StateEncryptionKey {
+    /// The identifier of the key.
+    ///
+    /// It's stored alongside each encrypted record, to pick the right key when decrypting it.
+    /// It must be unique within [`StateEncryptionConfig::keys`].
+    pub id: String,
+    /// The key material: 32 bytes, encoded using standard base64.
+    ///
+    /// You can generate a new key with `openssl rand -base64 32`.
+    #[serde(skip_serializing)]
+    pub key: Secret<String>,
+}
+
+impl StateEncryptionKey {
+    /// Create a new key from its identifier and its base64-encoded material.
+    pub fn new(id: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            key: Secret::new(key.into()),
+        }
+    }
+}
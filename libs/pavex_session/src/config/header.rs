@@ -0,0 +1,85 @@
+use pavex::cookie::Key;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+/// Configure the headers used to exchange session information with the client,
+/// when using [`SessionTransport::Header`].
+///
+/// The session token is encrypted with [`key`](Self::key): clients can neither read
+/// nor tamper with it.
+/// Tokens that can't be decrypted are ignored, and a new session is created.
+///
+/// # Key rotation
+///
+/// To rotate keys without invalidating the tokens that clients already hold:
+///
+/// 1. Set [`key`](Self::key) to a new key, and move the old one to
+///    [`fallback_keys`](Self::fallback_keys)
+/// 2. Wait for all tokens encrypted with the old key to be replaced or to expire
+/// 3. Remove the old key from [`fallback_keys`](Self::fallback_keys)
+///
+/// [`SessionTransport::Header`]: crate::SessionTransport::Header
+pub struct SessionHeaderConfig {
+    /// The name of the request header carrying the session token.
+    ///
+    /// By default, it's set to `authorization`.
+    #[serde(default = "default_request_header")]
+    pub request_header: String,
+    /// The authentication scheme that must precede the session token
+    /// in the request header, e.g. `Bearer` in `Authorization: Bearer <token>`.
+    ///
+    /// The scheme is matched case-insensitively.
+    /// Set it to `None` if the header value is the bare token.
+    ///
+    /// By default, it's set to `Bearer`.
+    #[serde(default = "default_scheme")]
+    pub scheme: Option<String>,
+    /// The name of the response header used to send the (possibly new) session token
+    /// back to the client.
+    ///
+    /// An empty value tells the client to discard its token, e.g. because
+    /// the session has been invalidated.
+    ///
+    /// By default, it's set to `x-session-token`.
+    #[serde(default = "default_response_header")]
+    pub response_header: String,
+    /// The key used to encrypt session tokens.
+    ///
+    /// It must be at least 32 bytes long and it should be generated using
+    /// a cryptographically secure random number generator.
+    ///
+    /// There is no default key: sessions can't be exchanged via headers if it's missing.
+    #[serde(default, skip_serializing)]
+    pub key: Option<Key>,
+    /// Keys that are no longer used to encrypt session tokens, but are still
+    /// accepted when decrypting them.
+    ///
+    /// They are tried in order, after [`key`](Self::key).
+    #[serde(default, skip_serializing)]
+    pub fallback_keys: Vec<Key>,
+}
+
+impl Default for SessionHeaderConfig {
+    fn default() -> Self {
+        Self {
+            request_header: default_request_header(),
+            scheme: default_scheme(),
+            response_header: default_response_header(),
+            key: None,
+            fallback_keys: Vec::new(),
+        }
+    }
+}
+
+fn default_request_header() -> String {
+    "authorization".to_string()
+}
+
+fn default_scheme() -> Option<String> {
+    Some("Bearer".to_string())
+}
+
+fn default_response_header() -> String {
+    "x-session-token".to_string()
+}
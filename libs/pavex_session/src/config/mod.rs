@@ -1,8 +1,14 @@
 //! Types related to [`SessionConfig`][crate::SessionConfig].
+mod binding;
 mod cookie;
+mod encryption;
+mod header;
 mod state;
 
+pub use binding::{BindingMismatchPolicy, BindingSignal, SessionBindingConfig};
 pub use cookie::{SessionCookieConfig, SessionCookieKind};
+pub use encryption::{StateEncryptionConfig, StateEncryptionKey};
+pub use header::SessionHeaderConfig;
 pub use state::{
     InvalidTtlExtensionThreshold, MissingServerState, ServerStateCreation, SessionStateConfig,
     TtlExtensionThreshold, TtlExtensionTrigger,
@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use super::StateEncryptionConfig;
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
@@ -60,6 +62,56 @@ pub struct SessionStateConfig {
     /// (e.g. only the client-side state remains or was ever created).
     #[serde(default)]
     pub missing_server_state: MissingServerState,
+    /// Encrypt the server-side session state before handing it over to the storage backend.
+    ///
+    /// Check out [`StateEncryptionConfig`] for more details.
+    ///
+    /// # Default
+    ///
+    /// By default, the server-side state is stored as plain JSON.
+    #[serde(default)]
+    pub encryption: Option<StateEncryptionConfig>,
+    /// The maximum lifetime of a session, measured from its creation,
+    /// regardless of activity.
+    ///
+    /// Once it's elapsed, the session is invalidated the next time its server-side state
+    /// is loaded.
+    /// The server-side state is never stored with a TTL that outlives the session,
+    /// so expired records are also removed by
+    /// [`SessionStore::delete_expired`](crate::SessionStore::delete_expired).
+    ///
+    /// Use [`Session::remaining_lifetime`](crate::Session::remaining_lifetime) to prompt
+    /// users for re-authentication before their session expires.
+    ///
+    /// # Default
+    ///
+    /// By default, sessions have no maximum lifetime.
+    #[serde(deserialize_with = "deserialize_optional_limit", default)]
+    pub max_lifetime: Option<std::time::Duration>,
+    /// How long a session can go without activity before it expires.
+    ///
+    /// Activity is tracked when the server-side state is loaded.
+    /// To limit the number of writes, the last activity timestamp is only refreshed
+    /// once a tenth of the timeout has elapsed: a session may therefore expire
+    /// up to 10% earlier than the configured timeout.
+    ///
+    /// Unlike [`ttl`](Self::ttl), which controls how long the storage backend keeps
+    /// the state around, the idle timeout doesn't depend on
+    /// [`extend_ttl`](Self::extend_ttl) nor [`ttl_extension_threshold`](Self::ttl_extension_threshold).
+    ///
+    /// # Default
+    ///
+    /// By default, sessions have no idle timeout.
+    #[serde(deserialize_with = "deserialize_optional_limit", default)]
+    pub idle_timeout: Option<std::time::Duration>,
+}
+
+impl SessionStateConfig {
+    /// Returns `true` if either [`max_lifetime`](Self::max_lifetime) or
+    /// [`idle_timeout`](Self::idle_timeout) is set.
+    pub(crate) fn has_lifetime_limits(&self) -> bool {
+        self.max_lifetime.is_some() || self.idle_timeout.is_some()
+    }
 }
 
 impl Default for SessionStateConfig {
@@ -70,6 +122,9 @@ impl Default for SessionStateConfig {
             ttl_extension_threshold: default_ttl_extension_threshold(),
             server_state_creation: Default::default(),
             missing_server_state: Default::default(),
+            encryption: None,
+            max_lifetime: None,
+            idle_timeout: None,
         }
     }
 }
@@ -93,6 +148,24 @@ where
     Ok(ttl)
 }
 
+fn deserialize_optional_limit<'de, D>(
+    deserializer: D,
+) -> Result<Option<std::time::Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(span) = Option::<pavex::time::Span>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    if span.is_negative() || span.is_zero() {
+        return Err(serde::de::Error::custom(
+            "Session lifetime limits must be positive",
+        ));
+    }
+    let limit = span.try_into().map_err(serde::de::Error::custom)?;
+    Ok(Some(limit))
+}
+
 fn default_ttl() -> std::time::Duration {
     // 1 day
     std::time::Duration::from_secs(60 * 60 * 24)
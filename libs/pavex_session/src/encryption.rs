@@ -0,0 +1,372 @@
+use std::{borrow::Cow, collections::HashMap, num::NonZeroUsize};
+
+use aes_gcm::{
+    Aes256Gcm, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use pavex_tracing::fields::{ERROR_DETAILS, ERROR_MESSAGE, error_details, error_message};
+
+use crate::{
+    SessionId, State,
+    config::StateEncryptionConfig,
+    store::{
+        PRINCIPAL_KEY, SessionRecord, SessionRecordRef, SessionStorageBackend,
+        errors::{
+            ChangeIdError, CreateError, DeleteByPrincipalError, DeleteError, DeleteExpiredError,
+            ListByPrincipalError, LoadError, UpdateError, UpdateTtlError,
+        },
+    },
+};
+
+/// The key used to mark an encrypted session state.
+const ENCRYPTED_STATE_KEY: &str = "pavex.encrypted";
+/// The length of an AES-GCM nonce, in bytes.
+const NONCE_LENGTH: usize = 12;
+
+/// A [`SessionStorageBackend`] that encrypts the session state before
+/// handing it over to another backend.
+///
+/// The state is encrypted using AES-256-GCM, with a random nonce for every write.
+/// The inner backend sees a state with a single entry, holding the identifier of the
+/// key used for encryption and the encrypted payload.
+/// The payload is bound to the ID of the session it belongs to: it can't be decrypted
+/// if it's moved to a different record (e.g. by someone with write access to the
+/// inner backend).
+/// The only exception is the [principal](crate::Session::bind_principal) the session is
+/// bound to, which is kept in plaintext to allow the inner backend to index it.
+///
+/// Check out [`StateEncryptionConfig`] to learn how to configure (and rotate) the keys.
+///
+/// # Plaintext records
+///
+/// Records that weren't encrypted (e.g. because they were created before encryption
+/// was enabled) are returned as they are when loaded.
+/// They'll be encrypted the next time they're updated.
+///
+/// Set [`StateEncryptionConfig::reject_plaintext`] to treat them as a
+/// [decryption error](LoadError::DecryptionError) instead, once all records have been
+/// encrypted.
+///
+/// # Example
+///
+/// Register a constructor for [`SessionStore`](crate::SessionStore) that wraps
+/// your backend of choice when encryption is configured:
+///
+/// ```rust
+/// use pavex_session::{SessionConfig, SessionStore};
+/// use pavex_session::store::{EncryptedStorageBackend, InvalidEncryptionConfig};
+/// use pavex_session_memory_store::InMemorySessionStore;
+///
+/// pub fn session_store(config: &SessionConfig) -> Result<SessionStore, InvalidEncryptionConfig> {
+///     let backend = InMemorySessionStore::default();
+///     match &config.state.encryption {
+///         Some(encryption) => Ok(SessionStore::new(EncryptedStorageBackend::new(
+///             backend, encryption,
+///         )?)),
+///         None => Ok(SessionStore::new(backend)),
+///     }
+/// }
+/// ```
+pub struct EncryptedStorageBackend<B> {
+    inner: B,
+    /// The identifier of the key used to encrypt.
+    encryption_key_id: String,
+    /// All known keys, by identifier.
+    keys: HashMap<String, Aes256Gcm>,
+    /// Whether to reject records that aren't encrypted.
+    reject_plaintext: bool,
+}
+
+impl<B> std::fmt::Debug for EncryptedStorageBackend<B>
+where
+    B: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedStorageBackend")
+            .field("inner", &self.inner)
+            .field("encryption_key_id", &self.encryption_key_id)
+            .field("keys", &"[REDACTED]")
+            .field("reject_plaintext", &self.reject_plaintext)
+            .finish()
+    }
+}
+
+impl<B> EncryptedStorageBackend<B> {
+    /// Wrap `inner`, encrypting the session state with the keys in `config`.
+    ///
+    /// It returns an error if there are no keys, if two keys share the same identifier,
+    /// or if a key isn't made of 32 base64-encoded bytes.
+    pub fn new(inner: B, config: &StateEncryptionConfig) -> Result<Self, InvalidEncryptionConfig> {
+        let Some(first) = config.keys.first() else {
+            return Err(InvalidEncryptionConfig::NoKeys);
+        };
+        let mut keys = HashMap::with_capacity(config.keys.len());
+        for key in &config.keys {
+            let invalid = || InvalidEncryptionConfig::InvalidKey { id: key.id.clone() };
+            let bytes = STANDARD
+                .decode(key.key.expose_secret())
+                .map_err(|_| invalid())?;
+            let cipher = Aes256Gcm::new_from_slice(&bytes).map_err(|_| invalid())?;
+            if keys.insert(key.id.clone(), cipher).is_some() {
+                return Err(InvalidEncryptionConfig::DuplicateKeyId { id: key.id.clone() });
+            }
+        }
+        Ok(Self {
+            inner,
+            encryption_key_id: first.id.clone(),
+            keys,
+            reject_plaintext: config.reject_plaintext,
+        })
+    }
+
+    /// Encrypt the state in `record`, for the session with the given `id`,
+    /// returning a record that can be handed over to the inner backend.
+    fn encrypt(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<SessionRecordRef<'static>, Error> {
+        let plaintext = serde_json::to_vec(&*record.state).map_err(Error::Serialization)?;
+        let cipher = &self.keys[&self.encryption_key_id];
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plaintext,
+                    aad: &associated_data(id, &self.encryption_key_id),
+                },
+            )
+            .map_err(|_| Error::Encryption)?;
+
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&ciphertext);
+        let envelope = serde_json::json!({
+            "kid": self.encryption_key_id,
+            "data": STANDARD.encode(data),
+        });
+        let mut state = HashMap::from([(ENCRYPTED_STATE_KEY.into(), envelope)]);
+        if let Some(principal) = record.state.get(PRINCIPAL_KEY) {
+            state.insert(PRINCIPAL_KEY.into(), principal.to_owned());
+        }
+        Ok(SessionRecordRef {
+            state: Cow::Owned(state),
+            ttl: record.ttl,
+        })
+    }
+
+    /// Decrypt the state in `record`, stored for the session with the given `id`.
+    ///
+    /// Plaintext records are returned as they are, unless they must be rejected.
+    fn decrypt(
+        &self,
+        id: &SessionId,
+        mut record: SessionRecord,
+    ) -> Result<SessionRecord, LoadError> {
+        let Some(envelope) = record.state.remove(ENCRYPTED_STATE_KEY) else {
+            if self.reject_plaintext {
+                return Err(LoadError::DecryptionError(anyhow::anyhow!(
+                    "The session state is not encrypted"
+                )));
+            }
+            return Ok(record);
+        };
+        let envelope: Envelope =
+            serde_json::from_value(envelope).map_err(|e| LoadError::DecryptionError(e.into()))?;
+        let cipher = self.keys.get(&envelope.kid).ok_or_else(|| {
+            LoadError::DecryptionError(anyhow::anyhow!(
+                "The session state was encrypted with an unknown key, `{}`",
+                envelope.kid
+            ))
+        })?;
+        let data = STANDARD
+            .decode(&envelope.data)
+            .map_err(|e| LoadError::DecryptionError(e.into()))?;
+        if data.len() < NONCE_LENGTH {
+            return Err(LoadError::DecryptionError(anyhow::anyhow!(
+                "The encrypted session state is too short to contain a nonce"
+            )));
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LENGTH);
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &associated_data(id, &envelope.kid),
+                },
+            )
+            .map_err(|_| {
+                LoadError::DecryptionError(anyhow::anyhow!(
+                    "The encrypted session state failed authentication"
+                ))
+            })?;
+        record.state = serde_json::from_slice::<State>(&plaintext)
+            .map_err(|e| LoadError::DeserializationError(e.into()))?;
+        Ok(record)
+    }
+}
+
+impl<B> EncryptedStorageBackend<B>
+where
+    B: SessionStorageBackend,
+{
+    /// Re-encrypt the record moved from `old_id` to `new_id`, binding it to its new ID.
+    async fn reencrypt(&self, old_id: &SessionId, new_id: &SessionId) -> Result<(), anyhow::Error> {
+        let Some(record) = self.inner.load(new_id).await? else {
+            // The record expired in the meantime: there's nothing to re-encrypt.
+            return Ok(());
+        };
+        if !record.state.contains_key(ENCRYPTED_STATE_KEY) {
+            return Ok(());
+        }
+        let record = self.decrypt(old_id, record)?;
+        let record = self.encrypt(
+            new_id,
+            SessionRecordRef {
+                state: Cow::Owned(record.state),
+                ttl: record.ttl,
+            },
+        )?;
+        self.inner.update(new_id, record).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<B> SessionStorageBackend for EncryptedStorageBackend<B>
+where
+    B: SessionStorageBackend,
+{
+    async fn create(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), CreateError> {
+        let record = self.encrypt(id, record).map_err(|e| match e {
+            Error::Serialization(e) => CreateError::SerializationError(e),
+            Error::Encryption => CreateError::Other(e.into()),
+        })?;
+        self.inner.create(id, record).await
+    }
+
+    async fn update(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), UpdateError> {
+        let record = self.encrypt(id, record).map_err(|e| match e {
+            Error::Serialization(e) => UpdateError::SerializationError(e),
+            Error::Encryption => UpdateError::Other(e.into()),
+        })?;
+        self.inner.update(id, record).await
+    }
+
+    async fn update_ttl(
+        &self,
+        id: &SessionId,
+        ttl: std::time::Duration,
+    ) -> Result<(), UpdateTtlError> {
+        self.inner.update_ttl(id, ttl).await
+    }
+
+    async fn load(&self, session_id: &SessionId) -> Result<Option<SessionRecord>, LoadError> {
+        self.inner
+            .load(session_id)
+            .await?
+            .map(|record| self.decrypt(session_id, record))
+            .transpose()
+    }
+
+    async fn delete(&self, session_id: &SessionId) -> Result<(), DeleteError> {
+        self.inner.delete(session_id).await
+    }
+
+    async fn change_id(&self, old_id: &SessionId, new_id: &SessionId) -> Result<(), ChangeIdError> {
+        // The encrypted state is bound to the session ID: it must be re-encrypted
+        // for the new ID once the record has been moved.
+        // We move it first, so that a failure (e.g. a duplicate ID) leaves the old record
+        // untouched. Nobody else knows the new ID yet: no concurrent update can be lost
+        // while we re-encrypt.
+        self.inner.change_id(old_id, new_id).await?;
+        if let Err(e) = self.reencrypt(old_id, new_id).await {
+            // Move the record back, to keep it readable under its old ID.
+            if let Err(rollback) = self.inner.change_id(new_id, old_id).await {
+                tracing::event!(
+                    tracing::Level::ERROR,
+                    { ERROR_MESSAGE } = error_message(&rollback),
+                    { ERROR_DETAILS } = error_details(&rollback),
+                    "Failed to move the session record back to its old ID after a failed ID change."
+                );
+            }
+            return Err(ChangeIdError::Other(e));
+        }
+        Ok(())
+    }
+
+    async fn delete_expired(
+        &self,
+        batch_size: Option<NonZeroUsize>,
+    ) -> Result<usize, DeleteExpiredError> {
+        self.inner.delete_expired(batch_size).await
+    }
+
+    async fn list_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<Vec<SessionId>, ListByPrincipalError> {
+        self.inner.list_by_principal(principal).await
+    }
+
+    async fn delete_by_principal(&self, principal: &str) -> Result<usize, DeleteByPrincipalError> {
+        self.inner.delete_by_principal(principal).await
+    }
+}
+
+/// The associated data authenticated alongside the encrypted state: the session ID,
+/// followed by the identifier of the encryption key.
+fn associated_data(id: &SessionId, key_id: &str) -> Vec<u8> {
+    let mut data = id.inner().as_bytes().to_vec();
+    data.extend_from_slice(key_id.as_bytes());
+    data
+}
+
+/// The shape of an encrypted session state, as seen by the inner backend.
+#[derive(serde::Deserialize)]
+struct Envelope {
+    /// The identifier of the key used for encryption.
+    kid: String,
+    /// The nonce followed by the ciphertext, base64-encoded.
+    data: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("Failed to serialize the session state.")]
+    Serialization(serde_json::Error),
+    #[error("Failed to encrypt the session state.")]
+    Encryption,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`EncryptedStorageBackend::new`] when
+/// the [`StateEncryptionConfig`] is invalid.
+pub enum InvalidEncryptionConfig {
+    #[error("At least one session state encryption key must be configured.")]
+    /// There are no keys.
+    NoKeys,
+    #[error("The session state encryption key `{id}` is not made of 32 base64-encoded bytes.")]
+    /// A key is not valid.
+    InvalidKey {
+        /// The identifier of the invalid key.
+        id: String,
+    },
+    #[error("There are multiple session state encryption keys with the same identifier, `{id}`.")]
+    /// Two keys share the same identifier.
+    DuplicateKeyId {
+        /// The duplicated identifier.
+        id: String,
+    },
+}
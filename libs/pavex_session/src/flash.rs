@@ -0,0 +1,354 @@
+//! One-shot messages that survive a redirect, e.g. "Your changes have been saved!".
+//!
+//! # Guide
+//!
+//! Register [`FlashKit`] against your [`Blueprint`],
+//! **before** your session kit:
+//!
+//! ```rust
+//! use pavex::blueprint::Blueprint;
+//! use pavex::cookie::CookieKit;
+//! use pavex_session::SessionKit;
+//! use pavex_session::flash::FlashKit;
+//!
+//! let mut bp = Blueprint::new();
+//! // Flash messages must be written into the session
+//! // before it's finalized.
+//! // Order is important here!
+//! FlashKit::new().register(&mut bp);
+//! SessionKit::new().register(&mut bp);
+//! CookieKit::new().register(&mut bp);
+//! ```
+//!
+//! Inject [`&mut Flash`](Flash) in your request handler to queue messages
+//! for the next request:
+//!
+//! ```rust
+//! use pavex::response::Response;
+//! use pavex::http::HeaderValue;
+//! use pavex_session::flash::Flash;
+//!
+//! pub fn update_profile(flash: &mut Flash) -> Response {
+//!     // [...]
+//!     flash.success("Your profile has been updated.");
+//!     Response::see_other().insert_header(
+//!         pavex::http::header::LOCATION,
+//!         HeaderValue::from_static("/profile"),
+//!     )
+//! }
+//! ```
+//!
+//! Inject [`IncomingFlashMessages`] to display them when rendering the next page:
+//!
+//! ```rust
+//! use pavex::response::Response;
+//! use pavex_session::flash::IncomingFlashMessages;
+//!
+//! pub fn profile(messages: &IncomingFlashMessages) -> Response {
+//!     let body: Vec<_> = messages
+//!         .iter()
+//!         .map(|m| format!("[{}] {}", m.level(), m.message()))
+//!         .collect();
+//!     Response::ok().set_typed_body(body.join("\n"))
+//! }
+//! ```
+//!
+//! # How does it work?
+//!
+//! Messages are stored in the client-side session state, under [`FLASH_KEY`].
+//!
+//! Queued messages are written into the session by [`persist_flash_messages`], a
+//! post-processing middleware that must run before [`finalize_session`](crate::finalize_session).
+//! Extracting [`IncomingFlashMessages`] marks the stored messages as consumed:
+//! [`persist_flash_messages`] removes them from the session, and
+//! [`finalize_session`](crate::finalize_session) takes care of propagating the
+//! removal to the client.
+//! Messages that are never extracted are kept until they are.
+use std::cell::Cell;
+
+use pavex::blueprint::Blueprint;
+use pavex::blueprint::constructor::Constructor;
+use pavex::blueprint::linter::Lint;
+use pavex::blueprint::middleware::PostProcessingMiddleware;
+use pavex::f;
+use pavex::response::Response;
+use pavex_tracing::fields::{ERROR_DETAILS, ERROR_MESSAGE, error_details, error_message};
+
+use crate::Session;
+
+/// The key used to store flash messages in the client-side session state.
+pub const FLASH_KEY: &str = "pavex.flash";
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+/// The severity of a [`FlashMessage`].
+pub enum Level {
+    /// Development-related information.
+    Debug,
+    /// Informational messages for the user.
+    Info,
+    /// An action was successful.
+    Success,
+    /// Something requires the user's attention, but nothing failed.
+    Warning,
+    /// An action was not successful.
+    Error,
+}
+
+impl Level {
+    /// The lowercase name of the level, e.g. `success`.
+    ///
+    /// Handy as a CSS class when rendering the message.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Success => "success",
+            Level::Warning => "warning",
+            Level::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// A one-shot message, queued via [`Flash`] and retrieved via [`IncomingFlashMessages`].
+pub struct FlashMessage {
+    level: Level,
+    message: String,
+}
+
+impl FlashMessage {
+    /// Create a new message with the given level.
+    pub fn new(level: Level, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+        }
+    }
+
+    /// The severity of the message.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// The content of the message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[derive(Debug, Default)]
+/// Queue [`FlashMessage`]s for the next request.
+///
+/// Check out the [module documentation](self) for more details.
+pub struct Flash {
+    queued: Vec<FlashMessage>,
+    /// Set when the incoming messages have been extracted.
+    consumed: Cell<bool>,
+}
+
+impl Flash {
+    /// Create a new [`Flash`] instance, with no queued messages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a message for the next request.
+    pub fn push(&mut self, message: FlashMessage) {
+        self.queued.push(message);
+    }
+
+    /// Queue a [`Level::Debug`] message for the next request.
+    pub fn debug(&mut self, message: impl Into<String>) {
+        self.push(FlashMessage::new(Level::Debug, message));
+    }
+
+    /// Queue a [`Level::Info`] message for the next request.
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(FlashMessage::new(Level::Info, message));
+    }
+
+    /// Queue a [`Level::Success`] message for the next request.
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(FlashMessage::new(Level::Success, message));
+    }
+
+    /// Queue a [`Level::Warning`] message for the next request.
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(FlashMessage::new(Level::Warning, message));
+    }
+
+    /// Queue a [`Level::Error`] message for the next request.
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(FlashMessage::new(Level::Error, message));
+    }
+
+    /// The messages that have been queued during the current request.
+    pub fn queued(&self) -> &[FlashMessage] {
+        &self.queued
+    }
+}
+
+#[derive(Debug, Default)]
+/// The flash messages queued by a previous request.
+///
+/// Extracting them marks them as consumed: they'll be removed from the session
+/// when the response is sent.
+/// Check out the [module documentation](self) for more details.
+pub struct IncomingFlashMessages(Vec<FlashMessage>);
+
+impl IncomingFlashMessages {
+    /// Retrieve the flash messages stored in the client-side session state.
+    ///
+    /// Invalid messages are discarded.
+    pub fn extract(session: &Session<'_>, flash: &Flash) -> Self {
+        let messages = match session.client().get::<Vec<FlashMessage>>(FLASH_KEY) {
+            Ok(Some(messages)) => messages,
+            Ok(None) => return Self::default(),
+            Err(e) => {
+                tracing::event!(
+                    tracing::Level::WARN,
+                    { ERROR_MESSAGE } = error_message(&e),
+                    { ERROR_DETAILS } = error_details(&e),
+                    "Invalid flash messages in the client-side session state, discarding them."
+                );
+                Vec::new()
+            }
+        };
+        flash.consumed.set(true);
+        Self(messages)
+    }
+
+    /// Iterate over the incoming messages, in the order they were queued.
+    pub fn iter(&self) -> std::slice::Iter<'_, FlashMessage> {
+        self.0.iter()
+    }
+
+    /// The number of incoming messages.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no incoming messages.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a IncomingFlashMessages {
+    type Item = &'a FlashMessage;
+    type IntoIter = std::slice::Iter<'a, FlashMessage>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A post-processing middleware to write flash messages into the client-side session state.
+///
+/// It removes the incoming messages, if they were extracted, and appends the ones
+/// that were queued during the current request.
+/// It must be registered before [`finalize_session`](crate::finalize_session).
+pub fn persist_flash_messages(
+    response: Response,
+    flash: &Flash,
+    session: &mut Session<'_>,
+) -> Response {
+    let consumed = flash.consumed.get();
+    if !consumed && flash.queued.is_empty() {
+        return response;
+    }
+
+    let mut client = session.client_mut();
+    let mut messages = if consumed {
+        client.remove_raw(FLASH_KEY);
+        Vec::new()
+    } else {
+        client
+            .get::<Vec<FlashMessage>>(FLASH_KEY)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    };
+    messages.extend(flash.queued.iter().cloned());
+    if !messages.is_empty() {
+        let messages =
+            serde_json::to_value(messages).expect("Flash messages can always be serialized");
+        client.insert_raw(FLASH_KEY, messages);
+    }
+    response
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The components required to work with flash messages.
+///
+/// It must be registered before your session kit.
+/// Check out the [module documentation](self) for more details.
+pub struct FlashKit {
+    /// The constructor for [`Flash`].
+    ///
+    /// By default, it uses [`Flash::new`].
+    pub flash: Option<Constructor>,
+    /// The constructor for [`IncomingFlashMessages`].
+    ///
+    /// By default, it uses [`IncomingFlashMessages::extract`].
+    pub incoming_flash_messages: Option<Constructor>,
+    /// A post-processing middleware to write flash messages into the session.
+    ///
+    /// By default, it's set to [`persist_flash_messages`].
+    pub flash_persister: Option<PostProcessingMiddleware>,
+}
+
+impl Default for FlashKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlashKit {
+    /// Create a new [`FlashKit`] with all the bundled constructors and middlewares.
+    pub fn new() -> Self {
+        Self {
+            flash: Some(
+                Constructor::request_scoped(f!(crate::flash::Flash::new)).ignore(Lint::Unused),
+            ),
+            incoming_flash_messages: Some(
+                Constructor::request_scoped(f!(crate::flash::IncomingFlashMessages::extract))
+                    .ignore(Lint::Unused),
+            ),
+            flash_persister: Some(PostProcessingMiddleware::new(f!(
+                crate::flash::persist_flash_messages
+            ))),
+        }
+    }
+
+    /// Register all the bundled constructors and middlewares with a [`Blueprint`].
+    ///
+    /// If a component is set to `None` it will not be registered.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredFlashKit {
+        if let Some(flash) = self.flash {
+            flash.register(bp);
+        }
+        if let Some(incoming_flash_messages) = self.incoming_flash_messages {
+            incoming_flash_messages.register(bp);
+        }
+        if let Some(flash_persister) = self.flash_persister {
+            flash_persister.register(bp);
+        }
+        RegisteredFlashKit {}
+    }
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The type returned by [`FlashKit::register`].
+pub struct RegisteredFlashKit {}
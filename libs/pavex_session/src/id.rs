@@ -22,6 +22,15 @@ impl SessionId {
         Self(uuid::Uuid::new_v4())
     }
 
+    /// Build a session identifier from a `uuid::Uuid` value.
+    ///
+    /// It's primarily useful when implementing custom session storage backends,
+    /// to convert the identifiers retrieved from the data store.
+    /// Use [`SessionId::random`] to generate a new identifier.
+    pub fn from_uuid(id: uuid::Uuid) -> Self {
+        Self(id)
+    }
+
     /// Returns the inner `uuid::Uuid` value.
     pub fn inner(&self) -> uuid::Uuid {
         self.0
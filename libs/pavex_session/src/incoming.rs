@@ -1,10 +1,16 @@
-use crate::{SessionId, State, config::SessionCookieConfig, wire::WireClientState};
+use crate::{
+    SessionConfig, SessionId, SessionTransport, State,
+    config::{SessionCookieConfig, SessionHeaderConfig},
+    wire::WireClientState,
+};
 use pavex::cookie::RequestCookies;
+use pavex::request::RequestHead;
 use pavex_tracing::fields::{ERROR_DETAILS, ERROR_MESSAGE, error_details, error_message};
 
 /// The session information attached to the incoming request.
 ///
-/// Built using [`IncomingSession::extract`].
+/// Built using [`IncomingSession::extract`], [`IncomingSession::extract_from_header`]
+/// or [`IncomingSession::extract_with_transport`].
 pub struct IncomingSession {
     pub(crate) id: SessionId,
     pub(crate) client_state: State,
@@ -33,6 +39,66 @@ impl IncomingSession {
         }
     }
 
+    /// Extract a session token from the headers of the incoming request, if it exists.
+    ///
+    /// If the header is not found, if it doesn't use the expected scheme, or if the token is invalid
+    /// (e.g. it can't be decrypted with the keys in [`SessionHeaderConfig`]), this method will
+    /// return `None`.
+    pub fn extract_from_header(
+        request_head: &RequestHead,
+        config: &SessionHeaderConfig,
+    ) -> Option<Self> {
+        let value = request_head.headers.get(config.request_header.as_str())?;
+        let Ok(value) = value.to_str() else {
+            tracing::event!(
+                tracing::Level::WARN,
+                "The session header contains non-ASCII characters, creating a new session."
+            );
+            return None;
+        };
+        let token = match config.scheme.as_deref() {
+            Some(scheme) => {
+                let (prefix, token) = value.trim().split_once(' ')?;
+                if !prefix.eq_ignore_ascii_case(scheme) {
+                    // The header is used for a different authentication scheme.
+                    return None;
+                }
+                token.trim()
+            }
+            None => value.trim(),
+        };
+        match WireClientState::from_token(token, config) {
+            Ok(s) => Some(Self {
+                id: s.session_id,
+                client_state: s.user_values.into_owned(),
+            }),
+            Err(e) => {
+                tracing::event!(
+                    tracing::Level::WARN,
+                    { ERROR_MESSAGE } = error_message(&e),
+                    { ERROR_DETAILS } = error_details(&e),
+                    "Invalid client state for session, creating a new session."
+                );
+                None
+            }
+        }
+    }
+
+    /// Extract the session from the incoming request, using the chosen [`SessionTransport`].
+    ///
+    /// It delegates to [`IncomingSession::extract`] or [`IncomingSession::extract_from_header`].
+    pub fn extract_with_transport(
+        transport: &SessionTransport,
+        cookies: &RequestCookies<'_>,
+        request_head: &RequestHead,
+        config: &SessionConfig,
+    ) -> Option<Self> {
+        match transport {
+            SessionTransport::Cookie => Self::extract(cookies, &config.cookie),
+            SessionTransport::Header => Self::extract_from_header(request_head, &config.header),
+        }
+    }
+
     /// Build an [`IncomingSession`] instance from its parts.
     pub fn from_parts(id: SessionId, state: State) -> Self {
         Self {
@@ -32,18 +32,36 @@ use pavex::{f, t};
 pub struct SessionKit {
     /// The constructor for [`Session`].
     ///
-    /// By default, it uses [`Session::new`].
+    /// By default, it uses [`Session::new_with_fingerprint`].
     ///
     /// [`Session`]: crate::Session
-    /// [`Session::new`]: crate::Session::new
+    /// [`Session::new_with_fingerprint`]: crate::Session::new_with_fingerprint
     pub session: Option<Constructor>,
+    /// The constructor for [`SessionFingerprint`].
+    ///
+    /// By default, it uses [`SessionFingerprint::extract`].
+    /// Override it if you need to compute the fingerprint from different inputs,
+    /// e.g. the client address forwarded by a reverse proxy.
+    ///
+    /// [`SessionFingerprint`]: crate::binding::SessionFingerprint
+    /// [`SessionFingerprint::extract`]: crate::binding::SessionFingerprint::extract
+    pub session_fingerprint: Option<Constructor>,
     /// The constructor for [`IncomingSession`].
     ///
-    /// By default, it uses [`IncomingSession::extract`].
+    /// By default, it uses [`IncomingSession::extract_with_transport`].
     ///
     /// [`IncomingSession`]: crate::IncomingSession
-    /// [`IncomingSession::extract`]: crate::IncomingSession::extract
+    /// [`IncomingSession::extract_with_transport`]: crate::IncomingSession::extract_with_transport
     pub incoming_session: Option<Constructor>,
+    /// The constructor for [`SessionTransport`].
+    ///
+    /// By default, it uses [`SessionTransport::cookie`].
+    /// Override it in a nested blueprint to use a different transport
+    /// for the routes registered there.
+    ///
+    /// [`SessionTransport`]: crate::SessionTransport
+    /// [`SessionTransport::cookie`]: crate::SessionTransport::cookie
+    pub session_transport: Option<Constructor>,
     /// Register [`SessionConfig`] as configuration.
     ///
     /// By default, it uses the `session` key.
@@ -51,13 +69,15 @@ pub struct SessionKit {
     /// [`SessionConfig`]: crate::SessionConfig
     pub session_config: Option<ConfigType>,
     /// A post-processing middleware to sync the session state with the session store
-    /// and inject the session cookie into the outgoing response via the `Set-Cookie` header.
+    /// and send the session to the client, via the `Set-Cookie` header or a custom header
+    /// depending on the chosen [`SessionTransport`].
     ///
     /// By default, it's set to [`finalize_session`].
     /// The error is handled by [`FinalizeError::into_response`].
     ///
     /// [`FinalizeError::into_response`]: crate::errors::FinalizeError::into_response
     /// [`finalize_session`]: crate::middleware::finalize_session
+    /// [`SessionTransport`]: crate::SessionTransport
     pub session_finalizer: Option<PostProcessingMiddleware>,
 }
 
@@ -70,9 +90,16 @@ impl Default for SessionKit {
 impl SessionKit {
     /// Create a new [`SessionKit`] with all the bundled constructors and middlewares.
     pub fn new() -> Self {
-        let session = Constructor::request_scoped(f!(crate::Session::new)).ignore(Lint::Unused);
+        let session = Constructor::request_scoped(f!(crate::Session::new_with_fingerprint))
+            .ignore(Lint::Unused);
+        let session_fingerprint =
+            Constructor::request_scoped(f!(crate::binding::SessionFingerprint::extract))
+                .ignore(Lint::Unused);
         let incoming_session =
-            Constructor::request_scoped(f!(crate::IncomingSession::extract)).ignore(Lint::Unused);
+            Constructor::request_scoped(f!(crate::IncomingSession::extract_with_transport))
+                .ignore(Lint::Unused);
+        let session_transport =
+            Constructor::request_scoped(f!(crate::SessionTransport::cookie)).ignore(Lint::Unused);
         let session_finalizer =
             PostProcessingMiddleware::new(f!(crate::middleware::finalize_session))
                 .error_handler(f!(crate::errors::FinalizeError::into_response));
@@ -80,7 +107,9 @@ impl SessionKit {
             ConfigType::new("session", t!(crate::SessionConfig)).default_if_missing();
         Self {
             session: Some(session),
+            session_fingerprint: Some(session_fingerprint),
             incoming_session: Some(incoming_session),
+            session_transport: Some(session_transport),
             session_config: Some(session_config),
             session_finalizer: Some(session_finalizer),
         }
@@ -100,9 +129,15 @@ impl SessionKit {
         if let Some(session) = self.session {
             session.register(bp);
         }
+        if let Some(session_fingerprint) = self.session_fingerprint {
+            session_fingerprint.register(bp);
+        }
         if let Some(incoming_session) = self.incoming_session {
             incoming_session.register(bp);
         }
+        if let Some(session_transport) = self.session_transport {
+            session_transport.register(bp);
+        }
         if let Some(session_config) = self.session_config {
             session_config.register(bp);
         }
@@ -111,6 +146,8 @@ impl SessionKit {
             .ignore(Lint::Unused);
         bp.transient(f!(crate::SessionConfig::state_config))
             .ignore(Lint::Unused);
+        bp.transient(f!(crate::SessionConfig::header_config))
+            .ignore(Lint::Unused);
 
         if let Some(session_finalizer) = self.session_finalizer {
             session_finalizer.register(bp);
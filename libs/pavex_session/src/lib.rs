@@ -4,13 +4,19 @@ Session management for Pavex.
 Check out the [session guide in Pavex's documentation](https://pavex.dev/guide/sessions/) for a thorough introduction to sessions
 and how to use them in your application.
 */
+pub mod binding;
+mod cache;
 pub mod config;
+mod encryption;
+pub mod flash;
 mod id;
 mod incoming;
 mod kit;
+mod lifetime;
 mod middleware;
 mod session_;
 mod store_;
+mod transport;
 pub(crate) mod wire;
 
 use std::collections::HashMap;
@@ -21,11 +27,16 @@ pub use kit::SessionKit;
 pub use middleware::finalize_session;
 pub use session_::Session;
 pub use store_::SessionStore;
+pub use transport::SessionTransport;
 
 pub mod store {
     //! Types and traits related to [`SessionStore`][super::SessionStore].
+    pub use crate::cache::{CacheStats, CachedSessionStore};
+    pub use crate::encryption::{EncryptedStorageBackend, InvalidEncryptionConfig};
     pub use crate::store_::errors;
-    pub use crate::store_::{SessionRecord, SessionRecordRef, SessionStorageBackend};
+    pub use crate::store_::{
+        PRINCIPAL_KEY, SessionRecord, SessionRecordRef, SessionStorageBackend, TIMESTAMPS_KEY,
+    };
 }
 
 pub use crate::session_::errors;
@@ -52,6 +63,12 @@ pub struct SessionConfig {
     #[serde(default)]
     /// Configure how the session state should behave.
     pub state: crate::config::SessionStateConfig,
+    #[serde(default)]
+    /// Configure the headers used by [`SessionTransport::Header`].
+    pub header: crate::config::SessionHeaderConfig,
+    #[serde(default)]
+    /// Configure how sessions are bound to the client that created them.
+    pub binding: crate::config::SessionBindingConfig,
 }
 
 impl SessionConfig {
@@ -69,4 +86,9 @@ impl SessionConfig {
     pub fn state_config(&self) -> &crate::config::SessionStateConfig {
         &self.state
     }
+
+    #[doc(hidden)]
+    pub fn header_config(&self) -> &crate::config::SessionHeaderConfig {
+        &self.header
+    }
 }
@@ -0,0 +1,119 @@
+//! Enforce the absolute lifetime and the idle timeout of a session.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use crate::State;
+use crate::config::SessionStateConfig;
+use crate::store::TIMESTAMPS_KEY;
+
+/// The timestamps stored in the server-side state, under [`TIMESTAMPS_KEY`].
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SessionTimestamps {
+    /// When the session was created, in seconds since the Unix epoch.
+    created_at: u64,
+    /// When the session was last active, in seconds since the Unix epoch.
+    last_active: u64,
+}
+
+/// The outcome of [`SessionTimestamps::check`].
+pub(crate) enum LifetimeCheck {
+    /// The session is still valid.
+    /// `refreshed` is `true` if the timestamps stored in the state have been updated.
+    Valid { refreshed: bool },
+    /// The session has expired.
+    Expired { reason: &'static str },
+}
+
+impl SessionTimestamps {
+    /// The timestamps of a session that's being created right now.
+    pub(crate) fn new() -> Self {
+        let now = now();
+        Self {
+            created_at: now,
+            last_active: now,
+        }
+    }
+
+    /// Retrieve the timestamps from the server-side state, if they're there and valid.
+    pub(crate) fn from_state(state: &State) -> Option<Self> {
+        serde_json::from_value(state.get(TIMESTAMPS_KEY)?.clone()).ok()
+    }
+
+    pub(crate) fn to_value(self) -> Value {
+        serde_json::to_value(self).expect("Session timestamps can always be serialized")
+    }
+
+    /// Check the lifetime limits against the timestamps stored in `state`, refreshing
+    /// the last activity timestamp if needed.
+    ///
+    /// Sessions that were created before the limits were configured don't have timestamps:
+    /// their clock starts now.
+    pub(crate) fn check(config: &SessionStateConfig, state: &mut State) -> LifetimeCheck {
+        let Some(mut timestamps) = Self::from_state(state) else {
+            state.insert(TIMESTAMPS_KEY.into(), Self::new().to_value());
+            return LifetimeCheck::Valid { refreshed: true };
+        };
+        let now = now();
+        if let Some(max_lifetime) = config.max_lifetime {
+            if now >= timestamps.created_at.saturating_add(max_lifetime.as_secs()) {
+                return LifetimeCheck::Expired {
+                    reason: "The session has exceeded its maximum lifetime.",
+                };
+            }
+        }
+        let mut refreshed = false;
+        if let Some(idle_timeout) = config.idle_timeout {
+            let idle_for = now.saturating_sub(timestamps.last_active);
+            if idle_for >= idle_timeout.as_secs() {
+                return LifetimeCheck::Expired {
+                    reason: "The session has been idle for longer than the configured timeout.",
+                };
+            }
+            if idle_for >= (idle_timeout.as_secs() / 10).max(1) {
+                timestamps.last_active = now;
+                state.insert(TIMESTAMPS_KEY.into(), timestamps.to_value());
+                refreshed = true;
+            }
+        }
+        LifetimeCheck::Valid { refreshed }
+    }
+
+    /// How long until the session reaches its maximum lifetime, if there's one.
+    pub(crate) fn remaining_lifetime(&self, config: &SessionStateConfig) -> Option<Duration> {
+        let max_lifetime = config.max_lifetime?;
+        let expires_at = self.created_at.saturating_add(max_lifetime.as_secs());
+        Some(Duration::from_secs(expires_at.saturating_sub(now())))
+    }
+
+    /// How long until the session reaches its idle timeout, if there's one,
+    /// unless there's further activity.
+    fn remaining_idle_time(&self, config: &SessionStateConfig) -> Option<Duration> {
+        let idle_timeout = config.idle_timeout?;
+        let expires_at = self.last_active.saturating_add(idle_timeout.as_secs());
+        Some(Duration::from_secs(expires_at.saturating_sub(now())))
+    }
+
+    /// The longest TTL the server-side state can be stored with, without outliving
+    /// the session.
+    ///
+    /// The last activity timestamp isn't refreshed on every load: the idle timeout
+    /// is computed from the stored timestamp, rather than assuming the session is active
+    /// right now.
+    pub(crate) fn max_ttl(&self, config: &SessionStateConfig) -> Option<Duration> {
+        match (
+            self.remaining_lifetime(config),
+            self.remaining_idle_time(config),
+        ) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -1,28 +1,57 @@
-use pavex::{cookie::ResponseCookies, response::Response};
+use pavex::{cookie::ResponseCookies, http::HeaderName, response::Response};
 use tracing::Span;
 
-use crate::{Session, errors::FinalizeError};
+use crate::{Session, SessionConfig, SessionTransport, errors::FinalizeError};
 
-/// A post-processing middleware to attach a session cookie to the outgoing response, if needed.
+/// A post-processing middleware to attach the session to the outgoing response, if needed.
+///
+/// Depending on the chosen [`SessionTransport`], the session is sent to the client
+/// via a cookie or via the response header specified in
+/// [`SessionHeaderConfig::response_header`](crate::config::SessionHeaderConfig::response_header).
 ///
 /// It will also sync the session server-side state with the chosen storage backend.
+///
+/// A [binding mismatch](Session::binding_mismatch) doesn't cause it to fail:
+/// the policy has already been applied to the session by the time it runs.
 #[tracing::instrument(
     name = "Finalize session",
     level = tracing::Level::DEBUG, skip_all,
-    fields(session.cookie.set = tracing::field::Empty)
+    fields(session.cookie.set = tracing::field::Empty, session.header.set = tracing::field::Empty)
 )]
 pub async fn finalize_session<'store>(
     response: Response,
     response_cookies: &mut ResponseCookies,
+    transport: &SessionTransport,
+    config: &SessionConfig,
     mut session: Session<'store>,
 ) -> Result<Response, FinalizeError> {
-    let cookie = session.finalize().await?;
+    match transport {
+        SessionTransport::Cookie => {
+            let cookie = session.finalize().await?;
 
-    Span::current().record("session.cookie.set", cookie.is_some());
+            Span::current().record("session.cookie.set", cookie.is_some());
 
-    if let Some(cookie) = cookie {
-        response_cookies.insert(cookie);
-    }
+            if let Some(cookie) = cookie {
+                response_cookies.insert(cookie);
+            }
+            Ok(response)
+        }
+        SessionTransport::Header => {
+            let name = &config.header.response_header;
+            let header_name = HeaderName::try_from(name.as_str()).map_err(|source| {
+                FinalizeError::InvalidResponseHeaderName {
+                    name: name.to_owned(),
+                    source,
+                }
+            })?;
+            let value = session.finalize_header().await?;
 
-    Ok(response)
+            Span::current().record("session.header.set", value.is_some());
+
+            match value {
+                Some(value) => Ok(response.insert_header(header_name, value)),
+                None => Ok(response),
+            }
+        }
+    }
 }
@@ -1,14 +1,15 @@
 use errors::{
-    FinalizeError, ServerGetError, ServerInsertError, ServerRemoveError, SyncError,
-    ValueDeserializationError, ValueLocation, ValueSerializationError,
+    BindingMismatchError, FinalizeError, ServerGetError, ServerInsertError, ServerRemoveError,
+    SyncError, ValueDeserializationError, ValueLocation, ValueSerializationError,
 };
 use pavex::cookie::{RemovalCookie, ResponseCookie};
+use pavex::http::HeaderValue;
 use pavex::time::SignedDuration;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::borrow::Cow;
-use std::cell::OnceCell;
+use std::cell::{Cell, OnceCell};
 use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::MutexGuard;
@@ -17,12 +18,15 @@ use crate::SessionConfig;
 use crate::SessionId;
 use crate::SessionStore;
 use crate::State;
+use crate::binding::{BINDING_KEY, SessionFingerprint};
 use crate::config::{
-    MissingServerState, ServerStateCreation, SessionCookieKind, TtlExtensionTrigger,
+    BindingMismatchPolicy, MissingServerState, ServerStateCreation, SessionCookieKind,
+    TtlExtensionTrigger,
 };
 use crate::incoming::IncomingSession;
-use crate::store::SessionRecordRef;
+use crate::lifetime::{LifetimeCheck, SessionTimestamps};
 use crate::store::errors::{ChangeIdError, DeleteError, LoadError};
+use crate::store::{PRINCIPAL_KEY, SessionRecord, SessionRecordRef, TIMESTAMPS_KEY};
 use crate::wire::WireClientState;
 
 /// The current HTTP session.
@@ -56,6 +60,15 @@ pub struct Session<'store> {
     invalidated: InvalidationFlag,
     store: &'store SessionStore,
     config: &'store SessionConfig,
+    /// The fingerprint of the current request, if binding is enabled.
+    fingerprint: Option<SessionFingerprint>,
+    /// Set when a binding mismatch requires the session ID to be cycled.
+    ///
+    /// The mismatch is detected when loading the server-side state, which
+    /// doesn't have mutable access to the session: the ID is cycled on the next sync.
+    cycle_pending: Cell<bool>,
+    /// The binding mismatch detected when loading the server-side state, if any.
+    binding_mismatch: OnceCell<BindingMismatchError>,
     /// This field is used to prevent `Send` being implemented for `Session`.
     _unsend: PhantomUnsend,
 }
@@ -69,6 +82,7 @@ impl std::fmt::Debug for Session<'_> {
             .field("invalidated", &self.invalidated)
             .field("store", &self.store)
             .field("config", &self.config)
+            .field("fingerprint", &self.fingerprint)
             .finish()
     }
 }
@@ -186,9 +200,33 @@ impl<'store> Session<'store> {
             invalidated: InvalidationFlag::new(),
             store,
             config,
+            fingerprint: None,
+            cycle_pending: Cell::new(false),
+            binding_mismatch: OnceCell::new(),
             _unsend: Default::default(),
         }
     }
+
+    /// Create a new HTTP session, bound to the client that sent the current request.
+    ///
+    /// It behaves like [`Session::new`], but it also enforces the
+    /// [binding policy](crate::config::SessionBindingConfig) when the server-side
+    /// state is loaded.
+    /// If binding is disabled, or the fingerprint is empty, it's equivalent to [`Session::new`].
+    ///
+    /// Check out the [`binding`](crate::binding) module for more details.
+    pub fn new_with_fingerprint(
+        store: &'store SessionStore,
+        config: &'store SessionConfig,
+        incoming_session: Option<IncomingSession>,
+        fingerprint: &SessionFingerprint,
+    ) -> Self {
+        let mut session = Self::new(store, config, incoming_session);
+        if config.binding.is_enabled() && !fingerprint.is_empty() {
+            session.fingerprint = Some(fingerprint.clone());
+        }
+        session
+    }
 }
 
 /// All the operations you can perform on the server-side state of your session.
@@ -351,7 +389,16 @@ impl Session<'_> {
                 Ok(None)
             }
             DoesNotExist => Ok(None),
-            Unchanged { state, .. } | Changed { state } => Ok(state.remove(key)),
+            Changed { state } => Ok(state.remove(key)),
+            Unchanged { state, .. } => {
+                let old_value = state.remove(key);
+                if old_value.is_some() {
+                    // The removal must be written back to the store when syncing.
+                    let state = std::mem::take(state);
+                    self.server_state = new_cell_with(Some(ServerState::Changed { state }));
+                }
+                Ok(old_value)
+            }
         }
     }
 
@@ -444,6 +491,101 @@ impl Session<'_> {
     pub fn is_invalidated(&self) -> bool {
         self.invalidated.is_invalidated()
     }
+
+    /// The binding mismatch detected when the server-side state was loaded, if any.
+    ///
+    /// It's always `None` if the [mismatch policy](crate::config::SessionBindingConfig::on_mismatch)
+    /// is set to [`BindingMismatchPolicy::Ignore`].
+    ///
+    /// # Reporting mismatches
+    ///
+    /// Mismatches are logged as a `WARN` event when they're detected, but they don't
+    /// cause [`finalize_session`](crate::finalize_session) to fail.
+    /// If you want to act on them (e.g. to alert your security team), register
+    /// a post-processing middleware that inspects the session **before** the one
+    /// bundled in [`SessionKit`](crate::SessionKit):
+    ///
+    /// ```rust
+    /// use pavex::response::Response;
+    /// use pavex_session::Session;
+    ///
+    /// pub fn report_binding_mismatch(response: Response, session: &Session<'_>) -> Response {
+    ///     if let Some(mismatch) = session.binding_mismatch() {
+    ///         tracing::error!(error.message = %mismatch, "Possible session hijacking");
+    ///     }
+    ///     response
+    /// }
+    /// ```
+    pub fn binding_mismatch(&self) -> Option<&BindingMismatchError> {
+        self.binding_mismatch.get()
+    }
+}
+
+/// Bind the session to a principal, to list or revoke all its sessions at once.
+impl Session<'_> {
+    /// Bind the session to a principal—e.g. the identifier of the user who just logged in.
+    ///
+    /// The principal is stored in the server-side state, under [`PRINCIPAL_KEY`].
+    /// Storage backends that support it will index the session by principal, allowing
+    /// you to [list](SessionStore::list_by_principal) or
+    /// [revoke](SessionStore::delete_by_principal) all the sessions of a principal
+    /// (e.g. "Log out of all devices").
+    ///
+    /// # Session fixation
+    ///
+    /// Binding a principal is usually a privilege change.
+    /// Call [`Session::cycle_id`] as well, to prevent
+    /// [session fixation attacks](https://owasp.org/www-community/attacks/Session_fixation).
+    pub async fn bind_principal<P>(&mut self, principal: P) -> Result<(), LoadError>
+    where
+        P: Into<String>,
+    {
+        self.insert_raw(PRINCIPAL_KEY, Value::String(principal.into()))
+            .await?;
+        Ok(())
+    }
+
+    /// Remove the association between the session and its principal, if there was one.
+    pub async fn unbind_principal(&mut self) -> Result<(), LoadError> {
+        self.remove_raw(PRINCIPAL_KEY).await?;
+        Ok(())
+    }
+
+    /// The principal this session is bound to, if any.
+    ///
+    /// Check out [`Session::bind_principal`] for more details.
+    pub async fn principal(&self) -> Result<Option<&str>, LoadError> {
+        Ok(self.get_raw(PRINCIPAL_KEY).await?.and_then(Value::as_str))
+    }
+}
+
+/// Inspect the lifetime limits of the session.
+impl Session<'_> {
+    /// How long until the session reaches its
+    /// [maximum lifetime](crate::config::SessionStateConfig::max_lifetime).
+    ///
+    /// Use it to prompt users for re-authentication before their session expires.
+    /// It returns `None` if no maximum lifetime has been configured, and
+    /// [`Duration::ZERO`](std::time::Duration::ZERO) if the session has been invalidated.
+    ///
+    /// The idle timeout is not taken into account, since it's pushed back
+    /// every time the session is used.
+    pub async fn remaining_lifetime(&self) -> Result<Option<std::time::Duration>, LoadError> {
+        use ServerState::*;
+
+        let Some(max_lifetime) = self.config.state.max_lifetime else {
+            return Ok(None);
+        };
+        let remaining = match force_load_ref(self).await? {
+            Unchanged { state, .. } | Changed { state } => SessionTimestamps::from_state(state)
+                .and_then(|t| t.remaining_lifetime(&self.config.state))
+                // The session hasn't been stored yet: its clock starts when it is.
+                .unwrap_or(max_lifetime),
+            DoesNotExist => max_lifetime,
+            MarkedForDeletion => std::time::Duration::ZERO,
+        };
+        Ok(Some(remaining))
+    }
 }
 
 /// Control when the server-side state is synchronized with the store.
@@ -456,13 +598,17 @@ impl Session<'_> {
     /// the post-processing middleware that attaches the session cookie to
     /// the response returned to the client.
     pub async fn sync(&mut self) -> Result<(), SyncError> {
+        if self.cycle_pending.take() {
+            self.cycle_id();
+        }
         let state_config = &self.config.state;
-        let fresh_ttl = state_config.ttl;
         let create_if_empty = {
             let has_client_side = self.id.old_id().is_some()
                 || matches!(self.client_state, ClientState::Updated { .. });
             has_client_side && state_config.server_state_creation == ServerStateCreation::NeverSkip
         };
+        self.stamp_server_state(create_if_empty);
+        let fresh_ttl = self.fresh_ttl();
         use ServerState::*;
         match self.server_state.get() {
             Some(DoesNotExist) => match self.id {
@@ -625,6 +771,62 @@ impl Session<'_> {
         Ok(())
     }
 
+    /// Store the metadata required by binding and lifetime limits in the server-side state,
+    /// if it's about to be written to the store without it.
+    fn stamp_server_state(&mut self, create_if_empty: bool) {
+        if self.is_invalidated() {
+            return;
+        }
+        let mut stamps: Vec<(&'static str, Value)> = Vec::new();
+        if let Some(fingerprint) = &self.fingerprint {
+            let value =
+                serde_json::to_value(fingerprint).expect("A fingerprint can always be serialized");
+            stamps.push((BINDING_KEY, value));
+        }
+        if self.config.state.has_lifetime_limits() {
+            stamps.push((TIMESTAMPS_KEY, SessionTimestamps::new().to_value()));
+        }
+        if stamps.is_empty() {
+            return;
+        }
+        let is_new = matches!(self.id, CurrentSessionId::NewlyGenerated(_));
+        match self.server_state.get_mut() {
+            Some(ServerState::Changed { state }) => {
+                for (key, value) in stamps {
+                    state.entry(key.into()).or_insert(value);
+                }
+            }
+            Some(ServerState::DoesNotExist) if create_if_empty => {
+                self.server_state = new_cell_with(Some(ServerState::Changed {
+                    state: stamps.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+                }));
+            }
+            Some(ServerState::Unchanged { .. }) if is_new && create_if_empty => {
+                self.server_state = new_cell_with(Some(ServerState::Changed {
+                    state: stamps.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    /// The TTL to be used when writing the server-side state to the store.
+    ///
+    /// It's the configured TTL, unless the session would outlive its lifetime limits.
+    /// It applies to TTL extensions as well: the record expires, and is
+    /// removed by [`SessionStore::delete_expired`], as soon as the session does, even if
+    /// it's never loaded again.
+    fn fresh_ttl(&self) -> std::time::Duration {
+        let ttl = self.config.state.ttl;
+        let state = match self.server_state.get() {
+            Some(ServerState::Unchanged { state, .. } | ServerState::Changed { state }) => state,
+            _ => return ttl,
+        };
+        SessionTimestamps::from_state(state)
+            .and_then(|t| t.max_ttl(&self.config.state))
+            .map_or(ttl, |max_ttl| ttl.min(max_ttl))
+    }
+
     /// Load the server-side state from the store.
     /// This method does nothing if the server-side state has already been loaded.
     ///
@@ -643,24 +845,87 @@ impl Session<'_> {
         Failing to do so will push the session into an invalid state."]
     pub async fn finalize(&mut self) -> Result<Option<ResponseCookie<'static>>, FinalizeError> {
         self.sync().await?;
+        let Some(update) = self.client_update() else {
+            return Ok(None);
+        };
 
         let cookie_config = &self.config.cookie;
         let cookie_name = &cookie_config.name;
-
-        if self.invalidated.is_invalidated() {
-            if self.id.old_id().is_none() {
-                // This is a new session, so there's nothing on the client-side
-                // to be removed.
-                return Ok(None);
-            }
-            let mut cookie = RemovalCookie::new(cookie_name.clone());
-            if let Some(domain) = cookie_config.domain.as_deref() {
-                cookie = cookie.set_domain(domain.to_owned());
+        match update {
+            ClientUpdate::Remove => {
+                let mut cookie = RemovalCookie::new(cookie_name.clone());
+                if let Some(domain) = cookie_config.domain.as_deref() {
+                    cookie = cookie.set_domain(domain.to_owned());
+                }
+                if let Some(path) = cookie_config.path.as_deref() {
+                    cookie = cookie.set_path(path.to_owned());
+                }
+                Ok(Some(cookie.into()))
             }
-            if let Some(path) = cookie_config.path.as_deref() {
-                cookie = cookie.set_path(path.to_owned());
+            ClientUpdate::Set(value) => {
+                let value = serde_json::to_string(&value)?;
+                let mut cookie = ResponseCookie::new(cookie_name.clone(), value);
+                if let Some(domain) = cookie_config.domain.as_deref() {
+                    cookie = cookie.set_domain(domain.to_owned());
+                }
+                if let Some(path) = cookie_config.path.as_deref() {
+                    cookie = cookie.set_path(path.to_owned());
+                }
+                if let Some(same_site) = cookie_config.same_site {
+                    cookie = cookie.set_same_site(same_site);
+                }
+                if cookie_config.secure {
+                    cookie = cookie.set_secure(true);
+                }
+                if cookie_config.http_only {
+                    cookie = cookie.set_http_only(true);
+                }
+                if cookie_config.kind == SessionCookieKind::Persistent {
+                    let max_age: SignedDuration =
+                        self.fresh_ttl().try_into().unwrap_or(SignedDuration::MAX);
+                    cookie = cookie.set_max_age(max_age);
+                }
+                Ok(Some(cookie))
             }
-            Ok(Some(cookie.into()))
+        }
+    }
+
+    /// Sync the current server-side state with the chosen storage backend.
+    /// If necessary, it returns a session token to be attached to the outgoing response,
+    /// via the header specified in [`SessionHeaderConfig::response_header`],
+    /// in order to sync the client-side state.
+    ///
+    /// An empty header value is returned if the client must discard its token,
+    /// e.g. because the session has been invalidated.
+    ///
+    /// It's the equivalent of [`finalize`](Self::finalize) for [`SessionTransport::Header`].
+    ///
+    /// [`SessionHeaderConfig::response_header`]: crate::config::SessionHeaderConfig::response_header
+    /// [`SessionTransport::Header`]: crate::SessionTransport::Header
+    #[must_use = "The header value returned by `finalize_header` must be attached to the outgoing HTTP response. \
+        Failing to do so will push the session into an invalid state."]
+    pub async fn finalize_header(&mut self) -> Result<Option<HeaderValue>, FinalizeError> {
+        self.sync().await?;
+        let Some(update) = self.client_update() else {
+            return Ok(None);
+        };
+        let value = match update {
+            ClientUpdate::Remove => HeaderValue::from_static(""),
+            ClientUpdate::Set(value) => HeaderValue::try_from(value.to_token(&self.config.header)?)
+                .expect("A base64url-encoded token is always a valid header value"),
+        };
+        Ok(Some(value))
+    }
+
+    /// Determine what the client must be told about the session,
+    /// regardless of the transport used to tell it.
+    ///
+    /// It must be called after the server-side state has been synchronized.
+    fn client_update(&self) -> Option<ClientUpdate<'_>> {
+        if self.invalidated.is_invalidated() {
+            // If this is a new session, there's nothing on the client-side
+            // to be removed.
+            self.id.old_id().map(|_| ClientUpdate::Remove)
         } else {
             match &self.client_state {
                 ClientState::Updated {
@@ -679,50 +944,32 @@ impl Session<'_> {
                         }
                     };
                     // The session is new, we don't have a server-side record, and the client state is empty.
-                    // We don't need to create a session cookie in this case.
+                    // We don't need to send anything to the client in this case.
                     if client_state.is_empty()
                         && self.id.old_id().is_none()
                         && !server_record_exists.unwrap_or(true)
                     {
-                        return Ok(None);
+                        return None;
                     }
-                    let value = WireClientState {
+                    Some(ClientUpdate::Set(WireClientState {
                         session_id: self.id.new_id(),
                         user_values: Cow::Borrowed(client_state),
-                    };
-                    let value = serde_json::to_string(&value)?;
-                    let mut cookie = ResponseCookie::new(cookie_name.clone(), value);
-                    if let Some(domain) = cookie_config.domain.as_deref() {
-                        cookie = cookie.set_domain(domain.to_owned());
-                    }
-                    if let Some(path) = cookie_config.path.as_deref() {
-                        cookie = cookie.set_path(path.to_owned());
-                    }
-                    if let Some(same_site) = cookie_config.same_site {
-                        cookie = cookie.set_same_site(same_site);
-                    }
-                    if cookie_config.secure {
-                        cookie = cookie.set_secure(true);
-                    }
-                    if cookie_config.http_only {
-                        cookie = cookie.set_http_only(true);
-                    }
-                    if cookie_config.kind == SessionCookieKind::Persistent {
-                        let max_age: SignedDuration = self
-                            .config
-                            .state
-                            .ttl
-                            .try_into()
-                            .unwrap_or(SignedDuration::MAX);
-                        cookie = cookie.set_max_age(max_age);
-                    }
-                    Ok(Some(cookie))
+                    }))
                 }
             }
         }
     }
 }
 
+/// The change that must be propagated to the client-side state
+/// when the session is finalized.
+enum ClientUpdate<'a> {
+    /// The client must discard the session.
+    Remove,
+    /// The client must store the given state.
+    Set(WireClientState<'a>),
+}
+
 /// APIs for manipulating the client-side session state.
 impl Session<'_> {
     /// Read values from the client-side state attached to this session.
@@ -989,11 +1236,13 @@ async fn force_load(session: &Session<'_>) -> Result<(), LoadError> {
     }
     let record = session.store.load(&session_id).await?;
     let mut must_invalidate = false;
+    let mut missing_state = false;
     let server_state = match record {
-        Some(r) => ServerState::Unchanged {
-            state: r.state,
-            ttl: r.ttl,
-        },
+        Some(r) => {
+            let (state, invalidate) = check_record(session, r);
+            must_invalidate = invalidate;
+            state
+        }
         None => {
             match session.config.state.missing_server_state {
                 MissingServerState::Allow => ServerState::DoesNotExist,
@@ -1002,6 +1251,7 @@ async fn force_load(session: &Session<'_>) -> Result<(), LoadError> {
                     // the time the server received the request and the time it tried to load
                     // the state.
                     must_invalidate = true;
+                    missing_state = true;
                     ServerState::MarkedForDeletion
                 }
             }
@@ -1019,22 +1269,111 @@ async fn force_load(session: &Session<'_>) -> Result<(), LoadError> {
         // based on the state they loaded.
         // Race conditions all the way down.
         if must_invalidate {
-            tracing::warn!(
-                "There is no server-side state for the current session, \
-                even though one was expected. Invalidating the current session."
-            );
+            if missing_state {
+                tracing::warn!(
+                    "There is no server-side state for the current session, \
+                    even though one was expected. Invalidating the current session."
+                );
+            }
             session.invalidated.invalidate();
         }
     }
     Ok(())
 }
 
+/// Enforce lifetime limits and binding on a record that was just loaded from the store.
+///
+/// It returns the server-side state to be cached in memory, and whether the session
+/// must be invalidated.
+fn check_record(session: &Session<'_>, record: SessionRecord) -> (ServerState, bool) {
+    let SessionRecord { mut state, ttl } = record;
+    let mut changed = false;
+    if session.config.state.has_lifetime_limits() {
+        match SessionTimestamps::check(&session.config.state, &mut state) {
+            LifetimeCheck::Valid { refreshed } => changed |= refreshed,
+            LifetimeCheck::Expired { reason } => {
+                tracing::debug!("{reason} Invalidating the current session.");
+                return (ServerState::MarkedForDeletion, true);
+            }
+        }
+    }
+    if let Some(fingerprint) = &session.fingerprint {
+        match check_binding(session, fingerprint, &mut state) {
+            Some(c) => changed |= c,
+            None => return (ServerState::MarkedForDeletion, true),
+        }
+    }
+    let server_state = if changed {
+        ServerState::Changed { state }
+    } else {
+        ServerState::Unchanged { state, ttl }
+    };
+    (server_state, false)
+}
+
+/// Compare the fingerprint of the current request with the one stored in the
+/// server-side state, applying the configured policy if they don't match.
+///
+/// It returns `None` if the session must be invalidated, or whether
+/// the state was modified otherwise.
+fn check_binding(
+    session: &Session<'_>,
+    fingerprint: &SessionFingerprint,
+    state: &mut State,
+) -> Option<bool> {
+    let stored = state
+        .get(BINDING_KEY)
+        .and_then(|v| serde_json::from_value::<SessionFingerprint>(v.clone()).ok());
+    let Some(mut stored) = stored else {
+        // The session was created before binding was enabled: bind it now.
+        let value =
+            serde_json::to_value(fingerprint).expect("A fingerprint can always be serialized");
+        state.insert(BINDING_KEY.into(), value);
+        return Some(true);
+    };
+
+    let mismatches = stored.mismatches(fingerprint);
+    if mismatches.is_empty() {
+        // Signals that have been enabled after the session was bound are added to it.
+        if !stored.merge_missing(fingerprint) {
+            return Some(false);
+        }
+        let value = serde_json::to_value(&stored).expect("A fingerprint can always be serialized");
+        state.insert(BINDING_KEY.into(), value);
+        return Some(true);
+    }
+
+    let policy = session.config.binding.on_mismatch;
+    if policy != BindingMismatchPolicy::Ignore {
+        tracing::warn!(
+            session.binding.mismatches = ?mismatches,
+            session.binding.policy = policy.as_str(),
+            "The current request doesn't match the fingerprint the session is bound to."
+        );
+        let _ = session
+            .binding_mismatch
+            .set(BindingMismatchError { mismatches, policy });
+    }
+    match policy {
+        BindingMismatchPolicy::Ignore | BindingMismatchPolicy::Log => Some(false),
+        BindingMismatchPolicy::Invalidate => None,
+        BindingMismatchPolicy::CycleId => {
+            session.cycle_pending.set(true);
+            let value =
+                serde_json::to_value(fingerprint).expect("A fingerprint can always be serialized");
+            state.insert(BINDING_KEY.into(), value);
+            Some(true)
+        }
+    }
+}
+
 /// Errors that can occur when interacting with the session state.
 pub mod errors {
     use std::borrow::Cow;
 
     use pavex::response::Response;
 
+    use crate::config::{BindingMismatchPolicy, BindingSignal};
     use crate::store::errors::{
         ChangeIdError, CreateError, DeleteError, LoadError, UpdateError, UpdateTtlError,
     };
@@ -1142,6 +1481,16 @@ pub mod errors {
         SerializationError(#[from] serde_json::Error),
         #[error("Failed to sync the server-side session state")]
         SyncErr(#[from] SyncError),
+        #[error(
+            "There is no key to encrypt the session token. Set `key` in the session header configuration"
+        )]
+        MissingHeaderKey,
+        #[error("`{name}` is not a valid name for the session response header")]
+        InvalidResponseHeaderName {
+            name: String,
+            #[source]
+            source: pavex::http::header::InvalidHeaderName,
+        },
     }
 
     impl FinalizeError {
@@ -1150,6 +1499,23 @@ pub mod errors {
             Response::internal_server_error()
         }
     }
+
+    /// The fingerprint of the current request doesn't match the one the session is bound to.
+    ///
+    /// Check out [`BindingMismatchPolicy`] for the actions that can be taken on the session.
+    #[derive(Debug, Clone, thiserror::Error)]
+    #[error(
+        "The current request doesn't match the fingerprint the session is bound to \
+        (mismatched signals: {mismatches:?}, policy: `{}`)",
+        policy.as_str()
+    )]
+    #[non_exhaustive]
+    pub struct BindingMismatchError {
+        /// The signals whose values differ.
+        pub mismatches: Vec<BindingSignal>,
+        /// The policy that was applied to the session.
+        pub policy: BindingMismatchPolicy,
+    }
 }
 
 #[cfg(test)]
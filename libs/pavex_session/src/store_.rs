@@ -1,7 +1,7 @@
 use crate::SessionId;
 use errors::{
-    ChangeIdError, CreateError, DeleteError, DeleteExpiredError, LoadError, UpdateError,
-    UpdateTtlError,
+    ChangeIdError, CreateError, DeleteByPrincipalError, DeleteError, DeleteExpiredError,
+    ListByPrincipalError, LoadError, UpdateError, UpdateTtlError,
 };
 use serde_json::Value;
 use std::{borrow::Cow, collections::HashMap, num::NonZeroUsize};
@@ -89,8 +89,45 @@ impl SessionStore {
     ) -> Result<usize, DeleteExpiredError> {
         self.0.delete_expired(batch_size).await
     }
+
+    /// List the identifiers of all live sessions bound to the given principal.
+    ///
+    /// Check out [`Session::bind_principal`](crate::Session::bind_principal) for more details.
+    pub async fn list_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<Vec<SessionId>, ListByPrincipalError> {
+        self.0.list_by_principal(principal).await
+    }
+
+    /// Delete all session records bound to the given principal.
+    ///
+    /// It returns the number of session records that have been deleted.
+    /// Check out [`Session::bind_principal`](crate::Session::bind_principal) for more details.
+    pub async fn delete_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<usize, DeleteByPrincipalError> {
+        self.0.delete_by_principal(principal).await
+    }
 }
 
+/// The key used to store the principal a session is bound to, in the server-side state.
+///
+/// Check out [`Session::bind_principal`](crate::Session::bind_principal) for more details.
+pub const PRINCIPAL_KEY: &str = "pavex.principal";
+
+/// The key used to store the creation and last activity timestamps of a session,
+/// in the server-side state.
+///
+/// It's only set if [`max_lifetime`] or [`idle_timeout`] are configured.
+/// The value is an object with two fields, `created_at` and `last_active`,
+/// both expressed as seconds since the Unix epoch.
+///
+/// [`max_lifetime`]: crate::config::SessionStateConfig::max_lifetime
+/// [`idle_timeout`]: crate::config::SessionStateConfig::idle_timeout
+pub const TIMESTAMPS_KEY: &str = "pavex.timestamps";
+
 #[async_trait::async_trait]
 /// The interface of a session storage backend.
 pub trait SessionStorageBackend: std::fmt::Debug + Send + Sync {
@@ -153,6 +190,37 @@ pub trait SessionStorageBackend: std::fmt::Debug + Send + Sync {
         &self,
         batch_size: Option<NonZeroUsize>,
     ) -> Result<usize, DeleteExpiredError>;
+
+    /// List the identifiers of all live sessions bound to the given principal.
+    ///
+    /// The principal of a session is stored in its state, under [`PRINCIPAL_KEY`].
+    /// Use [`SessionRecordRef::principal`] to retrieve it when creating or updating
+    /// a record.
+    ///
+    /// # Optional
+    ///
+    /// Indexing sessions by principal is optional.
+    /// The default implementation returns [`ListByPrincipalError::Unsupported`].
+    async fn list_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<Vec<SessionId>, ListByPrincipalError> {
+        let _ = principal;
+        Err(ListByPrincipalError::Unsupported)
+    }
+
+    /// Delete all session records bound to the given principal.
+    ///
+    /// If successful, it returns the number of session records that have been deleted.
+    ///
+    /// # Optional
+    ///
+    /// Indexing sessions by principal is optional.
+    /// The default implementation returns [`DeleteByPrincipalError::Unsupported`].
+    async fn delete_by_principal(&self, principal: &str) -> Result<usize, DeleteByPrincipalError> {
+        let _ = principal;
+        Err(DeleteByPrincipalError::Unsupported)
+    }
 }
 
 /// A server-side session record that's going to be stored in the
@@ -166,6 +234,13 @@ pub struct SessionRecordRef<'session> {
 }
 
 impl SessionRecordRef<'_> {
+    /// The principal this session is bound to, if any.
+    ///
+    /// It's the string value stored under [`PRINCIPAL_KEY`] in the session state.
+    pub fn principal(&self) -> Option<&str> {
+        self.state.get(PRINCIPAL_KEY).and_then(Value::as_str)
+    }
+
     pub(crate) fn empty(ttl: std::time::Duration) -> Self {
         Self {
             state: Cow::Owned(HashMap::new()),
@@ -237,6 +312,12 @@ pub mod errors {
         #[error("Failed to deserialize the session state.")]
         /// Failed to deserialize the session state.
         DeserializationError(#[source] anyhow::Error),
+        #[error("Failed to decrypt the session state.")]
+        /// Failed to decrypt the session state.
+        ///
+        /// It's returned by [`EncryptedStorageBackend`](crate::store::EncryptedStorageBackend)
+        /// when the stored state was tampered with or was encrypted using an unknown key.
+        DecryptionError(#[source] anyhow::Error),
         /// Something else went wrong when loading the session record.
         #[error("Something went wrong when loading the session record.")]
         Other(#[source] anyhow::Error),
@@ -269,6 +350,30 @@ pub mod errors {
         Other(#[source] anyhow::Error),
     }
 
+    #[non_exhaustive]
+    #[derive(Debug, thiserror::Error)]
+    /// The error returned by [`SessionStorageBackend::list_by_principal`][super::SessionStorageBackend::list_by_principal].
+    pub enum ListByPrincipalError {
+        /// The storage backend doesn't index sessions by principal.
+        #[error("The session storage backend doesn't support indexing sessions by principal.")]
+        Unsupported,
+        /// Something else went wrong when listing the session records.
+        #[error("Something went wrong when listing the sessions bound to a principal.")]
+        Other(#[source] anyhow::Error),
+    }
+
+    #[non_exhaustive]
+    #[derive(Debug, thiserror::Error)]
+    /// The error returned by [`SessionStorageBackend::delete_by_principal`][super::SessionStorageBackend::delete_by_principal].
+    pub enum DeleteByPrincipalError {
+        /// The storage backend doesn't index sessions by principal.
+        #[error("The session storage backend doesn't support indexing sessions by principal.")]
+        Unsupported,
+        /// Something else went wrong when deleting the session records.
+        #[error("Something went wrong when deleting the sessions bound to a principal.")]
+        Other(#[source] anyhow::Error),
+    }
+
     /// The error returned by [`SessionStorageBackend::delete_expired`][super::SessionStorageBackend::delete_expired].
     #[derive(Debug, thiserror::Error)]
     #[error("Something went wrong when deleting expired sessions")]
@@ -0,0 +1,58 @@
+/// How the session token is exchanged with the client.
+///
+/// It determines where [`IncomingSession::extract_with_transport`] looks for the session
+/// and how [`finalize_session`] sends it back.
+///
+/// # Choosing a transport
+///
+/// [`SessionKit`] registers [`SessionTransport::cookie`] as the constructor
+/// for [`SessionTransport`].
+/// Override it in a nested blueprint to switch transport for a subset of your routes,
+/// e.g. an API consumed by mobile or CLI clients:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::f;
+///
+/// fn api() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.request_scoped(f!(pavex_session::SessionTransport::header));
+///     // [...] Register your API routes here.
+///     bp
+/// }
+///
+/// let mut bp = Blueprint::new();
+/// bp.prefix("/api").nest(api());
+/// ```
+///
+/// [`IncomingSession::extract_with_transport`]: crate::IncomingSession::extract_with_transport
+/// [`finalize_session`]: crate::finalize_session
+/// [`SessionKit`]: crate::SessionKit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SessionTransport {
+    /// The session token is stored in a cookie, configured via
+    /// [`SessionCookieConfig`](crate::config::SessionCookieConfig).
+    ///
+    /// This is the default.
+    #[default]
+    Cookie,
+    /// The session token is read from a request header and sent back in a response header,
+    /// as configured via [`SessionHeaderConfig`](crate::config::SessionHeaderConfig).
+    ///
+    /// The token is encrypted: you must set
+    /// [`SessionHeaderConfig::key`](crate::config::SessionHeaderConfig::key) to use it.
+    Header,
+}
+
+impl SessionTransport {
+    /// Use cookies to exchange the session token.
+    pub fn cookie() -> Self {
+        Self::Cookie
+    }
+
+    /// Use headers to exchange the session token.
+    pub fn header() -> Self {
+        Self::Header
+    }
+}
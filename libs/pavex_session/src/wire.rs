@@ -1,6 +1,16 @@
-use crate::{SessionId, State};
+use crate::{SessionId, State, config::SessionHeaderConfig, errors::FinalizeError};
+use pavex::cookie::{
+    Processor, ProcessorConfig, ResponseCookie,
+    config::{CryptoAlgorithm, CryptoRule, FallbackConfig},
+};
 use std::{borrow::Cow, collections::HashMap};
 
+/// The name the encrypted session token is bound to.
+///
+/// The token travels in different headers for requests and responses:
+/// we use a fixed name rather than the name of either of them.
+const TOKEN_NAME: &str = "pavex.session.token";
+
 #[derive(serde::Deserialize, serde::Serialize)]
 /// The schema for the session cookie value.
 ///
@@ -11,3 +21,67 @@ pub(crate) struct WireClientState<'a> {
     #[serde(rename = "1", skip_serializing_if = "HashMap::is_empty", default)]
     pub(crate) user_values: Cow<'a, State>,
 }
+
+impl WireClientState<'_> {
+    /// Encode the state as a token that can be sent in an HTTP header.
+    ///
+    /// The JSON payload is encrypted with the key in [`SessionHeaderConfig`]:
+    /// the client can neither read nor tamper with it.
+    /// The encrypted payload is base64url-encoded, without padding, to make sure
+    /// it only contains characters that are valid in a header value.
+    pub(crate) fn to_token(&self, config: &SessionHeaderConfig) -> Result<String, FinalizeError> {
+        let processor = processor(config).ok_or(FinalizeError::MissingHeaderKey)?;
+        let json = serde_json::to_string(self)?;
+        let cookie = processor.process_outgoing(ResponseCookie::new(TOKEN_NAME, json));
+        Ok(cookie.value().to_owned())
+    }
+
+    /// Decode a token produced by [`WireClientState::to_token`].
+    ///
+    /// Tokens that can't be decrypted with any of the configured keys are rejected.
+    pub(crate) fn from_token(
+        token: &str,
+        config: &SessionHeaderConfig,
+    ) -> Result<WireClientState<'static>, InvalidToken> {
+        let processor = processor(config).ok_or(InvalidToken::MissingKey)?;
+        let cookie = processor
+            .process_incoming(TOKEN_NAME, token)
+            .map_err(|_| InvalidToken::Decryption)?;
+        Ok(serde_json::from_str(cookie.value())?)
+    }
+}
+
+/// A processor that encrypts (and decrypts) session tokens, using the keys in `config`.
+///
+/// It returns `None` if no key has been configured.
+fn processor(config: &SessionHeaderConfig) -> Option<Processor> {
+    let key = config.key.clone()?;
+    let fallbacks = config
+        .fallback_keys
+        .iter()
+        .map(|key| FallbackConfig {
+            key: key.clone(),
+            algorithm: CryptoAlgorithm::Encryption,
+        })
+        .collect();
+    let mut processor = ProcessorConfig::default();
+    // The encrypted value is already safe to use in a header.
+    processor.percent_encode = false;
+    processor.crypto_rules.push(CryptoRule {
+        cookie_names: vec![TOKEN_NAME.to_owned()],
+        algorithm: CryptoAlgorithm::Encryption,
+        key,
+        fallbacks,
+    });
+    Some(processor.into())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum InvalidToken {
+    #[error("There is no key to decrypt session tokens")]
+    MissingKey,
+    #[error("The session token can't be decrypted with any of the configured keys")]
+    Decryption,
+    #[error("The session token doesn't contain a valid client state")]
+    Payload(#[from] serde_json::Error),
+}
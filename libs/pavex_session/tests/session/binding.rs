@@ -0,0 +1,203 @@
+use pavex::{cookie::ResponseCookies, http::StatusCode, response::Response};
+use pavex_session::{
+    IncomingSession, Session, SessionConfig, SessionStore, SessionTransport,
+    binding::{BINDING_KEY, SessionFingerprint},
+    config::{BindingMismatchPolicy, BindingSignal},
+    finalize_session,
+};
+
+use crate::{
+    assertions::is_removal_cookie,
+    fixtures::{SessionFixture, store},
+    helpers::SetCookie,
+};
+use googletest::{assert_that, prelude::not};
+
+fn config(policy: BindingMismatchPolicy) -> SessionConfig {
+    let mut config = SessionConfig::default();
+    config.binding.signals = vec![BindingSignal::IpPrefix, BindingSignal::UserAgent];
+    config.binding.on_mismatch = policy;
+    config
+}
+
+fn fingerprint(config: &SessionConfig, ip: &str, user_agent: &str) -> SessionFingerprint {
+    SessionFingerprint::compute(
+        &config.binding,
+        Some(ip.parse().unwrap()),
+        Some(user_agent.as_bytes()),
+        None,
+    )
+}
+
+/// Create a session bound to `fingerprint`, with a server-side value.
+async fn bound_session(
+    store: &SessionStore,
+    config: &SessionConfig,
+    fingerprint: &SessionFingerprint,
+) -> SetCookie {
+    let mut session = Session::new_with_fingerprint(store, config, None, fingerprint);
+    session.insert("key", "value").await.unwrap();
+    SetCookie::parse(session.finalize().await.unwrap().unwrap())
+}
+
+fn incoming(cookie: &SetCookie) -> Option<IncomingSession> {
+    Some(IncomingSession::from_parts(
+        cookie.id,
+        cookie.client_state.clone(),
+    ))
+}
+
+#[tokio::test]
+async fn the_fingerprint_is_stored_when_the_session_is_created() {
+    let (store, config) = (store(), config(BindingMismatchPolicy::Invalidate));
+    let fp = fingerprint(&config, "203.0.113.7", "firefox");
+    let cookie = bound_session(&store, &config, &fp).await;
+
+    let record = store.load(&cookie.id).await.unwrap().unwrap();
+    let stored: SessionFingerprint =
+        serde_json::from_value(record.state[BINDING_KEY].clone()).unwrap();
+    assert_eq!(stored, fp);
+}
+
+#[tokio::test]
+async fn nothing_is_stored_if_binding_is_disabled() {
+    let (store, config) = (store(), SessionConfig::default());
+    let fp = SessionFingerprint::compute(
+        &config.binding,
+        Some("203.0.113.7".parse().unwrap()),
+        Some(b"firefox"),
+        None,
+    );
+    assert!(fp.is_empty());
+    let cookie = bound_session(&store, &config, &fp).await;
+
+    let record = store.load(&cookie.id).await.unwrap().unwrap();
+    assert!(!record.state.contains_key(BINDING_KEY));
+}
+
+#[tokio::test]
+async fn addresses_in_the_same_network_match() {
+    let (store, config) = (store(), config(BindingMismatchPolicy::Invalidate));
+    let cookie = bound_session(&store, &config, &fingerprint(&config, "203.0.113.7", "ua")).await;
+
+    let fp = fingerprint(&config, "203.0.113.42", "ua");
+    let session = Session::new_with_fingerprint(&store, &config, incoming(&cookie), &fp);
+    assert_eq!(
+        session.get::<String>("key").await.unwrap().as_deref(),
+        Some("value")
+    );
+    assert!(!session.is_invalidated());
+}
+
+#[tokio::test]
+async fn a_mismatch_invalidates_the_session_by_default() {
+    let (store, config) = (store(), config(BindingMismatchPolicy::default()));
+    let cookie = bound_session(&store, &config, &fingerprint(&config, "203.0.113.7", "ua")).await;
+
+    let fp = fingerprint(&config, "198.51.100.7", "ua");
+    let mut session = Session::new_with_fingerprint(&store, &config, incoming(&cookie), &fp);
+    assert!(session.get_raw("key").await.unwrap().is_none());
+    assert!(session.is_invalidated());
+
+    let removal = session.finalize().await.unwrap().unwrap();
+    assert_that!(removal, is_removal_cookie());
+    assert!(store.load(&cookie.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn a_mismatch_can_be_logged_and_ignored() {
+    for policy in [BindingMismatchPolicy::Log, BindingMismatchPolicy::Ignore] {
+        let (store, config) = (store(), config(policy));
+        let cookie =
+            bound_session(&store, &config, &fingerprint(&config, "203.0.113.7", "ua")).await;
+
+        let fp = fingerprint(&config, "203.0.113.7", "another ua");
+        let mut session = Session::new_with_fingerprint(&store, &config, incoming(&cookie), &fp);
+        assert!(session.get_raw("key").await.unwrap().is_some());
+        assert_eq!(
+            session.binding_mismatch().is_some(),
+            policy == BindingMismatchPolicy::Log
+        );
+        let after = SetCookie::parse(session.finalize().await.unwrap().unwrap());
+        assert_eq!(after.id(), cookie.id());
+        assert!(store.load(&cookie.id).await.unwrap().is_some());
+    }
+}
+
+#[tokio::test]
+async fn a_mismatch_can_cycle_the_session_id() {
+    let (store, config) = (store(), config(BindingMismatchPolicy::CycleId));
+    let original = fingerprint(&config, "203.0.113.7", "ua");
+    let cookie = bound_session(&store, &config, &original).await;
+
+    let fp = fingerprint(&config, "198.51.100.7", "ua");
+    let mut session = Session::new_with_fingerprint(&store, &config, incoming(&cookie), &fp);
+    assert!(session.get_raw("key").await.unwrap().is_some());
+    let cycled = SetCookie::parse(session.finalize().await.unwrap().unwrap());
+    assert_ne!(cycled.id(), cookie.id());
+
+    // The old ID no longer works, while the new one is bound to the new fingerprint.
+    assert!(store.load(&cookie.id).await.unwrap().is_none());
+    let session = Session::new_with_fingerprint(&store, &config, incoming(&cycled), &fp);
+    assert!(session.get_raw("key").await.unwrap().is_some());
+    assert!(!session.is_invalidated());
+}
+
+#[tokio::test]
+async fn mismatches_are_reported_without_failing_the_finalization() {
+    let (store, config) = (store(), config(BindingMismatchPolicy::CycleId));
+    let cookie = bound_session(&store, &config, &fingerprint(&config, "203.0.113.7", "ua")).await;
+
+    let fp = fingerprint(&config, "198.51.100.7", "ua");
+    let session = Session::new_with_fingerprint(&store, &config, incoming(&cookie), &fp);
+    session.force_load().await.unwrap();
+    let mismatch = session.binding_mismatch().unwrap();
+    assert_eq!(mismatch.mismatches, vec![BindingSignal::IpPrefix]);
+    assert_eq!(mismatch.policy, BindingMismatchPolicy::CycleId);
+
+    let mut cookies = ResponseCookies::new();
+    let response = finalize_session(
+        Response::ok().set_status(StatusCode::CREATED),
+        &mut cookies,
+        &SessionTransport::Cookie,
+        &config,
+        session,
+    )
+    .await
+    .unwrap();
+
+    // The original response is preserved, and the session ID has been cycled.
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let cycled = cookies.iter().next().unwrap();
+    assert_ne!(SetCookie::parse(cycled.clone()).id(), cookie.id());
+}
+
+#[tokio::test]
+async fn unbound_sessions_are_bound_when_loaded() {
+    let (store, config) = (store(), config(BindingMismatchPolicy::Invalidate));
+    let fixture = SessionFixture::default();
+    let incoming = fixture.setup(&store).await;
+    let fp = fingerprint(&config, "203.0.113.7", "ua");
+
+    let mut session = Session::new_with_fingerprint(&store, &config, Some(incoming), &fp);
+    session.force_load().await.unwrap();
+    let cookie = session.finalize().await.unwrap().unwrap();
+    assert_that!(cookie, not(is_removal_cookie()));
+
+    let record = store.load(&fixture.id).await.unwrap().unwrap();
+    assert!(record.state.contains_key(BINDING_KEY));
+}
+
+#[tokio::test]
+async fn unavailable_signals_are_skipped() {
+    let (store, mut config) = (store(), config(BindingMismatchPolicy::Invalidate));
+    config.binding.signals = vec![BindingSignal::TlsChannel, BindingSignal::UserAgent];
+    let bound_to = SessionFingerprint::compute(&config.binding, None, Some(b"ua"), Some(b"chan"));
+    let cookie = bound_session(&store, &config, &bound_to).await;
+
+    // The TLS channel identifier is not available for this request.
+    let fp = SessionFingerprint::compute(&config.binding, None, Some(b"ua"), None);
+    let session = Session::new_with_fingerprint(&store, &config, incoming(&cookie), &fp);
+    assert!(session.get_raw("key").await.unwrap().is_some());
+    assert!(!session.is_invalidated());
+}
@@ -0,0 +1,176 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use pavex_session::{
+    SessionId,
+    store::{CachedSessionStore, SessionStorageBackend},
+};
+use pavex_session_memory_store::InMemorySessionStore;
+
+use crate::fixtures::{TTL, record, state};
+
+#[tokio::test]
+async fn repeated_loads_are_served_from_the_cache() {
+    let inner = InMemorySessionStore::default();
+    let store = CachedSessionStore::new(inner.clone());
+    let stats = store.stats();
+    let id = SessionId::random();
+    store
+        .create(&id, record(&state("value"), TTL))
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        let loaded = store.load(&id).await.unwrap().unwrap();
+        assert_eq!(loaded.state, state("value"));
+        assert!(loaded.ttl <= Duration::from_secs(60));
+    }
+    assert_eq!(stats.misses(), 1);
+    assert_eq!(stats.hits(), 2);
+
+    // Changes made behind the cache's back aren't visible until the entry expires.
+    inner
+        .update(&id, record(&state("other"), TTL))
+        .await
+        .unwrap();
+    assert_eq!(
+        store.load(&id).await.unwrap().unwrap().state,
+        state("value")
+    );
+}
+
+#[tokio::test]
+async fn missing_records_are_not_cached() {
+    let store = CachedSessionStore::new(InMemorySessionStore::default());
+    let stats = store.stats();
+    let id = SessionId::random();
+
+    assert!(store.load(&id).await.unwrap().is_none());
+    store
+        .create(&id, record(&state("value"), TTL))
+        .await
+        .unwrap();
+    assert!(store.load(&id).await.unwrap().is_some());
+    assert_eq!(stats.misses(), 2);
+}
+
+#[tokio::test]
+async fn writes_invalidate_the_cache() {
+    let store = CachedSessionStore::new(InMemorySessionStore::default());
+    let id = SessionId::random();
+    store.create(&id, record(&state("old"), TTL)).await.unwrap();
+    store.load(&id).await.unwrap();
+
+    store.update(&id, record(&state("new"), TTL)).await.unwrap();
+    assert_eq!(store.load(&id).await.unwrap().unwrap().state, state("new"));
+
+    let new_id = SessionId::random();
+    store.change_id(&id, &new_id).await.unwrap();
+    assert!(store.load(&id).await.unwrap().is_none());
+    assert_eq!(
+        store.load(&new_id).await.unwrap().unwrap().state,
+        state("new")
+    );
+
+    store.delete(&new_id).await.unwrap();
+    assert!(store.load(&new_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn ttl_updates_keep_the_cached_state() {
+    let store = CachedSessionStore::new(InMemorySessionStore::default());
+    let stats = store.stats();
+    let id = SessionId::random();
+    store
+        .create(&id, record(&state("value"), TTL))
+        .await
+        .unwrap();
+    store.load(&id).await.unwrap();
+
+    store
+        .update_ttl(&id, Duration::from_secs(3600))
+        .await
+        .unwrap();
+    let loaded = store.load(&id).await.unwrap().unwrap();
+    assert!(loaded.ttl > Duration::from_secs(60));
+    assert_eq!(stats.hits(), 1);
+}
+
+#[tokio::test]
+async fn entries_expire_after_the_cache_ttl() {
+    let store = CachedSessionStore::new(InMemorySessionStore::default()).ttl(Duration::ZERO);
+    let stats = store.stats();
+    let id = SessionId::random();
+    store
+        .create(&id, record(&state("value"), TTL))
+        .await
+        .unwrap();
+
+    store.load(&id).await.unwrap();
+    store.load(&id).await.unwrap();
+    assert_eq!(stats.hits(), 0);
+    assert_eq!(stats.misses(), 2);
+}
+
+#[tokio::test]
+async fn least_recently_used_entries_are_evicted() {
+    let store = CachedSessionStore::new(InMemorySessionStore::default())
+        .capacity(NonZeroUsize::new(2).unwrap());
+    let stats = store.stats();
+    let ids = [
+        SessionId::random(),
+        SessionId::random(),
+        SessionId::random(),
+    ];
+    for id in &ids {
+        store
+            .create(id, record(&state("value"), TTL))
+            .await
+            .unwrap();
+    }
+
+    // Cache the first two records, then use the first one again.
+    store.load(&ids[0]).await.unwrap();
+    store.load(&ids[1]).await.unwrap();
+    store.load(&ids[0]).await.unwrap();
+    assert_eq!((stats.hits(), stats.misses()), (1, 2));
+
+    // The second record is evicted to make room for the third.
+    store.load(&ids[2]).await.unwrap();
+    store.load(&ids[0]).await.unwrap();
+    assert_eq!((stats.hits(), stats.misses()), (2, 3));
+    store.load(&ids[1]).await.unwrap();
+    assert_eq!((stats.hits(), stats.misses()), (2, 4));
+}
+
+#[tokio::test]
+async fn the_default_capacity_holds_ten_thousand_records() {
+    let store = CachedSessionStore::new(InMemorySessionStore::default());
+    let stats = store.stats();
+    let ids: Vec<_> = (0..10_000).map(|_| SessionId::random()).collect();
+    for id in &ids {
+        store
+            .create(id, record(&state("value"), TTL))
+            .await
+            .unwrap();
+        store.load(id).await.unwrap();
+    }
+    assert_eq!((stats.hits(), stats.misses()), (0, 10_000));
+
+    // They all fit in the cache.
+    for id in &ids {
+        store.load(id).await.unwrap();
+    }
+    assert_eq!((stats.hits(), stats.misses()), (10_000, 10_000));
+
+    // One more record evicts the least recently used one, i.e. the first.
+    let extra = SessionId::random();
+    store
+        .create(&extra, record(&state("value"), TTL))
+        .await
+        .unwrap();
+    store.load(&extra).await.unwrap();
+    store.load(&ids[1]).await.unwrap();
+    assert_eq!((stats.hits(), stats.misses()), (10_001, 10_001));
+    store.load(&ids[0]).await.unwrap();
+    assert_eq!((stats.hits(), stats.misses()), (10_001, 10_002));
+}
@@ -1,4 +1,9 @@
-use pavex_session::{Session, SessionConfig, config::TtlExtensionThreshold};
+use std::time::Duration;
+
+use pavex_session::{
+    Session, SessionConfig,
+    config::{SessionStateConfig, TtlExtensionThreshold},
+};
 
 use crate::fixtures::spy_store;
 
@@ -99,3 +104,17 @@ async fn default_ttl() {
     let last = oplog.last().unwrap();
     assert_eq!(last, &format!("create <id> {}s", 60 * 60 * 24));
 }
+
+#[test]
+fn lifetime_limits_can_be_configured() {
+    let config: SessionStateConfig =
+        serde_json::from_str(r#"{"max_lifetime": "12h", "idle_timeout": "15m"}"#).unwrap();
+    assert_eq!(config.max_lifetime, Some(Duration::from_secs(12 * 3600)));
+    assert_eq!(config.idle_timeout, Some(Duration::from_secs(15 * 60)));
+
+    let config: SessionStateConfig = serde_json::from_str("{}").unwrap();
+    assert!(config.max_lifetime.is_none());
+    assert!(config.idle_timeout.is_none());
+
+    assert!(serde_json::from_str::<SessionStateConfig>(r#"{"max_lifetime": "0s"}"#).is_err());
+}
@@ -0,0 +1,343 @@
+use std::{collections::HashMap, num::NonZeroUsize};
+
+use pavex_session::{
+    SessionId,
+    config::{StateEncryptionConfig, StateEncryptionKey},
+    store::{
+        EncryptedStorageBackend, InvalidEncryptionConfig, SessionRecord, SessionRecordRef,
+        SessionStorageBackend,
+        errors::{
+            ChangeIdError, CreateError, DeleteError, DeleteExpiredError, LoadError, UpdateError,
+            UpdateTtlError,
+        },
+    },
+};
+use pavex_session_memory_store::InMemorySessionStore;
+use serde_json::Value;
+
+use crate::fixtures::{TTL, record, state};
+
+const KEY_A: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+const KEY_B: &str = "BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBA=";
+
+fn config(keys: &[(&str, &str)]) -> StateEncryptionConfig {
+    StateEncryptionConfig::new(
+        keys.iter()
+            .map(|(id, key)| StateEncryptionKey::new(*id, *key))
+            .collect(),
+    )
+}
+
+#[tokio::test]
+async fn state_is_encrypted_at_rest() {
+    let inner = InMemorySessionStore::default();
+    let store = EncryptedStorageBackend::new(inner.clone(), &config(&[("a", KEY_A)])).unwrap();
+    let id = SessionId::random();
+    let initial_state = state("u-123");
+
+    store
+        .create(&id, record(&initial_state, TTL))
+        .await
+        .unwrap();
+
+    let raw = inner.load(&id).await.unwrap().unwrap();
+    assert!(!raw.state.contains_key("key"));
+    assert!(!serde_json::to_string(&raw.state).unwrap().contains("u-123"));
+
+    let loaded = store.load(&id).await.unwrap().unwrap();
+    assert_eq!(loaded.state, initial_state);
+
+    let new_state = state("u-456");
+    store.update(&id, record(&new_state, TTL)).await.unwrap();
+    assert_eq!(store.load(&id).await.unwrap().unwrap().state, new_state);
+}
+
+#[tokio::test]
+async fn old_keys_can_still_decrypt() {
+    let inner = InMemorySessionStore::default();
+    let old = EncryptedStorageBackend::new(inner.clone(), &config(&[("a", KEY_A)])).unwrap();
+    let id = SessionId::random();
+    old.create(&id, record(&state("u-123"), TTL)).await.unwrap();
+
+    let rotated =
+        EncryptedStorageBackend::new(inner.clone(), &config(&[("b", KEY_B), ("a", KEY_A)]))
+            .unwrap();
+    assert_eq!(
+        rotated.load(&id).await.unwrap().unwrap().state,
+        state("u-123")
+    );
+
+    // Once the record is updated, it's encrypted with the new key.
+    rotated
+        .update(&id, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+    let new_only = EncryptedStorageBackend::new(inner, &config(&[("b", KEY_B)])).unwrap();
+    assert_eq!(
+        new_only.load(&id).await.unwrap().unwrap().state,
+        state("u-123")
+    );
+}
+
+#[tokio::test]
+async fn unknown_keys_are_reported_as_decryption_errors() {
+    let inner = InMemorySessionStore::default();
+    let old = EncryptedStorageBackend::new(inner.clone(), &config(&[("a", KEY_A)])).unwrap();
+    let id = SessionId::random();
+    old.create(&id, record(&state("u-123"), TTL)).await.unwrap();
+
+    let new = EncryptedStorageBackend::new(inner, &config(&[("b", KEY_B)])).unwrap();
+    let err = new.load(&id).await.unwrap_err();
+    assert!(matches!(err, LoadError::DecryptionError(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn tampered_state_is_reported_as_a_decryption_error() {
+    let inner = InMemorySessionStore::default();
+    let store = EncryptedStorageBackend::new(inner.clone(), &config(&[("a", KEY_A)])).unwrap();
+    let id = SessionId::random();
+    store
+        .create(&id, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+
+    // Swap the key identifier: the ciphertext is bound to it.
+    let mut raw = inner.load(&id).await.unwrap().unwrap().state;
+    let envelope = raw.values_mut().next().unwrap();
+    envelope["kid"] = Value::from("b");
+    inner.update(&id, record(&raw, TTL)).await.unwrap();
+
+    let store =
+        EncryptedStorageBackend::new(inner, &config(&[("a", KEY_A), ("b", KEY_A)])).unwrap();
+    let err = store.load(&id).await.unwrap_err();
+    assert!(matches!(err, LoadError::DecryptionError(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn state_moved_to_another_session_cannot_be_decrypted() {
+    let inner = InMemorySessionStore::default();
+    let store = EncryptedStorageBackend::new(inner.clone(), &config(&[("a", KEY_A)])).unwrap();
+    let victim = SessionId::random();
+    let attacker = SessionId::random();
+    store
+        .create(&victim, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+    store
+        .create(&attacker, record(&HashMap::new(), TTL))
+        .await
+        .unwrap();
+
+    // Copy the victim's encrypted state into the attacker's record.
+    let raw = inner.load(&victim).await.unwrap().unwrap().state;
+    inner.update(&attacker, record(&raw, TTL)).await.unwrap();
+
+    let err = store.load(&attacker).await.unwrap_err();
+    assert!(matches!(err, LoadError::DecryptionError(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn state_can_still_be_decrypted_after_an_id_change() {
+    let inner = InMemorySessionStore::default();
+    let store = EncryptedStorageBackend::new(inner, &config(&[("a", KEY_A)])).unwrap();
+    let old_id = SessionId::random();
+    let new_id = SessionId::random();
+    store
+        .create(&old_id, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+
+    store.change_id(&old_id, &new_id).await.unwrap();
+
+    assert!(store.load(&old_id).await.unwrap().is_none());
+    assert_eq!(
+        store.load(&new_id).await.unwrap().unwrap().state,
+        state("u-123")
+    );
+}
+
+#[tokio::test]
+async fn changing_an_unknown_id_is_an_error() {
+    let store =
+        EncryptedStorageBackend::new(InMemorySessionStore::default(), &config(&[("a", KEY_A)]))
+            .unwrap();
+    let err = store
+        .change_id(&SessionId::random(), &SessionId::random())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ChangeIdError::UnknownId(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn a_failed_id_change_leaves_the_old_record_readable() {
+    let inner = InMemorySessionStore::default();
+    let store = EncryptedStorageBackend::new(inner.clone(), &config(&[("a", KEY_A)])).unwrap();
+    let old_id = SessionId::random();
+    let taken_id = SessionId::random();
+    store
+        .create(&old_id, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+    store
+        .create(&taken_id, record(&state("u-456"), TTL))
+        .await
+        .unwrap();
+
+    let err = store.change_id(&old_id, &taken_id).await.unwrap_err();
+    assert!(matches!(err, ChangeIdError::DuplicateId(_)), "{err:?}");
+
+    assert_eq!(
+        store.load(&old_id).await.unwrap().unwrap().state,
+        state("u-123")
+    );
+    assert_eq!(
+        store.load(&taken_id).await.unwrap().unwrap().state,
+        state("u-456")
+    );
+}
+
+#[tokio::test]
+async fn the_record_is_moved_back_if_it_cannot_be_re_encrypted() {
+    let inner = InMemorySessionStore::default();
+    let store = EncryptedStorageBackend::new(inner.clone(), &config(&[("a", KEY_A)])).unwrap();
+    let old_id = SessionId::random();
+    let new_id = SessionId::random();
+    store
+        .create(&old_id, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+
+    // Re-encrypting the record for the new ID requires an update, which fails.
+    let failing =
+        EncryptedStorageBackend::new(FailingUpdates(inner), &config(&[("a", KEY_A)])).unwrap();
+    let err = failing.change_id(&old_id, &new_id).await.unwrap_err();
+    assert!(matches!(err, ChangeIdError::Other(_)), "{err:?}");
+
+    assert!(store.load(&new_id).await.unwrap().is_none());
+    assert_eq!(
+        store.load(&old_id).await.unwrap().unwrap().state,
+        state("u-123")
+    );
+}
+
+#[tokio::test]
+async fn plaintext_records_are_returned_as_they_are() {
+    let inner = InMemorySessionStore::default();
+    let id = SessionId::random();
+    inner
+        .create(&id, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+
+    let store = EncryptedStorageBackend::new(inner, &config(&[("a", KEY_A)])).unwrap();
+    assert_eq!(
+        store.load(&id).await.unwrap().unwrap().state,
+        state("u-123")
+    );
+}
+
+#[tokio::test]
+async fn plaintext_records_can_be_rejected() {
+    let inner = InMemorySessionStore::default();
+    let id = SessionId::random();
+    inner
+        .create(&id, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+
+    let mut config = config(&[("a", KEY_A)]);
+    config.reject_plaintext = true;
+    let store = EncryptedStorageBackend::new(inner, &config).unwrap();
+    let err = store.load(&id).await.unwrap_err();
+    assert!(matches!(err, LoadError::DecryptionError(_)), "{err:?}");
+
+    // Encrypted records are still loaded.
+    store
+        .update(&id, record(&state("u-123"), TTL))
+        .await
+        .unwrap();
+    assert_eq!(
+        store.load(&id).await.unwrap().unwrap().state,
+        state("u-123")
+    );
+}
+
+#[test]
+fn invalid_configurations_are_rejected() {
+    let new = |keys: &[(&str, &str)]| {
+        EncryptedStorageBackend::new(InMemorySessionStore::default(), &config(keys)).unwrap_err()
+    };
+    assert!(matches!(new(&[]), InvalidEncryptionConfig::NoKeys));
+    assert!(matches!(
+        new(&[("a", "not base64")]),
+        InvalidEncryptionConfig::InvalidKey { .. }
+    ));
+    assert!(matches!(
+        new(&[("a", "AAAA")]),
+        InvalidEncryptionConfig::InvalidKey { .. }
+    ));
+    assert!(matches!(
+        new(&[("a", KEY_A), ("a", KEY_B)]),
+        InvalidEncryptionConfig::DuplicateKeyId { .. }
+    ));
+}
+
+#[test]
+fn keys_are_not_leaked_via_debug() {
+    let config = config(&[("a", KEY_A)]);
+    assert!(!format!("{config:?}").contains(KEY_A));
+    let store = EncryptedStorageBackend::new(InMemorySessionStore::default(), &config).unwrap();
+    assert!(!format!("{store:?}").contains(KEY_A));
+}
+
+/// A backend that fails every update, delegating all other operations.
+#[derive(Debug)]
+struct FailingUpdates<B>(B);
+
+#[async_trait::async_trait]
+impl<B: SessionStorageBackend> SessionStorageBackend for FailingUpdates<B> {
+    async fn create(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), CreateError> {
+        self.0.create(id, record).await
+    }
+
+    async fn update(
+        &self,
+        _id: &SessionId,
+        _record: SessionRecordRef<'_>,
+    ) -> Result<(), UpdateError> {
+        Err(UpdateError::Other(anyhow::anyhow!(
+            "The backend is unavailable"
+        )))
+    }
+
+    async fn update_ttl(
+        &self,
+        id: &SessionId,
+        ttl: std::time::Duration,
+    ) -> Result<(), UpdateTtlError> {
+        self.0.update_ttl(id, ttl).await
+    }
+
+    async fn load(&self, session_id: &SessionId) -> Result<Option<SessionRecord>, LoadError> {
+        self.0.load(session_id).await
+    }
+
+    async fn delete(&self, session_id: &SessionId) -> Result<(), DeleteError> {
+        self.0.delete(session_id).await
+    }
+
+    async fn change_id(&self, old_id: &SessionId, new_id: &SessionId) -> Result<(), ChangeIdError> {
+        self.0.change_id(old_id, new_id).await
+    }
+
+    async fn delete_expired(
+        &self,
+        batch_size: Option<NonZeroUsize>,
+    ) -> Result<usize, DeleteExpiredError> {
+        self.0.delete_expired(batch_size).await
+    }
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap, num::NonZeroUsize, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, num::NonZeroUsize, sync::Arc, time::Duration};
 
 use pavex_session::{
     IncomingSession, SessionId, SessionStore,
@@ -11,8 +11,12 @@ use pavex_session::{
     },
 };
 use pavex_session_memory_store::InMemorySessionStore;
+use serde_json::Value;
 use tokio::sync::Mutex;
 
+/// A TTL that's long enough to avoid expiration while we run the test suite.
+pub const TTL: Duration = Duration::from_secs(60);
+
 /// An empty in-memory session store.
 pub fn store() -> SessionStore {
     let backend = InMemorySessionStore::default();
@@ -28,6 +32,22 @@ pub fn spy_store() -> (SessionStore, CallTracker) {
     (SessionStore::new(spy_backend), call_tracker)
 }
 
+/// A session state with a single entry, `key`, set to `value`.
+pub fn state(value: &str) -> HashMap<Cow<'static, str>, Value> {
+    HashMap::from([("key".into(), Value::from(value))])
+}
+
+/// A record for `state`, to be handed over to a storage backend.
+pub fn record<'a>(
+    state: &'a HashMap<Cow<'static, str>, Value>,
+    ttl: Duration,
+) -> SessionRecordRef<'a> {
+    SessionRecordRef {
+        state: Cow::Borrowed(state),
+        ttl,
+    }
+}
+
 /// A helper to set up a pre-existing session.
 pub struct SessionFixture {
     pub id: SessionId,
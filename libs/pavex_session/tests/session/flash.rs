@@ -0,0 +1,109 @@
+use pavex::response::Response;
+use pavex_session::{
+    IncomingSession, Session, SessionConfig, SessionStore,
+    flash::{FLASH_KEY, Flash, FlashMessage, IncomingFlashMessages, Level, persist_flash_messages},
+};
+
+use crate::{fixtures::store, helpers::SetCookie};
+
+/// Process a request: `handler` gets to extract and queue messages,
+/// then the session is finalized.
+/// It returns the incoming session for the next request, if a cookie was set.
+async fn request(
+    store: &SessionStore,
+    config: &SessionConfig,
+    incoming: Option<IncomingSession>,
+    handler: impl FnOnce(&Session<'_>, &mut Flash),
+) -> Option<IncomingSession> {
+    let mut session = Session::new(store, config, incoming);
+    let mut flash = Flash::new();
+    handler(&session, &mut flash);
+    persist_flash_messages(Response::ok(), &flash, &mut session);
+    let cookie = session.finalize().await.unwrap()?;
+    let cookie = SetCookie::parse(cookie);
+    Some(IncomingSession::from_parts(cookie.id, cookie.client_state))
+}
+
+#[tokio::test]
+async fn messages_are_delivered_once() {
+    let (store, config) = (store(), SessionConfig::default());
+
+    let incoming = request(&store, &config, None, |_, flash| {
+        flash.success("Saved!");
+        flash.warning("Your password expires soon.");
+    })
+    .await
+    .expect("The messages must be stored in the session cookie");
+
+    let mut received = Vec::new();
+    let incoming = request(&store, &config, Some(incoming), |session, flash| {
+        let messages = IncomingFlashMessages::extract(session, flash);
+        received.extend(messages.iter().cloned());
+    })
+    .await
+    .expect("The removal must be propagated to the client");
+    assert_eq!(
+        received,
+        vec![
+            FlashMessage::new(Level::Success, "Saved!"),
+            FlashMessage::new(Level::Warning, "Your password expires soon."),
+        ]
+    );
+
+    let session = Session::new(&store, &config, Some(incoming));
+    assert!(session.client().get_raw(FLASH_KEY).is_none());
+    let messages = IncomingFlashMessages::extract(&session, &Flash::new());
+    assert!(messages.is_empty());
+}
+
+#[tokio::test]
+async fn messages_are_kept_until_they_are_extracted() {
+    let (store, config) = (store(), SessionConfig::default());
+
+    let incoming = request(&store, &config, None, |_, flash| flash.info("first"))
+        .await
+        .unwrap();
+    // A request that doesn't look at the messages, but queues a new one.
+    let incoming = request(&store, &config, Some(incoming), |_, flash| {
+        flash.error("second")
+    })
+    .await
+    .unwrap();
+
+    let mut received = Vec::new();
+    let incoming = request(&store, &config, Some(incoming), |session, flash| {
+        let messages = IncomingFlashMessages::extract(session, flash);
+        received.extend(messages.iter().map(|m| m.message().to_owned()));
+        // Messages queued while consuming the incoming ones are kept for the next request.
+        flash.success("third");
+    })
+    .await
+    .unwrap();
+    assert_eq!(received, vec!["first", "second"]);
+
+    let session = Session::new(&store, &config, Some(incoming));
+    let messages = IncomingFlashMessages::extract(&session, &Flash::new());
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages.iter().next().unwrap().level(), Level::Success);
+}
+
+#[tokio::test]
+async fn untouched_flash_does_not_set_a_cookie() {
+    let (store, config) = (store(), SessionConfig::default());
+    assert!(request(&store, &config, None, |_, _| {}).await.is_none());
+}
+
+#[tokio::test]
+async fn invalid_messages_are_discarded() {
+    let (store, config) = (store(), SessionConfig::default());
+    let mut session = Session::new(&store, &config, None);
+    session
+        .client_mut()
+        .insert(FLASH_KEY, "not a list of messages")
+        .unwrap();
+
+    let flash = Flash::new();
+    assert!(IncomingFlashMessages::extract(&session, &flash).is_empty());
+    persist_flash_messages(Response::ok(), &flash, &mut session);
+    assert!(session.client().get_raw(FLASH_KEY).is_none());
+}
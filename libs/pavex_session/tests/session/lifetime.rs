@@ -0,0 +1,186 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use googletest::{assert_that, prelude::not};
+use pavex_session::{Session, SessionConfig, store::TIMESTAMPS_KEY};
+use serde_json::json;
+
+use crate::{
+    assertions::is_removal_cookie,
+    fixtures::{SessionFixture, store},
+    helpers::SetCookie,
+};
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// A pre-existing session, created and last active the given number of seconds ago.
+fn fixture(created_ago: u64, idle_for: u64) -> SessionFixture {
+    let timestamps = json!({
+        "created_at": now() - created_ago,
+        "last_active": now() - idle_for,
+    });
+    SessionFixture {
+        server_state: Some(HashMap::from([
+            (TIMESTAMPS_KEY.to_owned(), timestamps),
+            ("key".to_owned(), json!("value")),
+        ])),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn records_never_outlive_the_maximum_lifetime() {
+    let (store, mut config) = (store(), SessionConfig::default());
+    config.state.max_lifetime = Some(Duration::from_secs(3600));
+
+    let mut session = Session::new(&store, &config, None);
+    session.insert("key", "value").await.unwrap();
+    assert_eq!(
+        session.remaining_lifetime().await.unwrap(),
+        Some(Duration::from_secs(3600))
+    );
+    let cookie = session.finalize().await.unwrap().unwrap();
+    assert!(cookie.max_age().unwrap().as_secs() <= 3600);
+
+    // The record expires with the session, so `delete_expired` will take care of it.
+    let record = store
+        .load(&SetCookie::parse(cookie).id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(record.ttl <= Duration::from_secs(3600));
+    assert!(record.state.contains_key(TIMESTAMPS_KEY));
+}
+
+#[tokio::test]
+async fn sessions_expire_after_their_maximum_lifetime() {
+    let (store, mut config) = (store(), SessionConfig::default());
+    config.state.max_lifetime = Some(Duration::from_secs(12 * 3600));
+
+    let expired = fixture(13 * 3600, 0);
+    let incoming = expired.setup(&store).await;
+    let mut session = Session::new(&store, &config, Some(incoming));
+    assert!(session.get_raw("key").await.unwrap().is_none());
+    assert!(session.is_invalidated());
+    assert_eq!(
+        session.remaining_lifetime().await.unwrap(),
+        Some(Duration::ZERO)
+    );
+    let cookie = session.finalize().await.unwrap().unwrap();
+    assert_that!(cookie, is_removal_cookie());
+    assert!(store.load(&expired.id).await.unwrap().is_none());
+
+    // Activity doesn't matter: a younger session is still valid.
+    let valid = fixture(11 * 3600, 0);
+    let incoming = valid.setup(&store).await;
+    let session = Session::new(&store, &config, Some(incoming));
+    assert!(session.get_raw("key").await.unwrap().is_some());
+    let remaining = session.remaining_lifetime().await.unwrap().unwrap();
+    assert!(remaining <= Duration::from_secs(3600));
+    assert!(remaining > Duration::from_secs(3500));
+}
+
+#[tokio::test]
+async fn sessions_expire_after_being_idle_for_too_long() {
+    let (store, mut config) = (store(), SessionConfig::default());
+    config.state.idle_timeout = Some(Duration::from_secs(15 * 60));
+
+    let idle = fixture(3600, 20 * 60);
+    let incoming = idle.setup(&store).await;
+    let mut session = Session::new(&store, &config, Some(incoming));
+    session.force_load().await.unwrap();
+    assert!(session.is_invalidated());
+    let cookie = session.finalize().await.unwrap().unwrap();
+    assert_that!(cookie, is_removal_cookie());
+
+    // No maximum lifetime was configured.
+    let session = Session::new(&store, &config, None);
+    assert_eq!(session.remaining_lifetime().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn activity_pushes_back_the_idle_timeout() {
+    let (store, mut config) = (store(), SessionConfig::default());
+    config.state.idle_timeout = Some(Duration::from_secs(15 * 60));
+
+    let active = fixture(3600, 5 * 60);
+    let incoming = active.setup(&store).await;
+    let mut session = Session::new(&store, &config, Some(incoming));
+    session.force_load().await.unwrap();
+    assert!(!session.is_invalidated());
+    let cookie = session.finalize().await.unwrap().unwrap();
+    assert_that!(cookie, not(is_removal_cookie()));
+
+    let record = store.load(&active.id).await.unwrap().unwrap();
+    let last_active = record.state[TIMESTAMPS_KEY]["last_active"]
+        .as_u64()
+        .unwrap();
+    assert!(last_active >= now() - 1);
+    assert!(record.ttl <= Duration::from_secs(15 * 60));
+}
+
+#[tokio::test]
+async fn ttl_extensions_never_outlive_the_idle_timeout() {
+    let (store, mut config) = (store(), SessionConfig::default());
+    config.state.idle_timeout = Some(Duration::from_secs(15 * 60));
+    // Always extend the TTL.
+    config.state.ttl_extension_threshold = None;
+
+    // Recent enough activity: the last activity timestamp isn't refreshed.
+    let active = fixture(3600, 60);
+    let incoming = active.setup(&store).await;
+    let mut session = Session::new(&store, &config, Some(incoming));
+    session.force_load().await.unwrap();
+    session.finalize().await.unwrap();
+
+    let record = store.load(&active.id).await.unwrap().unwrap();
+    assert!(record.ttl <= Duration::from_secs(14 * 60));
+}
+
+#[tokio::test]
+async fn delete_expired_removes_records_past_their_maximum_lifetime() {
+    let (store, mut config) = (store(), SessionConfig::default());
+    config.state.max_lifetime = Some(Duration::from_secs(3600));
+    // Always extend the TTL.
+    config.state.ttl_extension_threshold = None;
+
+    // The session is about to reach its maximum lifetime.
+    let expiring = fixture(3599, 0);
+    let incoming = expiring.setup(&store).await;
+    let mut session = Session::new(&store, &config, Some(incoming));
+    session.force_load().await.unwrap();
+    assert!(!session.is_invalidated());
+    session.finalize().await.unwrap();
+
+    // A session without lifetime limits, which must be left alone.
+    let unlimited = SessionFixture::default();
+    unlimited.setup(&store).await;
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert_eq!(store.delete_expired(None).await.unwrap(), 1);
+    assert!(store.load(&expiring.id).await.unwrap().is_none());
+    assert!(store.load(&unlimited.id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn pre_existing_sessions_start_their_clock_when_limits_are_enabled() {
+    let (store, mut config) = (store(), SessionConfig::default());
+    config.state.max_lifetime = Some(Duration::from_secs(3600));
+
+    let fixture = SessionFixture::default();
+    let incoming = fixture.setup(&store).await;
+    let mut session = Session::new(&store, &config, Some(incoming));
+    session.force_load().await.unwrap();
+    assert!(!session.is_invalidated());
+    session.finalize().await.unwrap();
+
+    let record = store.load(&fixture.id).await.unwrap().unwrap();
+    assert!(record.state.contains_key(TIMESTAMPS_KEY));
+}
@@ -14,10 +14,17 @@ use pavex_session::{
 };
 
 mod assertions;
+mod binding;
+mod cache;
 mod config;
+mod encryption;
 mod fixtures;
+mod flash;
 mod helpers;
+mod lifetime;
 mod operations;
+mod principal;
+mod transport;
 
 #[tokio::test]
 async fn id_can_be_cycled_for_a_fresh_session() {
@@ -1,5 +1,7 @@
 //! Tests looking at the interaction between different operations on the session state.
-use crate::fixtures::store;
+use std::collections::HashMap;
+
+use crate::fixtures::{SessionFixture, store};
 use googletest::{
     assert_that,
     prelude::{eq, none},
@@ -354,3 +356,26 @@ async fn client_get_methods_on_mut_and_non_mut_are_equivalent() {
     assert_that!(session.client_mut().get_raw(&key), none());
     assert!(session.client_mut().is_empty());
 }
+
+#[tokio::test]
+async fn removing_a_key_from_an_unchanged_server_state_is_persisted() {
+    let (store, config) = (store(), SessionConfig::default());
+    let fixture = SessionFixture {
+        server_state: Some(HashMap::from([
+            ("key".to_owned(), "value".into()),
+            ("other".to_owned(), "value".into()),
+        ])),
+        ..Default::default()
+    };
+    let incoming = fixture.setup(&store).await;
+
+    let mut session = Session::new(&store, &config, Some(incoming));
+    // The state is loaded, but not modified.
+    assert!(session.get_raw("key").await.unwrap().is_some());
+    assert!(session.remove_raw("key").await.unwrap().is_some());
+    session.finalize().await.unwrap();
+
+    let record = store.load(&fixture.id).await.unwrap().unwrap();
+    assert_that!(record.state.get("key"), none());
+    assert!(record.state.contains_key("other"));
+}
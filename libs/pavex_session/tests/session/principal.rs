@@ -0,0 +1,77 @@
+use googletest::{
+    assert_that,
+    prelude::{eq, none, some, unordered_elements_are},
+};
+use pavex_session::{IncomingSession, Session, SessionConfig, SessionStore};
+
+use crate::{
+    fixtures::{SessionFixture, store},
+    helpers::SetCookie,
+};
+
+/// `SessionId` doesn't implement `Debug`, so we compare the underlying UUIDs.
+async fn list(store: &SessionStore, principal: &str) -> Vec<uuid::Uuid> {
+    let ids = store.list_by_principal(principal).await.unwrap();
+    ids.iter().map(|id| id.inner()).collect()
+}
+
+#[tokio::test]
+async fn sessions_can_be_listed_and_revoked_by_principal() {
+    let (store, config) = (store(), SessionConfig::default());
+
+    let mut ids = Vec::new();
+    for principal in ["alice", "alice", "bob"] {
+        let mut session = Session::new(&store, &config, None);
+        session.bind_principal(principal).await.unwrap();
+        assert_that!(session.principal().await.unwrap(), some(eq(principal)));
+        let cookie = session.finalize().await.unwrap().unwrap();
+        ids.push(SetCookie::parse(cookie).id());
+    }
+
+    assert_that!(
+        list(&store, "alice").await,
+        unordered_elements_are![eq(&ids[0]), eq(&ids[1])]
+    );
+
+    let n_deleted = store.delete_by_principal("alice").await.unwrap();
+    assert_eq!(n_deleted, 2);
+    assert!(list(&store, "alice").await.is_empty());
+    // Other principals are left untouched.
+    assert_eq!(list(&store, "bob").await, vec![ids[2]]);
+}
+
+#[tokio::test]
+async fn principal_can_be_unbound() {
+    let (store, config) = (store(), SessionConfig::default());
+    let fixture = SessionFixture::default();
+    let incoming = fixture.setup(&store).await;
+
+    let mut session = Session::new(&store, &config, Some(incoming));
+    assert_that!(session.principal().await.unwrap(), none());
+    session.bind_principal("alice").await.unwrap();
+    session.finalize().await.unwrap();
+    assert_eq!(list(&store, "alice").await, vec![fixture.id()]);
+
+    let incoming = IncomingSession::from_parts(fixture.id, Default::default());
+    let mut session = Session::new(&store, &config, Some(incoming));
+    session.unbind_principal().await.unwrap();
+    assert_that!(session.principal().await.unwrap(), none());
+    session.finalize().await.unwrap();
+    assert!(store.list_by_principal("alice").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn expired_sessions_are_not_listed() {
+    let (store, config) = (store(), SessionConfig::default());
+    let mut session = Session::new(&store, &config, None);
+    session.bind_principal("alice").await.unwrap();
+    let cookie = session.finalize().await.unwrap().unwrap();
+    let id = SetCookie::parse(cookie).id;
+    store
+        .update_ttl(&id, std::time::Duration::ZERO)
+        .await
+        .unwrap();
+
+    assert!(store.list_by_principal("alice").await.unwrap().is_empty());
+    assert_eq!(store.delete_by_principal("alice").await.unwrap(), 0);
+}
@@ -0,0 +1,222 @@
+//! Verify that sessions can be exchanged via headers rather than cookies.
+use pavex::{
+    cookie::{Key, RequestCookies, ResponseCookies},
+    http::{HeaderMap, HeaderValue, Method, Version, header::AUTHORIZATION},
+    request::RequestHead,
+    response::Response,
+};
+use pavex_session::{
+    IncomingSession, Session, SessionConfig, SessionTransport, errors::FinalizeError,
+    finalize_session,
+};
+
+use crate::{
+    fixtures::{SessionFixture, store},
+    helpers::SetCookie,
+};
+
+fn request_head(name: &str, value: &str) -> RequestHead {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        pavex::http::HeaderName::try_from(name).unwrap(),
+        HeaderValue::try_from(value).unwrap(),
+    );
+    RequestHead {
+        method: Method::GET,
+        target: "/".parse().unwrap(),
+        version: Version::HTTP_11,
+        headers,
+    }
+}
+
+/// The default configuration, with a key to encrypt session tokens.
+fn config() -> SessionConfig {
+    let mut config = SessionConfig::default();
+    config.header.key = Some(Key::generate());
+    config
+}
+
+/// Set up an existing session and return its id alongside its token.
+async fn token(config: &SessionConfig) -> (uuid::Uuid, String) {
+    let store = store();
+    let fixture = SessionFixture::default();
+    let incoming = fixture.setup(&store).await;
+    let mut session = Session::new(&store, config, Some(incoming));
+    let value = session.finalize_header().await.unwrap().unwrap();
+    (fixture.id(), value.to_str().unwrap().to_owned())
+}
+
+#[tokio::test]
+async fn session_roundtrips_through_the_authorization_header() {
+    let (store, config) = (store(), config());
+    let mut session = Session::new(&store, &config, None);
+    session.client_mut().insert("key", "value").unwrap();
+    session.insert("server", "side").await.unwrap();
+    let value = session.finalize_header().await.unwrap().unwrap();
+
+    let head = request_head(
+        "authorization",
+        &format!("Bearer {}", value.to_str().unwrap()),
+    );
+    let incoming = IncomingSession::extract_from_header(&head, &config.header).unwrap();
+    let session = Session::new(&store, &config, Some(incoming));
+    assert_eq!(
+        session.client().get::<String>("key").unwrap().as_deref(),
+        Some("value")
+    );
+    assert_eq!(
+        session.get::<String>("server").await.unwrap().as_deref(),
+        Some("side")
+    );
+}
+
+#[tokio::test]
+async fn the_scheme_is_matched_case_insensitively() {
+    let config = config();
+    let (_, token) = token(&config).await;
+
+    let head = request_head("authorization", &format!("bearer {token}"));
+    assert!(IncomingSession::extract_from_header(&head, &config.header).is_some());
+
+    // A different scheme is ignored.
+    let head = request_head("authorization", &format!("Basic {token}"));
+    assert!(IncomingSession::extract_from_header(&head, &config.header).is_none());
+    // A missing scheme is ignored too.
+    let head = request_head("authorization", &token);
+    assert!(IncomingSession::extract_from_header(&head, &config.header).is_none());
+}
+
+#[tokio::test]
+async fn invalid_tokens_are_ignored() {
+    let config = config();
+    let head = request_head("authorization", "Bearer gibberish");
+    assert!(IncomingSession::extract_from_header(&head, &config.header).is_none());
+}
+
+#[tokio::test]
+async fn tampered_tokens_are_rejected() {
+    let config = config();
+    let (_, token) = token(&config).await;
+
+    // Flip a character in the middle of the token.
+    let mut tampered = token.into_bytes();
+    let i = tampered.len() / 2;
+    tampered[i] = if tampered[i] == b'A' { b'B' } else { b'A' };
+    let tampered = String::from_utf8(tampered).unwrap();
+
+    let head = request_head("authorization", &format!("Bearer {tampered}"));
+    assert!(IncomingSession::extract_from_header(&head, &config.header).is_none());
+}
+
+#[tokio::test]
+async fn tokens_are_only_accepted_if_encrypted_with_a_known_key() {
+    let old = config();
+    let (_, token) = token(&old).await;
+    let head = request_head("authorization", &format!("Bearer {token}"));
+
+    let mut rotated = config();
+    assert!(IncomingSession::extract_from_header(&head, &rotated.header).is_none());
+
+    // The old key is still accepted as a fallback.
+    rotated.header.fallback_keys = vec![old.header.key.clone().unwrap()];
+    assert!(IncomingSession::extract_from_header(&head, &rotated.header).is_some());
+}
+
+#[tokio::test]
+async fn tokens_cannot_be_issued_nor_accepted_without_a_key() {
+    let (_, token) = token(&config()).await;
+    let head = request_head("authorization", &format!("Bearer {token}"));
+    let (store, config) = (store(), SessionConfig::default());
+    assert!(IncomingSession::extract_from_header(&head, &config.header).is_none());
+
+    let mut session = Session::new(&store, &config, None);
+    session.insert("key", "value").await.unwrap();
+    let err = session.finalize_header().await.unwrap_err();
+    assert!(matches!(err, FinalizeError::MissingHeaderKey), "{err:?}");
+}
+
+#[tokio::test]
+async fn an_empty_header_is_sent_if_an_existing_session_is_invalidated() {
+    let (store, config) = (store(), config());
+    let incoming = SessionFixture::default().setup(&store).await;
+    let mut session = Session::new(&store, &config, Some(incoming));
+    session.invalidate();
+
+    let value = session.finalize_header().await.unwrap().unwrap();
+    assert!(value.is_empty());
+}
+
+#[tokio::test]
+async fn the_transport_determines_where_the_session_is_looked_up() {
+    let config = config();
+    let (id, token) = token(&config).await;
+    let head = request_head(AUTHORIZATION.as_str(), &format!("Bearer {token}"));
+    let cookies = RequestCookies::new();
+
+    let incoming = IncomingSession::extract_with_transport(
+        &SessionTransport::Header,
+        &cookies,
+        &head,
+        &config,
+    );
+    let store = store();
+    let mut session = Session::new(&store, &config, incoming);
+    let cookie = session.finalize().await.unwrap().unwrap();
+    assert_eq!(SetCookie::parse(cookie).id(), id);
+
+    // Cookies don't look at headers.
+    let incoming = IncomingSession::extract_with_transport(
+        &SessionTransport::Cookie,
+        &cookies,
+        &head,
+        &config,
+    );
+    assert!(incoming.is_none());
+}
+
+#[tokio::test]
+async fn finalizer_sets_the_response_header_instead_of_a_cookie() {
+    let (store, mut config) = (store(), config());
+    config.header.response_header = "x-my-session".into();
+    let mut session = Session::new(&store, &config, None);
+    session.insert("key", "value").await.unwrap();
+
+    let mut cookies = ResponseCookies::new();
+    let response = finalize_session(
+        Response::ok(),
+        &mut cookies,
+        &SessionTransport::Header,
+        &config,
+        session,
+    )
+    .await
+    .unwrap();
+
+    assert!(cookies.iter().next().is_none());
+    let token = response.headers().get("x-my-session").unwrap();
+    let head = request_head(
+        "authorization",
+        &format!("Bearer {}", token.to_str().unwrap()),
+    );
+    assert!(IncomingSession::extract_from_header(&head, &config.header).is_some());
+}
+
+#[tokio::test]
+async fn finalizer_fails_if_the_response_header_name_is_invalid() {
+    let (store, mut config) = (store(), config());
+    config.header.response_header = "not a header name".into();
+    let session = Session::new(&store, &config, None);
+
+    let outcome = finalize_session(
+        Response::ok(),
+        &mut ResponseCookies::new(),
+        &SessionTransport::Header,
+        &config,
+        session,
+    )
+    .await;
+    let Err(e) = outcome else {
+        panic!("Expected an error");
+    };
+    assert!(e.into_response().status().is_server_error());
+}
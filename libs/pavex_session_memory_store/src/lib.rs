@@ -6,10 +6,11 @@ use tokio::sync::{Mutex, MutexGuard};
 use pavex_session::{
     SessionId,
     store::{
-        SessionRecord, SessionRecordRef, SessionStorageBackend,
+        PRINCIPAL_KEY, SessionRecord, SessionRecordRef, SessionStorageBackend,
         errors::{
-            ChangeIdError, CreateError, DeleteError, DeleteExpiredError, DuplicateIdError,
-            LoadError, UnknownIdError, UpdateError, UpdateTtlError,
+            ChangeIdError, CreateError, DeleteByPrincipalError, DeleteError, DeleteExpiredError,
+            DuplicateIdError, ListByPrincipalError, LoadError, UnknownIdError, UpdateError,
+            UpdateTtlError,
         },
     },
 };
@@ -46,6 +47,10 @@ impl StoreRecord {
     fn is_stale(&self) -> bool {
         self.deadline <= Timestamp::now()
     }
+
+    fn is_bound_to(&self, principal: &str) -> bool {
+        self.state.get(PRINCIPAL_KEY).and_then(|p| p.as_str()) == Some(principal)
+    }
 }
 
 impl Default for InMemorySessionStore {
@@ -219,6 +224,34 @@ impl SessionStorageBackend for InMemorySessionStore {
         }
         Ok(num_deleted)
     }
+
+    /// List the identifiers of all live sessions bound to the given principal.
+    ///
+    /// It scans all records in the store.
+    #[tracing::instrument(name = "List session records by principal", level = tracing::Level::TRACE, skip_all)]
+    async fn list_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<Vec<SessionId>, ListByPrincipalError> {
+        let guard = self.0.lock().await;
+        let ids = guard
+            .iter()
+            .filter(|(_, record)| !record.is_stale() && record.is_bound_to(principal))
+            .map(|(id, _)| *id)
+            .collect();
+        Ok(ids)
+    }
+
+    /// Delete all live session records bound to the given principal.
+    ///
+    /// It scans all records in the store.
+    #[tracing::instrument(name = "Delete session records by principal", level = tracing::Level::TRACE, skip_all)]
+    async fn delete_by_principal(&self, principal: &str) -> Result<usize, DeleteByPrincipalError> {
+        let mut guard = self.0.lock().await;
+        let n_records = guard.len();
+        guard.retain(|_, record| record.is_stale() || !record.is_bound_to(principal));
+        Ok(n_records - guard.len())
+    }
 }
 
 mod kit {
@@ -258,17 +291,17 @@ mod kit {
     pub struct InMemorySessionKit {
         /// The constructor for [`Session`].
         ///
-        /// By default, it uses [`Session::new`].
+        /// By default, it uses [`Session::new_with_fingerprint`].
         ///
         /// [`Session`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html
-        /// [`Session::new`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html#method.new
+        /// [`Session::new_with_fingerprint`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html#method.new_with_fingerprint
         pub session: Option<Constructor>,
         /// The constructor for [`IncomingSession`].
         ///
-        /// By default, it uses [`IncomingSession::extract`].
+        /// By default, it uses [`IncomingSession::extract_with_transport`].
         ///
         /// [`IncomingSession`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html
-        /// [`IncomingSession::extract`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html#method.extract
+        /// [`IncomingSession::extract_with_transport`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html#method.extract_with_transport
         pub incoming_session: Option<Constructor>,
         /// Register [`SessionConfig`] as a configuration type.
         ///
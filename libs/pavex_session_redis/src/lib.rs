@@ -0,0 +1,454 @@
+#![deny(missing_docs)]
+//! A Redis-backed session store for [`pavex_session`](https://crates.io/crates/pavex_session).
+//!
+//! Check out [`RedisSessionStore`] for more details.
+use pavex::health::HealthCheck;
+use pavex_session::{
+    SessionId,
+    store::{
+        SessionRecord, SessionRecordRef, SessionStorageBackend,
+        errors::{
+            ChangeIdError, CreateError, DeleteError, DeleteExpiredError, DuplicateIdError,
+            LoadError, UnknownIdError, UpdateError, UpdateTtlError,
+        },
+    },
+};
+use redis::aio::ConnectionManager;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::time::Duration;
+
+pub use kit::{RedisSessionKit, RegisteredRedisSessionKit};
+pub use redis;
+
+#[derive(Clone)]
+/// A server-side session store using Redis as its backend.
+///
+/// # Implementation details
+///
+/// Each session record is stored as a string key, holding the JSON-serialized session state.
+/// Keys are prefixed with `pavex:session:` by default—use
+/// [`with_key_prefix`](Self::with_key_prefix) to customize it.
+///
+/// The session TTL is mapped to the expiration time of the key.
+/// Extending the TTL of a session is a single `PEXPIRE` command, and Redis takes care of
+/// removing expired sessions: [`delete_expired`](SessionStorageBackend::delete_expired)
+/// is a no-op.
+pub struct RedisSessionStore {
+    connection: ConnectionManager,
+    key_prefix: String,
+}
+
+impl std::fmt::Debug for RedisSessionStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisSessionStore")
+            .field("key_prefix", &self.key_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Rename the record, unless the new key is already taken.
+///
+/// Returns `-1` if the old key doesn't exist, `0` if the new key is already taken
+/// and `1` on success.
+const CHANGE_ID_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return -1
+end
+return redis.call('RENAMENX', KEYS[1], KEYS[2])
+";
+
+impl RedisSessionStore {
+    /// Creates a new Redis session store instance.
+    ///
+    /// It requires a connection to the Redis instance where the session records are stored.
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            key_prefix: "pavex:session:".into(),
+        }
+    }
+
+    /// Set the prefix used for the keys of session records.
+    ///
+    /// By default, it's `pavex:session:`.
+    pub fn with_key_prefix(mut self, key_prefix: impl Into<String>) -> Self {
+        self.key_prefix = key_prefix.into();
+        self
+    }
+
+    fn key(&self, id: &SessionId) -> String {
+        format!("{}{}", self.key_prefix, id.inner())
+    }
+
+    /// Redis rejects a zero expiration time when setting a key,
+    /// so we need to handle zero TTLs explicitly.
+    fn ttl_millis(ttl: Duration) -> Option<u64> {
+        let millis = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+        (millis > 0).then_some(millis)
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionStorageBackend for RedisSessionStore {
+    /// Creates a new session record in the store using the provided ID.
+    #[tracing::instrument(name = "Create server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn create(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), CreateError> {
+        let key = self.key(id);
+        let mut connection = self.connection.clone();
+        let Some(ttl) = Self::ttl_millis(record.ttl) else {
+            // The record would expire immediately: there is nothing to store,
+            // but we still have to detect duplicates.
+            let exists: bool = redis::cmd("EXISTS")
+                .arg(&key)
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| CreateError::Other(e.into()))?;
+            return if exists {
+                Err(DuplicateIdError { id: id.to_owned() }.into())
+            } else {
+                Ok(())
+            };
+        };
+        let state = serde_json::to_string(&record.state)?;
+        let outcome: Option<String> = redis::cmd("SET")
+            .arg(&key)
+            .arg(state)
+            .arg("PX")
+            .arg(ttl)
+            .arg("NX")
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| CreateError::Other(e.into()))?;
+        match outcome {
+            // All good, we created the session record.
+            Some(_) => Ok(()),
+            // `NX` prevented the write: the key is already taken.
+            None => Err(DuplicateIdError { id: id.to_owned() }.into()),
+        }
+    }
+
+    /// Update the state of an existing session in the store.
+    ///
+    /// It overwrites the existing record with the provided one.
+    #[tracing::instrument(name = "Update server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn update(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), UpdateError> {
+        let key = self.key(id);
+        let mut connection = self.connection.clone();
+        let Some(ttl) = Self::ttl_millis(record.ttl) else {
+            let deleted: u64 = redis::cmd("DEL")
+                .arg(&key)
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| UpdateError::Other(e.into()))?;
+            return as_unknown_id_error(deleted, id).map_err(Into::into);
+        };
+        let state = serde_json::to_string(&record.state)?;
+        let outcome: Option<String> = redis::cmd("SET")
+            .arg(&key)
+            .arg(state)
+            .arg("PX")
+            .arg(ttl)
+            .arg("XX")
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| UpdateError::Other(e.into()))?;
+        match outcome {
+            Some(_) => Ok(()),
+            // `XX` prevented the write: there is no live record with this ID.
+            None => Err(UnknownIdError { id: id.to_owned() }.into()),
+        }
+    }
+
+    /// Update the TTL of an existing session record in the store.
+    ///
+    /// It leaves the session state unchanged.
+    #[tracing::instrument(name = "Update TTL for server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn update_ttl(
+        &self,
+        id: &SessionId,
+        ttl: std::time::Duration,
+    ) -> Result<(), UpdateTtlError> {
+        let ttl = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+        let mut connection = self.connection.clone();
+        // A zero TTL deletes the key, which is exactly what we want.
+        let updated: u64 = redis::cmd("PEXPIRE")
+            .arg(self.key(id))
+            .arg(ttl)
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| UpdateTtlError::Other(e.into()))?;
+        as_unknown_id_error(updated, id).map_err(Into::into)
+    }
+
+    /// Loads an existing session record from the store using the provided ID.
+    ///
+    /// If a session with the given ID exists, it is returned. If the session
+    /// does not exist or has been invalidated (e.g., expired), `None` is
+    /// returned.
+    #[tracing::instrument(name = "Load server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn load(&self, session_id: &SessionId) -> Result<Option<SessionRecord>, LoadError> {
+        use anyhow::Context as _;
+
+        let key = self.key(session_id);
+        let mut connection = self.connection.clone();
+        let (state, ttl): (Option<String>, i64) = redis::pipe()
+            .atomic()
+            .get(&key)
+            .pttl(&key)
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| LoadError::Other(e.into()))?;
+        let Some(state) = state else {
+            return Ok(None);
+        };
+        let state = serde_json::from_str(&state)
+            .context("Failed to deserialize the retrieved session state")
+            .map_err(LoadError::DeserializationError)?;
+        Ok(Some(SessionRecord {
+            // A negative TTL means the key has no expiration time,
+            // which should not happen for keys managed by this store.
+            ttl: Duration::from_millis(ttl.try_into().unwrap_or(0)),
+            state,
+        }))
+    }
+
+    /// Deletes a session record from the store using the provided ID.
+    ///
+    /// If the session exists, it is removed from the store.
+    #[tracing::instrument(name = "Delete server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn delete(&self, id: &SessionId) -> Result<(), DeleteError> {
+        let mut connection = self.connection.clone();
+        let deleted: u64 = redis::cmd("DEL")
+            .arg(self.key(id))
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| DeleteError::Other(e.into()))?;
+        as_unknown_id_error(deleted, id).map_err(Into::into)
+    }
+
+    /// Change the session id associated with an existing session record.
+    ///
+    /// The server-side state and the TTL are left unchanged.
+    /// The operation is atomic.
+    #[tracing::instrument(name = "Change id for server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn change_id(&self, old_id: &SessionId, new_id: &SessionId) -> Result<(), ChangeIdError> {
+        let mut connection = self.connection.clone();
+        let outcome: i64 = redis::Script::new(CHANGE_ID_SCRIPT)
+            .key(self.key(old_id))
+            .key(self.key(new_id))
+            .invoke_async(&mut connection)
+            .await
+            .map_err(|e| ChangeIdError::Other(e.into()))?;
+        match outcome {
+            -1 => Err(UnknownIdError {
+                id: old_id.to_owned(),
+            }
+            .into()),
+            0 => Err(DuplicateIdError {
+                id: new_id.to_owned(),
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Redis removes expired keys on its own, so this is a no-op.
+    ///
+    /// It always returns `Ok(0)`.
+    async fn delete_expired(
+        &self,
+        _batch_size: Option<NonZeroUsize>,
+    ) -> Result<usize, DeleteExpiredError> {
+        Ok(0)
+    }
+}
+
+/// Ping Redis.
+///
+/// Register the store as a readiness check to fail the readiness probe
+/// when Redis can't be reached:
+///
+/// ```rust
+/// use pavex::health::HealthChecks;
+/// use pavex_session_redis::RedisSessionStore;
+///
+/// pub fn health_checks(store: &RedisSessionStore) -> HealthChecks {
+///     HealthChecks::new().register("redis", store.clone())
+/// }
+/// ```
+impl HealthCheck for RedisSessionStore {
+    fn check(&self) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send + '_>> {
+        Box::pin(async move {
+            let mut connection = self.connection.clone();
+            redis::cmd("PING")
+                .query_async::<()>(&mut connection)
+                .await?;
+            Ok(())
+        })
+    }
+}
+
+fn as_unknown_id_error(n_affected: u64, id: &SessionId) -> Result<(), UnknownIdError> {
+    if n_affected == 0 {
+        return Err(UnknownIdError { id: id.to_owned() });
+    }
+    Ok(())
+}
+
+mod kit {
+    use pavex::{
+        blueprint::{
+            Blueprint, config::ConfigType, constructor::Constructor, linter::Lint,
+            middleware::PostProcessingMiddleware,
+        },
+        f,
+    };
+
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    /// Components required to work with sessions using Redis
+    /// as the storage backend.
+    ///
+    /// # Guide
+    ///
+    /// Check out the [session installation](https://pavex.dev/guide/sessions/installation/)
+    /// section of Pavex's guide for a thorough introduction to sessions and how to
+    /// customize them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::blueprint::Blueprint;
+    /// use pavex::cookie::CookieKit;
+    /// use pavex_session_redis::RedisSessionKit;
+    ///
+    /// let mut bp = Blueprint::new();
+    /// RedisSessionKit::new().register(&mut bp);
+    /// // Sessions are built on top of cookies,
+    /// // so you need to set those up too.
+    /// // Order is important here!
+    /// CookieKit::new().register(&mut bp);
+    /// ```
+    ///
+    /// The default constructor for [`RedisSessionStore`] requires a
+    /// [`ConnectionManager`] as input: you need to register a constructor for it.
+    ///
+    /// [`RedisSessionStore`]: crate::RedisSessionStore
+    /// [`ConnectionManager`]: redis::aio::ConnectionManager
+    pub struct RedisSessionKit {
+        /// The constructor for [`Session`].
+        ///
+        /// By default, it uses [`Session::new_with_fingerprint`].
+        ///
+        /// [`Session`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html
+        /// [`Session::new_with_fingerprint`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html#method.new_with_fingerprint
+        pub session: Option<Constructor>,
+        /// The constructor for [`IncomingSession`].
+        ///
+        /// By default, it uses [`IncomingSession::extract_with_transport`].
+        ///
+        /// [`IncomingSession`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html
+        /// [`IncomingSession::extract_with_transport`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html#method.extract_with_transport
+        pub incoming_session: Option<Constructor>,
+        /// Register [`SessionConfig`] as a configuration type.
+        ///
+        /// By default, it uses `session` as configuration key.
+        ///
+        /// [`SessionConfig`]: https://pavex.dev/docs/api_reference/pavex_session/struct.SessionConfig.html
+        pub session_config: Option<ConfigType>,
+        /// The constructor for [`RedisSessionStore`].
+        ///
+        /// By default, it uses [`RedisSessionStore::new`].
+        ///
+        /// [`RedisSessionStore`]: crate::RedisSessionStore
+        /// [`RedisSessionStore::new`]: crate::RedisSessionStore::new
+        pub redis_session_store: Option<Constructor>,
+        /// The constructor for [`SessionStore`].
+        ///
+        /// By default, it uses [`SessionStore::new`] with [`RedisSessionStore`]
+        /// as its underlying storage backend.
+        ///
+        /// [`SessionStore`]: https://pavex.dev/docs/api_reference/pavex_session/struct.SessionStore.html
+        /// [`SessionStore::new`]: https://pavex.dev/docs/api_reference/pavex_session/struct.SessionStore.html#method.new
+        /// [`RedisSessionStore`]: crate::RedisSessionStore
+        pub session_store: Option<Constructor>,
+        /// A post-processing middleware to sync the session state with the session store
+        /// and inject the session cookie into the outgoing response via the `Set-Cookie` header.
+        ///
+        /// By default, it's set to [`finalize_session`].
+        /// The error is handled by [`FinalizeError::into_response`].
+        ///
+        /// [`FinalizeError::into_response`]: https://pavex.dev/docs/api_reference/pavex_session/errors/enum.FinalizeError.html#method.into_response
+        /// [`finalize_session`]: https://pavex.dev/docs/api_reference/pavex_session/fn.finalize_session.html
+        pub session_finalizer: Option<PostProcessingMiddleware>,
+    }
+
+    impl Default for RedisSessionKit {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl RedisSessionKit {
+        /// Create a new [`RedisSessionKit`] with all the bundled constructors and middlewares.
+        pub fn new() -> Self {
+            let pavex_session::SessionKit {
+                session,
+                session_config,
+                session_finalizer,
+                incoming_session,
+                ..
+            } = pavex_session::SessionKit::new();
+            Self {
+                session,
+                incoming_session,
+                session_config,
+                session_finalizer,
+                redis_session_store: Some(
+                    Constructor::singleton(f!(crate::RedisSessionStore::new)).ignore(Lint::Unused),
+                ),
+                session_store: Some(
+                    Constructor::singleton(f!(pavex_session::SessionStore::new::<
+                        crate::RedisSessionStore,
+                    >))
+                    .ignore(Lint::Unused),
+                ),
+            }
+        }
+
+        /// Register all the bundled constructors and middlewares with a [`Blueprint`].
+        ///
+        /// If a component is set to `None` it will not be registered.
+        pub fn register(self, bp: &mut Blueprint) -> RegisteredRedisSessionKit {
+            let mut kit = pavex_session::SessionKit::new();
+            kit.session = self.session;
+            kit.incoming_session = self.incoming_session;
+            kit.session_config = self.session_config;
+            kit.session_finalizer = self.session_finalizer;
+            kit.register(bp);
+            if let Some(redis_session_store) = self.redis_session_store {
+                redis_session_store.register(bp);
+            }
+            if let Some(session_store) = self.session_store {
+                session_store.register(bp);
+            }
+
+            RegisteredRedisSessionKit {}
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    /// The type returned by [`RedisSessionKit::register`].
+    pub struct RegisteredRedisSessionKit {}
+}
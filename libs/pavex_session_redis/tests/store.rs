@@ -0,0 +1,191 @@
+//! Tests run against a `redis-server` instance spawned for each test.
+//! They are ignored by default: run them with `cargo test -- --ignored`
+//! when `redis-server` is available in `PATH`.
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::time::Duration;
+
+use pavex_session::SessionId;
+use pavex_session::store::errors::{
+    ChangeIdError, CreateError, DeleteError, UpdateError, UpdateTtlError,
+};
+use pavex_session::store::{SessionRecordRef, SessionStorageBackend};
+use pavex_session_redis::RedisSessionStore;
+use serde_json::Value;
+use tokio::process::{Child, Command};
+
+const TTL: Duration = Duration::from_secs(60);
+
+/// A `redis-server` process, killed on drop.
+struct RedisServer {
+    _process: Child,
+    store: RedisSessionStore,
+}
+
+async fn redis() -> RedisServer {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let process = Command::new("redis-server")
+        .args([
+            "--port",
+            &port.to_string(),
+            "--save",
+            "",
+            "--appendonly",
+            "no",
+        ])
+        .stdout(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .expect("Failed to spawn `redis-server`. Is it installed and in `PATH`?");
+    let client = redis::Client::open(format!("redis://127.0.0.1:{port}")).unwrap();
+    for _ in 0..50 {
+        if let Ok(connection) = client.get_connection_manager().await {
+            return RedisServer {
+                _process: process,
+                store: RedisSessionStore::new(connection),
+            };
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("`redis-server` didn't start accepting connections in time");
+}
+
+fn state(value: &str) -> HashMap<Cow<'static, str>, Value> {
+    HashMap::from([("key".into(), Value::from(value))])
+}
+
+fn record<'a>(state: &'a HashMap<Cow<'static, str>, Value>, ttl: Duration) -> SessionRecordRef<'a> {
+    SessionRecordRef {
+        state: Cow::Borrowed(state),
+        ttl,
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires `redis-server`"]
+async fn create_and_load() {
+    let server = redis().await;
+    let store = &server.store;
+    let id = SessionId::random();
+    let state = state("value");
+    store.create(&id, record(&state, TTL)).await.unwrap();
+
+    let loaded = store.load(&id).await.unwrap().unwrap();
+    assert_eq!(loaded.state, state);
+    assert!(loaded.ttl <= TTL);
+    assert!(loaded.ttl > TTL - Duration::from_secs(10));
+
+    assert!(store.load(&SessionId::random()).await.unwrap().is_none());
+
+    let err = store.create(&id, record(&state, TTL)).await.unwrap_err();
+    assert!(matches!(err, CreateError::DuplicateId(_)), "{err:?}");
+}
+
+#[tokio::test]
+#[ignore = "requires `redis-server`"]
+async fn update_and_update_ttl() {
+    let server = redis().await;
+    let store = &server.store;
+    let id = SessionId::random();
+    store
+        .create(&id, record(&state("old"), Duration::from_secs(30)))
+        .await
+        .unwrap();
+
+    let new_state = state("new");
+    store.update(&id, record(&new_state, TTL)).await.unwrap();
+    store
+        .update_ttl(&id, Duration::from_secs(3600))
+        .await
+        .unwrap();
+    let loaded = store.load(&id).await.unwrap().unwrap();
+    assert_eq!(loaded.state, new_state);
+    assert!(loaded.ttl > TTL);
+
+    let unknown = SessionId::random();
+    let err = store
+        .update(&unknown, record(&new_state, TTL))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, UpdateError::UnknownIdError(_)), "{err:?}");
+    let err = store.update_ttl(&unknown, TTL).await.unwrap_err();
+    assert!(matches!(err, UpdateTtlError::UnknownId(_)), "{err:?}");
+}
+
+#[tokio::test]
+#[ignore = "requires `redis-server`"]
+async fn delete() {
+    let server = redis().await;
+    let store = &server.store;
+    let id = SessionId::random();
+    store
+        .create(&id, record(&state("value"), TTL))
+        .await
+        .unwrap();
+
+    store.delete(&id).await.unwrap();
+    assert!(store.load(&id).await.unwrap().is_none());
+
+    let err = store.delete(&id).await.unwrap_err();
+    assert!(matches!(err, DeleteError::UnknownId(_)), "{err:?}");
+}
+
+#[tokio::test]
+#[ignore = "requires `redis-server`"]
+async fn change_id() {
+    let server = redis().await;
+    let store = &server.store;
+    let (old_id, new_id, other_id) = (
+        SessionId::random(),
+        SessionId::random(),
+        SessionId::random(),
+    );
+    let state = state("value");
+    store.create(&old_id, record(&state, TTL)).await.unwrap();
+    store.create(&other_id, record(&state, TTL)).await.unwrap();
+
+    store.change_id(&old_id, &new_id).await.unwrap();
+    assert!(store.load(&old_id).await.unwrap().is_none());
+    let loaded = store.load(&new_id).await.unwrap().unwrap();
+    assert_eq!(loaded.state, state);
+    // The TTL is preserved.
+    assert!(loaded.ttl > TTL - Duration::from_secs(10));
+
+    let err = store.change_id(&new_id, &other_id).await.unwrap_err();
+    assert!(matches!(err, ChangeIdError::DuplicateId(_)), "{err:?}");
+
+    let err = store
+        .change_id(&old_id, &SessionId::random())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ChangeIdError::UnknownId(_)), "{err:?}");
+}
+
+#[tokio::test]
+#[ignore = "requires `redis-server`"]
+async fn expired_records() {
+    let server = redis().await;
+    let store = &server.store;
+    let id = SessionId::random();
+    let state = state("value");
+    store
+        .create(&id, record(&state, Duration::from_millis(50)))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Redis removes expired keys on its own...
+    assert!(store.load(&id).await.unwrap().is_none());
+    // ...so the ID can be reused straight away.
+    store.create(&id, record(&state, TTL)).await.unwrap();
+    // A zero TTL expires the record immediately.
+    store.update_ttl(&id, Duration::ZERO).await.unwrap();
+    assert!(store.load(&id).await.unwrap().is_none());
+
+    assert_eq!(store.delete_expired(None).await.unwrap(), 0);
+}
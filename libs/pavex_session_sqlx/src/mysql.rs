@@ -0,0 +1,486 @@
+//! Types related to [`MySqlSessionStore`].
+use pavex::blueprint::constructor::Constructor;
+use pavex::blueprint::linter::Lint;
+use pavex::blueprint::middleware::PostProcessingMiddleware;
+use pavex::blueprint::{Blueprint, config::ConfigType};
+use pavex::f;
+use pavex::health::HealthCheck;
+use pavex::time::Timestamp;
+use pavex_session::{
+    SessionId,
+    store::{
+        SessionRecord, SessionRecordRef, SessionStorageBackend,
+        errors::{
+            ChangeIdError, CreateError, DeleteError, DeleteExpiredError, DuplicateIdError,
+            LoadError, UnknownIdError, UpdateError, UpdateTtlError,
+        },
+    },
+};
+use sqlx::{MySqlPool, mysql::MySqlQueryResult, types::Json};
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+
+#[derive(Debug, Clone)]
+/// A server-side session store using MySQL as its backend.
+///
+/// # Implementation details
+///
+/// This store uses `sqlx` to interact with MySQL.
+/// All session records are stored in a single table. You can use
+/// [`migrate`](Self::migrate) to create the table and index
+/// required by the store in the database.
+/// Alternatively, you can use [`migration_query`](Self::migration_query)
+/// to get the SQL query that creates the table and index in order to run it yourself
+/// (e.g. as part of your database migration scripts).
+///
+/// Session deadlines are stored as UNIX timestamps, in milliseconds.
+/// They are compared against the clock of the application server, rather than
+/// the clock of the database.
+pub struct MySqlSessionStore(sqlx::MySqlPool);
+
+impl MySqlSessionStore {
+    /// Creates a new MySQL session store instance.
+    ///
+    /// It requires a pool of MySQL connections to interact with the database
+    /// where the session records are stored.
+    pub fn new(pool: MySqlPool) -> Self {
+        Self(pool)
+    }
+
+    /// Return the query used to create the sessions table and index.
+    ///
+    /// # Implementation details
+    ///
+    /// The query is designed to be idempotent, meaning it can be run multiple times
+    /// without causing any issues. If the table and index already exist, the query
+    /// does nothing.
+    ///
+    /// # Alternatives
+    ///
+    /// You can use this method to add the query to your database migration scripts.
+    /// Alternatively, you can use [`migrate`](Self::migrate)
+    /// to run the query directly on the database.
+    pub fn migration_query() -> &'static str {
+        "-- Create the sessions table if it doesn’t exist
+-- alongside the index on the deadline column
+CREATE TABLE IF NOT EXISTS sessions (
+    id BINARY(16) PRIMARY KEY,
+    deadline BIGINT NOT NULL,
+    state JSON NOT NULL,
+    INDEX idx_sessions_deadline (deadline)
+);"
+    }
+
+    /// Create the sessions table and index in the database.
+    ///
+    /// This method is idempotent, meaning it can be called multiple times without
+    /// causing any issues. If the table and index already exist, this method does nothing.
+    ///
+    /// If you prefer to run the query yourself, rely on [`migration_query`](Self::migration_query)
+    /// to get the SQL that's being executed.
+    pub async fn migrate(&self) -> Result<(), sqlx::Error> {
+        use sqlx::Executor as _;
+
+        self.0.execute(Self::migration_query()).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionStorageBackend for MySqlSessionStore {
+    /// Creates a new session record in the store using the provided ID.
+    #[tracing::instrument(name = "Create server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn create(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), CreateError> {
+        let now = Timestamp::now();
+        let deadline = now + record.ttl;
+        let state = serde_json::to_value(record.state)?;
+        let mut transaction = self
+            .0
+            .begin()
+            .await
+            .map_err(|e| CreateError::Other(e.into()))?;
+        // An expired record with the same ID is overwritten.
+        // `ON DUPLICATE KEY UPDATE` can't tell us whether the existing record was live,
+        // so we remove the expired one (if any) before inserting the new one.
+        sqlx::query("DELETE FROM sessions WHERE id = ? AND deadline <= ?")
+            .bind(id.inner())
+            .bind(now.as_millisecond())
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| CreateError::Other(e.into()))?;
+        let query = sqlx::query(
+            "INSERT INTO sessions (id, deadline, state) \
+            VALUES (?, ?, ?)",
+        )
+        .bind(id.inner())
+        .bind(deadline.as_millisecond())
+        .bind(Json(state));
+
+        match query.execute(&mut *transaction).await {
+            // All good, we created the session record.
+            Ok(_) => transaction
+                .commit()
+                .await
+                .map_err(|e| CreateError::Other(e.into())),
+            Err(e) => {
+                // Return the specialized error variant if the ID is already in use
+                if let Err(e) = as_duplicated_id_error(&e, id) {
+                    Err(e.into())
+                } else {
+                    Err(CreateError::Other(e.into()))
+                }
+            }
+        }
+    }
+
+    /// Update the state of an existing session in the store.
+    ///
+    /// It overwrites the existing record with the provided one.
+    #[tracing::instrument(name = "Update server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn update(
+        &self,
+        id: &SessionId,
+        record: SessionRecordRef<'_>,
+    ) -> Result<(), UpdateError> {
+        let now = Timestamp::now();
+        let new_deadline = now + record.ttl;
+        let new_state = serde_json::to_value(record.state)?;
+        let query = sqlx::query(
+            "UPDATE sessions \
+            SET deadline = ?, state = ? \
+            WHERE id = ? AND deadline > ?",
+        )
+        .bind(new_deadline.as_millisecond())
+        .bind(Json(new_state))
+        .bind(id.inner())
+        .bind(now.as_millisecond());
+
+        match query.execute(&self.0).await {
+            Ok(r) => as_unknown_id_error(&r, id).map_err(Into::into),
+            Err(e) => Err(UpdateError::Other(e.into())),
+        }
+    }
+
+    /// Update the TTL of an existing session record in the store.
+    ///
+    /// It leaves the session state unchanged.
+    #[tracing::instrument(name = "Update TTL for server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn update_ttl(
+        &self,
+        id: &SessionId,
+        ttl: std::time::Duration,
+    ) -> Result<(), UpdateTtlError> {
+        let now = Timestamp::now();
+        let new_deadline = now + ttl;
+        let query = sqlx::query(
+            "UPDATE sessions \
+            SET deadline = ? \
+            WHERE id = ? AND deadline > ?",
+        )
+        .bind(new_deadline.as_millisecond())
+        .bind(id.inner())
+        .bind(now.as_millisecond());
+        match query.execute(&self.0).await {
+            Ok(r) => as_unknown_id_error(&r, id).map_err(Into::into),
+            Err(e) => Err(UpdateTtlError::Other(e.into())),
+        }
+    }
+
+    /// Loads an existing session record from the store using the provided ID.
+    ///
+    /// If a session with the given ID exists, it is returned. If the session
+    /// does not exist or has been invalidated (e.g., expired), `None` is
+    /// returned.
+    #[tracing::instrument(name = "Load server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn load(&self, session_id: &SessionId) -> Result<Option<SessionRecord>, LoadError> {
+        let now = Timestamp::now().as_millisecond();
+        let row = sqlx::query(
+            "SELECT deadline, state \
+            FROM sessions \
+            WHERE id = ? AND deadline > ?",
+        )
+        .bind(session_id.inner())
+        .bind(now)
+        .fetch_optional(&self.0)
+        .await
+        .map_err(|e| LoadError::Other(e.into()))?;
+        row.map(|r| {
+            use anyhow::Context as _;
+            use sqlx::Row as _;
+
+            let deadline: i64 = r
+                .try_get(0)
+                .context("Failed to deserialize the retrieved session deadline")
+                .map_err(LoadError::DeserializationError)?;
+            let Json(state): Json<serde_json::Value> = r
+                .try_get(1)
+                .context("Failed to deserialize the retrieved session state")
+                .map_err(LoadError::DeserializationError)?;
+            let ttl = deadline.saturating_sub(now);
+            Ok(SessionRecord {
+                // The deadline is in the future, so the TTL can't be negative.
+                ttl: std::time::Duration::from_millis(ttl.try_into().unwrap_or(0)),
+                state: serde_json::from_value(state)
+                    .context("Failed to deserialize the retrieved session state")
+                    .map_err(LoadError::DeserializationError)?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Deletes a session record from the store using the provided ID.
+    ///
+    /// If the session exists, it is removed from the store.
+    #[tracing::instrument(name = "Delete server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn delete(&self, id: &SessionId) -> Result<(), DeleteError> {
+        let query = sqlx::query(
+            "DELETE FROM sessions \
+            WHERE id = ? AND deadline > ?",
+        )
+        .bind(id.inner())
+        .bind(Timestamp::now().as_millisecond());
+        match query.execute(&self.0).await {
+            Ok(r) => as_unknown_id_error(&r, id).map_err(Into::into),
+            Err(e) => Err(DeleteError::Other(e.into())),
+        }
+    }
+
+    /// Change the session id associated with an existing session record.
+    ///
+    /// The server-side state is left unchanged.
+    #[tracing::instrument(name = "Change id for server-side session record", level = tracing::Level::INFO, skip_all)]
+    async fn change_id(&self, old_id: &SessionId, new_id: &SessionId) -> Result<(), ChangeIdError> {
+        let query = sqlx::query(
+            "UPDATE sessions \
+            SET id = ? \
+            WHERE id = ? AND deadline > ?",
+        )
+        .bind(new_id.inner())
+        .bind(old_id.inner())
+        .bind(Timestamp::now().as_millisecond());
+        match query.execute(&self.0).await {
+            Ok(r) => as_unknown_id_error(&r, old_id).map_err(Into::into),
+            Err(e) => {
+                if let Err(e) = as_duplicated_id_error(&e, new_id) {
+                    Err(e.into())
+                } else {
+                    Err(ChangeIdError::Other(e.into()))
+                }
+            }
+        }
+    }
+
+    /// Delete expired sessions from the database.
+    ///
+    /// If `batch_size` is provided, the query will delete at most `batch_size` expired sessions.
+    /// In either case, if successful, the method returns the number of expired sessions that
+    /// have been deleted.
+    ///
+    /// # When should you delete in batches?
+    ///
+    /// If there are a lot of expired sessions in the database, deleting them all at once can
+    /// cause performance issues. By deleting in batches, you can limit the number of sessions
+    /// deleted in a single query, reducing the impact.
+    async fn delete_expired(
+        &self,
+        batch_size: Option<NonZeroUsize>,
+    ) -> Result<usize, DeleteExpiredError> {
+        let now = Timestamp::now().as_millisecond();
+        let query = if let Some(batch_size) = batch_size {
+            let batch_size: i64 = batch_size.get().try_into().unwrap_or(i64::MAX);
+            sqlx::query("DELETE FROM sessions WHERE deadline <= ? LIMIT ?")
+                .bind(now)
+                .bind(batch_size)
+        } else {
+            sqlx::query("DELETE FROM sessions WHERE deadline <= ?").bind(now)
+        };
+        let r = query.execute(&self.0).await.map_err(|e| {
+            let e: anyhow::Error = e.into();
+            e
+        })?;
+        Ok(r.rows_affected().try_into().unwrap_or(usize::MAX))
+    }
+}
+
+/// Ping the database.
+///
+/// Register the store as a readiness check to fail the readiness probe
+/// when MySQL can't be reached:
+///
+/// ```rust
+/// use pavex::health::HealthChecks;
+/// use pavex_session_sqlx::MySqlSessionStore;
+///
+/// pub fn health_checks(store: &MySqlSessionStore) -> HealthChecks {
+///     HealthChecks::new().register("mysql", store.clone())
+/// }
+/// ```
+impl HealthCheck for MySqlSessionStore {
+    fn check(&self) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send + '_>> {
+        Box::pin(async move {
+            sqlx::query("SELECT 1").execute(&self.0).await?;
+            Ok(())
+        })
+    }
+}
+
+fn as_duplicated_id_error(e: &sqlx::Error, id: &SessionId) -> Result<(), DuplicateIdError> {
+    if let Some(e) = e.as_database_error() {
+        // The session ID is the only unique column in the table.
+        if e.is_unique_violation() {
+            return Err(DuplicateIdError { id: id.to_owned() });
+        }
+    }
+    Ok(())
+}
+
+fn as_unknown_id_error(r: &MySqlQueryResult, id: &SessionId) -> Result<(), UnknownIdError> {
+    // Check if the session record was changed
+    if r.rows_affected() == 0 {
+        return Err(UnknownIdError { id: id.to_owned() });
+    }
+    // Sanity check
+    assert_eq!(
+        r.rows_affected(),
+        1,
+        "More than one session record was affected, even though the session ID is used as primary key. Something is deeply wrong here!"
+    );
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// Components required to work with sessions using MySQL as
+/// the storage backend.
+///
+/// # Guide
+///
+/// Check out the [session installation](https://pavex.dev/guide/sessions/installation/)
+/// section of Pavex's guide for a thorough introduction to sessions and how to
+/// customize them.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::cookie::CookieKit;
+/// use pavex_session_sqlx::MySqlSessionKit;
+///
+/// let mut bp = Blueprint::new();
+/// MySqlSessionKit::new().register(&mut bp);
+/// // Sessions are built on top of cookies,
+/// // so you need to set those up too.
+/// // Order is important here!
+/// CookieKit::new().register(&mut bp);
+/// ```
+pub struct MySqlSessionKit {
+    /// The constructor for [`Session`].
+    ///
+    /// By default, it uses [`Session::new_with_fingerprint`].
+    ///
+    /// [`Session`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html
+    /// [`Session::new_with_fingerprint`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html#method.new_with_fingerprint
+    pub session: Option<Constructor>,
+    /// The constructor for [`IncomingSession`].
+    ///
+    /// By default, it uses [`IncomingSession::extract_with_transport`].
+    ///
+    /// [`IncomingSession`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html
+    /// [`IncomingSession::extract_with_transport`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html#method.extract_with_transport
+    pub incoming_session: Option<Constructor>,
+    /// Register [`SessionConfig`] as a configuration type.
+    ///
+    /// By default, it uses `session` as configuration key.
+    ///
+    /// [`SessionConfig`]: https://pavex.dev/docs/api_reference/pavex_session/struct.SessionConfig.html
+    pub session_config: Option<ConfigType>,
+    /// The constructor for [`MySqlSessionStore`].
+    ///
+    /// By default, it uses [`MySqlSessionStore::new`].
+    ///
+    /// [`MySqlSessionStore`]: crate::MySqlSessionStore
+    /// [`MySqlSessionStore::new`]: crate::MySqlSessionStore::new
+    pub mysql_session_store: Option<Constructor>,
+    /// The constructor for [`SessionStore`].
+    ///
+    /// By default, it uses [`SessionStore::new`] with [`MySqlSessionStore`]
+    /// as its underlying storage backend.
+    ///
+    /// [`SessionStore`]: https://pavex.dev/docs/api_reference/pavex_session/struct.SessionStore.html
+    /// [`SessionStore::new`]: https://pavex.dev/docs/api_reference/pavex_session/struct.SessionStore.html#method.new
+    /// [`MySqlSessionStore`]: crate::MySqlSessionStore
+    pub session_store: Option<Constructor>,
+    /// A post-processing middleware to sync the session state with the session store
+    /// and inject the session cookie into the outgoing response via the `Set-Cookie` header.
+    ///
+    /// By default, it's set to [`finalize_session`].
+    /// The error is handled by [`FinalizeError::into_response`].
+    ///
+    /// [`FinalizeError::into_response`]: https://pavex.dev/docs/api_reference/pavex_session/errors/enum.FinalizeError.html#method.into_response
+    /// [`finalize_session`]: https://pavex.dev/docs/api_reference/pavex_session/fn.finalize_session.html
+    pub session_finalizer: Option<PostProcessingMiddleware>,
+}
+
+impl Default for MySqlSessionKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MySqlSessionKit {
+    /// Create a new [`MySqlSessionKit`] with all the bundled constructors and middlewares.
+    pub fn new() -> Self {
+        let pavex_session::SessionKit {
+            session,
+            session_config,
+            session_finalizer,
+            incoming_session,
+            ..
+        } = pavex_session::SessionKit::new();
+        Self {
+            session,
+            incoming_session,
+            session_config,
+            session_finalizer,
+            mysql_session_store: Some(
+                Constructor::singleton(f!(crate::MySqlSessionStore::new)).ignore(Lint::Unused),
+            ),
+            session_store: Some(
+                Constructor::singleton(f!(pavex_session::SessionStore::new::<
+                    crate::MySqlSessionStore,
+                >))
+                .ignore(Lint::Unused),
+            ),
+        }
+    }
+
+    /// Register all the bundled constructors and middlewares with a [`Blueprint`].
+    ///
+    /// If a component is set to `None` it will not be registered.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredMySqlSessionKit {
+        let mut kit = pavex_session::SessionKit::new();
+        kit.session = self.session;
+        kit.incoming_session = self.incoming_session;
+        kit.session_config = self.session_config;
+        kit.session_finalizer = self.session_finalizer;
+        kit.register(bp);
+        if let Some(mysql_session_store) = self.mysql_session_store {
+            mysql_session_store.register(bp);
+        }
+        if let Some(session_store) = self.session_store {
+            session_store.register(bp);
+        }
+
+        RegisteredMySqlSessionKit {}
+    }
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The type returned by [`MySqlSessionKit::register`].
+pub struct RegisteredMySqlSessionKit {}
@@ -5,14 +5,16 @@ use pavex::blueprint::linter::Lint;
 use pavex::blueprint::middleware::PostProcessingMiddleware;
 use pavex::blueprint::{Blueprint, config::ConfigType};
 use pavex::f;
+use pavex::health::HealthCheck;
 use pavex::time::Timestamp;
 use pavex_session::{
     SessionId,
     store::{
         SessionRecord, SessionRecordRef, SessionStorageBackend,
         errors::{
-            ChangeIdError, CreateError, DeleteError, DeleteExpiredError, DuplicateIdError,
-            LoadError, UnknownIdError, UpdateError, UpdateTtlError,
+            ChangeIdError, CreateError, DeleteByPrincipalError, DeleteError, DeleteExpiredError,
+            DuplicateIdError, ListByPrincipalError, LoadError, UnknownIdError, UpdateError,
+            UpdateTtlError,
         },
     },
 };
@@ -20,7 +22,9 @@ use sqlx::{
     PgPool,
     postgres::{PgDatabaseError, PgQueryResult},
 };
+use std::future::Future;
 use std::num::NonZeroUsize;
+use std::pin::Pin;
 
 #[derive(Debug, Clone)]
 /// A server-side session store using Postgres as its backend.
@@ -29,8 +33,13 @@ use std::num::NonZeroUsize;
 ///
 /// This store uses `sqlx` to interact with Postgres.
 /// All session records are stored in a single table. You can use
-/// [`migrate`](Self::migrate) to create the table and index
+/// [`migrate`](Self::migrate) to create the table and indexes
 /// required by the store in the database.
+///
+/// The [principal](pavex_session::Session::bind_principal) of each session is stored
+/// in a dedicated, indexed, column, to support
+/// [`list_by_principal`](SessionStorageBackend::list_by_principal) and
+/// [`delete_by_principal`](SessionStorageBackend::delete_by_principal).
 /// Alternatively, you can use [`migration_query`](Self::migration_query)
 /// to get the SQL query that creates the table and index in order to run it yourself
 /// (e.g. as part of your database migration scripts).
@@ -45,13 +54,15 @@ impl PostgresSessionStore {
         Self(pool)
     }
 
-    /// Return the query used to create the sessions table and index.
+    /// Return the query used to create the sessions table and indexes.
     ///
     /// # Implementation details
     ///
     /// The query is designed to be idempotent, meaning it can be run multiple times
-    /// without causing any issues. If the table and index already exist, the query
+    /// without causing any issues. If the table and indexes already exist, the query
     /// does nothing.
+    /// If the table was created by an older version of this crate, the query adds
+    /// the `principal` column and its index.
     ///
     /// # Alternatives
     ///
@@ -63,9 +74,13 @@ impl PostgresSessionStore {
 CREATE TABLE IF NOT EXISTS sessions (
     id UUID PRIMARY KEY,
     deadline TIMESTAMPTZ NOT NULL,
-    state JSONB NOT NULL
+    state JSONB NOT NULL,
+    principal TEXT
 );
 
+-- Add the principal column to tables created before it was introduced
+ALTER TABLE sessions ADD COLUMN IF NOT EXISTS principal TEXT;
+
 -- Create the index on the deadline column if it doesn’t exist
 DO $$
 BEGIN
@@ -77,13 +92,26 @@ BEGIN
     ) THEN
         CREATE INDEX idx_sessions_deadline ON sessions(deadline);
     END IF;
+END $$;
+
+-- Create the index on the principal column if it doesn’t exist
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_indexes
+        WHERE schemaname = current_schema()
+            AND tablename = 'sessions'
+            AND indexname = 'idx_sessions_principal'
+    ) THEN
+        CREATE INDEX idx_sessions_principal ON sessions(principal);
+    END IF;
 END $$;"
     }
 
-    /// Create the sessions table and index in the database.
+    /// Create the sessions table and indexes in the database.
     ///
     /// This method is idempotent, meaning it can be called multiple times without
-    /// causing any issues. If the table and index already exist, this method does nothing.
+    /// causing any issues. If the table and indexes already exist, this method does nothing.
     ///
     /// If you prefer to run the query yourself, rely on [`migration_query`](Self::migration_query)
     /// to get the SQL that's being executed.
@@ -105,19 +133,23 @@ impl SessionStorageBackend for PostgresSessionStore {
         record: SessionRecordRef<'_>,
     ) -> Result<(), CreateError> {
         let deadline = Timestamp::now() + record.ttl;
+        let principal = record.principal().map(ToOwned::to_owned);
         let state = serde_json::to_value(record.state)?;
         let query = sqlx::query(
-            "INSERT INTO sessions (id, deadline, state) \
-            VALUES ($1, $2, $3) \
+            "INSERT INTO sessions (id, deadline, state, principal) \
+            VALUES ($1, $2, $3, $4) \
             ON CONFLICT (id) DO UPDATE \
-            SET deadline = EXCLUDED.deadline, state = EXCLUDED.state \
+            SET deadline = EXCLUDED.deadline, state = EXCLUDED.state, principal = EXCLUDED.principal \
             WHERE sessions.deadline < (now() AT TIME ZONE 'UTC')",
         )
         .bind(id.inner())
         .bind(deadline.to_sqlx())
-        .bind(state);
+        .bind(state)
+        .bind(principal);
 
         match query.execute(&self.0).await {
+            // The ID belongs to a live session record: the `WHERE` clause prevented the update.
+            Ok(r) if r.rows_affected() == 0 => Err(DuplicateIdError { id: id.to_owned() }.into()),
             // All good, we created the session record.
             Ok(_) => Ok(()),
             Err(e) => {
@@ -141,14 +173,16 @@ impl SessionStorageBackend for PostgresSessionStore {
         record: SessionRecordRef<'_>,
     ) -> Result<(), UpdateError> {
         let new_deadline = Timestamp::now() + record.ttl;
+        let new_principal = record.principal().map(ToOwned::to_owned);
         let new_state = serde_json::to_value(record.state)?;
         let query = sqlx::query(
             "UPDATE sessions \
-            SET deadline = $1, state = $2 \
-            WHERE id = $3 AND deadline > (now() AT TIME ZONE 'UTC')",
+            SET deadline = $1, state = $2, principal = $3 \
+            WHERE id = $4 AND deadline > (now() AT TIME ZONE 'UTC')",
         )
         .bind(new_deadline.to_sqlx())
         .bind(new_state)
+        .bind(new_principal)
         .bind(id.inner());
 
         match query.execute(&self.0).await {
@@ -311,8 +345,12 @@ impl SessionStorageBackend for PostgresSessionStore {
     ) -> Result<usize, DeleteExpiredError> {
         let query = if let Some(batch_size) = batch_size {
             let batch_size: i64 = batch_size.get().try_into().unwrap_or(i64::MAX);
-            sqlx::query("DELETE FROM sessions WHERE deadline < (now() AT TIME ZONE 'UTC') LIMIT $1")
-                .bind(batch_size)
+            // Postgres doesn't support `DELETE ... LIMIT`, so we use a subquery instead.
+            sqlx::query(
+                "DELETE FROM sessions WHERE id IN \
+                (SELECT id FROM sessions WHERE deadline < (now() AT TIME ZONE 'UTC') LIMIT $1)",
+            )
+            .bind(batch_size)
         } else {
             sqlx::query("DELETE FROM sessions WHERE deadline < (now() AT TIME ZONE 'UTC')")
         };
@@ -322,6 +360,60 @@ impl SessionStorageBackend for PostgresSessionStore {
         })?;
         Ok(r.rows_affected().try_into().unwrap_or(usize::MAX))
     }
+
+    /// List the identifiers of all live sessions bound to the given principal.
+    #[tracing::instrument(name = "List server-side session records by principal", level = tracing::Level::INFO, skip_all)]
+    async fn list_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<Vec<SessionId>, ListByPrincipalError> {
+        let ids: Vec<sqlx::types::Uuid> = sqlx::query_scalar(
+            "SELECT id FROM sessions \
+            WHERE principal = $1 AND deadline > (now() AT TIME ZONE 'UTC')",
+        )
+        .bind(principal)
+        .fetch_all(&self.0)
+        .await
+        .map_err(|e| ListByPrincipalError::Other(e.into()))?;
+        Ok(ids.into_iter().map(SessionId::from_uuid).collect())
+    }
+
+    /// Delete all live session records bound to the given principal.
+    #[tracing::instrument(name = "Delete server-side session records by principal", level = tracing::Level::INFO, skip_all)]
+    async fn delete_by_principal(&self, principal: &str) -> Result<usize, DeleteByPrincipalError> {
+        let query = sqlx::query(
+            "DELETE FROM sessions \
+            WHERE principal = $1 AND deadline > (now() AT TIME ZONE 'UTC')",
+        )
+        .bind(principal);
+        let r = query
+            .execute(&self.0)
+            .await
+            .map_err(|e| DeleteByPrincipalError::Other(e.into()))?;
+        Ok(r.rows_affected().try_into().unwrap_or(usize::MAX))
+    }
+}
+
+/// Ping the database.
+///
+/// Register the store as a readiness check to fail the readiness probe
+/// when Postgres can't be reached:
+///
+/// ```rust
+/// use pavex::health::HealthChecks;
+/// use pavex_session_sqlx::PostgresSessionStore;
+///
+/// pub fn health_checks(store: &PostgresSessionStore) -> HealthChecks {
+///     HealthChecks::new().register("postgres", store.clone())
+/// }
+/// ```
+impl HealthCheck for PostgresSessionStore {
+    fn check(&self) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send + '_>> {
+        Box::pin(async move {
+            sqlx::query("SELECT 1").execute(&self.0).await?;
+            Ok(())
+        })
+    }
 }
 
 fn as_duplicated_id_error(e: &sqlx::Error, id: &SessionId) -> Result<(), DuplicateIdError> {
@@ -330,7 +422,8 @@ fn as_duplicated_id_error(e: &sqlx::Error, id: &SessionId) -> Result<(), Duplica
             // Check if the error is due to a duplicate ID
             // See https://www.postgresql.org/docs/current/errcodes-appendix.html
             // for the list of error codes for Postgres
-            if e.code() == "23505" && e.column() == Some("id") {
+            // The session ID is the only unique column in the table.
+            if e.code() == "23505" {
                 return Err(DuplicateIdError { id: id.to_owned() });
             }
         }
@@ -380,17 +473,17 @@ fn as_unknown_id_error(r: &PgQueryResult, id: &SessionId) -> Result<(), UnknownI
 pub struct PostgresSessionKit {
     /// The constructor for [`Session`].
     ///
-    /// By default, it uses [`Session::new`].
+    /// By default, it uses [`Session::new_with_fingerprint`].
     ///
     /// [`Session`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html
-    /// [`Session::new`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html#method.new
+    /// [`Session::new_with_fingerprint`]: https://pavex.dev/docs/api_reference/pavex_session/struct.Session.html#method.new_with_fingerprint
     pub session: Option<Constructor>,
     /// The constructor for [`IncomingSession`].
     ///
-    /// By default, it uses [`IncomingSession::extract`].
+    /// By default, it uses [`IncomingSession::extract_with_transport`].
     ///
     /// [`IncomingSession`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html
-    /// [`IncomingSession::extract`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html#method.extract
+    /// [`IncomingSession::extract_with_transport`]: https://pavex.dev/docs/api_reference/pavex_session/struct.IncomingSession.html#method.extract_with_transport
     pub incoming_session: Option<Constructor>,
     /// Register [`SessionConfig`] as a configuration type.
     ///
@@ -0,0 +1,378 @@
+//! The same test suite, run against every storage backend.
+//!
+//! SQLite runs in-process, against an in-memory database.
+//! Postgres and MySQL tests are ignored by default: run them with `cargo test -- --ignored`,
+//! providing a connection string via `PAVEX_SESSION_SQLX_POSTGRES_URL` and
+//! `PAVEX_SESSION_SQLX_MYSQL_URL`, respectively.
+use pavex_session_sqlx::{MySqlSessionStore, PostgresSessionStore, SqliteSessionStore};
+use sqlx::pool::PoolOptions;
+use tokio::sync::Mutex;
+
+async fn sqlite() -> SqliteSessionStore {
+    // Each connection to an in-memory database gets its own database.
+    let pool = PoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let store = SqliteSessionStore::new(pool);
+    store.migrate().await.unwrap();
+    store
+}
+
+async fn postgres() -> PostgresSessionStore {
+    // Run the migration only once per test binary, to avoid concurrent `CREATE TABLE`s.
+    static MIGRATED: Mutex<bool> = Mutex::const_new(false);
+
+    let url = database_url("PAVEX_SESSION_SQLX_POSTGRES_URL");
+    let store = PostgresSessionStore::new(sqlx::PgPool::connect(&url).await.unwrap());
+    let mut migrated = MIGRATED.lock().await;
+    if !*migrated {
+        store.migrate().await.unwrap();
+        *migrated = true;
+    }
+    store
+}
+
+async fn mysql() -> MySqlSessionStore {
+    // Run the migration only once per test binary, to avoid concurrent `CREATE TABLE`s.
+    static MIGRATED: Mutex<bool> = Mutex::const_new(false);
+
+    let url = database_url("PAVEX_SESSION_SQLX_MYSQL_URL");
+    let store = MySqlSessionStore::new(sqlx::MySqlPool::connect(&url).await.unwrap());
+    let mut migrated = MIGRATED.lock().await;
+    if !*migrated {
+        store.migrate().await.unwrap();
+        *migrated = true;
+    }
+    store
+}
+
+fn database_url(env_var: &str) -> String {
+    std::env::var(env_var)
+        .unwrap_or_else(|_| panic!("`{env_var}` must be set to run the tests for this backend"))
+}
+
+macro_rules! backend_suite {
+    ($backend:ident $(, #[$attr:meta])?) => {
+        mod $backend {
+            macro_rules! store {
+                () => {
+                    super::$backend().await
+                };
+            }
+
+            #[tokio::test]
+            $(#[$attr])?
+            async fn create_and_load() {
+                super::suite::create_and_load(&store!()).await
+            }
+
+            #[tokio::test]
+            $(#[$attr])?
+            async fn create_rejects_live_duplicates() {
+                super::suite::create_rejects_live_duplicates(&store!()).await
+            }
+
+            #[tokio::test]
+            $(#[$attr])?
+            async fn update() {
+                super::suite::update(&store!()).await
+            }
+
+            #[tokio::test]
+            $(#[$attr])?
+            async fn update_ttl() {
+                super::suite::update_ttl(&store!()).await
+            }
+
+            #[tokio::test]
+            $(#[$attr])?
+            async fn delete() {
+                super::suite::delete(&store!()).await
+            }
+
+            #[tokio::test]
+            $(#[$attr])?
+            async fn change_id() {
+                super::suite::change_id(&store!()).await
+            }
+
+            #[tokio::test]
+            $(#[$attr])?
+            async fn expired_records() {
+                super::suite::expired_records(&store!()).await
+            }
+        }
+    };
+}
+
+backend_suite!(sqlite);
+backend_suite!(postgres, #[ignore = "requires `PAVEX_SESSION_SQLX_POSTGRES_URL`"]);
+backend_suite!(mysql, #[ignore = "requires `PAVEX_SESSION_SQLX_MYSQL_URL`"]);
+
+#[tokio::test]
+#[ignore = "requires `PAVEX_SESSION_SQLX_POSTGRES_URL`"]
+async fn postgres_principal_index() {
+    suite::principal_index(&postgres().await).await
+}
+
+/// Postgres doesn't support `DELETE ... LIMIT`: batched deletions must go through a subquery.
+#[tokio::test]
+#[ignore = "requires `PAVEX_SESSION_SQLX_POSTGRES_URL`"]
+async fn postgres_delete_expired_in_batches() {
+    suite::delete_expired_in_batches(&postgres().await).await
+}
+
+#[tokio::test]
+async fn sqlite_does_not_index_principals() {
+    use pavex_session::store::SessionStorageBackend;
+    use pavex_session::store::errors::{DeleteByPrincipalError, ListByPrincipalError};
+
+    let store = sqlite().await;
+    let outcome = store.list_by_principal("alice").await;
+    assert!(matches!(outcome, Err(ListByPrincipalError::Unsupported)));
+    let err = store.delete_by_principal("alice").await.unwrap_err();
+    assert!(
+        matches!(err, DeleteByPrincipalError::Unsupported),
+        "{err:?}"
+    );
+}
+
+mod suite {
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use pavex_session::SessionId;
+    use pavex_session::store::errors::{
+        ChangeIdError, CreateError, DeleteError, UpdateError, UpdateTtlError,
+    };
+    use pavex_session::store::{PRINCIPAL_KEY, SessionRecordRef, SessionStorageBackend};
+    use serde_json::Value;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn state(value: &str) -> HashMap<Cow<'static, str>, Value> {
+        HashMap::from([("key".into(), Value::from(value))])
+    }
+
+    fn record<'a>(
+        state: &'a HashMap<Cow<'static, str>, Value>,
+        ttl: Duration,
+    ) -> SessionRecordRef<'a> {
+        SessionRecordRef {
+            state: Cow::Borrowed(state),
+            ttl,
+        }
+    }
+
+    pub async fn create_and_load(store: &impl SessionStorageBackend) {
+        let id = SessionId::random();
+        let state = state("value");
+        store.create(&id, record(&state, TTL)).await.unwrap();
+
+        let loaded = store.load(&id).await.unwrap().unwrap();
+        assert_eq!(loaded.state, state);
+        assert!(loaded.ttl <= TTL);
+        assert!(loaded.ttl > TTL - Duration::from_secs(10));
+
+        assert!(store.load(&SessionId::random()).await.unwrap().is_none());
+    }
+
+    pub async fn create_rejects_live_duplicates(store: &impl SessionStorageBackend) {
+        let id = SessionId::random();
+        let state = state("value");
+        store.create(&id, record(&state, TTL)).await.unwrap();
+
+        let err = store.create(&id, record(&state, TTL)).await.unwrap_err();
+        assert!(matches!(err, CreateError::DuplicateId(_)), "{err:?}");
+    }
+
+    pub async fn update(store: &impl SessionStorageBackend) {
+        let id = SessionId::random();
+        store.create(&id, record(&state("old"), TTL)).await.unwrap();
+
+        let new_state = state("new");
+        store.update(&id, record(&new_state, TTL)).await.unwrap();
+        let loaded = store.load(&id).await.unwrap().unwrap();
+        assert_eq!(loaded.state, new_state);
+
+        let err = store
+            .update(&SessionId::random(), record(&new_state, TTL))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, UpdateError::UnknownIdError(_)), "{err:?}");
+    }
+
+    pub async fn update_ttl(store: &impl SessionStorageBackend) {
+        let id = SessionId::random();
+        let state = state("value");
+        store
+            .create(&id, record(&state, Duration::from_secs(30)))
+            .await
+            .unwrap();
+
+        let new_ttl = Duration::from_secs(3600);
+        store.update_ttl(&id, new_ttl).await.unwrap();
+        let loaded = store.load(&id).await.unwrap().unwrap();
+        assert!(loaded.ttl > Duration::from_secs(30));
+        assert_eq!(loaded.state, state);
+
+        let err = store
+            .update_ttl(&SessionId::random(), new_ttl)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, UpdateTtlError::UnknownId(_)), "{err:?}");
+    }
+
+    pub async fn delete(store: &impl SessionStorageBackend) {
+        let id = SessionId::random();
+        store
+            .create(&id, record(&state("value"), TTL))
+            .await
+            .unwrap();
+
+        store.delete(&id).await.unwrap();
+        assert!(store.load(&id).await.unwrap().is_none());
+
+        let err = store.delete(&id).await.unwrap_err();
+        assert!(matches!(err, DeleteError::UnknownId(_)), "{err:?}");
+    }
+
+    pub async fn change_id(store: &impl SessionStorageBackend) {
+        let (old_id, new_id, other_id) = (
+            SessionId::random(),
+            SessionId::random(),
+            SessionId::random(),
+        );
+        let state = state("value");
+        store.create(&old_id, record(&state, TTL)).await.unwrap();
+        store.create(&other_id, record(&state, TTL)).await.unwrap();
+
+        store.change_id(&old_id, &new_id).await.unwrap();
+        assert!(store.load(&old_id).await.unwrap().is_none());
+        assert_eq!(store.load(&new_id).await.unwrap().unwrap().state, state);
+
+        let err = store.change_id(&new_id, &other_id).await.unwrap_err();
+        assert!(matches!(err, ChangeIdError::DuplicateId(_)), "{err:?}");
+
+        let err = store
+            .change_id(&old_id, &SessionId::random())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ChangeIdError::UnknownId(_)), "{err:?}");
+    }
+
+    pub async fn expired_records(store: &impl SessionStorageBackend) {
+        let id = SessionId::random();
+        let state = state("value");
+        store
+            .create(&id, record(&state, Duration::ZERO))
+            .await
+            .unwrap();
+        // Expired records are invisible...
+        assert!(store.load(&id).await.unwrap().is_none());
+        let err = store.update_ttl(&id, TTL).await.unwrap_err();
+        assert!(matches!(err, UpdateTtlError::UnknownId(_)), "{err:?}");
+        // ...and can be overwritten.
+        store.create(&id, record(&state, TTL)).await.unwrap();
+        assert!(store.load(&id).await.unwrap().is_some());
+
+        let expired = SessionId::random();
+        store
+            .create(&expired, record(&state, Duration::ZERO))
+            .await
+            .unwrap();
+        let deleted = store.delete_expired(None).await.unwrap();
+        assert!(deleted >= 1);
+        // Live records are left untouched.
+        assert!(store.load(&id).await.unwrap().is_some());
+
+        store
+            .create(&SessionId::random(), record(&state, Duration::ZERO))
+            .await
+            .unwrap();
+        let deleted = store
+            .delete_expired(Some(1.try_into().unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+    }
+
+    pub async fn delete_expired_in_batches(store: &impl SessionStorageBackend) {
+        let state = state("value");
+        for _ in 0..3 {
+            store
+                .create(&SessionId::random(), record(&state, Duration::ZERO))
+                .await
+                .unwrap();
+        }
+        let live = SessionId::random();
+        store.create(&live, record(&state, TTL)).await.unwrap();
+
+        // Other tests share the database and may delete our expired records concurrently:
+        // we can only check that the batch size is honoured.
+        let deleted = store
+            .delete_expired(Some(2.try_into().unwrap()))
+            .await
+            .unwrap();
+        assert!(deleted <= 2, "{deleted} records were deleted");
+        store.delete_expired(None).await.unwrap();
+        // Live records are left untouched.
+        assert!(store.load(&live).await.unwrap().is_some());
+    }
+
+    pub async fn principal_index(store: &impl SessionStorageBackend) {
+        // Use a unique principal, since the database is shared across test runs.
+        let principal = SessionId::random().inner().to_string();
+        let mut bound = state("value");
+        bound.insert(PRINCIPAL_KEY.into(), Value::from(principal.as_str()));
+
+        let (first, second, unbound) = (
+            SessionId::random(),
+            SessionId::random(),
+            SessionId::random(),
+        );
+        store.create(&first, record(&bound, TTL)).await.unwrap();
+        store
+            .create(&unbound, record(&state("value"), TTL))
+            .await
+            .unwrap();
+        // The principal is indexed on updates too.
+        store
+            .create(&second, record(&state("value"), TTL))
+            .await
+            .unwrap();
+        store.update(&second, record(&bound, TTL)).await.unwrap();
+
+        let mut ids: Vec<_> = store
+            .list_by_principal(&principal)
+            .await
+            .unwrap()
+            .iter()
+            .map(SessionId::inner)
+            .collect();
+        ids.sort();
+        let mut expected = vec![first.inner(), second.inner()];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        // It follows the record when its ID changes.
+        let renamed = SessionId::random();
+        store.change_id(&first, &renamed).await.unwrap();
+        let ids = store.list_by_principal(&principal).await.unwrap();
+        assert!(ids.iter().any(|id| id.inner() == renamed.inner()));
+
+        assert_eq!(store.delete_by_principal(&principal).await.unwrap(), 2);
+        assert!(
+            store
+                .list_by_principal(&principal)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(store.load(&renamed).await.unwrap().is_none());
+        assert!(store.load(&unbound).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,118 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use http_body::{Frame, SizeHint};
+use pavex::response::ResponseBody;
+use pavex::response::body::raw::{Bytes, RawBody};
+
+use super::entry::AccessLogEntry;
+
+/// A response body that counts the bytes it yields and emits the access log line
+/// once it has been fully sent—or dropped before completion.
+pub(super) struct LoggedBody {
+    inner: ResponseBody,
+    started_at: Instant,
+    bytes_sent: u64,
+    entry: Option<AccessLogEntry>,
+}
+
+impl LoggedBody {
+    pub(super) fn new(inner: ResponseBody, started_at: Instant, entry: AccessLogEntry) -> Self {
+        Self {
+            inner,
+            started_at,
+            bytes_sent: 0,
+            entry: Some(entry),
+        }
+    }
+
+    fn emit(&mut self) {
+        if let Some(entry) = self.entry.take() {
+            let line = entry.render(self.bytes_sent, self.started_at.elapsed());
+            tracing::info!(target: super::TARGET, "{line}");
+        }
+    }
+}
+
+impl RawBody for LoggedBody {
+    type Data = Bytes;
+    type Error = pavex::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let poll = Pin::new(&mut self.inner).poll_frame(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    self.bytes_sent += data.len() as u64;
+                }
+                // `hyper` stops polling as soon as the body reports that it's done.
+                if self.inner.is_end_stream() {
+                    self.emit();
+                }
+            }
+            Poll::Ready(Some(Err(_)) | None) => self.emit(),
+            Poll::Pending => {}
+        }
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for LoggedBody {
+    fn drop(&mut self) {
+        self.emit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use http_body_util::BodyExt;
+    use pavex::http::{HeaderMap, Method, StatusCode, Version};
+    use pavex::request::RequestHead;
+    use pavex::response::ResponseBody;
+    use pavex::response::body::raw::Full;
+    use pavex::telemetry::ServerRequestId;
+    use pavex::time::Timestamp;
+
+    use super::LoggedBody;
+    use crate::access_log::AccessLogFormat;
+    use crate::access_log::entry::AccessLogEntry;
+
+    #[tokio::test]
+    async fn the_line_is_emitted_once_the_body_has_been_sent() {
+        let request_head = RequestHead {
+            method: Method::GET,
+            target: "/".parse().unwrap(),
+            version: Version::HTTP_11,
+            headers: HeaderMap::new(),
+        };
+        let entry = AccessLogEntry::new(
+            AccessLogFormat::Common,
+            Timestamp::now(),
+            ServerRequestId::generate(),
+            "127.0.0.1:4000".parse().unwrap(),
+            &request_head,
+            StatusCode::OK,
+        );
+        let inner = ResponseBody::new(Full::new("hello world".into()));
+        let mut body = LoggedBody::new(inner, Instant::now(), entry);
+
+        let frame = body.frame().await.unwrap().unwrap();
+        assert_eq!(frame.data_ref().unwrap().as_ref(), b"hello world");
+        assert_eq!(body.bytes_sent, 11);
+        assert!(body.entry.is_none());
+    }
+}
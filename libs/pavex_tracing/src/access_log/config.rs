@@ -0,0 +1,148 @@
+use pavex::telemetry::ServerRequestId;
+
+/// The format of access log lines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessLogFormat {
+    /// [Common Log Format](https://httpd.apache.org/docs/current/logs.html#common):
+    ///
+    /// ```text
+    /// 127.0.0.1 - - [10/Oct/2024:13:55:36 +0000] "GET /index.html HTTP/1.1" 200 2326
+    /// ```
+    Common,
+    /// [Combined Log Format](https://httpd.apache.org/docs/current/logs.html#combined),
+    /// i.e. Common Log Format followed by the `Referer` and `User-Agent` headers:
+    ///
+    /// ```text
+    /// 127.0.0.1 - - [10/Oct/2024:13:55:36 +0000] "GET /index.html HTTP/1.1" 200 2326 "-" "curl/8.5.0"
+    /// ```
+    #[default]
+    Combined,
+    /// A JSON object per line, with the following fields:
+    /// `timestamp`, `request_id`, `peer_addr`, `method`, `target`, `protocol`, `status`,
+    /// `bytes_sent`, `duration_ms`, `referer` and `user_agent`.
+    ///
+    /// `referer` and `user_agent` are `null` if the corresponding header is missing.
+    Json,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+/// Configure which requests are logged by [`log_access`](super::log_access), and how.
+///
+/// # Example
+///
+/// [`AccessLogKit`](super::AccessLogKit) registers it as a configuration type, using
+/// `access_log` as its key:
+///
+/// ```yaml
+/// access_log:
+///   format: common
+///   sample_rate: 0.5
+///   exclude_paths: ["/health/*"]
+/// ```
+pub struct AccessLogConfig {
+    /// The format of each log line.
+    ///
+    /// By default, it's [`AccessLogFormat::Combined`].
+    pub format: AccessLogFormat,
+    /// The fraction of requests that should be logged, between `0.0` and `1.0`.
+    ///
+    /// The sampling decision is derived from the random bits of the
+    /// [`ServerRequestId`] of the request, therefore it's stable for a given request.
+    /// By default, it's `1.0`, i.e. every request is logged.
+    pub sample_rate: f64,
+    /// Requests whose path matches any of these patterns are not logged.
+    ///
+    /// A pattern matches a path if they are equal or, if the pattern ends with `*`,
+    /// if the path starts with the pattern (minus the trailing `*`).
+    /// By default, no path is excluded.
+    pub exclude_paths: Vec<String>,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self {
+            format: AccessLogFormat::default(),
+            sample_rate: 1.0,
+            exclude_paths: Vec::new(),
+        }
+    }
+}
+
+/// UUID v7 generators may use the most significant random bits as a monotonic counter,
+/// but the least significant 32 bits are always random.
+const RANDOM_BITS_MASK: u128 = u32::MAX as u128;
+
+impl AccessLogConfig {
+    /// Returns `true` if a request with the given path and identifier should be logged.
+    pub fn should_log(&self, path: &str, request_id: ServerRequestId) -> bool {
+        !self.is_excluded(path) && self.is_sampled(request_id)
+    }
+
+    fn is_excluded(&self, path: &str) -> bool {
+        self.exclude_paths
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => path.starts_with(prefix),
+                None => path == pattern,
+            })
+    }
+
+    fn is_sampled(&self, request_id: ServerRequestId) -> bool {
+        if self.sample_rate >= 1.0 {
+            return true;
+        }
+        if self.sample_rate <= 0.0 || self.sample_rate.is_nan() {
+            return false;
+        }
+        let random = (request_id.inner().as_u128() & RANDOM_BITS_MASK) as f64;
+        random < self.sample_rate * (RANDOM_BITS_MASK as f64 + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pavex::telemetry::ServerRequestId;
+
+    use super::AccessLogConfig;
+
+    #[test]
+    fn excluded_paths() {
+        let config = AccessLogConfig {
+            exclude_paths: vec!["/metrics".into(), "/health/*".into()],
+            ..Default::default()
+        };
+        let id = ServerRequestId::generate();
+        assert!(!config.should_log("/metrics", id));
+        assert!(!config.should_log("/health/ready", id));
+        assert!(config.should_log("/metrics/extra", id));
+        assert!(config.should_log("/users", id));
+    }
+
+    #[test]
+    fn sampling() {
+        let never = AccessLogConfig {
+            sample_rate: 0.0,
+            ..Default::default()
+        };
+        let half = AccessLogConfig {
+            sample_rate: 0.5,
+            ..Default::default()
+        };
+        let ids: Vec<_> = (0..1000).map(|_| ServerRequestId::generate()).collect();
+        assert!(ids.iter().all(|id| !never.should_log("/", *id)));
+        assert!(
+            ids.iter()
+                .all(|id| AccessLogConfig::default().should_log("/", *id))
+        );
+        let sampled = ids.iter().filter(|id| half.should_log("/", **id)).count();
+        assert!((350..650).contains(&sampled), "{sampled}");
+        // The decision is stable for a given request.
+        assert!(
+            ids.iter()
+                .all(|id| half.should_log("/", *id) == half.should_log("/", *id))
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use pavex::http::header::{REFERER, USER_AGENT};
+use pavex::http::{Method, StatusCode, Version};
+use pavex::request::RequestHead;
+use pavex::telemetry::ServerRequestId;
+use pavex::time::Timestamp;
+use pavex::time::tz::TimeZone;
+
+use super::AccessLogFormat;
+
+/// Everything we need to know about a request/response pair to render its access log line,
+/// except for the information that's only available once the body has been sent.
+#[derive(Debug)]
+pub(super) struct AccessLogEntry {
+    format: AccessLogFormat,
+    received_at: Timestamp,
+    request_id: ServerRequestId,
+    peer_addr: SocketAddr,
+    method: Method,
+    target: String,
+    version: Version,
+    status: StatusCode,
+    referer: Option<String>,
+    user_agent: Option<String>,
+}
+
+impl AccessLogEntry {
+    pub(super) fn new(
+        format: AccessLogFormat,
+        received_at: Timestamp,
+        request_id: ServerRequestId,
+        peer_addr: SocketAddr,
+        request_head: &RequestHead,
+        status: StatusCode,
+    ) -> Self {
+        let header = |name| {
+            request_head
+                .headers
+                .get(name)
+                .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+        };
+        let target = request_head
+            .target
+            .path_and_query()
+            .map(|p| p.as_str())
+            .unwrap_or("/")
+            .to_owned();
+        Self {
+            format,
+            received_at,
+            request_id,
+            peer_addr,
+            method: request_head.method.clone(),
+            target,
+            version: request_head.version,
+            status,
+            referer: header(REFERER),
+            user_agent: header(USER_AGENT),
+        }
+    }
+
+    /// Render the log line according to the configured format.
+    pub(super) fn render(&self, bytes_sent: u64, elapsed: Duration) -> String {
+        match self.format {
+            AccessLogFormat::Common => self.common(bytes_sent),
+            AccessLogFormat::Combined => {
+                let mut line = self.common(bytes_sent);
+                let _ = write!(
+                    line,
+                    " \"{}\" \"{}\"",
+                    escape(self.referer.as_deref().unwrap_or("-")),
+                    escape(self.user_agent.as_deref().unwrap_or("-")),
+                );
+                line
+            }
+            AccessLogFormat::Json => serde_json::json!({
+                "timestamp": self.received_at.to_string(),
+                "request_id": self.request_id.to_string(),
+                "peer_addr": self.peer_addr.to_string(),
+                "method": self.method.as_str(),
+                "target": self.target,
+                "protocol": format!("{:?}", self.version),
+                "status": self.status.as_u16(),
+                "bytes_sent": bytes_sent,
+                "duration_ms": elapsed.as_secs_f64() * 1000.0,
+                "referer": self.referer,
+                "user_agent": self.user_agent,
+            })
+            .to_string(),
+        }
+    }
+
+    fn common(&self, bytes_sent: u64) -> String {
+        let received_at = self
+            .received_at
+            .to_zoned(TimeZone::UTC)
+            .strftime("%d/%b/%Y:%H:%M:%S %z");
+        let bytes_sent = if bytes_sent == 0 {
+            "-".to_owned()
+        } else {
+            bytes_sent.to_string()
+        };
+        format!(
+            "{} - - [{received_at}] \"{} {} {:?}\" {} {bytes_sent}",
+            self.peer_addr.ip(),
+            self.method,
+            escape(&self.target),
+            self.version,
+            self.status.as_u16(),
+        )
+    }
+}
+
+/// Escape quotes, backslashes and control characters, as Apache does.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\x{:02x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pavex::http::{HeaderMap, HeaderValue, Method, StatusCode, Version};
+    use pavex::request::RequestHead;
+    use pavex::telemetry::ServerRequestId;
+
+    use super::AccessLogEntry;
+    use crate::access_log::AccessLogFormat;
+
+    fn entry(format: AccessLogFormat) -> AccessLogEntry {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "user-agent",
+            HeaderValue::from_static("curl/8.5.0 \"quoted\""),
+        );
+        let request_head = RequestHead {
+            method: Method::GET,
+            target: "/users?page=2".parse().unwrap(),
+            version: Version::HTTP_11,
+            headers,
+        };
+        AccessLogEntry::new(
+            format,
+            "2024-10-10T13:55:36Z".parse().unwrap(),
+            ServerRequestId::generate(),
+            "127.0.0.1:4000".parse().unwrap(),
+            &request_head,
+            StatusCode::OK,
+        )
+    }
+
+    #[test]
+    fn common_and_combined() {
+        let elapsed = Duration::from_millis(12);
+        assert_eq!(
+            entry(AccessLogFormat::Common).render(2326, elapsed),
+            r#"127.0.0.1 - - [10/Oct/2024:13:55:36 +0000] "GET /users?page=2 HTTP/1.1" 200 2326"#
+        );
+        assert_eq!(
+            entry(AccessLogFormat::Combined).render(0, elapsed),
+            r#"127.0.0.1 - - [10/Oct/2024:13:55:36 +0000] "GET /users?page=2 HTTP/1.1" 200 - "-" "curl/8.5.0 \"quoted\"""#
+        );
+    }
+
+    #[test]
+    fn json() {
+        let entry = entry(AccessLogFormat::Json);
+        let line = entry.render(42, Duration::from_micros(1500));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["timestamp"], "2024-10-10T13:55:36Z");
+        assert_eq!(value["request_id"], entry.request_id.to_string());
+        assert_eq!(value["peer_addr"], "127.0.0.1:4000");
+        assert_eq!(value["target"], "/users?page=2");
+        assert_eq!(value["protocol"], "HTTP/1.1");
+        assert_eq!(value["status"], 200);
+        assert_eq!(value["bytes_sent"], 42);
+        assert_eq!(value["duration_ms"], 1.5);
+        assert_eq!(value["referer"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,97 @@
+use pavex::blueprint::Blueprint;
+use pavex::blueprint::config::ConfigType;
+use pavex::blueprint::constructor::Constructor;
+use pavex::blueprint::linter::Lint;
+use pavex::blueprint::middleware::{PostProcessingMiddleware, PreProcessingMiddleware};
+use pavex::{f, t};
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// A collection of components to emit one access log line per request.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex_tracing::access_log::AccessLogKit;
+///
+/// let mut bp = Blueprint::new();
+/// // Register the kit before your other middlewares, to measure
+/// // the entire request processing pipeline.
+/// AccessLogKit::new().register(&mut bp);
+/// ```
+pub struct AccessLogKit {
+    /// The constructor for [`RequestStart`].
+    ///
+    /// By default, it uses [`RequestStart::now`].
+    ///
+    /// [`RequestStart`]: super::RequestStart
+    /// [`RequestStart::now`]: super::RequestStart::now
+    pub request_start: Option<Constructor>,
+    /// A pre-processing middleware to capture the start of the request processing pipeline.
+    ///
+    /// By default, it's set to [`start_access_log`](super::start_access_log).
+    pub timer: Option<PreProcessingMiddleware>,
+    /// A post-processing middleware to emit the access log line.
+    ///
+    /// By default, it's set to [`log_access`](super::log_access).
+    pub logger: Option<PostProcessingMiddleware>,
+    /// Register [`AccessLogConfig`] as a configuration type.
+    ///
+    /// By default, it uses `access_log` as its configuration key.
+    ///
+    /// [`AccessLogConfig`]: super::AccessLogConfig
+    pub config: Option<ConfigType>,
+}
+
+impl Default for AccessLogKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessLogKit {
+    /// Create a new [`AccessLogKit`] with all the bundled constructors and middlewares.
+    pub fn new() -> Self {
+        Self {
+            request_start: Some(
+                Constructor::request_scoped(f!(crate::access_log::RequestStart::now))
+                    .ignore(Lint::Unused),
+            ),
+            timer: Some(PreProcessingMiddleware::new(f!(
+                crate::access_log::start_access_log
+            ))),
+            logger: Some(PostProcessingMiddleware::new(f!(
+                crate::access_log::log_access
+            ))),
+            config: Some(
+                ConfigType::new("access_log", t!(crate::access_log::AccessLogConfig))
+                    .default_if_missing(),
+            ),
+        }
+    }
+
+    /// Register all the bundled constructors and middlewares with a [`Blueprint`].
+    ///
+    /// If a component is set to `None` it will not be registered.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredAccessLogKit {
+        if let Some(request_start) = self.request_start {
+            request_start.register(bp);
+        }
+        if let Some(timer) = self.timer {
+            timer.register(bp);
+        }
+        if let Some(logger) = self.logger {
+            logger.register(bp);
+        }
+        if let Some(config) = self.config {
+            config.register(bp);
+        }
+        RegisteredAccessLogKit {}
+    }
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The type returned by [`AccessLogKit::register`].
+pub struct RegisteredAccessLogKit {}
@@ -0,0 +1,52 @@
+//! Emit one access log line per request, in Common Log Format, Combined Log Format or JSON.
+//!
+//! # Guide
+//!
+//! Register [`AccessLogKit`] against your [`Blueprint`](pavex::blueprint::Blueprint):
+//!
+//! ```rust
+//! use pavex::blueprint::Blueprint;
+//! use pavex_tracing::access_log::AccessLogKit;
+//!
+//! let mut bp = Blueprint::new();
+//! // Register the kit before your other middlewares, to measure
+//! // the entire request processing pipeline.
+//! AccessLogKit::new().register(&mut bp);
+//! ```
+//!
+//! The kit relies on [`ServerRequestId`](pavex::telemetry::ServerRequestId), which is
+//! registered by [`ApiKit`](pavex::kit::ApiKit).
+//!
+//! # When is the line emitted?
+//!
+//! The line is emitted when the response body has been fully streamed to the client,
+//! or when it's dropped because the connection was interrupted.
+//! The number of bytes sent and the latency are measured at that point.
+//!
+//! Lines are emitted as `INFO` events with the [`TARGET`] target, with the formatted line
+//! as message. Use the target to route them to a dedicated sink, or to filter them out
+//! of your application logs.
+//!
+//! # Configuration
+//!
+//! The format, the sampling rate and the excluded paths are controlled via
+//! [`AccessLogConfig`], under the `access_log` key by default:
+//!
+//! ```yaml
+//! access_log:
+//!   format: json
+//!   sample_rate: 0.1
+//!   exclude_paths: ["/health/*", "/metrics"]
+//! ```
+mod body;
+mod config;
+mod entry;
+mod kit;
+mod mw;
+
+pub use config::{AccessLogConfig, AccessLogFormat};
+pub use kit::{AccessLogKit, RegisteredAccessLogKit};
+pub use mw::{RequestStart, log_access, start_access_log};
+
+/// The target of the events emitted for each access log line.
+pub const TARGET: &str = "pavex_tracing::access_log";
@@ -0,0 +1,101 @@
+use std::time::Instant;
+
+use pavex::connection::ConnectionInfo;
+use pavex::middleware::Processing;
+use pavex::request::RequestHead;
+use pavex::response::Response;
+use pavex::response::ResponseBody;
+use pavex::telemetry::ServerRequestId;
+use pavex::time::Timestamp;
+
+use super::AccessLogConfig;
+use super::body::LoggedBody;
+use super::entry::AccessLogEntry;
+
+/// The moment Pavex started processing the incoming request.
+///
+/// It's used by [`log_access`] to compute the latency of the request.
+/// It must be built as early as possible in the request processing pipeline:
+/// [`start_access_log`] takes care of it.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestStart {
+    instant: Instant,
+    timestamp: Timestamp,
+}
+
+impl RequestStart {
+    /// Capture the current time.
+    pub fn now() -> Self {
+        Self {
+            instant: Instant::now(),
+            timestamp: Timestamp::now(),
+        }
+    }
+
+    /// A monotonic measurement of the moment the request processing started.
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    /// The wall-clock time at which the request processing started.
+    pub fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+}
+
+/// A pre-processing middleware that forces the construction of [`RequestStart`]
+/// at the beginning of the request processing pipeline.
+///
+/// Register it before your other middlewares, alongside [`log_access`].
+/// [`AccessLogKit`](super::AccessLogKit) does it for you.
+pub fn start_access_log(_start: &RequestStart) -> Processing {
+    Processing::Continue
+}
+
+/// A post-processing middleware that emits an access log line for the outgoing response.
+///
+/// The line is emitted once the response body has been fully sent,
+/// according to the format specified in [`AccessLogConfig`].
+/// Requests that are excluded or not sampled are left untouched.
+///
+/// # Registration
+///
+/// [`AccessLogKit`](super::AccessLogKit) registers it for you.
+/// Alternatively, use [`Blueprint::post_process`] to register it manually:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::{f, t};
+///
+/// let mut bp = Blueprint::new();
+/// bp.request_scoped(f!(pavex_tracing::access_log::RequestStart::now));
+/// bp.config("access_log", t!(pavex_tracing::access_log::AccessLogConfig))
+///     .default_if_missing();
+/// bp.pre_process(f!(pavex_tracing::access_log::start_access_log));
+/// bp.post_process(f!(pavex_tracing::access_log::log_access));
+/// ```
+///
+/// [`Blueprint::post_process`]: pavex::blueprint::Blueprint::post_process
+pub fn log_access(
+    response: Response,
+    request_head: &RequestHead,
+    connection_info: &ConnectionInfo,
+    request_id: ServerRequestId,
+    start: &RequestStart,
+    config: &AccessLogConfig,
+) -> Response {
+    if !config.should_log(request_head.target.path(), request_id) {
+        return response;
+    }
+    let entry = AccessLogEntry::new(
+        config.format,
+        start.timestamp,
+        request_id,
+        connection_info.peer_addr(),
+        request_head,
+        response.status(),
+    );
+    let (head, body) = response.into_parts();
+    let body = ResponseBody::new(LoggedBody::new(body, start.instant, entry));
+    Response::from_parts(head, body)
+}
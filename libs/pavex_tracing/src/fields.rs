@@ -17,6 +17,7 @@
 //! The module doesn't cover the entirety of OpenTelemetry's semantic convention specification.\
 //! Feel free to open a PR if you need a **stable** field that isn't currently covered!
 
+use crate::propagation::TraceContext;
 use pavex::http::{Method, Version};
 use pavex::request::RequestHead;
 use pavex::request::path::MatchedPathPattern;
@@ -63,6 +64,19 @@ pub const NETWORK_PROTOCOL_NAME: &str = "network.protocol.name";
 /// Use [`network_protocol_version`] to populate the field.
 pub const NETWORK_PROTOCOL_VERSION: &str = "network.protocol.version";
 
+/// The field name for the identifier of the caller's span, extracted from the
+/// headers of the incoming request.\
+/// This field doesn't appear in OpenTelemetry's semantic convention specification.
+///
+/// Use [`parent_span_id`] to populate the field.
+pub const PARENT_SPAN_ID: &str = "parent_span_id";
+
+/// The field name for the identifier of the distributed trace the incoming request belongs to,
+/// according to [OpenTelemetry's log data model](https://opentelemetry.io/docs/specs/otel/logs/data-model/#field-traceid).
+///
+/// Use [`trace_id`] to populate the field.
+pub const TRACE_ID: &str = "trace_id";
+
 /// The path targeted by the incoming request,
 /// according to [OpenTelemetry's semantic convention](https://opentelemetry.io/docs/specs/semconv/attributes-registry/url/).
 ///
@@ -131,6 +145,16 @@ pub fn network_protocol_version(request_head: &RequestHead) -> impl Value + use<
     }
 }
 
+/// The canonical representation for the value in [`PARENT_SPAN_ID`].
+pub fn parent_span_id(trace_context: &TraceContext) -> impl Value + use<> {
+    tracing::field::display(trace_context.parent_span_id_hex())
+}
+
+/// The canonical representation for the value in [`TRACE_ID`].
+pub fn trace_id(trace_context: &TraceContext) -> impl Value + use<> {
+    tracing::field::display(trace_context.trace_id_hex())
+}
+
 /// The canonical representation for the value in [`URL_PATH`].
 pub fn url_path(request_head: &RequestHead) -> impl Value + '_ {
     request_head.target.path()
@@ -2,9 +2,11 @@
 //!
 //! [`tracing`]:https://docs.rs/tracing/0.1.40/tracing
 //! [Pavex]: https://pavex.dev
+pub mod access_log;
 pub mod fields;
 mod mw;
+pub mod propagation;
 mod root_span;
 
-pub use mw::logger;
+pub use mw::{logger, propagating_logger};
 pub use root_span::RootSpan;
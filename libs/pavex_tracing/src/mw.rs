@@ -1,5 +1,8 @@
 use crate::RootSpan;
+use crate::fields::{PARENT_SPAN_ID, TRACE_ID, parent_span_id, trace_id};
+use crate::propagation::{PropagationConfig, TraceContext};
 use pavex::middleware::Next;
+use pavex::request::RequestHead;
 use pavex::response::Response;
 use std::future::IntoFuture;
 use tracing::Instrument;
@@ -31,3 +34,70 @@ where
 {
     next.into_future().instrument(root_span.into_inner()).await
 }
+
+/// A logging middleware that instruments the request processing pipeline with
+/// [`RootSpan`], as a continuation of the distributed trace started by the caller.
+///
+/// It extracts the caller's trace context from the headers of the incoming request,
+/// using the [`Propagator`]s listed in [`PropagationConfig`], and records it on
+/// [`RootSpan`] via the [`TRACE_ID`] and [`PARENT_SPAN_ID`] fields.
+/// They must be declared when [`RootSpan`] is created, otherwise `tracing` will ignore them.
+///
+/// # OpenTelemetry
+///
+/// If the `opentelemetry` feature is enabled, the caller's trace context is also set as the
+/// parent of [`RootSpan`] via [`tracing-opentelemetry`], alongside the propagated baggage.
+/// The identity of [`RootSpan`] is then returned to the caller via the `traceresponse` header,
+/// unless [`PropagationConfig::traceresponse`] is set to `false`.
+///
+/// # Registration
+///
+/// Use [`Blueprint::wrap`] to register `propagating_logger` as a middleware,
+/// instead of [`logger`]:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::{f, t};
+///
+/// let mut bp = Blueprint::new();
+/// bp.config("trace_propagation", t!(pavex_tracing::propagation::PropagationConfig))
+///     .default_if_missing();
+/// bp.wrap(f!(pavex_tracing::propagating_logger));
+/// ```
+///
+/// [`Blueprint::wrap`]: pavex::blueprint::Blueprint::wrap
+/// [`Propagator`]: crate::propagation::Propagator
+/// [`TRACE_ID`]: crate::fields::TRACE_ID
+/// [`PARENT_SPAN_ID`]: crate::fields::PARENT_SPAN_ID
+/// [`tracing-opentelemetry`]: https://docs.rs/tracing-opentelemetry
+pub async fn propagating_logger<C>(
+    root_span: RootSpan,
+    request_head: &RequestHead,
+    config: &PropagationConfig,
+    next: Next<C>,
+) -> Response
+where
+    C: IntoFuture<Output = Response>,
+{
+    let span = root_span.into_inner();
+    if let Some(trace_context) = TraceContext::extract(&request_head.headers, &config.propagators) {
+        span.record(TRACE_ID, trace_id(&trace_context));
+        span.record(PARENT_SPAN_ID, parent_span_id(&trace_context));
+        #[cfg(feature = "opentelemetry")]
+        crate::propagation::otel::set_parent(&span, &trace_context);
+    }
+    #[allow(unused_mut)]
+    let mut response = next.into_future().instrument(span.clone()).await;
+    #[cfg(feature = "opentelemetry")]
+    if config.traceresponse {
+        if let Some(value) = crate::propagation::otel::traceresponse(&span)
+            .and_then(|v| pavex::http::HeaderValue::try_from(v).ok())
+        {
+            response = response.insert_header(
+                pavex::http::HeaderName::from_static(crate::propagation::TRACERESPONSE),
+                value,
+            );
+        }
+    }
+    response
+}
@@ -0,0 +1,446 @@
+//! Extract the trace context of incoming requests, to stitch together
+//! distributed traces across service boundaries.
+//!
+//! Check out [`propagating_logger`](crate::propagating_logger) for the middleware
+//! that puts these pieces together.
+use std::fmt::Write;
+
+use pavex::http::HeaderMap;
+
+/// The header used by W3C Trace Context to propagate the identity of the caller.
+pub const TRACEPARENT: &str = "traceparent";
+/// The header used by W3C Trace Context to propagate vendor-specific trace information.
+pub const TRACESTATE: &str = "tracestate";
+/// The header used by W3C Baggage to propagate application-defined key-value pairs.
+pub const BAGGAGE: &str = "baggage";
+/// The header used by W3C Trace Context to return the trace context of the server
+/// to the caller.
+pub const TRACERESPONSE: &str = "traceresponse";
+
+/// A format used to propagate the trace context across service boundaries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Propagator {
+    /// [W3C Trace Context](https://www.w3.org/TR/trace-context/), via the `traceparent` and
+    /// `tracestate` headers, alongside [W3C Baggage](https://www.w3.org/TR/baggage/),
+    /// via the `baggage` header.
+    TraceContext,
+    /// [Zipkin's B3](https://github.com/openzipkin/b3-propagation), either via the
+    /// single `b3` header or via the `X-B3-*` headers.
+    B3,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+/// Configure how [`propagating_logger`](crate::propagating_logger) propagates the
+/// trace context.
+///
+/// # Example
+///
+/// Register it as a configuration type:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::t;
+///
+/// let mut bp = Blueprint::new();
+/// bp.config("trace_propagation", t!(pavex_tracing::propagation::PropagationConfig))
+///     .default_if_missing();
+/// ```
+///
+/// To accept both W3C Trace Context and B3 headers, with the former taking precedence:
+///
+/// ```yaml
+/// trace_propagation:
+///   propagators: ["trace_context", "b3"]
+/// ```
+pub struct PropagationConfig {
+    /// The formats used to extract the trace context of the caller.
+    ///
+    /// They are tried in order: the first one that finds a valid trace context wins.
+    /// By default, only [`Propagator::TraceContext`] is enabled.
+    pub propagators: Vec<Propagator>,
+    /// Whether to set the `traceresponse` header on outgoing responses.
+    ///
+    /// It requires the `opentelemetry` feature: the header is never set otherwise,
+    /// since the identifiers of the server-side span are unknown.
+    /// It's enabled by default.
+    pub traceresponse: bool,
+}
+
+impl Default for PropagationConfig {
+    fn default() -> Self {
+        Self {
+            propagators: vec![Propagator::TraceContext],
+            traceresponse: true,
+        }
+    }
+}
+
+/// The trace context of the caller, extracted from the headers of an incoming request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    parent_span_id: [u8; 8],
+    sampled: Option<bool>,
+    trace_state: Option<String>,
+    baggage: Vec<(String, String)>,
+}
+
+impl TraceContext {
+    /// Extract the trace context from `headers`, trying each propagator in order.
+    ///
+    /// It returns `None` if none of the propagators finds a valid trace context.
+    pub fn extract(headers: &HeaderMap, propagators: &[Propagator]) -> Option<Self> {
+        propagators.iter().find_map(|propagator| match propagator {
+            Propagator::TraceContext => Self::extract_w3c(headers),
+            Propagator::B3 => Self::extract_b3(headers),
+        })
+    }
+
+    /// The identifier of the distributed trace.
+    pub fn trace_id(&self) -> [u8; 16] {
+        self.trace_id
+    }
+
+    /// The identifier of the caller's span.
+    pub fn parent_span_id(&self) -> [u8; 8] {
+        self.parent_span_id
+    }
+
+    /// Whether the caller has recorded its side of the trace.
+    ///
+    /// It's `None` if the caller didn't make a sampling decision and deferred it
+    /// to the server—e.g. a B3 header without a sampling state.
+    pub fn sampled(&self) -> Option<bool> {
+        self.sampled
+    }
+
+    /// The value of the `tracestate` header, if any.
+    pub fn trace_state(&self) -> Option<&str> {
+        self.trace_state.as_deref()
+    }
+
+    /// The key-value pairs propagated via the `baggage` header.
+    ///
+    /// Values are returned as they appear in the header, without percent-decoding them.
+    pub fn baggage(&self) -> &[(String, String)] {
+        &self.baggage
+    }
+
+    /// The hex-encoded trace identifier.
+    pub fn trace_id_hex(&self) -> String {
+        hex(&self.trace_id)
+    }
+
+    /// The hex-encoded identifier of the caller's span.
+    pub fn parent_span_id_hex(&self) -> String {
+        hex(&self.parent_span_id)
+    }
+
+    fn extract_w3c(headers: &HeaderMap) -> Option<Self> {
+        let traceparent = header(headers, TRACEPARENT)?;
+        let mut parts = traceparent.split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let parent_span_id = parts.next()?;
+        let flags = parts.next()?;
+        // Future versions may append more fields, but version `00` has exactly four.
+        if version.len() != 2 || version == "ff" || (version == "00" && parts.next().is_some()) {
+            return None;
+        }
+        if !is_lower_hex(version) || !is_lower_hex(flags) || flags.len() != 2 {
+            return None;
+        }
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        let trace_id = parse_trace_id(trace_id, false)?;
+        let parent_span_id = parse_span_id(parent_span_id)?;
+        let trace_state = header(headers, TRACESTATE)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(ToOwned::to_owned);
+        let baggage = header(headers, BAGGAGE)
+            .map(parse_baggage)
+            .unwrap_or_default();
+        Some(Self {
+            trace_id,
+            parent_span_id,
+            sampled: Some(flags & 1 == 1),
+            trace_state,
+            baggage,
+        })
+    }
+
+    fn extract_b3(headers: &HeaderMap) -> Option<Self> {
+        if let Some(b3) = header(headers, "b3") {
+            // `{TraceId}-{SpanId}-{SamplingState}-{ParentSpanId}`, where the last two are optional.
+            // A lone sampling state (e.g. `0`) carries no trace context.
+            // Without a sampling state, the sampling decision is deferred to us.
+            let mut parts = b3.split('-');
+            let trace_id = parse_trace_id(parts.next()?, true)?;
+            let parent_span_id = parse_span_id(parts.next()?)?;
+            let sampled = match parts.next() {
+                None => None,
+                Some("1" | "d") => Some(true),
+                Some("0") => Some(false),
+                Some(_) => return None,
+            };
+            return Some(Self::b3(trace_id, parent_span_id, sampled));
+        }
+        let trace_id = parse_trace_id(header(headers, "x-b3-traceid")?, true)?;
+        let parent_span_id = parse_span_id(header(headers, "x-b3-spanid")?)?;
+        // The debug flag implies an accept decision.
+        let sampled = if header(headers, "x-b3-flags") == Some("1") {
+            Some(true)
+        } else {
+            match header(headers, "x-b3-sampled") {
+                Some("1" | "true") => Some(true),
+                Some("0" | "false") => Some(false),
+                _ => None,
+            }
+        };
+        Some(Self::b3(trace_id, parent_span_id, sampled))
+    }
+
+    fn b3(trace_id: [u8; 16], parent_span_id: [u8; 8], sampled: Option<bool>) -> Self {
+        Self {
+            trace_id,
+            parent_span_id,
+            sampled,
+            trace_state: None,
+            baggage: Vec::new(),
+        }
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name)?.to_str().ok()
+}
+
+/// W3C Trace Context only allows lowercase hex digits.
+fn is_lower_hex(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Parse a 32-character trace id.
+/// B3 also allows 16-character trace ids, which are left-padded with zeros.
+fn parse_trace_id(s: &str, allow_short: bool) -> Option<[u8; 16]> {
+    if !(s.len() == 32 || (allow_short && s.len() == 16)) {
+        return None;
+    }
+    if !is_lower_hex(s) {
+        return None;
+    }
+    let id = u128::from_str_radix(s, 16).ok()?;
+    (id != 0).then(|| id.to_be_bytes())
+}
+
+fn parse_span_id(s: &str) -> Option<[u8; 8]> {
+    if s.len() != 16 {
+        return None;
+    }
+    if !is_lower_hex(s) {
+        return None;
+    }
+    let id = u64::from_str_radix(s, 16).ok()?;
+    (id != 0).then(|| id.to_be_bytes())
+}
+
+/// Parse a `baggage` header, ignoring properties and malformed entries.
+fn parse_baggage(header: &str) -> Vec<(String, String)> {
+    header
+        .split(',')
+        .filter_map(|member| {
+            let key_value = member.split(';').next()?;
+            let (key, value) = key_value.split_once('=')?;
+            let key = key.trim();
+            (!key.is_empty()).then(|| (key.to_owned(), value.trim().to_owned()))
+        })
+        .collect()
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
+
+#[cfg(feature = "opentelemetry")]
+pub(crate) mod otel {
+    use opentelemetry::KeyValue;
+    use opentelemetry::baggage::BaggageExt;
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+    };
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    use super::TraceContext;
+
+    /// Set the remote trace context as the parent of `span`.
+    ///
+    /// It must be invoked before `span` is entered for the first time.
+    /// The `sampled` flag is only set if the caller explicitly chose to sample the trace.
+    pub(crate) fn set_parent(span: &tracing::Span, trace_context: &TraceContext) {
+        let flags = match trace_context.sampled {
+            Some(true) => TraceFlags::SAMPLED,
+            Some(false) | None => TraceFlags::default(),
+        };
+        let trace_state = trace_context
+            .trace_state()
+            .and_then(|s| s.parse::<TraceState>().ok())
+            .unwrap_or_default();
+        let span_context = SpanContext::new(
+            TraceId::from_bytes(trace_context.trace_id),
+            SpanId::from_bytes(trace_context.parent_span_id),
+            flags,
+            true,
+            trace_state,
+        );
+        let mut cx = opentelemetry::Context::new().with_remote_span_context(span_context);
+        if !trace_context.baggage.is_empty() {
+            cx = cx.with_baggage(
+                trace_context
+                    .baggage
+                    .iter()
+                    .map(|(k, v)| KeyValue::new(k.clone(), v.clone())),
+            );
+        }
+        if let Err(e) = span.set_parent(cx) {
+            tracing::debug!(error.msg = %e, "Failed to set the remote parent of the root span");
+        }
+    }
+
+    /// The value of the `traceresponse` header for `span`, if it's being exported
+    /// via OpenTelemetry.
+    pub(crate) fn traceresponse(span: &tracing::Span) -> Option<String> {
+        let cx = span.context();
+        let span_ref = cx.span();
+        let span_context = span_ref.span_context();
+        if !span_context.is_valid() {
+            return None;
+        }
+        Some(format!(
+            "00-{}-{}-{:02x}",
+            span_context.trace_id(),
+            span_context.span_id(),
+            span_context.trace_flags().to_u8()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pavex::http::{HeaderMap, HeaderValue};
+
+    use super::{Propagator, TraceContext};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn w3c_trace_context() {
+        let h = headers(&[
+            (
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            ),
+            ("tracestate", "congo=t61rcWkgMzE"),
+            (
+                "baggage",
+                "userId=alice;ttl=3, isProduction=false,malformed",
+            ),
+        ]);
+        let cx = TraceContext::extract(&h, &[Propagator::TraceContext]).unwrap();
+        assert_eq!(cx.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(cx.parent_span_id_hex(), "00f067aa0ba902b7");
+        assert_eq!(cx.sampled(), Some(true));
+        assert_eq!(cx.trace_state(), Some("congo=t61rcWkgMzE"));
+        assert_eq!(
+            cx.baggage(),
+            &[
+                ("userId".to_owned(), "alice".to_owned()),
+                ("isProduction".to_owned(), "false".to_owned())
+            ]
+        );
+        // B3 headers are ignored unless the B3 propagator is enabled.
+        assert!(TraceContext::extract(&h, &[Propagator::B3]).is_none());
+    }
+
+    #[test]
+    fn invalid_traceparent_headers_are_ignored() {
+        for traceparent in [
+            // All-zero trace id.
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            // All-zero span id.
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            // Uppercase hex.
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            // Forbidden version.
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            // Extra fields for version 00.
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        ] {
+            let h = headers(&[("traceparent", traceparent)]);
+            assert!(
+                TraceContext::extract(&h, &[Propagator::TraceContext]).is_none(),
+                "{traceparent}"
+            );
+        }
+    }
+
+    #[test]
+    fn b3() {
+        let propagators = [Propagator::TraceContext, Propagator::B3];
+        let single = headers(&[("b3", "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1-1")]);
+        let cx = TraceContext::extract(&single, &propagators).unwrap();
+        assert_eq!(cx.trace_id_hex(), "80f198ee56343ba864fe8b2a57d3eff7");
+        assert_eq!(cx.parent_span_id_hex(), "e457b5a2e4d86bd1");
+        assert_eq!(cx.sampled(), Some(true));
+
+        let multi = headers(&[
+            ("x-b3-traceid", "64fe8b2a57d3eff7"),
+            ("x-b3-spanid", "e457b5a2e4d86bd1"),
+            ("x-b3-sampled", "0"),
+        ]);
+        let cx = TraceContext::extract(&multi, &propagators).unwrap();
+        assert_eq!(cx.trace_id_hex(), "000000000000000064fe8b2a57d3eff7");
+        assert_eq!(cx.sampled(), Some(false));
+
+        // A sampling decision on its own doesn't carry a trace context.
+        assert!(TraceContext::extract(&headers(&[("b3", "0")]), &propagators).is_none());
+        // An invalid sampling state invalidates the whole header.
+        let invalid = headers(&[("b3", "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1-x")]);
+        assert!(TraceContext::extract(&invalid, &propagators).is_none());
+    }
+
+    #[test]
+    fn b3_without_a_sampling_state_defers_the_decision() {
+        let propagators = [Propagator::B3];
+        let single = headers(&[("b3", "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1")]);
+        let cx = TraceContext::extract(&single, &propagators).unwrap();
+        assert_eq!(cx.sampled(), None);
+
+        let multi = headers(&[
+            ("x-b3-traceid", "80f198ee56343ba864fe8b2a57d3eff7"),
+            ("x-b3-spanid", "e457b5a2e4d86bd1"),
+        ]);
+        let cx = TraceContext::extract(&multi, &propagators).unwrap();
+        assert_eq!(cx.sampled(), None);
+
+        let debug = headers(&[
+            ("x-b3-traceid", "80f198ee56343ba864fe8b2a57d3eff7"),
+            ("x-b3-spanid", "e457b5a2e4d86bd1"),
+            ("x-b3-flags", "1"),
+        ]);
+        let cx = TraceContext::extract(&debug, &propagators).unwrap();
+        assert_eq!(cx.sampled(), Some(true));
+    }
+}
@@ -27,6 +27,9 @@ use super::{
 pub struct ApplicationConfig {
     bindings: BiHashMap<syn::Ident, ResolvedType>,
     binding2default: HashMap<syn::Ident, DefaultStrategy>,
+    /// The `Reloadable` handle type for each reloadable configuration type,
+    /// keyed by field name.
+    reloadable_bindings: BiHashMap<syn::Ident, ResolvedType>,
 }
 
 impl ApplicationConfig {
@@ -40,6 +43,7 @@ impl ApplicationConfig {
     ) -> Self {
         let mut bindings = BiHashMap::new();
         let mut binding2default = HashMap::new();
+        let mut reloadable_bindings = BiHashMap::new();
         // Temporary maps to track key-to-type and type-to-key relationships
         // and detect conflicts.
         let mut key2types: BTreeMap<ConfigKey, IndexMap<ResolvedType, ComponentId>> =
@@ -63,6 +67,9 @@ impl ApplicationConfig {
 
             let ident = config.key().ident();
             bindings.insert(ident.clone(), config.ty().to_owned());
+            if let Some(handle) = component_db.reloadable_handle(id) {
+                reloadable_bindings.insert(ident.clone(), handle.to_owned());
+            }
             binding2default.insert(ident, component_db.default_strategy(id));
         }
 
@@ -81,6 +88,7 @@ impl ApplicationConfig {
         Self {
             bindings,
             binding2default,
+            reloadable_bindings,
         }
     }
 
@@ -89,6 +97,12 @@ impl ApplicationConfig {
         &self.bindings
     }
 
+    /// Retrieve the bindings between configuration keys and the `Reloadable` handle
+    /// types for reloadable configuration types.
+    pub fn reloadable_bindings(&self) -> &BiHashMap<syn::Ident, ResolvedType> {
+        &self.reloadable_bindings
+    }
+
     /// Returns `true` if the field should be annotated with `#[serde(default)]`.
     pub fn should_default(&self, field_name: &syn::Ident) -> bool {
         self.binding2default[field_name] == DefaultStrategy::DefaultIfMissing
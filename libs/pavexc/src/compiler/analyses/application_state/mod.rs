@@ -10,11 +10,13 @@ use quote::format_ident;
 pub(crate) use thread_safety::runtime_singletons_are_thread_safe;
 
 use super::{
+    background_tasks::BackgroundTasks,
     call_graph::RawCallGraphExt,
     components::{ComponentDb, ComponentId},
     computations::ComputationDb,
     constructibles::ConstructibleDb,
     framework_items::FrameworkItemDb,
+    lifecycle_hooks::LifecycleHooks,
     processing_pipeline::RequestHandlerPipeline,
 };
 use crate::{
@@ -40,8 +42,13 @@ pub struct ApplicationState {
 impl ApplicationState {
     /// Examine the processing pipeline of all request handlers to
     /// determine which singletons are needed to serve user requests.
+    ///
+    /// Singletons taken as input by lifecycle hooks and background tasks are included as well,
+    /// together with the `Reloadable` handle of every reloadable configuration type.
     pub fn new(
         handler_id2pipeline: &IndexMap<ComponentId, RequestHandlerPipeline>,
+        lifecycle_hooks: &LifecycleHooks,
+        background_tasks: &BackgroundTasks,
         framework_item_db: &FrameworkItemDb,
         constructibles_db: &ConstructibleDb,
         component_db: &ComponentDb,
@@ -49,12 +56,15 @@ impl ApplicationState {
         krate_collection: &CrateCollection,
         diagnostics: &mut Vec<miette::Error>,
     ) -> Self {
-        let type2id = extract_runtime_singletons(
+        let mut type2id = extract_runtime_singletons(
             handler_id2pipeline.values(),
             framework_item_db,
             constructibles_db,
             component_db,
         );
+        type2id.extend(lifecycle_hooks.required_singletons().cloned());
+        type2id.extend(background_tasks.required_singletons().cloned());
+        type2id.extend(extract_reloadable_handles(constructibles_db, component_db));
         runtime_singletons_are_thread_safe(
             &type2id,
             component_db,
@@ -304,6 +314,27 @@ fn _field_name_candidate(ty_: &ResolvedType, strategy: NamingStrategy, candidate
     }
 }
 
+/// `Reloadable` handles must be stored in the application state, even if they're only used
+/// to build other singletons: otherwise `ConfigReloader` would have no way to update
+/// the values observed by those singletons.
+fn extract_reloadable_handles(
+    constructibles_db: &ConstructibleDb,
+    component_db: &ComponentDb,
+) -> Vec<(ResolvedType, ComponentId)> {
+    component_db
+        .iter()
+        .filter_map(|(config_id, _)| {
+            let handle = component_db.reloadable_handle(config_id)?;
+            let (constructor_id, _) = constructibles_db.get(
+                component_db.scope_id(config_id),
+                handle,
+                component_db.scope_graph(),
+            )?;
+            Some((handle.to_owned(), constructor_id))
+        })
+        .collect()
+}
+
 fn extract_runtime_singletons<'a>(
     handler_pipelines: impl Iterator<Item = &'a RequestHandlerPipeline>,
     framework_item_db: &FrameworkItemDb,
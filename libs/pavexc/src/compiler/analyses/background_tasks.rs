@@ -0,0 +1,314 @@
+use guppy::graph::PackageGraph;
+use miette::Severity;
+
+use pavex_bp_schema::TaskSchedule;
+
+use super::components::{ComponentDb, ComponentId, HydratedComponent};
+use super::computations::ComputationDb;
+use super::constructibles::ConstructibleDb;
+use super::lifecycle_hooks::{resolve_singleton, singleton_inputs, unit_or_result_output};
+use super::user_components::{ScopeId, UserComponent, UserComponentId};
+use crate::compiler::utils::process_framework_path;
+use crate::diagnostic::{self, CompilerDiagnostic, ComponentKind, OptionalSourceSpanExt};
+use crate::language::{Callable, ResolvedType};
+use crate::rustdoc::CrateCollection;
+use crate::try_source;
+
+/// The background tasks registered via `Blueprint::background_task` and `Blueprint::cron`.
+pub(crate) struct BackgroundTasks {
+    /// The valid tasks, in registration order.
+    tasks: Vec<BackgroundTask>,
+    /// The type of `pavex::background::ShutdownSignal`, if there is at least one task.
+    shutdown_signal: Option<ResolvedType>,
+}
+
+pub(crate) struct BackgroundTask {
+    pub(crate) callable: Callable,
+    pub(crate) schedule: TaskSchedule,
+    /// The singletons taken as input by the task, alongside the id of their constructor.
+    pub(crate) singletons: Vec<(ResolvedType, ComponentId)>,
+    /// `true` if the task takes a `ShutdownSignal` as input.
+    pub(crate) takes_shutdown_signal: bool,
+    /// `true` if the task returns a `Result`.
+    pub(crate) is_fallible: bool,
+    /// The error observers to be invoked if the task fails.
+    pub(crate) error_observers: Vec<TaskErrorObserver>,
+}
+
+pub(crate) struct TaskErrorObserver {
+    pub(crate) callable: Callable,
+    /// The index of `&pavex::Error` in the inputs of `callable`.
+    pub(crate) error_input_index: usize,
+    /// The singletons taken as input by the observer, alongside the id of their constructor.
+    pub(crate) singletons: Vec<(ResolvedType, ComponentId)>,
+}
+
+impl BackgroundTasks {
+    /// Validate all registered tasks.
+    ///
+    /// Tasks run outside of the request-response lifecycle: they can only take singletons
+    /// (or a `ShutdownSignal`) as inputs, and they must return either `()` or `Result<(), E>`.
+    #[tracing::instrument("Validate background tasks", skip_all)]
+    pub(crate) fn new(
+        component_db: &ComponentDb,
+        computation_db: &ComputationDb,
+        constructible_db: &ConstructibleDb,
+        package_graph: &PackageGraph,
+        krate_collection: &CrateCollection,
+        diagnostics: &mut Vec<miette::Error>,
+    ) -> Self {
+        let user_component_db = component_db.user_component_db();
+        let mut tasks = Vec::new();
+        if user_component_db.background_tasks().next().is_none() {
+            return Self {
+                tasks,
+                shutdown_signal: None,
+            };
+        }
+        let shutdown_signal =
+            process_framework_path("pavex::background::ShutdownSignal", krate_collection);
+        let is_shutdown_signal = |t: &ResolvedType| match t {
+            ResolvedType::Reference(r) => r.inner.as_ref() == &shutdown_signal,
+            _ => t == &shutdown_signal,
+        };
+        for (user_component_id, user_component) in user_component_db.background_tasks() {
+            let UserComponent::BackgroundTask {
+                scope_id, schedule, ..
+            } = user_component
+            else {
+                unreachable!()
+            };
+            if let Err(e) = validate_schedule(schedule) {
+                invalid_schedule(
+                    e,
+                    schedule,
+                    user_component_id,
+                    component_db,
+                    package_graph,
+                    diagnostics,
+                );
+                continue;
+            }
+            let Ok(error_type) = unit_or_result_output(
+                ComponentKind::BackgroundTask,
+                user_component_id,
+                component_db,
+                computation_db,
+                package_graph,
+                diagnostics,
+            ) else {
+                continue;
+            };
+            let Some(singletons) = singleton_inputs(
+                ComponentKind::BackgroundTask,
+                user_component_id,
+                *scope_id,
+                is_shutdown_signal,
+                component_db,
+                computation_db,
+                constructible_db,
+                package_graph,
+                krate_collection,
+                diagnostics,
+            ) else {
+                continue;
+            };
+            let callable = &computation_db[user_component_id];
+            let error_observers = if error_type.is_some() {
+                Self::error_observers(
+                    user_component_id,
+                    *scope_id,
+                    component_db,
+                    computation_db,
+                    constructible_db,
+                    package_graph,
+                    diagnostics,
+                )
+            } else {
+                Vec::new()
+            };
+            tasks.push(BackgroundTask {
+                callable: callable.to_owned(),
+                schedule: schedule.to_owned(),
+                singletons,
+                takes_shutdown_signal: callable.inputs.iter().any(is_shutdown_signal),
+                is_fallible: error_type.is_some(),
+                error_observers,
+            });
+        }
+        Self {
+            tasks,
+            shutdown_signal: Some(shutdown_signal),
+        }
+    }
+
+    /// The error observers in scope for a background task.
+    ///
+    /// Observers that take non-singleton inputs (other than `&pavex::Error`) can't be invoked
+    /// outside of the request-response lifecycle: they are skipped, with a warning.
+    fn error_observers(
+        task_id: UserComponentId,
+        scope_id: ScopeId,
+        component_db: &ComponentDb,
+        computation_db: &ComputationDb,
+        constructible_db: &ConstructibleDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) -> Vec<TaskErrorObserver> {
+        let user_component_db = component_db.user_component_db();
+        let mut observers = Vec::new();
+        for observer_id in user_component_db.get_error_observer_ids(task_id) {
+            // Invalid error observers have already been reported.
+            let Some(component_id) = component_db
+                .user_component_id2component_id()
+                .get(observer_id)
+            else {
+                continue;
+            };
+            let HydratedComponent::ErrorObserver(observer) =
+                component_db.hydrated_component(*component_id, computation_db)
+            else {
+                unreachable!()
+            };
+            let mut singletons = Vec::new();
+            let mut unsupported_input = None;
+            for (i, input) in observer.callable.inputs.iter().enumerate() {
+                if i == observer.error_input_index {
+                    continue;
+                }
+                match resolve_singleton(input, scope_id, component_db, constructible_db) {
+                    Ok(singleton) => singletons.push(singleton),
+                    Err(_) => {
+                        unsupported_input = Some(input.to_owned());
+                        break;
+                    }
+                }
+            }
+            if let Some(input) = unsupported_input {
+                skipped_error_observer(
+                    task_id,
+                    &observer.callable,
+                    &input,
+                    component_db,
+                    computation_db,
+                    package_graph,
+                    diagnostics,
+                );
+                continue;
+            }
+            observers.push(TaskErrorObserver {
+                callable: observer.callable.into_owned(),
+                error_input_index: observer.error_input_index,
+                singletons,
+            });
+        }
+        observers
+    }
+
+    /// The singletons that must be stored in the application state
+    /// for the tasks (and their error observers) to be invoked.
+    pub(crate) fn required_singletons(
+        &self,
+    ) -> impl Iterator<Item = &(ResolvedType, ComponentId)> + '_ {
+        self.tasks.iter().flat_map(|t| {
+            t.singletons
+                .iter()
+                .chain(t.error_observers.iter().flat_map(|o| o.singletons.iter()))
+        })
+    }
+
+    /// All tasks, in registration order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &BackgroundTask> + '_ {
+        self.tasks.iter()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// The type of `pavex::background::ShutdownSignal`.
+    ///
+    /// It's `None` if no background task has been registered.
+    pub(crate) fn shutdown_signal(&self) -> Option<&ResolvedType> {
+        self.shutdown_signal.as_ref()
+    }
+}
+
+fn validate_schedule(schedule: &TaskSchedule) -> Result<(), String> {
+    match schedule {
+        TaskSchedule::Once => Ok(()),
+        TaskSchedule::Every(period) if period.is_zero() => {
+            Err("The period of a background task can't be zero.".into())
+        }
+        TaskSchedule::Every(_) => Ok(()),
+        TaskSchedule::Cron(expression) => pavex::background::CronSchedule::parse(expression)
+            .map(|_| ())
+            .map_err(|e| format!("{e}.")),
+    }
+}
+
+fn invalid_schedule(
+    reason: String,
+    schedule: &TaskSchedule,
+    user_component_id: UserComponentId,
+    component_db: &ComponentDb,
+    package_graph: &PackageGraph,
+    diagnostics: &mut Vec<miette::Error>,
+) {
+    let location = component_db
+        .user_component_db()
+        .get_location(user_component_id);
+    let source = try_source!(location, package_graph, diagnostics);
+    let label = source.as_ref().and_then(|source| {
+        diagnostic::get_f_macro_invocation_span(source, location)
+            .labeled("The background task was registered here".into())
+    });
+    let help = matches!(schedule, TaskSchedule::Cron(_)).then(|| {
+        "Check out the documentation of `pavex::background::CronSchedule` for the supported \
+        cron syntax."
+            .to_owned()
+    });
+    let diagnostic = CompilerDiagnostic::builder(anyhow::anyhow!(reason))
+        .optional_source(source)
+        .optional_label(label)
+        .optional_help(help)
+        .build();
+    diagnostics.push(diagnostic.into());
+}
+
+fn skipped_error_observer(
+    task_id: UserComponentId,
+    observer: &Callable,
+    input: &ResolvedType,
+    component_db: &ComponentDb,
+    computation_db: &ComputationDb,
+    package_graph: &PackageGraph,
+    diagnostics: &mut Vec<miette::Error>,
+) {
+    let location = component_db.user_component_db().get_location(task_id);
+    let source = try_source!(location, package_graph, diagnostics);
+    let label = source.as_ref().and_then(|source| {
+        diagnostic::get_f_macro_invocation_span(source, location)
+            .labeled("The background task was registered here".into())
+    });
+    let e = anyhow::anyhow!(
+        "`{}` won't be invoked when the background task `{}` fails.\n\
+        Error observers invoked for background tasks can only take singletons as inputs, \
+        alongside `&pavex::Error`, but `{}` takes `{}` as input.",
+        observer.path,
+        computation_db[task_id].path,
+        observer.path,
+        input.display_for_error()
+    );
+    let diagnostic = CompilerDiagnostic::builder(e)
+        .optional_source(source)
+        .optional_label(label)
+        .severity(Severity::Warning)
+        .help(
+            "Register the background task before the error observer, or against a \
+            different blueprint, if the observer isn't meant to handle its errors."
+                .into(),
+        )
+        .build();
+    diagnostics.push(diagnostic.into());
+}
@@ -1015,6 +1015,40 @@ impl ComponentDb {
         diagnostics.push(diagnostic.into());
     }
 
+    pub(super) fn ambiguous_default_error_handler(
+        error_type: &ResolvedType,
+        first_handler_id: UserComponentId,
+        second_handler_id: UserComponentId,
+        raw_user_component_db: &UserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let first = raw_user_component_db.get_raw_callable_identifiers(first_handler_id);
+        let second = raw_user_component_db.get_raw_callable_identifiers(second_handler_id);
+        let location = raw_user_component_db.get_location(second_handler_id);
+        let source = try_source!(location, package_graph, diagnostics);
+        let label = source.as_ref().and_then(|source| {
+            diagnostic::get_f_macro_invocation_span(source, location)
+                .labeled("The default error handlers were imported here".into())
+        });
+        let error = anyhow::anyhow!(
+            "There is more than one default error handler for `{error_type:?}`: `{}` and `{}`.\n\
+                I don't know which one to use for the components that fail with that error.",
+            first.import_path,
+            second.import_path,
+        );
+        let diagnostic = CompilerDiagnostic::builder(error)
+            .optional_source(source)
+            .optional_label(label)
+            .help(
+                "Remove `#[pavex::error_handler]` from one of them, or register an error handler \
+                explicitly via `.error_handler` for each fallible component."
+                    .to_string(),
+            )
+            .build();
+        diagnostics.push(diagnostic.into());
+    }
+
     pub(super) fn missing_error_handler(
         fallible_id: UserComponentId,
         raw_user_component_db: &UserComponentDb,
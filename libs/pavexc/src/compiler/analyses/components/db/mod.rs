@@ -59,6 +59,11 @@ pub(crate) struct ComponentDb {
     ///
     /// Invariants: there is an entry for every configuration type.
     config_id2default_strategy: HashMap<ComponentId, DefaultStrategy>,
+    /// For each reloadable configuration type, the type of the `pavex::config::Reloadable`
+    /// handle that Pavex stores in the application state.
+    ///
+    /// Invariants: there is an entry for every reloadable configuration type.
+    config_id2reloadable_handle: HashMap<ComponentId, ResolvedType>,
     /// Associate each request handler with the ordered list of middlewares that wrap around it.
     ///
     /// Invariants: there is an entry for every single request handler.
@@ -175,6 +180,7 @@ impl ComponentDb {
             id2lifecycle: Default::default(),
             id2cloning_strategy: Default::default(),
             config_id2default_strategy: Default::default(),
+            config_id2reloadable_handle: Default::default(),
             handler_id2middleware_ids: Default::default(),
             handler_id2error_observer_ids: Default::default(),
             transformer_id2info: Default::default(),
@@ -262,7 +268,7 @@ impl ComponentDb {
             );
 
             self_.process_prebuilt_types(computation_db);
-            self_.process_config_types(computation_db);
+            self_.process_config_types(computation_db, package_graph, krate_collection);
 
             for fallible_id in needs_error_handler {
                 Self::missing_error_handler(
@@ -490,18 +496,22 @@ impl ComponentDb {
                         .unwrap();
                     self.config_id2default_strategy
                         .insert(id, *default_strategy);
-                    self.get_or_intern(
-                        UnregisteredComponent::SyntheticConstructor {
-                            computation_id: computation_db.get_or_intern(Constructor(
-                                Computation::PrebuiltType(Cow::Owned(config.ty().to_owned())),
-                            )),
-                            scope_id: user_component.scope_id(),
-                            lifecycle: self.user_component_db.get_lifecycle(user_component_id),
-                            cloning_strategy: cloning_strategy.to_owned(),
-                            derived_from: Some(id),
-                        },
-                        computation_db,
-                    );
+                    // The constructors for reloadable configuration types
+                    // are registered in `process_config_types`.
+                    if !self.user_component_db.is_reloadable(user_component_id) {
+                        self.get_or_intern(
+                            UnregisteredComponent::SyntheticConstructor {
+                                computation_id: computation_db.get_or_intern(Constructor(
+                                    Computation::PrebuiltType(Cow::Owned(config.ty().to_owned())),
+                                )),
+                                scope_id: user_component.scope_id(),
+                                lifecycle: self.user_component_db.get_lifecycle(user_component_id),
+                                cloning_strategy: cloning_strategy.to_owned(),
+                                derived_from: Some(id),
+                            },
+                            computation_db,
+                        );
+                    }
                 }
                 RequestHandler { .. }
                 | SyntheticWrappingMiddleware { .. }
@@ -672,20 +682,93 @@ impl ComponentDb {
 
     /// Validate all user-registered config types.
     /// We add their information to the relevant metadata stores.
-    fn process_config_types(&mut self, computation_db: &mut ComputationDb) {
+    fn process_config_types(
+        &mut self,
+        computation_db: &mut ComputationDb,
+        package_graph: &PackageGraph,
+        krate_collection: &CrateCollection,
+    ) {
         let ids = self
             .user_component_db
             .config_types()
             .map(|(id, _)| id)
             .collect::<Vec<_>>();
         for user_component_id in ids {
-            self.get_or_intern(
+            let id = self.get_or_intern(
                 UnregisteredComponent::UserConfigType { user_component_id },
                 computation_db,
             );
+            if self.user_component_db.is_reloadable(user_component_id) {
+                self.register_reloadable_config(
+                    id,
+                    user_component_id,
+                    computation_db,
+                    package_graph,
+                    krate_collection,
+                );
+            }
         }
     }
 
+    /// Reloadable configuration types are not stored as-is in the application state.
+    /// We store a `pavex::config::Reloadable<T>` handle instead, as a singleton, and
+    /// retrieve a copy of the current values for each request via
+    /// `pavex::config::Reloadable::current`.
+    fn register_reloadable_config(
+        &mut self,
+        config_id: ComponentId,
+        user_component_id: UserComponentId,
+        computation_db: &mut ComputationDb,
+        package_graph: &PackageGraph,
+        krate_collection: &CrateCollection,
+    ) {
+        let config_ty = self.config_type_db[user_component_id].ty().to_owned();
+        let scope_id = self.user_component_db[user_component_id].scope_id();
+        let cloning_strategy = *self
+            .user_component_db
+            .get_cloning_strategy(user_component_id)
+            .unwrap();
+
+        let mut bindings = HashMap::new();
+        bindings.insert("T".to_owned(), config_ty);
+        let current = process_framework_callable_path(
+            "pavex::config::Reloadable::current",
+            package_graph,
+            krate_collection,
+        )
+        .bind_generic_type_parameters(&bindings);
+        let ResolvedType::Reference(handle_ref) = &current.inputs[0] else {
+            unreachable!("`Reloadable::current` takes `&self` as input")
+        };
+        let handle_ty = handle_ref.inner.as_ref().to_owned();
+
+        self.get_or_intern(
+            UnregisteredComponent::SyntheticConstructor {
+                computation_id: computation_db.get_or_intern(Constructor(
+                    Computation::PrebuiltType(Cow::Owned(handle_ty.clone())),
+                )),
+                scope_id,
+                lifecycle: Lifecycle::Singleton,
+                cloning_strategy: CloningStrategy::CloneIfNecessary,
+                derived_from: Some(config_id),
+            },
+            computation_db,
+        );
+        self.get_or_intern(
+            UnregisteredComponent::SyntheticConstructor {
+                computation_id: computation_db
+                    .get_or_intern(Constructor(Computation::Callable(Cow::Owned(current)))),
+                scope_id,
+                lifecycle: Lifecycle::RequestScoped,
+                cloning_strategy,
+                derived_from: Some(config_id),
+            },
+            computation_db,
+        );
+        self.config_id2reloadable_handle
+            .insert(config_id, handle_ty);
+    }
+
     fn process_request_handlers(
         &mut self,
         needs_error_handler: &mut IndexSet<UserComponentId>,
@@ -933,11 +1016,25 @@ impl ComponentDb {
                 Some((id, *fallible_callable_identifiers_id))
             })
             .collect::<Vec<_>>();
+        // The fallible components that were matched by a default error handler,
+        // mapped to the matching handler.
+        let mut fallible_id2default_handler_id = HashMap::new();
+        // The error types that we already reported as having more than one default
+        // error handler, to avoid reporting the same ambiguity once per component.
+        let mut ambiguous_error_types = IndexSet::new();
         for (error_handler_user_component_id, fallible_user_component_id) in iter {
+            // Default error handlers are attached to every component that may fail,
+            // they're discarded (without a diagnostic) if they don't match.
+            let is_default = self
+                .user_component_db
+                .is_default_error_handler(error_handler_user_component_id);
             let lifecycle = self
                 .user_component_db
                 .get_lifecycle(fallible_user_component_id);
             if lifecycle == Lifecycle::Singleton {
+                if is_default {
+                    continue;
+                }
                 Self::error_handler_for_a_singleton(
                     error_handler_user_component_id,
                     fallible_user_component_id,
@@ -950,8 +1047,10 @@ impl ComponentDb {
             let fallible_callable = &computation_db[fallible_user_component_id];
             if fallible_callable.is_fallible() {
                 let error_handler_callable = &computation_db[error_handler_user_component_id];
-                // Capture immediately that an error handler was registered for this fallible component.
-                missing_error_handlers.shift_remove(&fallible_user_component_id);
+                if !is_default {
+                    // Capture immediately that an error handler was registered for this fallible component.
+                    missing_error_handlers.shift_remove(&fallible_user_component_id);
+                }
                 match ErrorHandler::new(
                     error_handler_callable.to_owned(),
                     fallible_callable,
@@ -978,6 +1077,31 @@ impl ComponentDb {
                                 unreachable!()
                             };
 
+                            if is_default {
+                                // Default error handlers are matched against the exact
+                                // error type returned by the fallible component.
+                                if error_ref.inner.as_ref() != fallible_error {
+                                    continue;
+                                }
+                                if let Some(other_id) = fallible_id2default_handler_id.insert(
+                                    fallible_user_component_id,
+                                    error_handler_user_component_id,
+                                ) {
+                                    if ambiguous_error_types.insert(fallible_error.to_owned()) {
+                                        Self::ambiguous_default_error_handler(
+                                            fallible_error,
+                                            other_id,
+                                            error_handler_user_component_id,
+                                            &self.user_component_db,
+                                            package_graph,
+                                            diagnostics,
+                                        );
+                                    }
+                                    continue;
+                                }
+                                missing_error_handlers.shift_remove(&fallible_user_component_id);
+                            }
+
                             // The error handler doesn't use the concrete type
                             // returned by the fallible component,
                             // it targets Pavex's error type.
@@ -1007,6 +1131,7 @@ impl ComponentDb {
                             );
                         }
                     }
+                    Err(_) if is_default => {}
                     Err(e) => {
                         Self::invalid_error_handler(
                             e,
@@ -1019,7 +1144,7 @@ impl ComponentDb {
                         );
                     }
                 };
-            } else {
+            } else if !is_default {
                 Self::error_handler_for_infallible_component(
                     error_handler_user_component_id,
                     fallible_user_component_id,
@@ -1478,6 +1603,13 @@ impl ComponentDb {
         self.config_id2default_strategy[&component_id]
     }
 
+    /// Given the id of a configuration type, return the type of the `Reloadable` handle
+    /// stored in the application state.
+    /// It returns `None` if the configuration type is not reloadable.
+    pub fn reloadable_handle(&self, config_id: ComponentId) -> Option<&ResolvedType> {
+        self.config_id2reloadable_handle.get(&config_id)
+    }
+
     /// Iterate over all constructors in the component database, either user-provided or synthetic.
     pub fn constructors<'a>(
         &'a self,
@@ -0,0 +1,642 @@
+//! Export the shape of the code-generated `ApplicationConfig` as a JSON Schema,
+//! alongside a sample configuration file.
+//!
+//! The schema is derived from the type information in the JSON documentation of the
+//! configuration types. We honor the `serde` attributes that affect the shape of the
+//! deserialized data (`rename`, `rename_all`, `default`, `skip`, `deny_unknown_fields`).
+//! Types that we can't make sense of (e.g. enums with data, or types with a custom
+//! `Deserialize` implementation) are represented by an unconstrained schema.
+use std::collections::BTreeMap;
+
+use ahash::{HashMap, HashMapExt};
+use rustdoc_types::{GenericParamDefKind, ItemEnum, StructKind, VariantKind};
+use serde_json::{Map, Value, json};
+
+use crate::compiler::resolvers::{GenericBindings, resolve_type};
+use crate::language::{PathType, ResolvedType, ScalarPrimitive};
+use crate::rustdoc::{CrateCollection, GlobalItemId};
+
+use super::application_config::ApplicationConfig;
+
+/// The custom keyword used to mark `pavex::config::Secret` fields in the schema.
+const SECRET_KEYWORD: &str = "x-pavex-secret";
+
+/// The JSON Schema for `ApplicationConfig` and a sample configuration file that matches it.
+pub(crate) struct ConfigSchema {
+    pub(crate) json_schema: Value,
+    pub(crate) sample: String,
+}
+
+impl ConfigSchema {
+    pub(crate) fn new(
+        application_config: &ApplicationConfig,
+        krate_collection: &CrateCollection,
+    ) -> Self {
+        let mut builder = SchemaBuilder {
+            krate_collection,
+            definitions: BTreeMap::new(),
+            in_progress: Vec::new(),
+        };
+        let fields: BTreeMap<_, _> = application_config
+            .bindings()
+            .iter()
+            .map(|(ident, ty)| (ident.to_string(), (ident, ty)))
+            .collect();
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for (key, (ident, ty)) in fields {
+            let mut schema = builder.schema(ty);
+            if let Value::Object(o) = &mut schema {
+                o.entry("description")
+                    .or_insert_with(|| format!("`{}`", ty.display_for_error()).into());
+            }
+            properties.insert(key.clone(), schema);
+            if !application_config.should_default(ident) {
+                required.push(Value::String(key));
+            }
+        }
+        let mut json_schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "ApplicationConfig",
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+        if !builder.definitions.is_empty() {
+            json_schema["$defs"] = Value::Object(builder.definitions.into_iter().collect());
+        }
+        let sample = sample_yaml(&json_schema);
+        Self {
+            json_schema,
+            sample,
+        }
+    }
+}
+
+struct SchemaBuilder<'a> {
+    krate_collection: &'a CrateCollection,
+    /// Schemas for named types (structs and enums), referenced via `$ref`.
+    definitions: BTreeMap<String, Value>,
+    /// The named types whose schema is being computed, to avoid infinite recursion
+    /// on recursive types.
+    in_progress: Vec<String>,
+}
+
+impl SchemaBuilder<'_> {
+    fn schema(&mut self, ty: &ResolvedType) -> Value {
+        match ty {
+            ResolvedType::ScalarPrimitive(p) => primitive_schema(p),
+            ResolvedType::Reference(r) => self.schema(&r.inner),
+            ResolvedType::Slice(s) => {
+                json!({ "type": "array", "items": self.schema(&s.element_type) })
+            }
+            ResolvedType::Tuple(t) if t.elements.is_empty() => json!({ "type": "null" }),
+            ResolvedType::Tuple(t) => {
+                let items: Vec<_> = t.elements.iter().map(|e| self.schema(e)).collect();
+                json!({
+                    "type": "array",
+                    "prefixItems": items,
+                    "minItems": items.len(),
+                    "maxItems": items.len(),
+                })
+            }
+            ResolvedType::Generic(_) => json!({}),
+            ResolvedType::ResolvedPath(p) => self.path_schema(p),
+        }
+    }
+
+    fn path_schema(&mut self, ty: &PathType) -> Value {
+        let type_args: Vec<&ResolvedType> = ty
+            .generic_arguments
+            .iter()
+            .filter_map(|a| match a {
+                crate::language::GenericArgument::TypeParameter(t) => Some(t),
+                crate::language::GenericArgument::Lifetime(_) => None,
+            })
+            .collect();
+        if let (Some("pavex"), Some("Secret"), [inner]) = (
+            ty.base_type.first().map(String::as_str),
+            ty.base_type.last().map(String::as_str),
+            type_args.as_slice(),
+        ) {
+            let mut schema = self.schema(inner);
+            if let Value::Object(o) = &mut schema {
+                o.insert("writeOnly".into(), true.into());
+                o.insert(SECRET_KEYWORD.into(), true.into());
+            }
+            return schema;
+        }
+        let is_std = matches!(
+            ty.base_type.first().map(String::as_str),
+            Some("std" | "core" | "alloc")
+        );
+        if is_std {
+            let name = ty.base_type.last().map(String::as_str).unwrap_or_default();
+            match (name, type_args.as_slice()) {
+                (
+                    "String" | "PathBuf" | "OsString" | "IpAddr" | "Ipv4Addr" | "Ipv6Addr"
+                    | "SocketAddr" | "SocketAddrV4" | "SocketAddrV6",
+                    _,
+                ) => {
+                    return json!({ "type": "string" });
+                }
+                ("Option", [inner]) => {
+                    let inner = self.schema(inner);
+                    return json!({ "anyOf": [inner, { "type": "null" }] });
+                }
+                (
+                    "Box" | "Arc" | "Rc" | "Cow" | "Cell" | "RefCell" | "Mutex" | "RwLock",
+                    [inner],
+                ) => {
+                    return self.schema(inner);
+                }
+                (
+                    "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" | "BinaryHeap",
+                    [inner],
+                ) => {
+                    return json!({ "type": "array", "items": self.schema(inner) });
+                }
+                ("HashMap" | "BTreeMap", [_, value]) => {
+                    return json!({ "type": "object", "additionalProperties": self.schema(value) });
+                }
+                ("Duration", _) => {
+                    return json!({
+                        "type": "object",
+                        "properties": {
+                            "secs": { "type": "integer", "minimum": 0 },
+                            "nanos": { "type": "integer", "minimum": 0 },
+                        },
+                        "required": ["secs", "nanos"],
+                    });
+                }
+                ("NonZeroU8" | "NonZeroU16" | "NonZeroU32" | "NonZeroU64" | "NonZeroUsize", _) => {
+                    return json!({ "type": "integer", "minimum": 1 });
+                }
+                _ => {}
+            }
+        }
+
+        let name = ResolvedType::ResolvedPath(ty.clone()).display_for_error();
+        let reference = json!({ "$ref": format!("#/$defs/{name}") });
+        if self.definitions.contains_key(&name) || self.in_progress.contains(&name) {
+            return reference;
+        }
+        self.in_progress.push(name.clone());
+        let schema = self
+            .item_schema(ty, &type_args)
+            .unwrap_or_else(|| json!({}));
+        self.in_progress.pop();
+        let mut schema = schema;
+        if let Value::Object(o) = &mut schema {
+            o.insert("title".into(), name.clone().into());
+        }
+        self.definitions.insert(name, schema);
+        reference
+    }
+
+    /// Derive the schema from the definition of the type in the JSON documentation.
+    fn item_schema(&mut self, ty: &PathType, type_args: &[&ResolvedType]) -> Option<Value> {
+        let item_id = match &ty.rustdoc_id {
+            Some(id) => GlobalItemId {
+                rustdoc_item_id: id.to_owned(),
+                package_id: ty.package_id.clone(),
+            },
+            None => self
+                .krate_collection
+                .get_or_compute_crate_by_package_id(&ty.package_id)
+                .ok()?
+                .get_item_id_by_path(&ty.base_type, self.krate_collection)
+                .ok()?
+                .ok()?,
+        };
+        let item = self.krate_collection.get_item_by_global_type_id(&item_id);
+        let container_attrs = serde_attributes(&item.attrs);
+        let rename_all = container_attrs
+            .iter()
+            .find(|(k, _)| k == "rename_all")
+            .and_then(|(_, v)| v.clone());
+        let container_default = container_attrs.iter().any(|(k, _)| k == "default");
+        let deny_unknown_fields = container_attrs
+            .iter()
+            .any(|(k, _)| k == "deny_unknown_fields");
+
+        match &item.inner {
+            ItemEnum::Struct(s) => {
+                let mut bindings = GenericBindings::default();
+                let type_params = s
+                    .generics
+                    .params
+                    .iter()
+                    .filter(|p| matches!(p.kind, GenericParamDefKind::Type { .. }));
+                for (param, arg) in type_params.zip(type_args) {
+                    bindings.types.insert(param.name.clone(), (*arg).to_owned());
+                }
+                let StructKind::Plain { fields, .. } = &s.kind else {
+                    return None;
+                };
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                for field_id in fields {
+                    let field = self
+                        .krate_collection
+                        .get_item_by_global_type_id(&GlobalItemId {
+                            rustdoc_item_id: field_id.to_owned(),
+                            package_id: item_id.package_id.clone(),
+                        })
+                        .into_owned();
+                    let ItemEnum::StructField(field_ty) = &field.inner else {
+                        continue;
+                    };
+                    let field_attrs = serde_attributes(&field.attrs);
+                    let has = |key: &str| field_attrs.iter().any(|(k, _)| k == key);
+                    if has("skip") || has("skip_deserializing") || has("flatten") {
+                        // Flattened fields would require merging the properties of the inner
+                        // type: we don't constrain the object instead.
+                        if has("flatten") {
+                            return Some(json!({ "type": "object" }));
+                        }
+                        continue;
+                    }
+                    let name = field.name.clone().unwrap_or_default();
+                    let name = match field_attrs.iter().find(|(k, _)| k == "rename") {
+                        Some((_, Some(renamed))) => renamed.to_owned(),
+                        _ => rename(&name, rename_all.as_deref(), false),
+                    };
+                    let field_ty = resolve_type(
+                        field_ty,
+                        &item_id.package_id,
+                        self.krate_collection,
+                        &bindings,
+                    );
+                    let is_optional = matches!(
+                        &field_ty,
+                        Ok(ResolvedType::ResolvedPath(p)) if p.base_type.last().map(String::as_str) == Some("Option")
+                    );
+                    let schema = match field_ty {
+                        Ok(field_ty) => self.schema(&field_ty),
+                        Err(_) => json!({}),
+                    };
+                    if !(is_optional || container_default || has("default")) {
+                        required.push(Value::String(name.clone()));
+                    }
+                    properties.insert(name, schema);
+                }
+                let mut schema = json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                });
+                if deny_unknown_fields {
+                    schema["additionalProperties"] = false.into();
+                }
+                Some(schema)
+            }
+            ItemEnum::Enum(e) => {
+                let mut variants = Vec::new();
+                for variant_id in &e.variants {
+                    let variant = self
+                        .krate_collection
+                        .get_item_by_global_type_id(&GlobalItemId {
+                            rustdoc_item_id: variant_id.to_owned(),
+                            package_id: item_id.package_id.clone(),
+                        })
+                        .into_owned();
+                    let ItemEnum::Variant(v) = &variant.inner else {
+                        continue;
+                    };
+                    if !matches!(v.kind, VariantKind::Plain) {
+                        // Enums with data can be represented in many different ways.
+                        return None;
+                    }
+                    let variant_attrs = serde_attributes(&variant.attrs);
+                    if variant_attrs
+                        .iter()
+                        .any(|(k, _)| k == "skip" || k == "skip_deserializing")
+                    {
+                        continue;
+                    }
+                    let name = variant.name.clone().unwrap_or_default();
+                    let name = match variant_attrs.iter().find(|(k, _)| k == "rename") {
+                        Some((_, Some(renamed))) => renamed.to_owned(),
+                        _ => rename(&name, rename_all.as_deref(), true),
+                    };
+                    variants.push(Value::String(name));
+                }
+                Some(json!({ "type": "string", "enum": variants }))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn primitive_schema(p: &ScalarPrimitive) -> Value {
+    match p {
+        ScalarPrimitive::Usize
+        | ScalarPrimitive::U8
+        | ScalarPrimitive::U16
+        | ScalarPrimitive::U32
+        | ScalarPrimitive::U64 => json!({ "type": "integer", "minimum": 0 }),
+        ScalarPrimitive::Isize
+        | ScalarPrimitive::I8
+        | ScalarPrimitive::I16
+        | ScalarPrimitive::I32
+        | ScalarPrimitive::I64 => json!({ "type": "integer" }),
+        ScalarPrimitive::F32 | ScalarPrimitive::F64 => json!({ "type": "number" }),
+        ScalarPrimitive::Bool => json!({ "type": "boolean" }),
+        ScalarPrimitive::Char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+        ScalarPrimitive::Str => json!({ "type": "string" }),
+    }
+}
+
+/// Extract the arguments of all `#[serde(...)]` attributes as key-value pairs,
+/// e.g. `#[serde(rename = "a", default)]` becomes `[("rename", Some("a")), ("default", None)]`.
+fn serde_attributes(attrs: &[String]) -> Vec<(String, Option<String>)> {
+    let mut arguments = Vec::new();
+    for attr in attrs {
+        let Some(inner) = attr
+            .trim()
+            .strip_prefix("#[serde(")
+            .and_then(|a| a.strip_suffix(")]"))
+        else {
+            continue;
+        };
+        // Split on commas that are not inside a string literal.
+        let mut in_string = false;
+        let mut current = String::new();
+        let mut parts = Vec::new();
+        for c in inner.chars() {
+            match c {
+                '"' => {
+                    in_string = !in_string;
+                    current.push(c);
+                }
+                ',' if !in_string => parts.push(std::mem::take(&mut current)),
+                c => current.push(c),
+            }
+        }
+        parts.push(current);
+        for part in parts {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            match part.split_once('=') {
+                Some((key, value)) => arguments.push((
+                    key.trim().to_owned(),
+                    Some(value.trim().trim_matches('"').to_owned()),
+                )),
+                None => arguments.push((part.to_owned(), None)),
+            }
+        }
+    }
+    arguments
+}
+
+/// Apply a `#[serde(rename_all = "...")]` rule.
+///
+/// Fields are expected to be in `snake_case`, variants in `PascalCase`.
+fn rename(name: &str, rule: Option<&str>, is_variant: bool) -> String {
+    let Some(rule) = rule else {
+        return name.to_owned();
+    };
+    // Normalize to a list of lowercase words.
+    let words: Vec<String> = if is_variant {
+        let mut words = Vec::new();
+        let mut current = String::new();
+        for c in name.chars() {
+            if c.is_uppercase() && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            current.extend(c.to_lowercase());
+        }
+        words.push(current);
+        words
+    } else {
+        name.split('_').map(str::to_owned).collect()
+    };
+    let capitalize = |w: &str| {
+        let mut chars = w.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+            None => String::new(),
+        }
+    };
+    match rule {
+        "lowercase" => words.concat(),
+        "UPPERCASE" => words.concat().to_uppercase(),
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        "PascalCase" => words.iter().map(|w| capitalize(w)).collect(),
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.to_owned() } else { capitalize(w) })
+            .collect(),
+        _ => name.to_owned(),
+    }
+}
+
+/// Generate a sample YAML configuration file for the given schema.
+///
+/// Required keys are set to a placeholder value of the expected type, optional keys
+/// are commented out.
+fn sample_yaml(schema: &Value) -> String {
+    let mut out = String::from(
+        "# A sample configuration file for the application, generated by Pavex.\n\
+        # Required keys are set to a placeholder value, optional keys are commented out.\n",
+    );
+    let mut lookup = HashMap::new();
+    if let Some(Value::Object(defs)) = schema.get("$defs") {
+        for (name, def) in defs {
+            lookup.insert(format!("#/$defs/{name}"), def);
+        }
+    }
+    write_object(schema, &lookup, 0, false, &mut out, &mut Vec::new());
+    out
+}
+
+fn resolve<'a>(schema: &'a Value, lookup: &HashMap<String, &'a Value>) -> &'a Value {
+    match schema.get("$ref").and_then(Value::as_str) {
+        Some(r) => lookup.get(r).copied().unwrap_or(schema),
+        None => schema,
+    }
+}
+
+fn write_object<'a>(
+    schema: &'a Value,
+    lookup: &HashMap<String, &'a Value>,
+    depth: usize,
+    commented: bool,
+    out: &mut String,
+    visiting: &mut Vec<&'a str>,
+) {
+    let Some(Value::Object(properties)) = schema.get("properties") else {
+        return;
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let indent = "  ".repeat(depth);
+    for (key, property) in properties {
+        let is_required = required.contains(&key.as_str());
+        let commented = commented || !is_required;
+        let prefix = if commented { "# " } else { "" };
+        let (property, nullable) = strip_null(property);
+        let is_secret = property.get(SECRET_KEYWORD) == Some(&Value::Bool(true));
+        let reference = property.get("$ref").and_then(Value::as_str);
+        let property = resolve(property, lookup);
+
+        let mut comment = vec![describe(property)];
+        if is_secret {
+            comment.push("secret".to_owned());
+        }
+        if let Some(description) = property
+            .get("title")
+            .or_else(|| property.get("description"))
+            .and_then(Value::as_str)
+        {
+            comment.push(description.to_owned());
+        }
+        if !is_required || nullable {
+            comment.push("optional".to_owned());
+        }
+        out.push_str(&format!("{indent}# {}\n", comment.join(", ")));
+
+        let is_object = property.get("properties").is_some();
+        let recursive = reference.is_some_and(|r| visiting.contains(&r));
+        if is_object && !recursive {
+            out.push_str(&format!("{indent}{prefix}{key}:\n"));
+            if let Some(r) = reference {
+                visiting.push(r);
+            }
+            write_object(property, lookup, depth + 1, commented, out, visiting);
+            if reference.is_some() {
+                visiting.pop();
+            }
+        } else {
+            out.push_str(&format!(
+                "{indent}{prefix}{key}: {}\n",
+                placeholder(property)
+            ));
+        }
+    }
+}
+
+/// Unwrap `anyOf: [T, null]`, the schema we emit for `Option<T>`.
+fn strip_null(schema: &Value) -> (&Value, bool) {
+    if let Some([inner, null]) = schema
+        .get("anyOf")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+    {
+        if null.get("type").and_then(Value::as_str) == Some("null") {
+            return (inner, true);
+        }
+    }
+    (schema, false)
+}
+
+fn describe(schema: &Value) -> String {
+    if let Some(Value::Array(variants)) = schema.get("enum") {
+        let variants: Vec<_> = variants.iter().map(|v| v.to_string()).collect();
+        return format!("one of {}", variants.join(", "));
+    }
+    match schema.get("type").and_then(Value::as_str) {
+        Some("array") => "list".to_owned(),
+        Some("object") if schema.get("properties").is_none() => "map".to_owned(),
+        Some(t) => t.to_owned(),
+        None => "any".to_owned(),
+    }
+}
+
+fn placeholder(schema: &Value) -> String {
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|v| v.first())
+    {
+        return first.to_string();
+    }
+    match schema.get("type").and_then(Value::as_str) {
+        Some("string") => "\"\"".to_owned(),
+        Some("integer") => schema
+            .get("minimum")
+            .and_then(Value::as_i64)
+            .unwrap_or(0)
+            .to_string(),
+        Some("number") => "0.0".to_owned(),
+        Some("boolean") => "false".to_owned(),
+        Some("array") => "[]".to_owned(),
+        Some("object") => "{}".to_owned(),
+        _ => "~".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rename, sample_yaml, serde_attributes};
+    use serde_json::json;
+
+    #[test]
+    fn serde_attributes_are_parsed() {
+        let attrs = vec![
+            "#[serde(rename = \"a,b\", default)]".to_owned(),
+            "#[non_exhaustive]".to_owned(),
+        ];
+        assert_eq!(
+            serde_attributes(&attrs),
+            vec![
+                ("rename".to_owned(), Some("a,b".to_owned())),
+                ("default".to_owned(), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_rules() {
+        assert_eq!(
+            rename("max_pool_size", Some("camelCase"), false),
+            "maxPoolSize"
+        );
+        assert_eq!(
+            rename("max_pool_size", Some("kebab-case"), false),
+            "max-pool-size"
+        );
+        assert_eq!(rename("LocalDev", Some("snake_case"), true), "local_dev");
+        assert_eq!(
+            rename("LocalDev", Some("SCREAMING_SNAKE_CASE"), true),
+            "LOCAL_DEV"
+        );
+        assert_eq!(rename("LocalDev", None, true), "LocalDev");
+    }
+
+    #[test]
+    fn secrets_are_marked_in_the_sample() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "database": { "$ref": "#/$defs/DatabaseConfig" },
+            },
+            "required": ["database"],
+            "$defs": {
+                "DatabaseConfig": {
+                    "type": "object",
+                    "title": "DatabaseConfig",
+                    "properties": {
+                        "password": { "type": "string", "writeOnly": true, "x-pavex-secret": true },
+                    },
+                    "required": ["password"],
+                }
+            }
+        });
+        let sample = sample_yaml(&schema);
+        assert!(
+            sample.ends_with("database:\n  # string, secret\n  password: \"\"\n"),
+            "{sample}"
+        );
+    }
+}
@@ -20,6 +20,7 @@ use crate::diagnostic::{AnnotatedSnippet, CompilerDiagnostic, HelpWithSnippet, S
 use crate::language::{Callable, ResolvedType};
 use crate::rustdoc::CrateCollection;
 use crate::try_source;
+use crate::utils::comma_separated_list;
 
 use super::framework_items::FrameworkItemDb;
 
@@ -58,6 +59,12 @@ impl ConstructibleDb {
         diagnostics: &mut Vec<miette::Error>,
     ) -> Self {
         let mut self_ = Self::_build(component_db, computation_db);
+        self_.verify_imported_constructors_do_not_conflict(
+            component_db,
+            computation_db,
+            package_graph,
+            diagnostics,
+        );
         self_.detect_missing_constructors(
             component_db,
             computation_db,
@@ -444,6 +451,115 @@ impl ConstructibleDb {
         }
     }
 
+    /// When multiple constructors are registered for the same type in the same scope,
+    /// the last one takes precedence.
+    ///
+    /// That's a reasonable behaviour when constructors are registered explicitly, but it's a
+    /// recipe for surprises when two different crates, imported via `Blueprint::import`,
+    /// export a constructor for the same type: the winner would be determined by the order
+    /// of the imports.
+    /// We report an error instead, unless the user has explicitly registered a constructor
+    /// for that type after the imports.
+    fn verify_imported_constructors_do_not_conflict(
+        &self,
+        component_db: &ComponentDb,
+        computation_db: &ComputationDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let user_component_db = component_db.user_component_db();
+        let mut key2component_ids: IndexMap<(ScopeId, ResolvedType), Vec<ComponentId>> =
+            IndexMap::new();
+        for (component_id, _) in component_db.constructors(computation_db) {
+            if component_db.user_component_id(component_id).is_none() {
+                continue;
+            }
+            // For fallible constructors, the constructed type is the output of the `Ok` matcher.
+            let constructible_id = component_db
+                .match_ids(component_id)
+                .map(|(ok_id, _)| *ok_id)
+                .unwrap_or(component_id);
+            let component = component_db.hydrated_component(constructible_id, computation_db);
+            let Some(output) = component.output_type() else {
+                continue;
+            };
+            key2component_ids
+                .entry((component_db.scope_id(component_id), output.to_owned()))
+                .or_default()
+                .push(component_id);
+        }
+
+        let import_source = |component_id: ComponentId| {
+            component_db
+                .user_component_id(component_id)
+                .and_then(|id| user_component_db.get_import_source(id))
+        };
+        for ((scope_id, type_), component_ids) in key2component_ids {
+            let Some(winner_id) = self
+                .scope_id2constructibles
+                .get(&scope_id)
+                .and_then(|c| c.type2constructor_id.get(&type_))
+            else {
+                continue;
+            };
+            let Some(winner_id) = component_ids.iter().copied().find(|id| {
+                id == winner_id
+                    || component_db.match_ids(*id).map(|(ok_id, _)| ok_id) == Some(winner_id)
+            }) else {
+                continue;
+            };
+            // The user has explicitly picked a constructor: there's no ambiguity.
+            if import_source(winner_id).is_none() {
+                continue;
+            }
+            let sources: IndexSet<&str> = component_ids
+                .iter()
+                .filter_map(|id| import_source(*id))
+                .collect();
+            if sources.len() < 2 {
+                continue;
+            }
+
+            let mut snippets = Vec::new();
+            let mut source_code = None;
+            for component_id in &component_ids {
+                let Some(crate_name) = import_source(*component_id) else {
+                    continue;
+                };
+                let (Some(source), Some(source_span)) =
+                    component_db.registration_span(*component_id, package_graph, diagnostics)
+                else {
+                    continue;
+                };
+                if source_code.is_none() {
+                    source_code = Some(source.clone());
+                }
+                let label = source_span.labeled(format!(
+                    "A constructor for `{type_:?}` was imported from `{crate_name}` here"
+                ));
+                snippets.push(AnnotatedSnippet::new(source, label));
+            }
+            let mut crate_list = String::new();
+            comma_separated_list(&mut crate_list, sources.iter(), |s| format!("`{s}`"), "and")
+                .unwrap();
+            let error = anyhow::anyhow!(
+                "There are conflicting constructors for `{type_:?}`.\n\
+                {crate_list} all export a constructor for `{type_:?}` and you imported \
+                all of them. I don't know which one to use!",
+            );
+            let diagnostic = CompilerDiagnostic::builder(error)
+                .optional_source(source_code)
+                .additional_annotated_snippets(snippets.into_iter())
+                .help(format!(
+                    "Register the constructor you want to use for `{type_:?}` explicitly, \
+                    after the imports (e.g. via `bp.request_scoped(f!(...))`): \
+                    explicit registrations take precedence over imported ones."
+                ))
+                .build();
+            diagnostics.push(diagnostic.into());
+        }
+    }
+
     /// Singletons are built before the application starts, outside of the request-response cycle.
     ///
     /// Therefore they cannot depend on types which have a request-scoped lifecycle.
@@ -916,6 +1032,15 @@ impl ConstructibleDb {
                     )),
                 ));
         }
+        if let Some(handle_type) = component_db
+            .derived_from(&dependency_id)
+            .and_then(|config_id| component_db.reloadable_handle(config_id))
+        {
+            diagnostic_builder = diagnostic_builder.help(format!(
+                "`{dependency_type:?}` is a reloadable configuration type. \
+                Take `&{handle_type:?}` as input instead, and read the current values when you need them."
+            ));
+        }
         diagnostics.push(diagnostic_builder.build().into());
     }
 
@@ -0,0 +1,605 @@
+use std::time::Duration;
+
+use ahash::{HashSet, HashSetExt};
+use convert_case::{Case, Casing};
+use guppy::graph::PackageGraph;
+use indexmap::IndexMap;
+use petgraph::visit::{Dfs, Reversed};
+use syn::spanned::Spanned;
+
+use pavex_bp_schema::{CloningStrategy, Lifecycle, LifecyclePhase};
+
+use super::call_graph::{ApplicationStateCallGraph, CallGraphNode};
+use super::components::{ComponentDb, ComponentId};
+use super::computations::ComputationDb;
+use super::constructibles::ConstructibleDb;
+use super::user_components::{ScopeId, UserComponent, UserComponentId};
+use crate::compiler::utils::{get_err_variant, get_ok_variant, process_framework_path};
+use crate::diagnostic::{
+    self, AnnotatedSnippet, CallableDefinition, CompilerDiagnostic, ComponentKind,
+    OptionalSourceSpanExt, SourceSpanExt,
+};
+use crate::language::{Callable, ResolvedType};
+use crate::rustdoc::CrateCollection;
+use crate::try_source;
+
+/// The name of the `ApplicationStateError` variant used to report hooks that timed out.
+const TIMEOUT_VARIANT: &str = "HookTimeout";
+
+/// The startup and shutdown hooks registered via `Blueprint::on_startup` and
+/// `Blueprint::on_shutdown`.
+pub(crate) struct LifecycleHooks {
+    /// The valid hooks, in registration order.
+    hooks: Vec<LifecycleHook>,
+    /// The indexes of the startup hooks in `hooks`, in invocation order.
+    startup_order: Vec<usize>,
+    /// The indexes of the shutdown hooks in `hooks`, in invocation order.
+    shutdown_order: Vec<usize>,
+    /// The `ApplicationStateError` variants required to report hook failures.
+    error_variants: IndexMap<String, ResolvedType>,
+}
+
+pub(crate) struct LifecycleHook {
+    pub(crate) callable: Callable,
+    pub(crate) phase: LifecyclePhase,
+    pub(crate) timeout: Option<Duration>,
+    /// The singletons taken as input by the hook, alongside the id of their constructor.
+    pub(crate) singletons: Vec<(ResolvedType, ComponentId)>,
+    /// The error type returned by the hook, if it's fallible.
+    pub(crate) error_type: Option<ResolvedType>,
+    /// The `ApplicationStateError` variant used to report the hook's errors, if it's fallible.
+    pub(crate) error_variant: Option<String>,
+}
+
+impl LifecycleHooks {
+    /// Validate all registered hooks.
+    ///
+    /// Hooks are invoked outside of the request-response lifecycle: they can only
+    /// take singletons as inputs, and they must return either `()` or `Result<(), E>`.
+    #[tracing::instrument("Validate lifecycle hooks", skip_all)]
+    pub(crate) fn new(
+        component_db: &ComponentDb,
+        computation_db: &ComputationDb,
+        constructible_db: &ConstructibleDb,
+        package_graph: &PackageGraph,
+        krate_collection: &CrateCollection,
+        diagnostics: &mut Vec<miette::Error>,
+    ) -> Self {
+        let user_component_db = component_db.user_component_db();
+        let mut hooks = Vec::new();
+        for (user_component_id, user_component) in user_component_db.lifecycle_hooks() {
+            let UserComponent::LifecycleHook {
+                scope_id,
+                phase,
+                timeout,
+                ..
+            } = user_component
+            else {
+                unreachable!()
+            };
+            let Ok(error_type) = unit_or_result_output(
+                ComponentKind::LifecycleHook,
+                user_component_id,
+                component_db,
+                computation_db,
+                package_graph,
+                diagnostics,
+            ) else {
+                continue;
+            };
+            let Some(singletons) = singleton_inputs(
+                ComponentKind::LifecycleHook,
+                user_component_id,
+                *scope_id,
+                |_| false,
+                component_db,
+                computation_db,
+                constructible_db,
+                package_graph,
+                krate_collection,
+                diagnostics,
+            ) else {
+                continue;
+            };
+            let callable = &computation_db[user_component_id];
+
+            hooks.push(LifecycleHook {
+                callable: callable.to_owned(),
+                phase: *phase,
+                timeout: *timeout,
+                singletons,
+                error_type,
+                error_variant: None,
+            });
+        }
+
+        let startup_order = hooks
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| h.phase == LifecyclePhase::Startup)
+            .map(|(i, _)| i)
+            .collect();
+        let shutdown_order = hooks
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| h.phase == LifecyclePhase::Shutdown)
+            .map(|(i, _)| i)
+            .collect();
+        Self {
+            hooks,
+            startup_order,
+            shutdown_order,
+            error_variants: IndexMap::new(),
+        }
+    }
+
+    /// The singletons that must be stored in the application state
+    /// for the hooks to be invoked.
+    pub(crate) fn required_singletons(
+        &self,
+    ) -> impl Iterator<Item = &(ResolvedType, ComponentId)> + '_ {
+        self.hooks.iter().flat_map(|h| h.singletons.iter())
+    }
+
+    /// Determine the invocation order of the hooks and the error variants required
+    /// to report their failures, now that we know how the application state is built.
+    pub(crate) fn finalize(
+        &mut self,
+        application_state_call_graph: &ApplicationStateCallGraph,
+        component_db: &ComponentDb,
+        computation_db: &ComputationDb,
+        krate_collection: &CrateCollection,
+    ) {
+        let depends_on =
+            self.dependencies(application_state_call_graph, component_db, computation_db);
+        self.startup_order = dependency_order(&self.startup_order, &depends_on);
+        // Tear down in the reverse order of setup, but keep registration order for
+        // hooks that are independent of each other.
+        self.shutdown_order = reverse_dependency_order(&self.shutdown_order, &depends_on);
+
+        let existing = &application_state_call_graph.error_variants;
+        let mut taken: HashSet<String> = existing.keys().cloned().collect();
+        for hook in &mut self.hooks {
+            let Some(error_type) = &hook.error_type else {
+                continue;
+            };
+            let base = variant_name(&hook.callable);
+            let mut name = base.clone();
+            let mut n = 2;
+            while taken.contains(&name) || name == TIMEOUT_VARIANT {
+                name = format!("{base}{n}");
+                n += 1;
+            }
+            taken.insert(name.clone());
+            self.error_variants
+                .insert(name.clone(), error_type.to_owned());
+            hook.error_variant = Some(name);
+        }
+        if self.hooks.iter().any(|h| h.timeout.is_some()) {
+            self.error_variants.insert(
+                TIMEOUT_VARIANT.into(),
+                process_framework_path("pavex::lifecycle::HookTimeoutError", krate_collection),
+            );
+        }
+    }
+
+    /// For each hook, the set of hooks that it depends on—i.e. at least one of its
+    /// singletons is built (directly or indirectly) from one of the singletons used by
+    /// the other hook.
+    fn dependencies(
+        &self,
+        application_state_call_graph: &ApplicationStateCallGraph,
+        component_db: &ComponentDb,
+        computation_db: &ComputationDb,
+    ) -> Vec<HashSet<usize>> {
+        let call_graph = &application_state_call_graph.call_graph.call_graph;
+        let hook2nodes: Vec<HashSet<_>> = self
+            .hooks
+            .iter()
+            .map(|hook| {
+                call_graph
+                    .node_indices()
+                    .filter(|i| match &call_graph[*i] {
+                        CallGraphNode::Compute { component_id, .. } => {
+                            hook.singletons.iter().any(|(ty, id)| {
+                                id == component_id
+                                    || component_db
+                                        .hydrated_component(*component_id, computation_db)
+                                        .output_type()
+                                        == Some(ty)
+                            })
+                        }
+                        CallGraphNode::InputParameter { type_, .. } => {
+                            hook.singletons.iter().any(|(ty, _)| ty == type_)
+                        }
+                        CallGraphNode::MatchBranching => false,
+                    })
+                    .collect()
+            })
+            .collect();
+        hook2nodes
+            .iter()
+            .enumerate()
+            .map(|(i, nodes)| {
+                let mut ancestors = HashSet::new();
+                let reversed = Reversed(call_graph);
+                for node in nodes {
+                    let mut dfs = Dfs::new(reversed, *node);
+                    while let Some(ancestor) = dfs.next(reversed) {
+                        if ancestor != *node {
+                            ancestors.insert(ancestor);
+                        }
+                    }
+                }
+                hook2nodes
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, other)| *j != i && other.iter().any(|n| ancestors.contains(n)))
+                    .map(|(j, _)| j)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The startup hooks, in invocation order.
+    pub(crate) fn startup(&self) -> impl Iterator<Item = &LifecycleHook> + '_ {
+        self.startup_order.iter().map(|i| &self.hooks[*i])
+    }
+
+    /// The shutdown hooks, in invocation order.
+    pub(crate) fn shutdown(&self) -> impl Iterator<Item = &LifecycleHook> + '_ {
+        self.shutdown_order.iter().map(|i| &self.hooks[*i])
+    }
+
+    /// All hooks, in registration order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &LifecycleHook> + '_ {
+        self.hooks.iter()
+    }
+
+    pub(crate) fn has_startup_hooks(&self) -> bool {
+        !self.startup_order.is_empty()
+    }
+
+    pub(crate) fn has_shutdown_hooks(&self) -> bool {
+        !self.shutdown_order.is_empty()
+    }
+
+    /// The `ApplicationStateError` variants required to report hook failures.
+    pub(crate) fn error_variants(&self) -> &IndexMap<String, ResolvedType> {
+        &self.error_variants
+    }
+}
+
+fn is_unit(t: &ResolvedType) -> bool {
+    matches!(t, ResolvedType::Tuple(t) if t.elements.is_empty())
+}
+
+/// Sort `ids` so that each hook comes after the hooks it depends on.
+/// Ties are broken using the original order.
+fn dependency_order(ids: &[usize], depends_on: &[HashSet<usize>]) -> Vec<usize> {
+    let mut remaining = ids.to_vec();
+    let mut sorted = Vec::with_capacity(ids.len());
+    while !remaining.is_empty() {
+        let position = remaining
+            .iter()
+            .position(|i| depends_on[*i].iter().all(|dep| !remaining.contains(dep)))
+            // There is a cycle: hooks depend on each other through different singletons.
+            // Fall back to registration order.
+            .unwrap_or(0);
+        sorted.push(remaining.remove(position));
+    }
+    sorted
+}
+
+/// Sort `ids` so that each hook comes before the hooks it depends on.
+/// Ties are broken using the original order.
+fn reverse_dependency_order(ids: &[usize], depends_on: &[HashSet<usize>]) -> Vec<usize> {
+    let mut remaining = ids.to_vec();
+    let mut sorted = Vec::with_capacity(ids.len());
+    while !remaining.is_empty() {
+        let position = remaining
+            .iter()
+            .position(|i| {
+                remaining
+                    .iter()
+                    .all(|other| other == i || !depends_on[*other].contains(i))
+            })
+            // There is a cycle, see `dependency_order`.
+            .unwrap_or(0);
+        sorted.push(remaining.remove(position));
+    }
+    sorted
+}
+
+/// The name of the `ApplicationStateError` variant for a fallible hook,
+/// following the same conventions used for fallible singleton constructors.
+fn variant_name(callable: &Callable) -> String {
+    let n_path_segments = callable.path.segments.len();
+    let last_segment = callable.path.segments[n_path_segments - 1]
+        .ident
+        .to_case(Case::Pascal);
+    if n_path_segments >= 3 {
+        let second_to_last_segment = &callable.path.segments[n_path_segments - 2].ident;
+        if second_to_last_segment.is_case(Case::Pascal) {
+            // This is likely to be a method on a struct/enum.
+            return format!("{second_to_last_segment}{last_segment}");
+        }
+    }
+    last_segment
+}
+
+/// Check that a component invoked outside of the request-response lifecycle
+/// (e.g. a lifecycle hook or a background task) returns either `()` or `Result<(), E>`.
+///
+/// It returns the error type, if the component is fallible.
+/// It returns `Err` if the output type is invalid, after emitting a diagnostic.
+pub(super) fn unit_or_result_output(
+    kind: ComponentKind,
+    user_component_id: UserComponentId,
+    component_db: &ComponentDb,
+    computation_db: &ComputationDb,
+    package_graph: &PackageGraph,
+    diagnostics: &mut Vec<miette::Error>,
+) -> Result<Option<ResolvedType>, ()> {
+    let callable = &computation_db[user_component_id];
+    let output = match &callable.output {
+        None => return Ok(None),
+        Some(output) if is_unit(output) => return Ok(None),
+        Some(output) if output.is_result() && is_unit(get_ok_variant(output)) => {
+            return Ok(Some(get_err_variant(output).to_owned()));
+        }
+        Some(output) => output,
+    };
+    let user_component_db = component_db.user_component_db();
+    let location = user_component_db.get_location(user_component_id);
+    let source = try_source!(location, package_graph, diagnostics);
+    let label = source.as_ref().and_then(|source| {
+        diagnostic::get_f_macro_invocation_span(source, location)
+            .labeled(format!("The {kind} was registered here"))
+    });
+    let e = anyhow::anyhow!(
+        "{} must return either `()` or `Result<(), E>`.\n\
+        `{}` returns `{}`.",
+        plural(kind),
+        callable.path,
+        output.display_for_error()
+    );
+    let diagnostic = CompilerDiagnostic::builder(e)
+        .optional_source(source)
+        .optional_label(label)
+        .help(format!(
+            "If the {kind} produces a value you need at runtime, register it as a singleton \
+            constructor instead."
+        ))
+        .build();
+    diagnostics.push(diagnostic.into());
+    Err(())
+}
+
+/// Map the inputs of a component invoked outside of the request-response lifecycle
+/// (e.g. a lifecycle hook or a background task) to the singletons they refer to.
+///
+/// Inputs for which `is_framework_input` returns `true` are provided by the framework:
+/// they are skipped.
+/// It returns `None` if any of the inputs is invalid, after emitting a diagnostic.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn singleton_inputs(
+    kind: ComponentKind,
+    user_component_id: UserComponentId,
+    scope_id: ScopeId,
+    is_framework_input: impl Fn(&ResolvedType) -> bool,
+    component_db: &ComponentDb,
+    computation_db: &ComputationDb,
+    constructible_db: &ConstructibleDb,
+    package_graph: &PackageGraph,
+    krate_collection: &CrateCollection,
+    diagnostics: &mut Vec<miette::Error>,
+) -> Option<Vec<(ResolvedType, ComponentId)>> {
+    let callable = &computation_db[user_component_id];
+    let mut singletons = Vec::with_capacity(callable.inputs.len());
+    let mut is_valid = true;
+    for (input_index, input) in callable.inputs.iter().enumerate() {
+        if is_framework_input(input) {
+            continue;
+        }
+        match resolve_singleton(input, scope_id, component_db, constructible_db) {
+            Ok(singleton) => singletons.push(singleton),
+            Err(reason) => {
+                invalid_input(
+                    kind,
+                    reason,
+                    input_index,
+                    user_component_id,
+                    component_db,
+                    computation_db,
+                    package_graph,
+                    krate_collection,
+                    diagnostics,
+                );
+                is_valid = false;
+            }
+        }
+    }
+    is_valid.then_some(singletons)
+}
+
+/// Find the singleton that can be used to provide `input`, taken either by value
+/// or by shared reference.
+pub(super) fn resolve_singleton(
+    input: &ResolvedType,
+    scope_id: ScopeId,
+    component_db: &ComponentDb,
+    constructible_db: &ConstructibleDb,
+) -> Result<(ResolvedType, ComponentId), InvalidInput> {
+    let (inner, by_value) = match input {
+        ResolvedType::Reference(r) if r.is_mutable => {
+            return Err(InvalidInput::MutableReference);
+        }
+        ResolvedType::Reference(r) if !r.lifetime.is_static() => (r.inner.as_ref(), false),
+        _ => (input, true),
+    };
+    let Some((component_id, _)) = constructible_db.get(scope_id, inner, component_db.scope_graph())
+    else {
+        return Err(InvalidInput::NotConstructible);
+    };
+    let lifecycle = component_db.lifecycle(component_id);
+    if lifecycle != Lifecycle::Singleton {
+        let reloadable_handle = component_db
+            .derived_from(&component_id)
+            .and_then(|config_id| component_db.reloadable_handle(config_id))
+            .cloned();
+        return Err(InvalidInput::NotASingleton {
+            lifecycle,
+            reloadable_handle,
+        });
+    }
+    if by_value && component_db.cloning_strategy(component_id) != CloningStrategy::CloneIfNecessary
+    {
+        return Err(InvalidInput::NotCloneable);
+    }
+    Ok((inner.to_owned(), component_id))
+}
+
+/// Capitalize and pluralize the name of a component kind, e.g. `Lifecycle hooks`.
+fn plural(kind: ComponentKind) -> String {
+    let kind = kind.to_string();
+    let mut chars = kind.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}s", first.to_uppercase(), chars.as_str()),
+        None => kind,
+    }
+}
+
+pub(super) enum InvalidInput {
+    MutableReference,
+    NotConstructible,
+    NotASingleton {
+        lifecycle: Lifecycle,
+        reloadable_handle: Option<ResolvedType>,
+    },
+    NotCloneable,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn invalid_input(
+    kind: ComponentKind,
+    reason: InvalidInput,
+    input_index: usize,
+    user_component_id: UserComponentId,
+    component_db: &ComponentDb,
+    computation_db: &ComputationDb,
+    package_graph: &PackageGraph,
+    krate_collection: &CrateCollection,
+    diagnostics: &mut Vec<miette::Error>,
+) {
+    let user_component_db = component_db.user_component_db();
+    let callable = &computation_db[user_component_id];
+    let input = &callable.inputs[input_index];
+    let location = user_component_db.get_location(user_component_id);
+    let source = try_source!(location, package_graph, diagnostics);
+    let label = source.as_ref().and_then(|source| {
+        diagnostic::get_f_macro_invocation_span(source, location)
+            .labeled(format!("The {kind} was registered here"))
+    });
+    let definition_snippet = CallableDefinition::compute(callable, krate_collection, package_graph)
+        .map(|def| {
+            let label = def
+                .convert_local_span(def.sig.inputs[input_index].span())
+                .labeled("The invalid input".into());
+            AnnotatedSnippet::new(def.named_source(), label)
+        });
+    let input = input.display_for_error();
+    let kinds = plural(kind);
+    let (e, help) = match reason {
+        InvalidInput::MutableReference => (
+            anyhow::anyhow!(
+                "{kinds} can't take mutable references (`{input}`) as inputs.\n\
+                Singletons are shared with all request handlers, they can't be mutated."
+            ),
+            Some(
+                "Take a shared reference instead. \
+                If you need to mutate the singleton, consider internal mutability (e.g. `Arc<Mutex<..>>`)."
+                    .to_owned(),
+            ),
+        ),
+        InvalidInput::NotConstructible => (
+            anyhow::anyhow!(
+                "{kinds} can only take singletons as inputs.\n\
+                But I can't find a constructor for `{input}`, the input parameter #{input_index} of `{}`.",
+                callable.path
+            ),
+            Some(
+                "Register a singleton constructor, a prebuilt type or a configuration type for it."
+                    .to_owned(),
+            ),
+        ),
+        InvalidInput::NotASingleton {
+            lifecycle,
+            reloadable_handle,
+        } => (
+            anyhow::anyhow!(
+                "{kinds} can only take singletons as inputs.\n\
+                They are invoked outside of the request-response lifecycle, but `{input}` has a \
+                {lifecycle} lifecycle.",
+            ),
+            Some(match reloadable_handle {
+                Some(handle) => format!(
+                    "`{input}` is a reloadable configuration type. \
+                    Take `&{}` as input instead.",
+                    handle.display_for_error()
+                ),
+                None => "Change the lifecycle of its constructor to singleton.".to_owned(),
+            }),
+        ),
+        InvalidInput::NotCloneable => (
+            anyhow::anyhow!(
+                "{kinds} can only take singletons by value if they can be cloned.\n\
+                `{input}` is marked as `NeverClone`.",
+            ),
+            Some(format!(
+                "Take `&{input}` as input, or mark `{input}` as `CloneIfNecessary`."
+            )),
+        ),
+    };
+    let diagnostic = CompilerDiagnostic::builder(e)
+        .optional_source(source)
+        .optional_label(label)
+        .optional_additional_annotated_snippet(definition_snippet)
+        .optional_help(help)
+        .build();
+    diagnostics.push(diagnostic.into());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dependency_order, reverse_dependency_order};
+    use ahash::HashSet;
+
+    fn deps(edges: &[&[usize]]) -> Vec<HashSet<usize>> {
+        edges.iter().map(|e| e.iter().copied().collect()).collect()
+    }
+
+    #[test]
+    fn hooks_run_after_their_dependencies_at_startup() {
+        // Hook #0 depends on hook #2.
+        let depends_on = deps(&[&[2], &[], &[]]);
+        assert_eq!(dependency_order(&[0, 1, 2], &depends_on), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn hooks_run_before_their_dependencies_at_shutdown() {
+        // Hook #2 depends on hook #0.
+        let depends_on = deps(&[&[], &[], &[0]]);
+        assert_eq!(
+            reverse_dependency_order(&[0, 1, 2], &depends_on),
+            vec![1, 2, 0]
+        );
+
+        // Independent hooks are invoked in registration order.
+        let depends_on = deps(&[&[], &[], &[]]);
+        assert_eq!(
+            reverse_dependency_order(&[0, 1, 2], &depends_on),
+            vec![0, 1, 2]
+        );
+    }
+}
@@ -1,14 +1,17 @@
 pub(crate) mod application_config;
 pub(crate) mod application_state;
+pub(crate) mod background_tasks;
 pub(crate) mod call_graph;
 pub(crate) mod cloning;
 pub(crate) mod components;
 pub(crate) mod computations;
+pub(crate) mod config_schema;
 pub(crate) mod config_types;
 pub(crate) mod constructibles;
 pub(crate) mod domain;
 pub(crate) mod framework_items;
 pub(crate) mod into_error;
+pub(crate) mod lifecycle_hooks;
 pub(crate) mod prebuilt_types;
 pub(crate) mod processing_pipeline;
 pub(crate) mod route_path;
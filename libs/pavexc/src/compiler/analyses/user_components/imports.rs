@@ -0,0 +1,440 @@
+//! Expand the imports registered via `Blueprint::import` into the components that
+//! have been annotated with one of Pavex's attribute macros (e.g. `#[pavex::get]`).
+//!
+//! The attribute macros don't generate any code: they attach a normalized
+//! `#[diagnostic::pavex::<kind>(...)]` attribute to the annotated function, which
+//! we then retrieve from the JSON documentation of the imported modules.
+//!
+//! Modules can be imported from the current crate as well as from its dependencies.
+//! For dependencies, only the public items are taken into account: they are the components
+//! that the dependency chose to export.
+use std::collections::BTreeMap;
+
+use ahash::{HashMap, HashSet, HashSetExt};
+use guppy::PackageId;
+use guppy::graph::PackageGraph;
+use pavex_bp_schema::{
+    Blueprint, Callable, CloningStrategy, Component, Constructor, ErrorObserver, Import, Lifecycle,
+    MethodGuard, NestedBlueprint, PostProcessingMiddleware, PreProcessingMiddleware,
+    RawIdentifiers, RegisteredAt, Route, WrappingMiddleware,
+};
+use pavex_cli_diagnostic::anyhow2miette;
+use rustdoc_types::{Item, ItemEnum, Visibility};
+use syn::punctuated::Punctuated;
+
+use crate::diagnostic::{self, CompilerDiagnostic, OptionalSourceSpanExt};
+use crate::language::{PathKind, ResolvedPath};
+use crate::rustdoc::{Crate, CrateCollection};
+use crate::try_source;
+
+/// The callables registered via `Blueprint::import`, mapped to the name of the
+/// crate they were imported from.
+pub(super) type ImportedComponents = HashMap<RawIdentifiers, String>;
+
+/// Return a copy of the blueprint where all imports (including those registered
+/// against nested blueprints) have been replaced by the components they point to.
+///
+/// Every imported component is recorded in `imported`.
+/// Default error handlers (i.e. functions annotated with `#[pavex::error_handler]`) don't
+/// map to a blueprint component: they are collected in `default_error_handlers` instead.
+#[tracing::instrument(name = "Expand imports", skip_all, level = "trace")]
+pub(super) fn expand_imports(
+    bp: &Blueprint,
+    package_graph: &PackageGraph,
+    krate_collection: &CrateCollection,
+    imported: &mut ImportedComponents,
+    default_error_handlers: &mut Vec<Callable>,
+    diagnostics: &mut Vec<miette::Error>,
+) -> Blueprint {
+    let mut components = Vec::with_capacity(bp.components.len());
+    for component in &bp.components {
+        match component {
+            Component::Import(import) => {
+                components.extend(expand_import(
+                    import,
+                    package_graph,
+                    krate_collection,
+                    imported,
+                    default_error_handlers,
+                    diagnostics,
+                ));
+            }
+            Component::NestedBlueprint(nested) => {
+                components.push(Component::NestedBlueprint(NestedBlueprint {
+                    blueprint: expand_imports(
+                        &nested.blueprint,
+                        package_graph,
+                        krate_collection,
+                        imported,
+                        default_error_handlers,
+                        diagnostics,
+                    ),
+                    ..nested.clone()
+                }));
+            }
+            c => components.push(c.clone()),
+        }
+    }
+    Blueprint {
+        creation_location: bp.creation_location.clone(),
+        components,
+    }
+}
+
+/// A function (or a method) annotated with one of Pavex's attribute macros.
+struct AnnotatedItem {
+    /// The path of the module where the item was found, starting with the crate name.
+    module_path: Vec<String>,
+    /// The path of the item, starting with the crate name.
+    item_path: Vec<String>,
+    /// The normalized attribute, e.g. `#[diagnostic::pavex::constructor(lifecycle = "singleton")]`.
+    attribute: String,
+}
+
+/// What an annotated item turns into once imported.
+enum Imported {
+    Component(Component),
+    DefaultErrorHandler(Callable),
+}
+
+fn expand_import(
+    import: &Import,
+    package_graph: &PackageGraph,
+    krate_collection: &CrateCollection,
+    imported: &mut ImportedComponents,
+    default_error_handlers: &mut Vec<Callable>,
+    diagnostics: &mut Vec<miette::Error>,
+) -> Vec<Component> {
+    let mut components = Vec::new();
+    let mut visited = HashSet::new();
+    for source in &import.sources {
+        let identifiers = RawIdentifiers {
+            registered_at: import.registered_at.clone(),
+            import_path: source.to_owned(),
+        };
+        let path = match ResolvedPath::parse(&identifiers, package_graph, PathKind::Type) {
+            Ok(path) => path,
+            Err(e) => {
+                invalid_import(e.into(), import, package_graph, diagnostics);
+                continue;
+            }
+        };
+        let krate = match krate_collection.get_or_compute_crate_by_package_id(&path.package_id) {
+            Ok(krate) => krate,
+            Err(e) => {
+                diagnostics.push(anyhow2miette(anyhow::anyhow!(e).context(format!(
+                    "I failed to compute the JSON documentation for the crate that `{source}` belongs to."
+                ))));
+                continue;
+            }
+        };
+        let module_path: Vec<String> = path.segments.iter().map(|s| s.ident.clone()).collect();
+        let Some(module_id) = krate.get_module_id_by_path(&module_path) else {
+            let e = anyhow::anyhow!(
+                "There is no module named `{}`.\n\
+                `Blueprint::import` expects the path of a module (e.g. `crate::routes`) \
+                as input.",
+                module_path.join("::")
+            );
+            invalid_import(e, import, package_graph, diagnostics);
+            continue;
+        };
+
+        let crate_name = package_graph
+            .metadata(&path.package_id)
+            .map(|m| m.name().to_owned())
+            .unwrap_or_else(|_| path.crate_name().to_owned());
+        // Items that aren't public can only be imported from within the crate
+        // that defines them.
+        let only_public =
+            crate_name.replace('-', "_") != import.registered_at.crate_name.replace('-', "_");
+
+        let mut items = Vec::new();
+        collect_annotated_items(
+            krate,
+            module_id,
+            module_path,
+            only_public,
+            &mut visited,
+            &mut items,
+        );
+        for item in items {
+            let identifiers = RawIdentifiers {
+                registered_at: RegisteredAt {
+                    crate_name: crate_name.clone(),
+                    module_path: item.module_path.join("::"),
+                },
+                import_path: item.item_path.join("::"),
+            };
+            match annotated_item2component(&item.attribute, &identifiers, import) {
+                Ok(Imported::Component(component)) => {
+                    imported.insert(identifiers, crate_name.clone());
+                    components.push(component);
+                }
+                Ok(Imported::DefaultErrorHandler(error_handler)) => {
+                    imported.insert(identifiers, crate_name.clone());
+                    default_error_handlers.push(error_handler);
+                }
+                Err(e) => {
+                    let e = anyhow::anyhow!(e).context(format!(
+                        "I can't register `{}`: its annotation is malformed.",
+                        item.item_path.join("::")
+                    ));
+                    invalid_import(e, import, package_graph, diagnostics);
+                }
+            }
+        }
+    }
+    // Middlewares and error observers only apply to the routes registered after them:
+    // imported routes go last, so that they are wrapped by the imported middlewares
+    // no matter where they were discovered.
+    let (routes, mut components): (Vec<_>, Vec<_>) = components
+        .into_iter()
+        .partition(|c| matches!(c, Component::Route(_)));
+    components.extend(routes);
+    components
+}
+
+/// Walk the module tree, starting at `module_id`, to collect all annotated functions
+/// and all annotated methods defined in inherent `impl` blocks.
+///
+/// If `only_public` is set, items that aren't public (and the contents of modules that
+/// aren't public) are skipped.
+fn collect_annotated_items(
+    krate: &Crate,
+    module_id: rustdoc_types::Id,
+    module_path: Vec<String>,
+    only_public: bool,
+    visited: &mut HashSet<(PackageId, rustdoc_types::Id)>,
+    items: &mut Vec<AnnotatedItem>,
+) {
+    // The same module may be imported more than once, either directly
+    // or indirectly via one of its parents.
+    if !visited.insert((krate.core.package_id.clone(), module_id)) {
+        return;
+    }
+    let is_visible = |item: &Item| !only_public || item.visibility == Visibility::Public;
+    let module = krate.get_item_by_local_type_id(&module_id);
+    let ItemEnum::Module(module) = &module.inner else {
+        return;
+    };
+    for id in &module.items {
+        let Some(item) = krate.maybe_get_item_by_local_type_id(id) else {
+            continue;
+        };
+        let Some(name) = item.name.as_deref() else {
+            continue;
+        };
+        if !is_visible(&item) {
+            continue;
+        }
+        let item_path = [module_path.as_slice(), &[name.to_owned()]].concat();
+        let impls = match &item.inner {
+            ItemEnum::Module(_) => {
+                collect_annotated_items(krate, *id, item_path, only_public, visited, items);
+                continue;
+            }
+            ItemEnum::Function(_) => {
+                if let Some(attribute) = pavex_attribute(&item) {
+                    items.push(AnnotatedItem {
+                        module_path: module_path.clone(),
+                        item_path,
+                        attribute,
+                    });
+                }
+                continue;
+            }
+            ItemEnum::Struct(s) => &s.impls,
+            ItemEnum::Enum(e) => &e.impls,
+            _ => continue,
+        };
+        for impl_id in impls {
+            let Some(impl_item) = krate.maybe_get_item_by_local_type_id(impl_id) else {
+                continue;
+            };
+            let ItemEnum::Impl(impl_) = &impl_item.inner else {
+                continue;
+            };
+            if impl_.trait_.is_some() {
+                continue;
+            }
+            for method_id in &impl_.items {
+                let Some(method) = krate.maybe_get_item_by_local_type_id(method_id) else {
+                    continue;
+                };
+                let (ItemEnum::Function(_), Some(method_name)) = (&method.inner, &method.name)
+                else {
+                    continue;
+                };
+                if !is_visible(&method) {
+                    continue;
+                }
+                if let Some(attribute) = pavex_attribute(&method) {
+                    items.push(AnnotatedItem {
+                        module_path: module_path.clone(),
+                        item_path: [item_path.as_slice(), &[method_name.to_owned()]].concat(),
+                        attribute,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn pavex_attribute(item: &Item) -> Option<String> {
+    item.attrs
+        .iter()
+        .find(|a| a.starts_with("#[diagnostic::pavex::"))
+        .cloned()
+}
+
+fn annotated_item2component(
+    attribute: &str,
+    identifiers: &RawIdentifiers,
+    import: &Import,
+) -> Result<Imported, syn::Error> {
+    let attributes = syn::parse::Parser::parse_str(syn::Attribute::parse_outer, attribute)?;
+    let [attribute] = attributes.as_slice() else {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "Expected a single attribute",
+        ));
+    };
+    let kind = attribute
+        .path()
+        .segments
+        .last()
+        .map(|s| s.ident.to_string())
+        .unwrap_or_default();
+    let mut properties = BTreeMap::new();
+    if let syn::Meta::List(_) = &attribute.meta {
+        let args = attribute
+            .parse_args_with(Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated)?;
+        for arg in args {
+            let key = arg
+                .path
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(value),
+                ..
+            }) = &arg.value
+            else {
+                return Err(syn::Error::new_spanned(
+                    &arg.value,
+                    "Expected a string literal",
+                ));
+            };
+            properties.insert(key, value.value());
+        }
+    }
+
+    let callable = |import_path: String| Callable {
+        callable: RawIdentifiers {
+            registered_at: identifiers.registered_at.clone(),
+            import_path,
+        },
+        location: import.location.clone(),
+    };
+    let missing = |key: &str| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!("`{key}` is missing"),
+        )
+    };
+    let unknown = |key: &str, value: &str| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!("`{value}` is not a valid value for `{key}`"),
+        )
+    };
+    let this = callable(identifiers.import_path.clone());
+    let error_handler = properties.get("error_handler").cloned().map(callable);
+
+    let component = match kind.as_str() {
+        "constructor" => {
+            let lifecycle = match properties.get("lifecycle").map(String::as_str) {
+                Some("singleton") => Lifecycle::Singleton,
+                Some("request_scoped") => Lifecycle::RequestScoped,
+                Some("transient") => Lifecycle::Transient,
+                Some(s) => return Err(unknown("lifecycle", s)),
+                None => return Err(missing("lifecycle")),
+            };
+            let cloning_strategy = match properties.get("cloning_strategy").map(String::as_str) {
+                Some("clone_if_necessary") => Some(CloningStrategy::CloneIfNecessary),
+                Some("never_clone") => Some(CloningStrategy::NeverClone),
+                Some(s) => return Err(unknown("cloning_strategy", s)),
+                None => None,
+            };
+            Constructor {
+                constructor: this,
+                lifecycle,
+                cloning_strategy,
+                error_handler,
+                lints: BTreeMap::new(),
+                condition: None,
+            }
+            .into()
+        }
+        "route" => {
+            let method_guard = match properties.get("method").map(String::as_str) {
+                Some("*") => MethodGuard::Any,
+                Some(methods) => MethodGuard::Some(methods.split(',').map(str::to_owned).collect()),
+                None => return Err(missing("method")),
+            };
+            let Some(path) = properties.get("path") else {
+                return Err(missing("path"));
+            };
+            Route {
+                path: path.to_owned(),
+                method_guard,
+                request_handler: this,
+                error_handler,
+                condition: None,
+            }
+            .into()
+        }
+        "wrap" => WrappingMiddleware {
+            middleware: this,
+            error_handler,
+        }
+        .into(),
+        "pre_process" => PreProcessingMiddleware {
+            middleware: this,
+            error_handler,
+        }
+        .into(),
+        "post_process" => PostProcessingMiddleware {
+            middleware: this,
+            error_handler,
+        }
+        .into(),
+        "error_observer" => ErrorObserver {
+            error_observer: this,
+        }
+        .into(),
+        "error_handler" => return Ok(Imported::DefaultErrorHandler(this)),
+        s => return Err(unknown("the component kind", s)),
+    };
+    Ok(Imported::Component(component))
+}
+
+fn invalid_import(
+    e: anyhow::Error,
+    import: &Import,
+    package_graph: &PackageGraph,
+    diagnostics: &mut Vec<miette::Error>,
+) {
+    let location = &import.location;
+    let source = try_source!(location, package_graph, diagnostics);
+    let label = source.as_ref().and_then(|source| {
+        diagnostic::get_f_macro_invocation_span(source, location)
+            .labeled("The import was registered here".to_string())
+    });
+    let diagnostic = CompilerDiagnostic::builder(e)
+        .optional_source(source)
+        .optional_label(label)
+        .build();
+    diagnostics.push(diagnostic.into());
+}
@@ -3,6 +3,7 @@ pub use raw_db::{UserComponent, UserComponentId};
 pub(crate) use router::{DomainRouter, PathRouter, Router};
 pub use scope_graph::{ScopeGraph, ScopeId};
 
+mod imports;
 mod processed_db;
 mod raw_db;
 mod resolved_paths;
@@ -1,4 +1,4 @@
-use ahash::HashMap;
+use ahash::{HashMap, HashSet};
 use guppy::graph::PackageGraph;
 use indexmap::IndexSet;
 use std::collections::BTreeMap;
@@ -12,6 +12,7 @@ use pavex_cli_diagnostic::anyhow2miette;
 use crate::compiler::analyses::computations::ComputationDb;
 use crate::compiler::analyses::config_types::ConfigTypeDb;
 use crate::compiler::analyses::prebuilt_types::PrebuiltTypeDb;
+use crate::compiler::analyses::user_components::imports::{ImportedComponents, expand_imports};
 use crate::compiler::analyses::user_components::raw_db::RawUserComponentDb;
 use crate::compiler::analyses::user_components::resolved_paths::ResolvedPathDb;
 use crate::compiler::analyses::user_components::router::Router;
@@ -66,6 +67,11 @@ pub struct UserComponentDb {
     ///
     /// Invariants: there is an entry for configuration type.
     config_id2default_strategy: HashMap<UserComponentId, DefaultStrategy>,
+    /// The configuration types that can be updated while the application is running.
+    reloadable_config_ids: HashSet<UserComponentId>,
+    /// The error handlers that were imported as default error handlers rather than
+    /// registered explicitly against the fallible component.
+    default_error_handler_ids: HashSet<UserComponentId>,
     /// Associate each request handler with the ordered list of middlewares that wrap around it.
     ///
     /// Invariants: there is an entry for every single request handler.
@@ -75,6 +81,10 @@ pub struct UserComponentDb {
     ///
     /// Invariants: there is an entry for every single request handler.
     handler_id2error_observer_ids: HashMap<UserComponentId, Vec<UserComponentId>>,
+    /// Associate each component registered via `Blueprint::import` with the name of
+    /// the crate it was imported from.
+    /// If there is no entry for a component, it was registered explicitly.
+    id2import_source: HashMap<UserComponentId, String>,
     scope_graph: ScopeGraph,
 }
 
@@ -103,7 +113,27 @@ impl UserComponentDb {
             };
         }
 
-        let (raw_db, scope_graph) = RawUserComponentDb::build(bp, package_graph, diagnostics);
+        let mut imported = ImportedComponents::default();
+        let mut default_error_handlers = Vec::new();
+        let bp = expand_imports(
+            bp,
+            package_graph,
+            krate_collection,
+            &mut imported,
+            &mut default_error_handlers,
+            diagnostics,
+        );
+        exit_on_errors!(diagnostics);
+
+        let (raw_db, scope_graph) =
+            RawUserComponentDb::build(&bp, &default_error_handlers, package_graph, diagnostics);
+        let id2import_source = raw_db
+            .iter()
+            .filter_map(|(id, component)| {
+                let crate_name = imported.get(component.raw_identifiers(&raw_db))?;
+                Some((id, crate_name.to_owned()))
+            })
+            .collect();
         let resolved_path_db = ResolvedPathDb::build(&raw_db, package_graph, diagnostics);
         let router = Router::new(&raw_db, &scope_graph, package_graph, diagnostics)?;
         exit_on_errors!(diagnostics);
@@ -131,6 +161,8 @@ impl UserComponentDb {
             id2lifecycle,
             identifiers_interner,
             config_id2default_strategy,
+            reloadable_config_ids,
+            default_error_handler_ids,
             handler_id2middleware_ids,
             handler_id2error_observer_ids,
             fallback_id2domain_guard: _,
@@ -147,8 +179,11 @@ impl UserComponentDb {
                 id2cloning_strategy,
                 id2lifecycle,
                 config_id2default_strategy,
+                reloadable_config_ids,
+                default_error_handler_ids,
                 handler_id2middleware_ids,
                 handler_id2error_observer_ids,
+                id2import_source,
                 scope_graph,
                 id2lints,
             },
@@ -250,6 +285,26 @@ impl UserComponentDb {
             .filter(|(_, c)| matches!(c, UserComponent::ErrorObserver { .. }))
     }
 
+    /// Iterate over all the startup and shutdown hooks in the database, returning their id and the
+    /// associated `UserComponent`, in registration order.
+    pub fn lifecycle_hooks(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (UserComponentId, &UserComponent)> {
+        self.component_interner
+            .iter()
+            .filter(|(_, c)| matches!(c, UserComponent::LifecycleHook { .. }))
+    }
+
+    /// Iterate over all the background tasks in the database, returning their id and the
+    /// associated `UserComponent`, in registration order.
+    pub fn background_tasks(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (UserComponentId, &UserComponent)> {
+        self.component_interner
+            .iter()
+            .filter(|(_, c)| matches!(c, UserComponent::BackgroundTask { .. }))
+    }
+
     /// Return the lifecycle of the component with the given id.
     pub fn get_lifecycle(&self, id: UserComponentId) -> Lifecycle {
         self.id2lifecycle[&id]
@@ -275,6 +330,24 @@ impl UserComponentDb {
         self.config_id2default_strategy.get(&id)
     }
 
+    /// Returns `true` if the component is a configuration type that can be updated
+    /// while the application is running.
+    pub fn is_reloadable(&self, id: UserComponentId) -> bool {
+        self.reloadable_config_ids.contains(&id)
+    }
+
+    /// Returns `true` if the component is an error handler that was attached to its
+    /// fallible component because it was imported as a default error handler.
+    pub fn is_default_error_handler(&self, id: UserComponentId) -> bool {
+        self.default_error_handler_ids.contains(&id)
+    }
+
+    /// Return the name of the crate the component was imported from, if it was
+    /// registered via `Blueprint::import`.
+    pub fn get_import_source(&self, id: UserComponentId) -> Option<&str> {
+        self.id2import_source.get(&id).map(String::as_str)
+    }
+
     /// Return the scope tree that was built from the application blueprint.
     pub fn scope_graph(&self) -> &ScopeGraph {
         &self.scope_graph
@@ -302,9 +375,10 @@ impl UserComponentDb {
     }
 
     /// Return the ids of the error observers that must be invoked when something goes wrong
-    /// in the request processing pipeline for this handler.
+    /// in the request processing pipeline for this handler, or when this background task fails.
     ///
-    /// It panics if the component with the given id is not a request handler.
+    /// It panics if the component with the given id is neither a request handler
+    /// nor a background task.
     pub fn get_error_observer_ids(&self, id: UserComponentId) -> &[UserComponentId] {
         &self.handler_id2error_observer_ids[&id]
     }
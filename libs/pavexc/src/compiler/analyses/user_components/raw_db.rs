@@ -1,14 +1,14 @@
-use ahash::{HashMap, HashMapExt};
+use ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
 use anyhow::anyhow;
 use guppy::graph::PackageGraph;
 use indexmap::IndexMap;
 use std::collections::BTreeMap;
 
 use pavex_bp_schema::{
-    Blueprint, Callable, CloningStrategy, Component, ConfigType, Constructor, Domain,
-    ErrorObserver, Fallback, Lifecycle, Lint, LintSetting, Location, NestedBlueprint, PathPrefix,
-    PostProcessingMiddleware, PreProcessingMiddleware, PrebuiltType, RawIdentifiers, RegisteredAt,
-    Route, WrappingMiddleware,
+    BackgroundTask, Blueprint, Callable, CloningStrategy, Component, ConfigType, Constructor,
+    Domain, ErrorObserver, Fallback, Lifecycle, LifecycleHook, LifecyclePhase, Lint, LintSetting,
+    Location, NestedBlueprint, PathPrefix, PostProcessingMiddleware, PreProcessingMiddleware,
+    PrebuiltType, RawIdentifiers, RegisteredAt, Route, TaskSchedule, WrappingMiddleware,
 };
 
 use crate::compiler::analyses::domain::{DomainGuard, InvalidDomainConstraint};
@@ -72,6 +72,17 @@ pub enum UserComponent {
         raw_callable_identifiers_id: RawIdentifierId,
         scope_id: ScopeId,
     },
+    LifecycleHook {
+        raw_callable_identifiers_id: RawIdentifierId,
+        scope_id: ScopeId,
+        phase: LifecyclePhase,
+        timeout: Option<std::time::Duration>,
+    },
+    BackgroundTask {
+        raw_callable_identifiers_id: RawIdentifierId,
+        scope_id: ScopeId,
+        schedule: TaskSchedule,
+    },
 }
 
 impl UserComponent {
@@ -88,6 +99,8 @@ impl UserComponent {
             UserComponent::WrappingMiddleware { .. } => ComponentKind::WrappingMiddleware,
             UserComponent::Fallback { .. } => ComponentKind::RequestHandler,
             UserComponent::ErrorObserver { .. } => ComponentKind::ErrorObserver,
+            UserComponent::LifecycleHook { .. } => ComponentKind::LifecycleHook,
+            UserComponent::BackgroundTask { .. } => ComponentKind::BackgroundTask,
             UserComponent::PostProcessingMiddleware { .. } => {
                 ComponentKind::PostProcessingMiddleware
             }
@@ -135,6 +148,14 @@ impl UserComponent {
                 raw_callable_identifiers_id,
                 ..
             }
+            | UserComponent::LifecycleHook {
+                raw_callable_identifiers_id,
+                ..
+            }
+            | UserComponent::BackgroundTask {
+                raw_callable_identifiers_id,
+                ..
+            }
             | UserComponent::Constructor {
                 raw_callable_identifiers_id,
                 ..
@@ -146,6 +167,8 @@ impl UserComponent {
     pub fn scope_id(&self) -> ScopeId {
         match self {
             UserComponent::ErrorObserver { scope_id, .. }
+            | UserComponent::LifecycleHook { scope_id, .. }
+            | UserComponent::BackgroundTask { scope_id, .. }
             | UserComponent::RequestHandler { scope_id, .. }
             | UserComponent::Fallback { scope_id, .. }
             | UserComponent::ErrorHandler { scope_id, .. }
@@ -206,6 +229,11 @@ pub(super) struct RawUserComponentDb {
     ///
     /// Invariants: there is an entry for configuration type.
     pub(super) config_id2default_strategy: HashMap<UserComponentId, DefaultStrategy>,
+    /// The configuration types that can be updated while the application is running.
+    pub(super) reloadable_config_ids: HashSet<UserComponentId>,
+    /// The error handlers that were attached to a component because they were imported
+    /// as default error handlers, rather than registered against it explicitly.
+    pub(super) default_error_handler_ids: HashSet<UserComponentId>,
     /// Associate each request handler with the ordered list of middlewares that wrap around it.
     ///
     /// Invariants: there is an entry for every single request handler.
@@ -252,6 +280,7 @@ impl RawUserComponentDb {
     /// that have been registered against it.
     pub fn build(
         bp: &Blueprint,
+        default_error_handlers: &[Callable],
         package_graph: &PackageGraph,
         diagnostics: &mut Vec<miette::Error>,
     ) -> (Self, ScopeGraph) {
@@ -263,6 +292,8 @@ impl RawUserComponentDb {
             id2lints: HashMap::new(),
             id2cloning_strategy: HashMap::new(),
             config_id2default_strategy: HashMap::new(),
+            reloadable_config_ids: HashSet::new(),
+            default_error_handler_ids: HashSet::new(),
             handler_id2middleware_ids: HashMap::new(),
             handler_id2error_observer_ids: HashMap::new(),
             fallback_id2path_prefix: HashMap::new(),
@@ -346,6 +377,8 @@ impl RawUserComponentDb {
             );
         }
 
+        self_.process_default_error_handlers(default_error_handlers);
+
         #[cfg(debug_assertions)]
         self_.check_invariants();
 
@@ -430,12 +463,24 @@ impl RawUserComponentDb {
                 Component::ErrorObserver(eo) => {
                     self.process_error_observer(eo, current_scope_id, current_observer_chain);
                 }
+                Component::LifecycleHook(h) => {
+                    self.process_lifecycle_hook(h, current_scope_id);
+                }
+                Component::BackgroundTask(t) => {
+                    self.process_background_task(t, current_scope_id, current_observer_chain);
+                }
                 Component::PrebuiltType(si) => {
                     self.process_prebuilt_type(si, current_scope_id);
                 }
                 Component::ConfigType(t) => {
                     self.process_config_type(t, current_scope_id);
                 }
+                Component::Import(_) => {
+                    unreachable!(
+                        "Imports are expanded into the components they point to before \
+                        the user component database is built"
+                    )
+                }
             }
         }
         if let Some(fallback) = &fallback {
@@ -754,6 +799,51 @@ impl RawUserComponentDb {
         current_observer_chain.push(id);
     }
 
+    /// Register with [`RawUserComponentDb`] a startup or shutdown hook that has been
+    /// registered against the provided `Blueprint`.
+    /// It is associated with or nested under the provided `current_scope_id`.
+    fn process_lifecycle_hook(&mut self, h: &LifecycleHook, current_scope_id: ScopeId) {
+        // Hooks are invoked once, at the application level, just like singletons.
+        const LIFECYCLE: Lifecycle = Lifecycle::Singleton;
+
+        let raw_callable_identifiers_id = self
+            .identifiers_interner
+            .get_or_intern(h.hook.callable.clone());
+        let component = UserComponent::LifecycleHook {
+            raw_callable_identifiers_id,
+            scope_id: current_scope_id,
+            phase: h.phase,
+            timeout: h.timeout,
+        };
+        self.intern_component(component, LIFECYCLE, h.hook.location.clone());
+    }
+
+    /// Register with [`RawUserComponentDb`] a background task that has been
+    /// registered against the provided `Blueprint`.
+    /// It is associated with or nested under the provided `current_scope_id`.
+    fn process_background_task(
+        &mut self,
+        t: &BackgroundTask,
+        current_scope_id: ScopeId,
+        current_observer_chain: &[UserComponentId],
+    ) {
+        // Tasks are spawned once, at the application level, just like singletons.
+        const LIFECYCLE: Lifecycle = Lifecycle::Singleton;
+
+        let raw_callable_identifiers_id = self
+            .identifiers_interner
+            .get_or_intern(t.task.callable.clone());
+        let component = UserComponent::BackgroundTask {
+            raw_callable_identifiers_id,
+            scope_id: current_scope_id,
+            schedule: t.schedule.clone(),
+        };
+        let id = self.intern_component(component, LIFECYCLE, t.task.location.clone());
+        // Failures are reported to the error observers in scope, just like for request handlers.
+        self.handler_id2error_observer_ids
+            .insert(id, current_observer_chain.to_owned());
+    }
+
     /// Register with [`RawUserComponentDb`] a prebuilt type that has been
     /// registered against the provided `Blueprint`.
     /// It is associated with or nested under the provided `current_scope_id`.
@@ -804,6 +894,9 @@ impl RawUserComponentDb {
             })
             .unwrap_or(DefaultStrategy::Required);
         self.config_id2default_strategy.insert(id, default_strategy);
+        if t.reloadable == Some(true) {
+            self.reloadable_config_ids.insert(id);
+        }
     }
 
     /// A helper function to intern a component without forgetting to do the necessary
@@ -829,9 +922,9 @@ impl RawUserComponentDb {
         lifecycle: Lifecycle,
         scope_id: ScopeId,
         fallible_component_id: UserComponentId,
-    ) {
+    ) -> Option<UserComponentId> {
         let Some(error_handler) = error_handler else {
-            return;
+            return None;
         };
         let raw_callable_identifiers_id = self
             .identifiers_interner
@@ -841,7 +934,59 @@ impl RawUserComponentDb {
             fallible_callable_identifiers_id: fallible_component_id,
             scope_id,
         };
-        self.intern_component(component, lifecycle, error_handler.location.to_owned());
+        Some(self.intern_component(component, lifecycle, error_handler.location.to_owned()))
+    }
+
+    /// Attach every default error handler to each component that may fail and doesn't
+    /// have an error handler of its own.
+    ///
+    /// We don't know the error type returned by each component at this stage:
+    /// the candidates that don't match are discarded when error handlers are validated.
+    fn process_default_error_handlers(&mut self, default_error_handlers: &[Callable]) {
+        if default_error_handlers.is_empty() {
+            return;
+        }
+        let handled_ids: HashSet<_> = self
+            .component_interner
+            .iter()
+            .filter_map(|(_, c)| match c {
+                UserComponent::ErrorHandler {
+                    fallible_callable_identifiers_id,
+                    ..
+                } => Some(*fallible_callable_identifiers_id),
+                _ => None,
+            })
+            .collect();
+        let fallible_ids: Vec<_> = self
+            .component_interner
+            .iter()
+            .filter(|(id, c)| {
+                !handled_ids.contains(id)
+                    && match c {
+                        UserComponent::Constructor { .. } => {
+                            self.id2lifecycle[id] != Lifecycle::Singleton
+                        }
+                        UserComponent::RequestHandler { .. }
+                        | UserComponent::Fallback { .. }
+                        | UserComponent::WrappingMiddleware { .. }
+                        | UserComponent::PreProcessingMiddleware { .. }
+                        | UserComponent::PostProcessingMiddleware { .. } => true,
+                        _ => false,
+                    }
+            })
+            .map(|(id, c)| (id, c.scope_id()))
+            .collect();
+        for (fallible_id, scope_id) in fallible_ids {
+            let lifecycle = self.id2lifecycle[&fallible_id];
+            for error_handler in default_error_handlers {
+                let error_handler = Some(error_handler.to_owned());
+                if let Some(id) =
+                    self.process_error_handler(&error_handler, lifecycle, scope_id, fallible_id)
+                {
+                    self.default_error_handler_ids.insert(id);
+                }
+            }
+        }
     }
 
     /// Check the path of the registered route.
@@ -1003,7 +1148,9 @@ impl RawUserComponentDb {
                 | UserComponent::WrappingMiddleware { .. }
                 | UserComponent::PostProcessingMiddleware { .. }
                 | UserComponent::PreProcessingMiddleware { .. }
-                | UserComponent::ErrorObserver { .. } => {}
+                | UserComponent::ErrorObserver { .. }
+                | UserComponent::LifecycleHook { .. }
+                | UserComponent::BackgroundTask { .. } => {}
             }
         }
     }
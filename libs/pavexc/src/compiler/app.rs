@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
-use ahash::{HashMap, HashMapExt};
+use ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
 use guppy::graph::PackageGraph;
 use indexmap::IndexMap;
 
@@ -12,21 +12,25 @@ use pavex_cli_diagnostic::anyhow2miette;
 
 use crate::compiler::analyses::application_config::ApplicationConfig;
 use crate::compiler::analyses::application_state::ApplicationState;
+use crate::compiler::analyses::background_tasks::BackgroundTasks;
 use crate::compiler::analyses::call_graph::{
     ApplicationStateCallGraph, application_state_call_graph,
 };
 use crate::compiler::analyses::cloning::clonables_can_be_cloned;
 use crate::compiler::analyses::components::{ComponentDb, ComponentId};
 use crate::compiler::analyses::computations::ComputationDb;
+use crate::compiler::analyses::config_schema::ConfigSchema;
 use crate::compiler::analyses::config_types::ConfigTypeDb;
 use crate::compiler::analyses::constructibles::ConstructibleDb;
 use crate::compiler::analyses::framework_items::FrameworkItemDb;
+use crate::compiler::analyses::lifecycle_hooks::LifecycleHooks;
 use crate::compiler::analyses::prebuilt_types::PrebuiltTypeDb;
 use crate::compiler::analyses::processing_pipeline::RequestHandlerPipeline;
 use crate::compiler::analyses::router::Router;
 use crate::compiler::analyses::unused::detect_unused;
 use crate::compiler::analyses::user_components::UserComponentDb;
-use crate::compiler::generated_app::GeneratedApp;
+use crate::compiler::conditions::Variant;
+use crate::compiler::generated_app::{GeneratedApp, GeneratedConfigSchema, GeneratedManifest};
 use crate::compiler::resolvers::CallableResolutionError;
 use crate::compiler::{codegen, path_parameters};
 use crate::rustdoc::CrateCollection;
@@ -36,6 +40,14 @@ pub(crate) const GENERATED_APP_PACKAGE_ID: &str = "crate";
 /// An in-memory representation that can be used to generate application code that matches
 /// the constraints and instructions from a [`Blueprint`] instance.
 pub struct App {
+    package_graph: PackageGraph,
+    /// One entry for each combination of the conditions used in the blueprint.
+    /// There is a single (unconditional) variant if no component is registered conditionally.
+    variants: Vec<(Variant, AppVariant)>,
+}
+
+/// The analysis of the [`Blueprint`] for a specific [`Variant`].
+struct AppVariant {
     package_graph: PackageGraph,
     router: Router,
     handler_id2pipeline: IndexMap<ComponentId, RequestHandlerPipeline>,
@@ -43,6 +55,9 @@ pub struct App {
     framework_item_db: FrameworkItemDb,
     application_state: ApplicationState,
     application_config: ApplicationConfig,
+    config_schema: ConfigSchema,
+    lifecycle_hooks: LifecycleHooks,
+    background_tasks: BackgroundTasks,
     codegen_deps: HashMap<String, guppy::PackageId>,
     component_db: ComponentDb,
     computation_db: ComputationDb,
@@ -54,6 +69,9 @@ impl App {
     /// representation that can be used to generate application code that matches the constraints
     /// and instructions in the blueprint.
     ///
+    /// If some components are registered conditionally, the blueprint is processed once
+    /// for every combination of the conditions it uses.
+    ///
     /// Many different things can go wrong during this process: this method tries its best to
     /// report all errors to the user, but it may not be able to do so in all cases.
     pub fn build(
@@ -61,6 +79,143 @@ impl App {
         docs_toolchain_name: String,
         package_graph: PackageGraph,
         cache_workpace_packages: bool,
+    ) -> Result<(Self, Vec<miette::Error>), Vec<miette::Error>> {
+        let variants = Variant::enumerate(&bp).map_err(|e| vec![anyhow2miette(e)])?;
+        let krate_collection = CrateCollection::new(
+            docs_toolchain_name,
+            // TODO: avoid cloning here.
+            package_graph.clone(),
+            bp.creation_location.file.clone(),
+            cache_workpace_packages,
+        )
+        .map_err(|e| vec![anyhow2miette(e)])?;
+        let package_graph = krate_collection.package_graph().to_owned();
+
+        let n_variants = variants.len();
+        let mut analysed = Vec::with_capacity(n_variants);
+        let mut warnings = Vec::new();
+        // Most warnings are going to be identical across variants, we don't want to
+        // report them more than once.
+        let mut seen = HashSet::new();
+        for variant in variants {
+            let span = tracing::info_span!("Analyse variant", variant = %variant);
+            let _guard = span.enter();
+            let bp = if variant.is_unconditional() {
+                bp.clone()
+            } else {
+                variant.filter(&bp)
+            };
+            match AppVariant::build(&bp, &krate_collection) {
+                Ok((app, diagnostics)) => {
+                    warnings.extend(
+                        diagnostics
+                            .into_iter()
+                            .filter(|e| seen.insert(format!("{e:?}"))),
+                    );
+                    analysed.push((variant, app));
+                }
+                Err(mut diagnostics) => {
+                    if n_variants > 1 {
+                        diagnostics.push(miette::miette!(
+                            severity = miette::Severity::Advice,
+                            "The errors above were detected while analysing your application \
+                            with {variant}."
+                        ));
+                    }
+                    return Err(diagnostics);
+                }
+            }
+        }
+        Ok((
+            Self {
+                package_graph,
+                variants: analysed,
+            },
+            warnings,
+        ))
+    }
+
+    /// Generate the manifest and the Rust code for the analysed application.
+    ///
+    /// They are generated in-memory, they are not persisted to disk.
+    #[tracing::instrument(skip_all, level = tracing::Level::INFO)]
+    pub fn codegen(&self) -> Result<GeneratedApp, anyhow::Error> {
+        if let [(variant, app)] = self.variants.as_slice() {
+            if variant.is_unconditional() {
+                return app.codegen();
+            }
+        }
+
+        // The code generated for each variant is guarded by a `#[cfg(...)]` attribute
+        // that matches the conditions of the variant.
+        let mut items = Vec::new();
+        let mut cargo_toml: Option<GeneratedManifest> = None;
+        let mut config_schema = None;
+        for (variant, app) in &self.variants {
+            let generated = app.codegen()?;
+            // Conditions don't apply to configuration types: the schema is the same
+            // for all variants.
+            config_schema.get_or_insert(generated.config_schema);
+            let predicate = variant.cfg_predicate();
+            let file: syn::File = syn::parse2(generated.lib_rs)?;
+            for item in file.items {
+                items.push(quote::quote! {
+                    #[cfg(#predicate)]
+                    #item
+                });
+            }
+            let manifest = cargo_toml.get_or_insert_with(|| GeneratedManifest {
+                dependencies: Default::default(),
+                edition: generated.cargo_toml.edition,
+                features: variant.features().map(ToOwned::to_owned).collect(),
+                profiles: variant.profiles().map(ToOwned::to_owned).collect(),
+            });
+            for (name, dependency) in generated.cargo_toml.dependencies {
+                match manifest.dependencies.get(&name) {
+                    Some(existing) if existing != &dependency => {
+                        anyhow::bail!(
+                            "The code generated for different variants of your application \
+                            requires two different crates named `{name}`. This is not supported."
+                        );
+                    }
+                    _ => {
+                        manifest.dependencies.insert(name, dependency);
+                    }
+                }
+            }
+        }
+        Ok(GeneratedApp {
+            lib_rs: quote::quote! { #(#items)* },
+            cargo_toml: cargo_toml.expect("There is always at least one variant"),
+            config_schema: config_schema.expect("There is always at least one variant"),
+            package_graph: self.package_graph.clone(),
+        })
+    }
+
+    /// A representation of an `App` geared towards debugging and testing.
+    ///
+    /// If there are multiple variants, their call graphs are concatenated.
+    pub fn diagnostic_representation(&self) -> AppDiagnostics {
+        let mut handlers = Vec::new();
+        let mut application_state = Vec::new();
+        for (_, app) in &self.variants {
+            let diagnostics = app.diagnostic_representation();
+            handlers.extend(diagnostics.handlers);
+            application_state.push(diagnostics.application_state);
+        }
+        AppDiagnostics {
+            handlers,
+            application_state: application_state.join("\n"),
+        }
+    }
+}
+
+impl AppVariant {
+    #[tracing::instrument(skip_all)]
+    /// Analyse a (filtered) [`Blueprint`].
+    fn build(
+        bp: &Blueprint,
+        krate_collection: &CrateCollection,
     ) -> Result<(Self, Vec<miette::Error>), Vec<miette::Error>> {
         /// Exit early if there is at least one error.
         macro_rules! exit_on_errors {
@@ -76,32 +231,24 @@ impl App {
             };
         }
 
-        let krate_collection = CrateCollection::new(
-            docs_toolchain_name,
-            // TODO: avoid cloning here.
-            package_graph.clone(),
-            bp.creation_location.file.clone(),
-            cache_workpace_packages,
-        )
-        .map_err(|e| vec![anyhow2miette(e)])?;
         let package_graph = krate_collection.package_graph().to_owned();
         let mut diagnostics = vec![];
         let mut computation_db = ComputationDb::new();
         let mut prebuilt_type_db = PrebuiltTypeDb::new();
         let mut config_type_db = ConfigTypeDb::new();
         let Ok((router, user_component_db)) = UserComponentDb::build(
-            &bp,
+            bp,
             &mut computation_db,
             &mut prebuilt_type_db,
             &mut config_type_db,
             &package_graph,
-            &krate_collection,
+            krate_collection,
             &mut diagnostics,
         ) else {
             return Err(diagnostics);
         };
 
-        let framework_item_db = FrameworkItemDb::new(&krate_collection);
+        let framework_item_db = FrameworkItemDb::new(krate_collection);
         let mut component_db = ComponentDb::build(
             user_component_db,
             &framework_item_db,
@@ -109,7 +256,7 @@ impl App {
             prebuilt_type_db,
             config_type_db,
             &package_graph,
-            &krate_collection,
+            krate_collection,
             &mut diagnostics,
         );
         let router = Router::lift(router, component_db.user_component_id2component_id());
@@ -118,7 +265,7 @@ impl App {
             &mut component_db,
             &mut computation_db,
             &package_graph,
-            &krate_collection,
+            krate_collection,
             &framework_item_db,
             &mut diagnostics,
         );
@@ -126,7 +273,7 @@ impl App {
             &component_db,
             &computation_db,
             &package_graph,
-            &krate_collection,
+            krate_collection,
             &mut diagnostics,
         );
         exit_on_errors!(diagnostics);
@@ -146,7 +293,7 @@ impl App {
                     &mut constructible_db,
                     &framework_item_db,
                     &package_graph,
-                    &krate_collection,
+                    krate_collection,
                     &mut diagnostics,
                 ) else {
                     continue;
@@ -161,7 +308,7 @@ impl App {
             &computation_db,
             &component_db,
             &package_graph,
-            &krate_collection,
+            krate_collection,
             &mut diagnostics,
         );
         let application_config = ApplicationConfig::new(
@@ -171,14 +318,34 @@ impl App {
             &mut diagnostics,
         );
         exit_on_errors!(diagnostics);
+        let config_schema = ConfigSchema::new(&application_config, krate_collection);
+        let mut lifecycle_hooks = LifecycleHooks::new(
+            &component_db,
+            &computation_db,
+            &constructible_db,
+            &package_graph,
+            krate_collection,
+            &mut diagnostics,
+        );
+        let background_tasks = BackgroundTasks::new(
+            &component_db,
+            &computation_db,
+            &constructible_db,
+            &package_graph,
+            krate_collection,
+            &mut diagnostics,
+        );
+        exit_on_errors!(diagnostics);
 
         let application_state = ApplicationState::new(
             &handler_id2pipeline,
+            &lifecycle_hooks,
+            &background_tasks,
             &framework_item_db,
             &constructible_db,
             &component_db,
             &computation_db,
-            &krate_collection,
+            krate_collection,
             &mut diagnostics,
         );
         exit_on_errors!(diagnostics);
@@ -191,11 +358,17 @@ impl App {
             &mut constructible_db,
             &framework_item_db,
             &package_graph,
-            &krate_collection,
+            krate_collection,
             &mut diagnostics,
         ) else {
             return Err(diagnostics);
         };
+        lifecycle_hooks.finalize(
+            &application_state_call_graph,
+            &component_db,
+            &computation_db,
+            krate_collection,
+        );
         detect_unused(
             handler_id2pipeline.values(),
             &application_state_call_graph,
@@ -216,23 +389,25 @@ impl App {
                 framework_item_db,
                 application_state,
                 application_config,
+                config_schema,
+                lifecycle_hooks,
+                background_tasks,
                 codegen_deps,
             },
             diagnostics,
         ))
     }
 
-    /// Generate the manifest and the Rust code for the analysed application.
-    ///
-    /// They are generated in-memory, they are not persisted to disk.
-    #[tracing::instrument(skip_all, level = tracing::Level::INFO)]
-    pub fn codegen(&self) -> Result<GeneratedApp, anyhow::Error> {
+    /// Generate the manifest and the Rust code for this variant of the application.
+    fn codegen(&self) -> Result<GeneratedApp, anyhow::Error> {
         let framework_bindings = self.framework_item_db.bindings();
         let (cargo_toml, package_ids2deps) = codegen::codegen_manifest(
             &self.package_graph,
             self.handler_id2pipeline.values(),
             &self.application_state_call_graph.call_graph.call_graph,
             &self.application_config,
+            &self.lifecycle_hooks,
+            &self.background_tasks,
             &framework_bindings,
             &self.codegen_deps,
             &self.component_db,
@@ -246,6 +421,8 @@ impl App {
             &package_ids2deps,
             &self.application_state,
             &self.application_config,
+            &self.lifecycle_hooks,
+            &self.background_tasks,
             &self.codegen_deps,
             &self.component_db,
             &self.computation_db,
@@ -254,17 +431,22 @@ impl App {
         Ok(GeneratedApp {
             lib_rs,
             cargo_toml,
+            config_schema: GeneratedConfigSchema {
+                json_schema: serde_json::to_string_pretty(&self.config_schema.json_schema)?,
+                sample: self.config_schema.sample.clone(),
+            },
             package_graph: self.package_graph.clone(),
         })
     }
 
-    /// A representation of an `App` geared towards debugging and testing.
-    pub fn diagnostic_representation(&self) -> AppDiagnostics {
+    fn diagnostic_representation(&self) -> AppDiagnostics {
         let (_, package_ids2deps) = codegen::codegen_manifest(
             &self.package_graph,
             self.handler_id2pipeline.values(),
             &self.application_state_call_graph.call_graph.call_graph,
             &self.application_config,
+            &self.lifecycle_hooks,
+            &self.background_tasks,
             &self.framework_item_db.bindings(),
             &self.codegen_deps,
             &self.component_db,
@@ -12,16 +12,19 @@ use quote::quote;
 use router::codegen_router;
 use state::{
     define_application_config, define_application_state, define_application_state_error,
+    define_background_tasks, define_config_reloader, define_lifecycle_hooks,
     get_application_state_new, get_application_state_private_new, get_build_application_state,
 };
 use syn::{ItemFn, ItemStruct};
 
+use crate::compiler::analyses::background_tasks::BackgroundTasks;
 use crate::compiler::analyses::call_graph::{
     ApplicationStateCallGraph, CallGraphNode, RawCallGraph,
 };
 use crate::compiler::analyses::components::{ComponentDb, ComponentId};
 use crate::compiler::analyses::computations::ComputationDb;
 use crate::compiler::analyses::framework_items::FrameworkItemDb;
+use crate::compiler::analyses::lifecycle_hooks::LifecycleHooks;
 use crate::compiler::analyses::processing_pipeline::RequestHandlerPipeline;
 use crate::compiler::analyses::router::Router;
 use crate::compiler::app::GENERATED_APP_PACKAGE_ID;
@@ -47,6 +50,8 @@ pub(crate) fn codegen_app(
     package_id2name: &BiHashMap<PackageId, String>,
     application_state: &ApplicationState,
     application_config: &ApplicationConfig,
+    lifecycle_hooks: &LifecycleHooks,
+    background_tasks: &BackgroundTasks,
     codegen_deps: &HashMap<String, PackageId>,
     component_db: &ComponentDb,
     computation_db: &ComputationDb,
@@ -62,11 +67,15 @@ pub(crate) fn codegen_app(
             quote! { #application_state_def }
         );
     }
-    let define_application_state_error = define_application_state_error(
-        &application_state_call_graph.error_variants,
-        package_id2name,
-        &sdk_deps,
-    )?;
+    let mut error_variants = application_state_call_graph.error_variants.clone();
+    error_variants.extend(
+        lifecycle_hooks
+            .error_variants()
+            .iter()
+            .map(|(name, ty)| (name.to_owned(), ty.to_owned())),
+    );
+    let define_application_state_error =
+        define_application_state_error(&error_variants, package_id2name, &sdk_deps)?;
     let application_state_private_new = get_application_state_private_new(
         application_state_call_graph,
         package_id2name,
@@ -77,11 +86,27 @@ pub(crate) fn codegen_app(
         &application_state_private_new,
         application_state_call_graph,
         application_config,
+        lifecycle_hooks,
         package_id2name,
     )?;
     let application_state_init = get_build_application_state(&application_state_new)?;
+    let config_reloader =
+        define_config_reloader(application_state, application_config, package_id2name);
+    let lifecycle_hooks_def = define_lifecycle_hooks(
+        lifecycle_hooks,
+        application_state,
+        package_id2name,
+        &sdk_deps,
+    );
+    let background_tasks_def = define_background_tasks(
+        background_tasks,
+        application_state,
+        package_id2name,
+        &sdk_deps,
+    );
 
-    let define_server_state = define_server_state(&application_state_def);
+    let define_server_state =
+        define_server_state(&application_state_def, !background_tasks.is_empty());
 
     let route_infos = router.route_infos();
     let handler_id2codegened_pipeline = handler_id2pipeline
@@ -103,7 +128,12 @@ pub(crate) fn codegen_app(
         .map(|p| p.as_inline_module())
         .collect::<Vec<_>>();
 
-    let entrypoint = server_startup(&sdk_deps);
+    let entrypoint = server_startup(
+        &sdk_deps,
+        lifecycle_hooks.has_shutdown_hooks(),
+        !background_tasks.is_empty(),
+        readiness_binding(application_state),
+    );
     let alloc_extern_import = if package_id2name.contains_right(ALLOC_PACKAGE_ID_REPR) {
         // The fact that an item from `alloc` is used in the generated code does not imply
         // that we need to have an `alloc` import (e.g. it might not appear in function
@@ -139,6 +169,9 @@ pub(crate) fn codegen_app(
             #application_state_private_new
         }
         #application_state_init
+        #config_reloader
+        #lifecycle_hooks_def
+        #background_tasks_def
         #define_application_state_error
         #entrypoint
         #router
@@ -147,10 +180,52 @@ pub(crate) fn codegen_app(
     Ok(code)
 }
 
-fn server_startup(sdk_deps: &ServerSdkDeps) -> ItemFn {
+fn server_startup(
+    sdk_deps: &ServerSdkDeps,
+    has_shutdown_hooks: bool,
+    has_background_tasks: bool,
+    readiness_binding: Option<&Ident>,
+) -> ItemFn {
     let pavex = sdk_deps.pavex_ident();
     let http = sdk_deps.http_ident();
     let hyper = sdk_deps.hyper_ident();
+    // Background tasks share the application state with the server, so it
+    // must be wrapped in an `Arc`.
+    // They are stopped as soon as the server has stopped processing requests,
+    // before the shutdown hooks are invoked.
+    let (start_background_tasks, stop_background_tasks) = if has_background_tasks {
+        (
+            Some(quote! {
+                let application_state = std::sync::Arc::new(application_state);
+                let background_tasks = application_state.clone().start_background_tasks();
+            }),
+            Some(quote! {
+                let server_builder = server_builder.on_shutdown(move || background_tasks.shutdown());
+            }),
+        )
+    } else {
+        (None, None)
+    };
+    // Invoke the shutdown hooks once the server has stopped processing requests.
+    let on_shutdown = has_shutdown_hooks.then(|| {
+        quote! {
+            let server_builder = {
+                let server_state = server_state.clone();
+                server_builder.on_shutdown(move || async move {
+                    let _ = server_state.application_state.shutdown().await;
+                })
+            };
+        }
+    });
+    // Fail the readiness probe as soon as the server starts shutting down.
+    let on_drain_start = readiness_binding.map(|field| {
+        quote! {
+            let server_builder = {
+                let readiness = server_state.application_state.#field.clone();
+                server_builder.on_drain_start(move || readiness.mark_draining())
+            };
+        }
+    });
     syn::parse2(quote! {
         pub fn run(
             server_builder: #pavex::server::Server,
@@ -167,11 +242,15 @@ fn server_startup(sdk_deps: &ServerSdkDeps) -> ItemFn {
                 router.route(request, connection_info, state).await
             }
 
+            #start_background_tasks
             let router = Router::new();
             let server_state = std::sync::Arc::new(ServerState {
                 router,
                 application_state
             });
+            #on_drain_start
+            #stop_background_tasks
+            #on_shutdown
 
             server_builder.serve(handler, server_state)
         }
@@ -179,7 +258,20 @@ fn server_startup(sdk_deps: &ServerSdkDeps) -> ItemFn {
     .unwrap()
 }
 
-fn define_server_state(application_state_def: &ItemStruct) -> ItemStruct {
+/// The field of `ApplicationState` that holds `pavex::health::Readiness`, if there is one.
+fn readiness_binding(application_state: &ApplicationState) -> Option<&Ident> {
+    application_state
+        .bindings()
+        .iter()
+        .find_map(|(field, type_)| match type_ {
+            ResolvedType::ResolvedPath(t) if t.base_type == ["pavex", "health", "Readiness"] => {
+                Some(field)
+            }
+            _ => None,
+        })
+}
+
+fn define_server_state(application_state_def: &ItemStruct, is_shared: bool) -> ItemStruct {
     let dead_code = if application_state_def.fields.is_empty() {
         quote! {
             #[allow(dead_code)]
@@ -187,11 +279,17 @@ fn define_server_state(application_state_def: &ItemStruct) -> ItemStruct {
     } else {
         quote! {}
     };
+    // The application state is shared with the background tasks, if there are any.
+    let application_state_type = if is_shared {
+        quote! { std::sync::Arc<ApplicationState> }
+    } else {
+        quote! { ApplicationState }
+    };
     syn::parse2(quote! {
         struct ServerState {
             router: Router,
             #dead_code
-            application_state: ApplicationState
+            application_state: #application_state_type
         }
     })
     .unwrap()
@@ -202,6 +300,8 @@ pub(crate) fn codegen_manifest<'a, I>(
     handler_call_graphs: I,
     application_state_call_graph: &'a RawCallGraph,
     application_config: &'a ApplicationConfig,
+    lifecycle_hooks: &'a LifecycleHooks,
+    background_tasks: &'a BackgroundTasks,
     request_scoped_framework_bindings: &'a BiHashMap<Ident, ResolvedType>,
     codegen_deps: &'a HashMap<String, PackageId>,
     component_db: &'a ComponentDb,
@@ -215,6 +315,8 @@ where
         handler_call_graphs,
         application_state_call_graph,
         application_config,
+        lifecycle_hooks,
+        background_tasks,
         request_scoped_framework_bindings,
         codegen_deps,
         component_db,
@@ -223,6 +325,8 @@ where
     let manifest = GeneratedManifest {
         dependencies,
         edition: Edition::E2024,
+        features: Default::default(),
+        profiles: Default::default(),
     };
 
     // Toolchain crates are not listed as dependencies in the manifest, but we need to add them to
@@ -247,6 +351,8 @@ fn compute_dependencies<'a, I>(
     handler_pipelines: I,
     application_state_call_graph: &'a RawCallGraph,
     application_config: &'a ApplicationConfig,
+    lifecycle_hooks: &'a LifecycleHooks,
+    background_tasks: &'a BackgroundTasks,
     request_scoped_framework_bindings: &'a BiHashMap<Ident, ResolvedType>,
     codegen_deps: &'a HashMap<String, PackageId>,
     component_db: &'a ComponentDb,
@@ -259,6 +365,8 @@ where
         handler_pipelines,
         application_state_call_graph,
         application_config,
+        lifecycle_hooks,
+        background_tasks,
         request_scoped_framework_bindings,
         codegen_deps,
         component_db,
@@ -381,6 +489,8 @@ fn collect_package_ids<'a, I>(
     handler_pipelines: I,
     application_state_call_graph: &'a RawCallGraph,
     application_config: &'a ApplicationConfig,
+    lifecycle_hooks: &'a LifecycleHooks,
+    background_tasks: &'a BackgroundTasks,
     request_scoped_framework_bindings: &'a BiHashMap<Ident, ResolvedType>,
     codegen_deps: &'a HashMap<String, PackageId>,
     component_db: &'a ComponentDb,
@@ -399,6 +509,15 @@ where
     for package_id in codegen_deps.values() {
         package_ids.insert(package_id.to_owned());
     }
+    for hook in lifecycle_hooks.iter() {
+        collect_callable_package_ids(&mut package_ids, &hook.callable);
+    }
+    for task in background_tasks.iter() {
+        collect_callable_package_ids(&mut package_ids, &task.callable);
+        for observer in &task.error_observers {
+            collect_callable_package_ids(&mut package_ids, &observer.callable);
+        }
+    }
     collect_call_graph_package_ids(
         &mut package_ids,
         component_db,
@@ -1,17 +1,23 @@
 use std::collections::BTreeMap;
 
+use ahash::{HashMap, HashMapExt};
 use bimap::BiHashMap;
 use guppy::PackageId;
 use indexmap::IndexMap;
-use quote::{format_ident, quote};
-use syn::{ItemEnum, ItemFn, ItemStruct};
+use pavex_bp_schema::TaskSchedule;
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::{Ident, ItemEnum, ItemFn, ItemStruct};
 
 use crate::compiler::{
     analyses::{
-        application_config::ApplicationConfig, application_state::ApplicationState,
+        application_config::ApplicationConfig,
+        application_state::ApplicationState,
+        background_tasks::BackgroundTasks,
         computations::ComputationDb,
+        lifecycle_hooks::{LifecycleHook, LifecycleHooks},
     },
-    codegen_utils::VariableNameGenerator,
+    codegen_utils::{VariableNameGenerator, codegen_call},
 };
 
 use super::{ApplicationStateCallGraph, ComponentDb, ResolvedType, deps::ServerSdkDeps};
@@ -140,6 +146,7 @@ pub(super) fn get_application_state_new(
     application_state_private_new: &ItemFn,
     application_state_call_graph: &ApplicationStateCallGraph,
     application_config: &ApplicationConfig,
+    lifecycle_hooks: &LifecycleHooks,
     package_id2name: &BiHashMap<PackageId, String>,
 ) -> Result<ItemFn, anyhow::Error> {
     let input_types = &application_state_call_graph
@@ -171,7 +178,23 @@ pub(super) fn get_application_state_new(
                     unreachable!("Generic types should have been resolved by now")
                 }
             };
-            if let Some(field_name) = application_config.bindings().get_by_right(inner_type) {
+            if let Some(field_name) = application_config
+                .reloadable_bindings()
+                .get_by_right(inner_type)
+            {
+                used_config = true;
+                let handle_type = inner_type.syn_type(package_id2name);
+                if is_shared_reference {
+                    quote! {
+                        &<#handle_type>::new(#config_ident.#field_name)
+                    }
+                } else {
+                    quote! {
+                        <#handle_type>::new(#config_ident.#field_name)
+                    }
+                }
+            } else if let Some(field_name) = application_config.bindings().get_by_right(inner_type)
+            {
                 used_config = true;
                 if is_shared_reference {
                     quote! {
@@ -200,12 +223,23 @@ pub(super) fn get_application_state_new(
     if is_async {
         invocation = quote! { #invocation.await };
     }
-    let async_keyword = is_async.then(|| quote! { async });
-    if application_state_call_graph.error_variants.is_empty() {
+    if lifecycle_hooks.has_startup_hooks() {
+        // Startup hooks are invoked as soon as all singletons have been built.
+        if !application_state_call_graph.error_variants.is_empty() {
+            invocation = quote! { #invocation? };
+        }
+        invocation = quote! {
+            let state = #invocation;
+            state._on_startup().await?;
+            Ok(state)
+        };
+    } else if application_state_call_graph.error_variants.is_empty() {
         invocation = quote! {
             Ok(#invocation)
         };
     }
+    let is_async = is_async || lifecycle_hooks.has_startup_hooks();
+    let async_keyword = is_async.then(|| quote! { async });
     let fn_ = syn::parse2(quote! {
         pub #async_keyword fn new(
             #config_ident: crate::ApplicationConfig,
@@ -240,3 +274,329 @@ pub(super) fn get_application_state_private_new(
         syn::ReturnType::Type(Default::default(), Box::new(syn::parse2(output_type)?));
     Ok(function)
 }
+
+/// Define the `ConfigReloader` type, used to update the values of reloadable
+/// configuration types while the application is running.
+///
+/// It returns `None` if no configuration type has been marked as reloadable.
+pub(super) fn define_config_reloader(
+    application_state: &ApplicationState,
+    application_config: &ApplicationConfig,
+    package_id2name: &BiHashMap<PackageId, String>,
+) -> Option<TokenStream> {
+    // Field name in `ApplicationState` -> (handle type, field name in `ApplicationConfig`)
+    let handles = application_state
+        .bindings()
+        .iter()
+        .filter_map(|(state_field, type_)| {
+            let config_field = application_config
+                .reloadable_bindings()
+                .get_by_right(type_)?;
+            Some((state_field, (type_.syn_type(package_id2name), config_field)))
+        })
+        .collect::<BTreeMap<_, _>>();
+    if handles.is_empty() {
+        return None;
+    }
+    let fields = handles.iter().map(|(state_field, (type_, _))| {
+        quote! { #state_field: #type_ }
+    });
+    let updates = handles.iter().map(|(state_field, (_, config_field))| {
+        quote! { self.#state_field.update(config.#config_field); }
+    });
+    let clones = handles.keys().map(|state_field| {
+        quote! { #state_field: self.#state_field.clone() }
+    });
+    Some(quote! {
+        /// Update the values of reloadable configuration types while the application is running.
+        ///
+        /// Retrieve it via [`ApplicationState::config_reloader`].
+        #[derive(Clone)]
+        pub struct ConfigReloader {
+            #(#fields),*
+        }
+
+        impl ConfigReloader {
+            /// Replace the values of all reloadable configuration types with the ones
+            /// in `config`.
+            ///
+            /// Values for configuration types that are not reloadable are ignored.
+            pub fn reload(&self, config: crate::ApplicationConfig) {
+                #(#updates)*
+            }
+        }
+
+        impl ApplicationState {
+            /// Get a [`ConfigReloader`] for this application state.
+            ///
+            /// All reloaders share the same underlying values with the application state.
+            pub fn config_reloader(&self) -> ConfigReloader {
+                ConfigReloader {
+                    #(#clones),*
+                }
+            }
+        }
+    })
+}
+
+/// Define the methods used to invoke the lifecycle hooks registered by the user:
+///
+/// - `_on_startup`, invoked by `ApplicationState::new` once all singletons have been built;
+/// - `shutdown`, to be invoked when the application is shutting down.
+///
+/// It returns `None` if there are no lifecycle hooks.
+pub(super) fn define_lifecycle_hooks(
+    lifecycle_hooks: &LifecycleHooks,
+    application_state: &ApplicationState,
+    package_id2name: &BiHashMap<PackageId, String>,
+    sdk_deps: &ServerSdkDeps,
+) -> Option<TokenStream> {
+    let pavex = sdk_deps.pavex_ident();
+    let startup = lifecycle_hooks.startup().map(|hook| {
+        let invocation = invoke_hook(hook, application_state, package_id2name, pavex);
+        quote! { #invocation; }
+    });
+    let startup = lifecycle_hooks.has_startup_hooks().then(|| {
+        quote! {
+            async fn _on_startup(&self) -> Result<(), crate::ApplicationStateError> {
+                #(#startup)*
+                Ok(())
+            }
+        }
+    });
+    let shutdown = lifecycle_hooks.shutdown().map(|hook| {
+        let invocation = invoke_hook(hook, application_state, package_id2name, pavex);
+        if hook.error_variant.is_none() && hook.timeout.is_none() {
+            return quote! { #invocation; };
+        }
+        let name = hook.callable.path.to_string();
+        quote! {
+            if let Err(e) = async {
+                #invocation;
+                Ok::<(), crate::ApplicationStateError>(())
+            }
+            .await
+            {
+                #pavex::lifecycle::log_shutdown_error(#name, &e);
+                if outcome.is_ok() {
+                    outcome = Err(e);
+                }
+            }
+        }
+    });
+    let shutdown = lifecycle_hooks.has_shutdown_hooks().then(|| {
+        quote! {
+            /// Invoke the shutdown hooks registered against the application blueprint.
+            ///
+            /// All hooks are invoked, even if some of them fail.
+            /// Failures are logged, and the first one is returned.
+            pub async fn shutdown(&self) -> Result<(), crate::ApplicationStateError> {
+                #[allow(unused_mut)]
+                let mut outcome = Ok(());
+                #(#shutdown)*
+                outcome
+            }
+        }
+    });
+    if startup.is_none() && shutdown.is_none() {
+        return None;
+    }
+    Some(quote! {
+        impl ApplicationState {
+            #startup
+            #shutdown
+        }
+    })
+}
+
+/// Invoke a lifecycle hook, passing the relevant fields of the application state as inputs.
+///
+/// Errors (and timeouts) are converted into `ApplicationStateError` and propagated via `?`.
+fn invoke_hook(
+    hook: &LifecycleHook,
+    application_state: &ApplicationState,
+    package_id2name: &BiHashMap<PackageId, String>,
+    pavex: &Ident,
+) -> TokenStream {
+    let mut bindings: HashMap<ResolvedType, Box<dyn ToTokens>> = HashMap::new();
+    for input in &hook.callable.inputs {
+        let (inner, by_ref) = match input {
+            ResolvedType::Reference(r) if !r.lifetime.is_static() => (r.inner.as_ref(), true),
+            _ => (input, false),
+        };
+        let field_name = application_state
+            .bindings()
+            .get_by_right(inner)
+            .expect("Lifecycle hook inputs are always stored in the application state");
+        let tokens = if by_ref {
+            quote! { &self.#field_name }
+        } else {
+            quote! { self.#field_name.clone() }
+        };
+        bindings.insert(input.to_owned(), Box::new(tokens));
+    }
+    let call = codegen_call(&hook.callable, &bindings, package_id2name);
+    let name = hook.callable.path.to_string();
+    let mut invocation = match hook.timeout {
+        Some(timeout) => {
+            let (secs, nanos) = (timeout.as_secs(), timeout.subsec_nanos());
+            quote! {
+                #pavex::lifecycle::run_hook_with_timeout(
+                    #name,
+                    std::time::Duration::new(#secs, #nanos),
+                    async { #call },
+                )
+                .await
+                .map_err(crate::ApplicationStateError::HookTimeout)?
+            }
+        }
+        None => quote! {
+            #pavex::lifecycle::run_hook(#name, async { #call }).await
+        },
+    };
+    if let Some(variant) = &hook.error_variant {
+        let variant = format_ident!("{}", variant);
+        invocation = quote! {
+            #invocation.map_err(crate::ApplicationStateError::#variant)?
+        };
+    }
+    invocation
+}
+
+/// Define the `ApplicationState::start_background_tasks` method, used to spawn the
+/// background tasks registered by the user.
+///
+/// It returns `None` if there are no background tasks.
+pub(super) fn define_background_tasks(
+    background_tasks: &BackgroundTasks,
+    application_state: &ApplicationState,
+    package_id2name: &BiHashMap<PackageId, String>,
+    sdk_deps: &ServerSdkDeps,
+) -> Option<TokenStream> {
+    let shutdown_signal = background_tasks.shutdown_signal()?;
+    let pavex = sdk_deps.pavex_ident();
+    let singleton_binding = |input: &ResolvedType| -> Box<dyn ToTokens> {
+        let (inner, by_ref) = match input {
+            ResolvedType::Reference(r) if !r.lifetime.is_static() => (r.inner.as_ref(), true),
+            _ => (input, false),
+        };
+        let field_name = application_state
+            .bindings()
+            .get_by_right(inner)
+            .expect("Background task inputs are always stored in the application state");
+        if by_ref {
+            Box::new(quote! { &state.#field_name })
+        } else {
+            Box::new(quote! { state.#field_name.clone() })
+        }
+    };
+    let spawns = background_tasks.iter().map(|task| {
+        let name = task.callable.path.to_string();
+        let mut bindings: HashMap<ResolvedType, Box<dyn ToTokens>> = HashMap::new();
+        for input in &task.callable.inputs {
+            let binding: Box<dyn ToTokens> = match input {
+                ResolvedType::Reference(r) if r.inner.as_ref() == shutdown_signal => {
+                    Box::new(quote! { &shutdown_signal })
+                }
+                _ if input == shutdown_signal => Box::new(quote! { shutdown_signal }),
+                _ => singleton_binding(input),
+            };
+            bindings.insert(input.to_owned(), binding);
+        }
+        let call = codegen_call(&task.callable, &bindings, package_id2name);
+        let body = if task.is_fallible {
+            let report = if task.error_observers.is_empty() {
+                quote! {
+                    #pavex::background::log_task_error(#name, &e);
+                }
+            } else {
+                let observers = task.error_observers.iter().map(|observer| {
+                    let mut bindings: HashMap<ResolvedType, Box<dyn ToTokens>> = HashMap::new();
+                    for (i, input) in observer.callable.inputs.iter().enumerate() {
+                        let binding = if i == observer.error_input_index {
+                            Box::new(quote! { &e })
+                        } else {
+                            singleton_binding(input)
+                        };
+                        bindings.insert(input.to_owned(), binding);
+                    }
+                    let call = codegen_call(&observer.callable, &bindings, package_id2name);
+                    quote! { #call; }
+                });
+                quote! { #(#observers)* }
+            };
+            quote! {
+                if let Err(e) = #call {
+                    let e = #pavex::Error::new(e);
+                    #report
+                }
+            }
+        } else {
+            quote! { #call; }
+        };
+        let schedule = match &task.schedule {
+            TaskSchedule::Once => {
+                let cancel_on_shutdown = !task.takes_shutdown_signal;
+                quote! {
+                    #pavex::background::Schedule::Once { cancel_on_shutdown: #cancel_on_shutdown }
+                }
+            }
+            TaskSchedule::Every(period) => {
+                let (secs, nanos) = (period.as_secs(), period.subsec_nanos());
+                quote! {
+                    #pavex::background::Schedule::Every(std::time::Duration::new(#secs, #nanos))
+                }
+            }
+            TaskSchedule::Cron(expression) => quote! {
+                #pavex::background::Schedule::Cron(
+                    #pavex::background::CronSchedule::parse(#expression)
+                        .expect("The cron expression was validated by Pavex")
+                )
+            },
+        };
+        let uses_state = !task.singletons.is_empty()
+            || task
+                .error_observers
+                .iter()
+                .any(|o| !o.singletons.is_empty());
+        let (outer_clone, inner_clone) = if uses_state {
+            (
+                quote! { let state = self.clone(); },
+                quote! { let state = state.clone(); },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+        let signal = if task.takes_shutdown_signal {
+            quote! { shutdown_signal }
+        } else {
+            quote! { _ }
+        };
+        quote! {
+            {
+                #outer_clone
+                tasks.spawn(#name, #schedule, move |#signal: #pavex::background::ShutdownSignal| {
+                    #inner_clone
+                    async move {
+                        #body
+                    }
+                });
+            }
+        }
+    });
+    Some(quote! {
+        impl ApplicationState {
+            /// Spawn the background tasks registered against the application blueprint
+            /// on the current Tokio runtime.
+            ///
+            /// The generated `run` function calls this method for you.
+            pub fn start_background_tasks(
+                self: std::sync::Arc<Self>
+            ) -> #pavex::background::BackgroundTasks {
+                let mut tasks = #pavex::background::BackgroundTasks::new();
+                #(#spawns)*
+                tasks
+            }
+        }
+    })
+}
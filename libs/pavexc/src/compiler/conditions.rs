@@ -0,0 +1,240 @@
+//! Conditional registrations.
+//!
+//! Components can be registered with a compile-time [`Condition`] attached to them.
+//! We don't try to reason about conditions while analysing the application: we
+//! enumerate every possible combination of the cargo features and profiles that appear in the
+//! blueprint (a [`Variant`]), filter out the components whose conditions don't hold for that
+//! combination and analyse each filtered blueprint on its own.
+//! The code generated for each variant is then guarded by the appropriate `#[cfg(...)]`
+//! attribute.
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+use pavex_bp_schema::{Blueprint, Component, Condition};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// The name of the `cfg` used to select a profile, e.g. `--cfg pavex_profile="test"`.
+pub(crate) const PROFILE_CFG: &str = "pavex_profile";
+
+/// We analyse the blueprint once per variant: we don't want the number of variants to grow
+/// unbounded.
+const MAX_VARIANTS: usize = 32;
+
+/// A combination of enabled cargo features and (optionally) a profile.
+#[derive(Debug, Clone)]
+pub(crate) struct Variant {
+    /// All the features mentioned in the blueprint, with their state in this variant.
+    features: BTreeMap<String, bool>,
+    /// All the profiles mentioned in the blueprint.
+    profiles: BTreeSet<String>,
+    /// The profile selected in this variant, if any.
+    profile: Option<String>,
+}
+
+impl Variant {
+    /// Enumerate all the variants of the application described by the given blueprint.
+    ///
+    /// If no component is registered conditionally, a single unconditional variant is returned.
+    pub(crate) fn enumerate(bp: &Blueprint) -> Result<Vec<Variant>, anyhow::Error> {
+        let mut features = BTreeSet::new();
+        let mut profiles = BTreeSet::new();
+        collect_atoms(bp, &mut features, &mut profiles);
+
+        let n_variants = 2usize
+            .checked_pow(features.len() as u32)
+            .and_then(|n| n.checked_mul(profiles.len() + 1))
+            .unwrap_or(usize::MAX);
+        if n_variants > MAX_VARIANTS {
+            anyhow::bail!(
+                "Your blueprint registers components conditionally on {} cargo features and {} profiles.\n\
+                I need to analyse your application once for every possible combination of them, \
+                {n_variants} in total, but I can handle at most {MAX_VARIANTS}.\n\
+                Reduce the number of distinct features and profiles used in `only_if` conditions.",
+                features.len(),
+                profiles.len()
+            );
+        }
+
+        let profile_choices: Vec<Option<String>> = std::iter::once(None)
+            .chain(profiles.iter().cloned().map(Some))
+            .collect();
+        let mut variants = Vec::with_capacity(n_variants);
+        for profile in profile_choices {
+            for mask in 0..(1usize << features.len()) {
+                let features = features
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| (f.clone(), mask & (1 << i) != 0))
+                    .collect();
+                variants.push(Variant {
+                    features,
+                    profiles: profiles.clone(),
+                    profile: profile.clone(),
+                });
+            }
+        }
+        Ok(variants)
+    }
+
+    /// `true` if no component is registered conditionally.
+    pub(crate) fn is_unconditional(&self) -> bool {
+        self.features.is_empty() && self.profiles.is_empty()
+    }
+
+    /// All the cargo features that are mentioned in the blueprint.
+    pub(crate) fn features(&self) -> impl Iterator<Item = &str> {
+        self.features.keys().map(String::as_str)
+    }
+
+    /// All the profiles that are mentioned in the blueprint.
+    pub(crate) fn profiles(&self) -> impl Iterator<Item = &str> {
+        self.profiles.iter().map(String::as_str)
+    }
+
+    /// Evaluate a condition for this variant.
+    pub(crate) fn holds(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Feature(f) => self.features.get(f).copied().unwrap_or(false),
+            Condition::Profile(p) => self.profile.as_ref() == Some(p),
+            Condition::Not(c) => !self.holds(c),
+            Condition::All(c) => c.iter().all(|c| self.holds(c)),
+            Condition::Any(c) => c.iter().any(|c| self.holds(c)),
+        }
+    }
+
+    /// Return a copy of the blueprint without the components whose conditions
+    /// don't hold for this variant.
+    pub(crate) fn filter(&self, bp: &Blueprint) -> Blueprint {
+        let mut bp = bp.clone();
+        self.filter_in_place(&mut bp);
+        bp
+    }
+
+    fn filter_in_place(&self, bp: &mut Blueprint) {
+        bp.components.retain_mut(|component| match component {
+            Component::Constructor(c) => c.condition.as_ref().is_none_or(|c| self.holds(c)),
+            Component::Route(r) => r.condition.as_ref().is_none_or(|c| self.holds(c)),
+            Component::NestedBlueprint(n) => {
+                self.filter_in_place(&mut n.blueprint);
+                true
+            }
+            _ => true,
+        });
+    }
+
+    /// The predicate of the `#[cfg(...)]` attribute that guards the code generated
+    /// for this variant.
+    pub(crate) fn cfg_predicate(&self) -> TokenStream {
+        let mut predicates = Vec::new();
+        for (feature, enabled) in &self.features {
+            predicates.push(if *enabled {
+                quote! { feature = #feature }
+            } else {
+                quote! { not(feature = #feature) }
+            });
+        }
+        let profile_cfg = quote::format_ident!("{}", PROFILE_CFG);
+        match &self.profile {
+            Some(profile) => predicates.push(quote! { #profile_cfg = #profile }),
+            None if !self.profiles.is_empty() => {
+                let profiles = self.profiles.iter();
+                predicates.push(quote! { not(any(#(#profile_cfg = #profiles),*)) });
+            }
+            None => {}
+        }
+        quote! { all(#(#predicates),*) }
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let enabled: Vec<_> = self
+            .features
+            .iter()
+            .filter(|(_, enabled)| **enabled)
+            .map(|(feature, _)| format!("`{feature}`"))
+            .collect();
+        if enabled.is_empty() {
+            write!(f, "no features enabled")?;
+        } else {
+            write!(f, "features {} enabled", enabled.join(", "))?;
+        }
+        match &self.profile {
+            Some(profile) => write!(f, " and the `{profile}` profile"),
+            None => Ok(()),
+        }
+    }
+}
+
+fn collect_atoms(bp: &Blueprint, features: &mut BTreeSet<String>, profiles: &mut BTreeSet<String>) {
+    fn visit(c: &Condition, features: &mut BTreeSet<String>, profiles: &mut BTreeSet<String>) {
+        match c {
+            Condition::Feature(f) => {
+                features.insert(f.clone());
+            }
+            Condition::Profile(p) => {
+                profiles.insert(p.clone());
+            }
+            Condition::Not(c) => visit(c, features, profiles),
+            Condition::All(c) | Condition::Any(c) => {
+                c.iter().for_each(|c| visit(c, features, profiles))
+            }
+        }
+    }
+
+    for component in &bp.components {
+        let condition = match component {
+            Component::Constructor(c) => c.condition.as_ref(),
+            Component::Route(r) => r.condition.as_ref(),
+            Component::NestedBlueprint(n) => {
+                collect_atoms(&n.blueprint, features, profiles);
+                None
+            }
+            _ => None,
+        };
+        if let Some(condition) = condition {
+            visit(condition, features, profiles);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Variant;
+    use pavex_bp_schema::Condition;
+
+    fn variant(features: &[(&str, bool)], profiles: &[&str], profile: Option<&str>) -> Variant {
+        Variant {
+            features: features.iter().map(|(f, e)| (f.to_string(), *e)).collect(),
+            profiles: profiles.iter().map(|p| p.to_string()).collect(),
+            profile: profile.map(ToOwned::to_owned),
+        }
+    }
+
+    #[test]
+    fn conditions_are_evaluated_against_the_variant() {
+        let fake = Condition::Feature("fake".into());
+        let dev = Condition::Profile("dev".into());
+        let v = variant(&[("fake", true), ("other", false)], &["dev"], None);
+        assert!(v.holds(&fake));
+        assert!(!v.holds(&dev));
+        assert!(!v.holds(&Condition::Not(Box::new(fake.clone()))));
+        assert!(v.holds(&Condition::Any(vec![fake.clone(), dev.clone()])));
+        assert!(!v.holds(&Condition::All(vec![fake, dev])));
+    }
+
+    #[test]
+    fn cfg_predicates() {
+        let v = variant(&[("a", true), ("b", false)], &["dev", "test"], None);
+        assert_eq!(
+            v.cfg_predicate().to_string(),
+            r#"all (feature = "a" , not (feature = "b") , not (any (pavex_profile = "dev" , pavex_profile = "test")))"#
+        );
+        let v = variant(&[], &["dev"], Some("dev"));
+        assert_eq!(
+            v.cfg_predicate().to_string(),
+            r#"all (pavex_profile = "dev")"#
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
+use anyhow::Context;
 use cargo_manifest::{Dependency, Edition};
 use guppy::graph::PackageGraph;
 use proc_macro2::TokenStream;
@@ -8,6 +9,7 @@ use serde::Serialize;
 use toml_edit::ser::ValueSerializer;
 
 use crate::AppWriter;
+use crate::compiler::conditions::PROFILE_CFG;
 
 #[derive(Clone)]
 /// The manifest and the code for a generated application.
@@ -16,9 +18,19 @@ use crate::AppWriter;
 pub struct GeneratedApp {
     pub(crate) lib_rs: TokenStream,
     pub(crate) cargo_toml: GeneratedManifest,
+    pub(crate) config_schema: GeneratedConfigSchema,
     pub(crate) package_graph: PackageGraph,
 }
 
+#[derive(Clone, Debug)]
+/// The JSON Schema for the generated `ApplicationConfig`, with a sample configuration file.
+///
+/// They are persisted under the `configuration` directory of the generated crate.
+pub struct GeneratedConfigSchema {
+    pub(crate) json_schema: String,
+    pub(crate) sample: String,
+}
+
 #[derive(Clone, Debug)]
 /// The fields that we *must* control in the manifest for the generated application.  
 pub struct GeneratedManifest {
@@ -26,10 +38,17 @@ pub struct GeneratedManifest {
     pub dependencies: BTreeMap<String, Dependency>,
     /// Edition used by the generated code.
     pub edition: Edition,
+    /// The cargo features used in the conditions of the registered components.
+    pub features: BTreeSet<String>,
+    /// The profiles used in the conditions of the registered components.
+    pub profiles: BTreeSet<String>,
 }
 
 impl GeneratedManifest {
-    fn overwrite(&self, existing_manifest: &mut toml_edit::DocumentMut) {
+    fn overwrite(
+        &self,
+        existing_manifest: &mut toml_edit::DocumentMut,
+    ) -> Result<(), anyhow::Error> {
         // Set dependencies
         existing_manifest["dependencies"] = toml_edit::Item::Table(
             self.dependencies
@@ -51,6 +70,46 @@ impl GeneratedManifest {
         // Set edition
         let edition_value = Serialize::serialize(&self.edition, ValueSerializer::new()).unwrap();
         existing_manifest["package"]["edition"] = toml_edit::Item::Value(edition_value);
+        // Declare the features used in conditions.
+        // We don't touch features that have already been declared: users may want
+        // them to enable features of their dependencies.
+        if !self.features.is_empty() {
+            let features = existing_manifest["features"].or_insert(toml_edit::table());
+            let type_name = features.type_name();
+            let Some(features) = features.as_table_like_mut() else {
+                anyhow::bail!(
+                    "`features` must be a table in the manifest of the generated server SDK, \
+                    but it's a `{type_name}`.\n\
+                    I need to declare the cargo features used in the conditions of your blueprint there."
+                );
+            };
+            for feature in &self.features {
+                if !features.contains_key(feature) {
+                    features.insert(feature, toml_edit::value(toml_edit::Array::new()));
+                }
+            }
+        }
+        // Let `rustc` know about the profile `cfg`, to avoid `unexpected_cfgs` warnings.
+        // Lints can't be customized if they are inherited from the workspace.
+        let inherits_lints = existing_manifest
+            .get("lints")
+            .and_then(|l| l.get("workspace"))
+            .is_some();
+        if !self.profiles.is_empty() && !inherits_lints {
+            let values = self
+                .profiles
+                .iter()
+                .map(|p| format!("\"{p}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let check_cfg = format!("cfg({PROFILE_CFG}, values({values}))");
+            let mut unexpected_cfgs = toml_edit::InlineTable::new();
+            unexpected_cfgs.insert("level", "warn".into());
+            unexpected_cfgs.insert("check-cfg", toml_edit::Array::from_iter([check_cfg]).into());
+            existing_manifest["lints"]["rust"]["unexpected_cfgs"] =
+                toml_edit::value(unexpected_cfgs);
+        }
+        Ok(())
     }
 }
 
@@ -62,6 +121,7 @@ impl GeneratedApp {
         let Self {
             lib_rs,
             mut cargo_toml,
+            config_schema,
             package_graph,
         } = self;
         let workspace = package_graph.workspace();
@@ -82,6 +142,17 @@ impl GeneratedApp {
         let lib_rs = prettyplease::unparse(&syn::parse2(lib_rs)?);
         writer.persist_if_changed(&source_directory.join("lib.rs"), lib_rs.as_bytes())?;
 
+        let configuration_directory = pkg_directory.join("configuration");
+        fs_err::create_dir_all(&configuration_directory)?;
+        writer.persist_if_changed(
+            &configuration_directory.join("schema.json"),
+            config_schema.json_schema.as_bytes(),
+        )?;
+        writer.persist_if_changed(
+            &configuration_directory.join("base.sample.yml"),
+            config_schema.sample.as_bytes(),
+        )?;
+
         Ok(())
     }
 
@@ -134,7 +205,9 @@ impl GeneratedApp {
                 }
             }
         };
-        cargo_toml.overwrite(&mut manifest);
+        cargo_toml
+            .overwrite(&mut manifest)
+            .with_context(|| format!("Failed to update `{}`", cargo_toml_path.display()))?;
         writer.persist_if_changed(&cargo_toml_path, manifest.to_string().as_bytes())?;
         Ok(())
     }
@@ -204,3 +277,45 @@ impl GeneratedApp {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::GeneratedManifest;
+    use cargo_manifest::Edition;
+    use insta::assert_snapshot;
+
+    fn manifest(features: &[&str]) -> GeneratedManifest {
+        GeneratedManifest {
+            dependencies: Default::default(),
+            edition: Edition::E2021,
+            features: features.iter().map(|f| f.to_string()).collect(),
+            profiles: Default::default(),
+        }
+    }
+
+    #[test]
+    fn features_used_in_conditions_are_declared() {
+        let mut existing = "[package]\nname = \"app\"\n\n[features]\nfake = [\"dep/fake\"]\n"
+            .parse::<toml_edit::DocumentMut>()
+            .unwrap();
+        manifest(&["fake", "debug"])
+            .overwrite(&mut existing)
+            .unwrap();
+        let features = existing["features"].as_table().unwrap();
+        // Existing declarations are left untouched.
+        assert_eq!(features["fake"].to_string().trim(), "[\"dep/fake\"]");
+        assert_eq!(features["debug"].to_string().trim(), "[]");
+    }
+
+    #[test]
+    fn a_malformed_features_entry_is_reported() {
+        let mut existing = "features = \"fake\"\n\n[package]\nname = \"app\"\n"
+            .parse::<toml_edit::DocumentMut>()
+            .unwrap();
+        let err = manifest(&["fake"]).overwrite(&mut existing).unwrap_err();
+        assert_snapshot!(err, @r"
+        `features` must be a table in the manifest of the generated server SDK, but it's a `string`.
+        I need to declare the cargo features used in the conditions of your blueprint there.
+        ");
+    }
+}
@@ -8,6 +8,7 @@ mod codegen;
 mod codegen_utils;
 mod component;
 mod computation;
+mod conditions;
 mod generated_app;
 mod interner;
 mod path_parameters;
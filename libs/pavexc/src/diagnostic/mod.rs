@@ -36,6 +36,8 @@ pub enum ComponentKind {
     ErrorObserver,
     PrebuiltType,
     ConfigType,
+    LifecycleHook,
+    BackgroundTask,
 }
 
 impl Display for ComponentKind {
@@ -50,6 +52,8 @@ impl Display for ComponentKind {
             ComponentKind::ErrorObserver => "error observer",
             ComponentKind::PrebuiltType => "prebuilt type",
             ComponentKind::ConfigType => "config type",
+            ComponentKind::LifecycleHook => "lifecycle hook",
+            ComponentKind::BackgroundTask => "background task",
         };
         write!(f, "{s}")
     }
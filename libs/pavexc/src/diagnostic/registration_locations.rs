@@ -41,8 +41,8 @@ pub(crate) fn get_f_macro_invocation_span(
             let argument_index = match node.method.to_string().as_str() {
                 "error_handler" | "error_observer" | "constructor" | "wrap" | "pre_process"
                 | "post_process" | "fallback" | "singleton" | "request_scoped" | "transient"
-                | "prebuilt" => 0,
-                "config" => 1,
+                | "prebuilt" | "import" | "on_startup" | "on_shutdown" | "background_task" => 0,
+                "config" | "cron" => 1,
                 "route" => 2,
                 s => {
                     tracing::trace!(
@@ -73,6 +73,11 @@ pub(crate) fn get_f_macro_invocation_span(
                         | ("Blueprint", "post_process")
                         | ("Blueprint", "prebuilt")
                         | ("Blueprint", "fallback")
+                        | ("Blueprint", "import")
+                        | ("Blueprint", "on_startup")
+                        | ("Blueprint", "on_shutdown")
+                        | ("Blueprint", "background_task")
+                        | ("BackgroundTask", "cron")
                         | ("ConfigType", "new") => {
                             // Blueprint::error_handler(bp, handler)
                             // Blueprint::error_observer(bp, observer)
@@ -85,6 +90,11 @@ pub(crate) fn get_f_macro_invocation_span(
                             // Blueprint::post_process(bp, middleware)
                             // Blueprint::fallback(bp, fallback)
                             // Blueprint::prebuilt(bp, prebuilt)
+                            // Blueprint::import(bp, sources)
+                            // Blueprint::on_startup(bp, hook)
+                            // Blueprint::on_shutdown(bp, hook)
+                            // Blueprint::background_task(bp, task)
+                            // BackgroundTask::cron(schedule, task)
                             // ConfigType::new(key, config)
                             1
                         }
@@ -92,8 +102,9 @@ pub(crate) fn get_f_macro_invocation_span(
                             // Blueprint::route(bp, method, path_pattern, handler)
                             3
                         }
-                        ("Route", "new") | ("Blueprint", "config") => {
+                        ("Route", "new") | ("Blueprint", "config") | ("Blueprint", "cron") => {
                             // Blueprint::config(bp, key, config)
+                            // Blueprint::cron(bp, schedule, task)
                             // Route::new(method, path_pattern, handler)
                             2
                         }
@@ -106,6 +117,9 @@ pub(crate) fn get_f_macro_invocation_span(
                         | ("PostProcessingMiddleware", "new")
                         | ("ErrorObserver", "new")
                         | ("PrebuiltType", "new")
+                        | ("LifecycleHook", "startup")
+                        | ("LifecycleHook", "shutdown")
+                        | ("BackgroundTask", "new")
                         | ("Fallback", "new") => {
                             // Constructor::new(constructor, lifecycle)
                             // Constructor::request_scoped(constructor)
@@ -117,6 +131,9 @@ pub(crate) fn get_f_macro_invocation_span(
                             // ErrorObserver::new(observer)
                             // Fallback::new(fallback)
                             // PrebuiltType::new(prebuilt)
+                            // LifecycleHook::startup(hook)
+                            // LifecycleHook::shutdown(hook)
+                            // BackgroundTask::new(task)
                             0
                         }
                         _ => {
@@ -6,8 +6,8 @@ pub(crate) use resolved_path::{
     UnknownPath,
 };
 pub(crate) use resolved_type::{
-    Generic, GenericArgument, GenericLifetimeParameter, Lifetime, PathType, ResolvedType, Slice,
-    Tuple, TypeReference,
+    Generic, GenericArgument, GenericLifetimeParameter, Lifetime, PathType, ResolvedType,
+    ScalarPrimitive, Slice, Tuple, TypeReference,
 };
 
 mod callable;
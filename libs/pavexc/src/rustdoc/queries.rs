@@ -841,6 +841,30 @@ impl Crate {
         }))
     }
 
+    /// Return the id of the module defined at the given path, if there is one.
+    ///
+    /// The first segment of the path is expected to be the name of the crate.
+    /// Modules are not indexed in `import_path2id`, therefore we walk the module tree
+    /// starting from the crate root.
+    /// Re-exported modules (i.e. `pub use` statements) are not followed.
+    pub fn get_module_id_by_path(&self, path: &[String]) -> Option<rustdoc_types::Id> {
+        let mut current = self.core.krate.root_item_id;
+        for segment in path.iter().skip(1) {
+            let item = self.maybe_get_item_by_local_type_id(&current)?;
+            let ItemEnum::Module(module) = &item.inner else {
+                return None;
+            };
+            current = module.items.iter().copied().find(|id| {
+                self.maybe_get_item_by_local_type_id(id)
+                    .is_some_and(|item| {
+                        matches!(item.inner, ItemEnum::Module(_))
+                            && item.name.as_deref() == Some(segment.as_str())
+                    })
+            })?;
+        }
+        Some(current)
+    }
+
     /// Return the crate_id, path and item kind for a **local** type id.
     ///
     /// It only works for structs, enums and functions.
@@ -0,0 +1,571 @@
+//! Emit the diagnostics produced by `pavexc` in the format requested by the user.
+//!
+//! Human-readable diagnostics are rendered via `pavex_miette`.
+//! For tools (editors, CI annotators, etc.), we support two machine-readable formats:
+//!
+//! - `json`, one JSON object per line, using the same `compiler-message` envelope emitted
+//!   by `cargo --message-format json`, with `rustc`'s diagnostic schema in its `message` field;
+//! - `sarif`, a single SARIF v2.1.0 log, emitted once all diagnostics have been collected.
+//!
+//! Machine-readable diagnostics are written to `stdout`, human-readable ones to `stderr`.
+use std::collections::HashSet;
+use std::fmt::{Debug, Display, Formatter};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use guppy::graph::{BuildTargetId, BuildTargetKind, PackageGraph};
+use miette::{Diagnostic, LabeledSpan, ReportHandler, Severity, SourceCode};
+use pavex_miette::PavexMietteHandler;
+use serde_json::{Value, json};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Rendered for humans, using `pavex_miette`.
+    Human,
+    /// One `cargo`-compatible `compiler-message` per line.
+    Json,
+    /// A SARIF v2.1.0 log.
+    Sarif,
+}
+
+impl Display for MessageFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageFormat::Human => write!(f, "human"),
+            MessageFormat::Json => write!(f, "json"),
+            MessageFormat::Sarif => write!(f, "sarif"),
+        }
+    }
+}
+
+impl FromStr for MessageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            "sarif" => Ok(MessageFormat::Sarif),
+            s => Err(anyhow::anyhow!("Invalid message format: {}", s)),
+        }
+    }
+}
+
+/// The package that `pavexc` is generating code for.
+///
+/// It identifies the origin of JSON diagnostics, just like the `package_id`,
+/// `manifest_path` and `target` fields of `cargo`'s `compiler-message`s.
+#[derive(Debug, Clone, Default)]
+pub struct MessageOrigin {
+    package_id: Option<String>,
+    manifest_path: Option<String>,
+    target: Option<Value>,
+}
+
+impl MessageOrigin {
+    /// Look up the server SDK crate in the package graph.
+    ///
+    /// The manifest path is always set, but the package may be missing from the graph
+    /// (e.g. the first time the server SDK is generated). Its id and target are then left empty.
+    pub fn server_sdk(package_graph: &PackageGraph, output: &Path) -> Self {
+        let manifest_path = output.join("Cargo.toml");
+        let mut origin = Self {
+            manifest_path: Some(manifest_path.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        let Some(package) = package_graph
+            .packages()
+            .find(|p| p.manifest_path().as_std_path() == manifest_path)
+        else {
+            return origin;
+        };
+        origin.package_id = Some(package.id().repr().to_owned());
+        if let Some(lib) = package.build_target(&BuildTargetId::Library) {
+            let crate_types: Vec<&str> = match lib.kind() {
+                BuildTargetKind::LibraryOrExample(crate_types) => {
+                    crate_types.iter().map(String::as_str).collect()
+                }
+                BuildTargetKind::ProcMacro => vec!["proc-macro"],
+                _ => vec!["lib"],
+            };
+            origin.target = Some(json!({
+                "kind": crate_types,
+                "crate_types": crate_types,
+                "name": lib.name(),
+                "src_path": lib.path(),
+                "edition": package.edition(),
+                "doctest": true,
+                "test": true,
+                "doc": true,
+            }));
+        }
+        origin
+    }
+}
+
+/// The compiler may emit the same diagnostic more than once
+/// (for a variety of reasons). We use this helper to dedup them.
+pub struct DiagnosticReporter<W: Write = std::io::Stdout> {
+    format: MessageFormat,
+    origin: MessageOrigin,
+    already_emitted: HashSet<String>,
+    /// The handler used to build the `rendered` field of JSON diagnostics.
+    /// It never uses colors, since the output is going to be consumed by other tools.
+    plain_handler: PavexMietteHandler,
+    /// SARIF results are buffered, since they must be emitted as a single document.
+    sarif_results: Vec<Value>,
+    /// Where machine-readable diagnostics are written to.
+    out: W,
+}
+
+impl DiagnosticReporter {
+    pub fn new(format: MessageFormat) -> Self {
+        Self::with_writer(format, std::io::stdout())
+    }
+}
+
+impl<W: Write> DiagnosticReporter<W> {
+    /// Write machine-readable diagnostics to `out` rather than `stdout`.
+    pub fn with_writer(format: MessageFormat, out: W) -> Self {
+        Self {
+            format,
+            origin: MessageOrigin::default(),
+            already_emitted: Default::default(),
+            plain_handler: pavex_miette::PavexMietteHandlerOpts::new()
+                .color(false)
+                .without_cause_chain()
+                .build(),
+            sarif_results: Vec::new(),
+            out,
+        }
+    }
+
+    /// Set the package that JSON diagnostics are attributed to.
+    pub fn set_origin(&mut self, origin: MessageOrigin) {
+        self.origin = origin;
+    }
+
+    pub fn print_report(&mut self, e: &miette::Report) {
+        let formatted = format!("{e:?}");
+        if self.already_emitted.contains(&formatted) {
+            // Avoid printing the same diagnostic multiple times.
+            return;
+        }
+        let diagnostic: &dyn Diagnostic = e.as_ref();
+        match self.format {
+            MessageFormat::Human => eprintln!("{formatted}"),
+            MessageFormat::Json => {
+                let rendered = format!(
+                    "{:?}",
+                    Rendered {
+                        handler: &self.plain_handler,
+                        diagnostic,
+                    }
+                );
+                let mut message = rustc_diagnostic(diagnostic, diagnostic.source_code());
+                message["$message_type"] = json!("diagnostic");
+                message["rendered"] = json!(rendered);
+                let envelope = json!({
+                    "reason": "compiler-message",
+                    "package_id": self.origin.package_id,
+                    "manifest_path": self.origin.manifest_path,
+                    "target": self.origin.target,
+                    "message": message,
+                });
+                let _ = writeln!(self.out, "{envelope}");
+            }
+            MessageFormat::Sarif => {
+                self.sarif_results.push(sarif_result(diagnostic));
+            }
+        }
+        self.already_emitted.insert(formatted);
+    }
+
+    /// Emit all buffered diagnostics, if the chosen format requires it.
+    ///
+    /// It must be called before returning, even if generation failed: tools
+    /// expect a SARIF log to be emitted in all cases.
+    pub fn finish(mut self) {
+        if self.format != MessageFormat::Sarif {
+            return;
+        }
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "pavexc",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://pavex.dev",
+                    }
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": self.sarif_results,
+            }]
+        });
+        let _ = writeln!(self.out, "{log}");
+    }
+}
+
+/// Render a diagnostic using a specific handler, rather than the global `miette` hook.
+struct Rendered<'a> {
+    handler: &'a PavexMietteHandler,
+    diagnostic: &'a dyn Diagnostic,
+}
+
+impl Debug for Rendered<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.handler.debug(self.diagnostic, f)
+    }
+}
+
+/// A labeled span, resolved to a position in a named source file.
+struct ResolvedSpan {
+    file_name: String,
+    byte_start: usize,
+    byte_end: usize,
+    /// 1-based.
+    line_start: usize,
+    /// 1-based.
+    line_end: usize,
+    /// 1-based, in characters.
+    column_start: usize,
+    /// 1-based, in characters.
+    column_end: usize,
+    /// The full text of the lines covered by the span.
+    lines: Vec<String>,
+    label: Option<String>,
+    is_primary: bool,
+}
+
+fn resolve_labels(
+    diagnostic: &dyn Diagnostic,
+    source: Option<&dyn SourceCode>,
+) -> Vec<ResolvedSpan> {
+    let (Some(source), Some(labels)) = (source, diagnostic.labels()) else {
+        return Vec::new();
+    };
+    // We ask for an empty span with unbounded context to get the entire source file.
+    let Ok(contents) = source.read_span(&(0, 0).into(), usize::MAX, usize::MAX) else {
+        return Vec::new();
+    };
+    let Some(file_name) = contents.name().filter(|n| !n.is_empty()) else {
+        return Vec::new();
+    };
+    let Ok(text) = std::str::from_utf8(contents.data()) else {
+        return Vec::new();
+    };
+    labels
+        .filter_map(|label| resolve_label(file_name, text, label))
+        .collect()
+}
+
+fn resolve_label(file_name: &str, text: &str, label: LabeledSpan) -> Option<ResolvedSpan> {
+    let byte_start = label.offset();
+    let byte_end = byte_start + label.len();
+    if byte_end > text.len() {
+        return None;
+    }
+    let (line_start, column_start) = line_and_column(text, byte_start)?;
+    let (line_end, column_end) = line_and_column(text, byte_end)?;
+    let lines = text
+        .lines()
+        .skip(line_start - 1)
+        .take(line_end - line_start + 1)
+        .map(ToOwned::to_owned)
+        .collect();
+    Some(ResolvedSpan {
+        file_name: file_name.to_owned(),
+        byte_start,
+        byte_end,
+        line_start,
+        line_end,
+        column_start,
+        column_end,
+        lines,
+        is_primary: label.primary(),
+        label: label.label().map(ToOwned::to_owned),
+    })
+}
+
+/// Convert a byte offset into a 1-based (line, column) pair.
+/// Columns are measured in characters.
+fn line_and_column(text: &str, offset: usize) -> Option<(usize, usize)> {
+    let before = text.get(..offset)?;
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    Some((line, column))
+}
+
+fn rustc_level(severity: Option<Severity>) -> &'static str {
+    match severity.unwrap_or(Severity::Error) {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Advice => "help",
+    }
+}
+
+/// Convert a diagnostic into the JSON schema used by `rustc`.
+///
+/// Related diagnostics become children of the top-level diagnostic:
+/// `pavexc` uses them to point at code snippets located in a different source file,
+/// so we preserve their spans.
+fn rustc_diagnostic(diagnostic: &dyn Diagnostic, source: Option<&dyn SourceCode>) -> Value {
+    let mut children = Vec::new();
+    if let Some(related) = diagnostic.related() {
+        for r in related {
+            let mut child = rustc_diagnostic(r, r.source_code().or(source));
+            if child["message"].as_str().is_some_and(str::is_empty) {
+                child["level"] = json!("note");
+            }
+            children.push(child);
+        }
+    }
+    if let Some(help) = diagnostic.help() {
+        children.push(json!({
+            "message": help.to_string(),
+            "code": null,
+            "level": "help",
+            "spans": [],
+            "children": [],
+            "rendered": null,
+        }));
+    }
+    let spans: Vec<Value> = resolve_labels(diagnostic, source)
+        .into_iter()
+        .map(|s| {
+            let text: Vec<Value> = s
+                .lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let n_chars = line.chars().count() + 1;
+                    let highlight_start = if i == 0 { s.column_start } else { 1 };
+                    let highlight_end = if i + 1 == s.lines.len() {
+                        s.column_end
+                    } else {
+                        n_chars
+                    };
+                    json!({
+                        "text": line,
+                        "highlight_start": highlight_start,
+                        "highlight_end": highlight_end,
+                    })
+                })
+                .collect();
+            json!({
+                "file_name": s.file_name,
+                "byte_start": s.byte_start,
+                "byte_end": s.byte_end,
+                "line_start": s.line_start,
+                "line_end": s.line_end,
+                "column_start": s.column_start,
+                "column_end": s.column_end,
+                "is_primary": s.is_primary,
+                "text": text,
+                "label": s.label,
+                "suggested_replacement": null,
+                "suggestion_applicability": null,
+                "expansion": null,
+            })
+        })
+        .collect();
+    json!({
+        "message": diagnostic.to_string(),
+        "code": diagnostic.code().map(|c| json!({ "code": c.to_string(), "explanation": null })),
+        "level": rustc_level(diagnostic.severity()),
+        "spans": spans,
+        "children": children,
+        "rendered": null,
+    })
+}
+
+fn sarif_level(severity: Option<Severity>) -> &'static str {
+    match severity.unwrap_or(Severity::Error) {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Advice => "note",
+    }
+}
+
+fn sarif_location(span: &ResolvedSpan) -> Value {
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": span.file_name },
+            "region": {
+                "startLine": span.line_start,
+                "startColumn": span.column_start,
+                "endLine": span.line_end,
+                "endColumn": span.column_end,
+                "byteOffset": span.byte_start,
+                "byteLength": span.byte_end - span.byte_start,
+            }
+        }
+    });
+    if let Some(label) = &span.label {
+        location["message"] = json!({ "text": label });
+    }
+    location
+}
+
+/// Convert a diagnostic into a SARIF `result` object.
+///
+/// The first label of the diagnostic becomes its location, all other labels
+/// (including those attached to related diagnostics) become related locations.
+fn sarif_result(diagnostic: &dyn Diagnostic) -> Value {
+    let source = diagnostic.source_code();
+    let mut spans = resolve_labels(diagnostic, source);
+    // Primary labels come first.
+    spans.sort_by_key(|s| !s.is_primary);
+    let mut spans = spans.into_iter();
+    let locations: Vec<Value> = spans.next().iter().map(sarif_location).collect();
+    let mut related: Vec<(Option<String>, ResolvedSpan)> = spans.map(|s| (None, s)).collect();
+    if let Some(related_diagnostics) = diagnostic.related() {
+        for r in related_diagnostics {
+            let message = r.to_string();
+            let message = (!message.is_empty()).then_some(message);
+            for span in resolve_labels(r, r.source_code().or(source)) {
+                related.push((message.clone(), span));
+            }
+        }
+    }
+    let related_locations: Vec<Value> = related
+        .into_iter()
+        .enumerate()
+        .map(|(id, (message, span))| {
+            let mut location = sarif_location(&span);
+            location["id"] = json!(id);
+            if let Some(message) = message {
+                let text = match &span.label {
+                    Some(label) => format!("{message}: {label}"),
+                    None => message,
+                };
+                location["message"] = json!({ "text": text });
+            }
+            location
+        })
+        .collect();
+
+    let mut text = diagnostic.to_string();
+    if let Some(help) = diagnostic.help() {
+        text.push_str(&format!("\nhelp: {help}"));
+    }
+    let mut result = json!({
+        "level": sarif_level(diagnostic.severity()),
+        "message": { "text": text },
+        "locations": locations,
+        "relatedLocations": related_locations,
+    });
+    if let Some(code) = diagnostic.code() {
+        result["ruleId"] = json!(code.to_string());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiagnosticReporter, MessageFormat, MessageOrigin};
+    use miette::{NamedSource, SourceSpan};
+    use serde_json::Value;
+
+    #[derive(Debug, thiserror::Error, miette::Diagnostic)]
+    #[error("`handler` is not a valid request handler")]
+    #[diagnostic(
+        code(pavexc::invalid_handler),
+        help("Check the signature of `handler`")
+    )]
+    struct InvalidHandler {
+        #[source_code]
+        code: NamedSource<String>,
+        #[label("The handler was registered here")]
+        span: SourceSpan,
+    }
+
+    fn report() -> miette::Report {
+        let source = "fn main() {\n    bp.route(GET, \"/\", f!(handler));\n}\n";
+        let start = source.find("f!").unwrap();
+        InvalidHandler {
+            code: NamedSource::new("src/blueprint.rs", source.to_owned()),
+            span: (start, "f!(handler)".len()).into(),
+        }
+        .into()
+    }
+
+    fn lines(out: &[u8]) -> Vec<Value> {
+        std::str::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn json_diagnostics_are_wrapped_in_a_compiler_message() {
+        let mut out = Vec::new();
+        let mut reporter = DiagnosticReporter::with_writer(MessageFormat::Json, &mut out);
+        reporter.set_origin(MessageOrigin {
+            package_id: Some("path+file:///app/server_sdk#0.1.0".into()),
+            manifest_path: Some("/app/server_sdk/Cargo.toml".into()),
+            target: None,
+        });
+        reporter.print_report(&report());
+        reporter.finish();
+
+        let [message] = lines(&out).try_into().unwrap();
+        assert_eq!(message["reason"], "compiler-message");
+        assert_eq!(message["package_id"], "path+file:///app/server_sdk#0.1.0");
+        assert_eq!(message["manifest_path"], "/app/server_sdk/Cargo.toml");
+
+        let diagnostic = &message["message"];
+        assert_eq!(diagnostic["$message_type"], "diagnostic");
+        assert_eq!(diagnostic["level"], "error");
+        assert_eq!(diagnostic["code"]["code"], "pavexc::invalid_handler");
+        assert_eq!(diagnostic["children"][0]["level"], "help");
+        assert!(diagnostic["rendered"].as_str().unwrap().contains("handler"));
+        let span = &diagnostic["spans"][0];
+        assert_eq!(span["file_name"], "src/blueprint.rs");
+        assert_eq!(span["line_start"], 2);
+        assert_eq!(span["column_start"], 24);
+        assert_eq!(span["label"], "The handler was registered here");
+    }
+
+    #[test]
+    fn duplicated_diagnostics_are_emitted_once() {
+        let mut out = Vec::new();
+        let mut reporter = DiagnosticReporter::with_writer(MessageFormat::Json, &mut out);
+        reporter.print_report(&report());
+        reporter.print_report(&report());
+        reporter.finish();
+        assert_eq!(lines(&out).len(), 1);
+    }
+
+    #[test]
+    fn sarif_log_is_emitted_on_finish() {
+        let mut out = Vec::new();
+        let mut reporter = DiagnosticReporter::with_writer(MessageFormat::Sarif, &mut out);
+        reporter.print_report(&report());
+        // Nothing is written until the reporter is finished.
+        assert!(reporter.out.is_empty());
+        reporter.finish();
+
+        let [log] = lines(&out).try_into().unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        let result = &log["runs"][0]["results"][0];
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["ruleId"], "pavexc::invalid_handler");
+        let region = &result["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 2);
+        assert_eq!(region["startColumn"], 24);
+    }
+
+    #[test]
+    fn an_empty_sarif_log_is_emitted_if_there_are_no_diagnostics() {
+        let mut out = Vec::new();
+        DiagnosticReporter::with_writer(MessageFormat::Sarif, &mut out).finish();
+        let [log] = lines(&out).try_into().unwrap();
+        assert_eq!(log["runs"][0]["results"], serde_json::json!([]));
+    }
+}
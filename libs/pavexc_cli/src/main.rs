@@ -1,16 +1,18 @@
 #![allow(clippy::too_many_arguments)]
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use std::str::FromStr;
 
+mod diagnostic_reporter;
 mod formatter;
 mod telemetry;
 
 use anyhow::Context;
 use cargo_like_utils::shell::Shell;
 use clap::{Parser, Subcommand};
+use diagnostic_reporter::{DiagnosticReporter, MessageFormat, MessageOrigin};
 use formatter::ReversedFull;
 use generate_from_path::GenerateArgs;
 use liquid_core::Value;
@@ -135,6 +137,13 @@ enum Commands {
         /// since they're likely to change almost every time the project is built.
         /// You can change this behavior by setting this flag.
         cache_workspace_packages: bool,
+        #[clap(long, env = "PAVEXC_MESSAGE_FORMAT", default_value_t = MessageFormat::Human)]
+        /// The format used to report diagnostics: human, json, sarif.
+        ///
+        /// `json` emits one JSON object per line on stdout, using the same `compiler-message`
+        /// envelope emitted by `cargo --message-format json`.
+        /// `sarif` emits a single SARIF v2.1.0 log on stdout, once generation has completed.
+        message_format: MessageFormat,
         /// Optional. The path to a file that contains the JSON returned by `cargo metadata --format-version 1`.
         /// If provided, `pavexc` will use this metadata to build the package graph instead of invoking `cargo metadata`
         /// itself.
@@ -302,7 +311,9 @@ fn _main(cli: Cli) -> Result<ExitCode, miette::Error> {
             docs_toolchain,
             precomputed_metadata,
             cache_workspace_packages,
-        } => generate(
+            message_format,
+        } => Ok(generate(
+            DiagnosticReporter::new(message_format),
             blueprint,
             docs_toolchain,
             diagnostics,
@@ -311,8 +322,7 @@ fn _main(cli: Cli) -> Result<ExitCode, miette::Error> {
             cache_workspace_packages,
             precomputed_metadata,
             check,
-        )
-        .map_err(anyhow2miette),
+        )),
         Commands::New { path, template } => scaffold_project(path, template).map_err(anyhow2miette),
         Commands::Self_ {
             command: SelfCommands::Setup { docs_toolchain },
@@ -347,8 +357,13 @@ struct ToolchainInfo {
     name: String,
 }
 
-#[tracing::instrument("Generate server sdk")]
-fn generate(
+/// Generate the server SDK, reporting all failures via `reporter`.
+///
+/// Machine-readable formats must account for every failure, so errors are never propagated
+/// to the caller: they're emitted as diagnostics and the function returns a failure exit code.
+#[tracing::instrument("Generate server sdk", skip(reporter))]
+fn generate<W: std::io::Write>(
+    mut reporter: DiagnosticReporter<W>,
     blueprint: PathBuf,
     docs_toolchain: String,
     diagnostics: Option<PathBuf>,
@@ -357,14 +372,44 @@ fn generate(
     cache_workspace_packages: bool,
     precomputed_metadata: Option<PathBuf>,
     check: bool,
+) -> ExitCode {
+    let code = match _generate(
+        &mut reporter,
+        blueprint,
+        docs_toolchain,
+        diagnostics,
+        output,
+        cache_workspace_packages,
+        precomputed_metadata,
+        check,
+    ) {
+        Ok(code) => code,
+        Err(e) => {
+            reporter.print_report(&anyhow2miette(e));
+            ExitCode::FAILURE
+        }
+    };
+    reporter.finish();
+    code
+}
+
+fn _generate<W: std::io::Write>(
+    reporter: &mut DiagnosticReporter<W>,
+    blueprint: PathBuf,
+    docs_toolchain: String,
+    diagnostics: Option<PathBuf>,
+    output: PathBuf,
+    cache_workspace_packages: bool,
+    precomputed_metadata: Option<PathBuf>,
+    check: bool,
 ) -> Result<ExitCode, anyhow::Error> {
     let blueprint = {
         let file = fs_err::OpenOptions::new().read(true).open(blueprint)?;
-        ron::de::from_reader(&file)?
+        ron::de::from_reader(&file).context("Failed to deserialize the application blueprint")?
     };
-    let mut reporter = DiagnosticReporter::new();
 
     let package_graph = package_graph::retrieve_or_compute_package_graph(precomputed_metadata)?;
+    reporter.set_origin(MessageOrigin::server_sdk(&package_graph, &output));
     let (app, issues) = match App::build(
         blueprint,
         docs_toolchain,
@@ -406,10 +451,9 @@ fn generate(
         for e in errors {
             reporter.print_report(&e);
         }
-        Ok(ExitCode::FAILURE)
-    } else {
-        Ok(ExitCode::SUCCESS)
+        return Ok(ExitCode::FAILURE);
     }
+    Ok(ExitCode::SUCCESS)
 }
 
 mod package_graph {
@@ -450,29 +494,6 @@ mod package_graph {
     }
 }
 
-/// The compiler may emit the same diagnostic more than once
-/// (for a variety of reasons). We use this helper to dedup them.
-struct DiagnosticReporter {
-    already_emitted: HashSet<String>,
-}
-
-impl DiagnosticReporter {
-    fn new() -> Self {
-        Self {
-            already_emitted: Default::default(),
-        }
-    }
-    fn print_report(&mut self, e: &miette::Report) {
-        let formatted = format!("{e:?}");
-        if self.already_emitted.contains(&formatted) {
-            // Avoid printing the same diagnostic multiple times.
-            return;
-        }
-        eprintln!("{formatted}");
-        self.already_emitted.insert(formatted);
-    }
-}
-
 fn use_color_on_stderr(color_profile: Color) -> bool {
     match color_profile {
         Color::Auto => supports_color::on(Stream::Stderr).is_some(),
@@ -588,3 +609,54 @@ fn cargo_fmt(project_dir: &Path) -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, generate};
+    use crate::diagnostic_reporter::{DiagnosticReporter, MessageFormat};
+    use std::process::ExitCode;
+
+    fn generate_from(blueprint: &str, format: MessageFormat) -> (ExitCode, serde_json::Value) {
+        let dir = tempfile::tempdir().unwrap();
+        let blueprint_path = dir.path().join("blueprint.ron");
+        fs_err::write(&blueprint_path, blueprint).unwrap();
+        let mut out = Vec::new();
+        let code = generate(
+            DiagnosticReporter::with_writer(format, &mut out),
+            blueprint_path,
+            "nightly".into(),
+            None,
+            dir.path().join("server_sdk"),
+            Color::Never,
+            false,
+            None,
+            false,
+        );
+        let output = std::str::from_utf8(&out).unwrap();
+        let [line] = output.lines().collect::<Vec<_>>().try_into().unwrap();
+        (code, serde_json::from_str(line).unwrap())
+    }
+
+    #[test]
+    fn failures_are_reported_as_json_diagnostics() {
+        let (code, message) = generate_from("not a blueprint", MessageFormat::Json);
+        assert_eq!(code, ExitCode::FAILURE);
+        assert_eq!(message["reason"], "compiler-message");
+        assert_eq!(message["message"]["level"], "error");
+        assert!(
+            message["message"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("Failed to deserialize the application blueprint")
+        );
+    }
+
+    #[test]
+    fn the_sarif_log_is_emitted_even_if_generation_fails() {
+        let (code, log) = generate_from("not a blueprint", MessageFormat::Sarif);
+        assert_eq!(code, ExitCode::FAILURE);
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["level"], "error");
+    }
+}
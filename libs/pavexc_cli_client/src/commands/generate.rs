@@ -1,6 +1,7 @@
 use std::{path::PathBuf, process::Command};
 
 use crate::commands::errors::{InvocationError, NonZeroExitCode, SignalTermination};
+use crate::config::MessageFormat;
 use pavex::blueprint::Blueprint;
 
 /// The configuration for `pavexc`'s `generate` command.
@@ -14,6 +15,7 @@ pub struct GenerateBuilder {
     blueprint: BlueprintArgument,
     output_directory: PathBuf,
     check: bool,
+    message_format: MessageFormat,
 }
 
 pub enum BlueprintArgument {
@@ -36,6 +38,7 @@ impl GenerateBuilder {
             cmd,
             output_directory,
             check: false,
+            message_format: MessageFormat::Human,
         }
     }
 
@@ -104,6 +107,15 @@ impl GenerateBuilder {
         if self.check {
             self.cmd.arg("--check");
         }
+        match self.message_format {
+            MessageFormat::Human => {}
+            MessageFormat::Json => {
+                self.cmd.arg("--message-format").arg("json");
+            }
+            MessageFormat::Sarif => {
+                self.cmd.arg("--message-format").arg("sarif");
+            }
+        }
         Ok(self.cmd)
     }
 
@@ -127,6 +139,16 @@ impl GenerateBuilder {
         self
     }
 
+    /// Set the format used by `pavexc` to report diagnostics.
+    ///
+    /// Machine-readable formats ([`MessageFormat::Json`] and [`MessageFormat::Sarif`])
+    /// are written to `stdout`.
+    /// If this is not set, diagnostics will be rendered for humans.
+    pub fn message_format(mut self, format: MessageFormat) -> Self {
+        self.message_format = format;
+        self
+    }
+
     /// Disable check mode.
     ///
     /// `pavexc` will regenerate the server SDK and update it on disk if it is outdated.
@@ -11,3 +11,15 @@ pub enum Color {
     /// Never use colors.
     Never,
 }
+
+/// Control the format used by `pavexc` to report diagnostics.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum MessageFormat {
+    /// Rendered for humans.
+    Human,
+    /// One JSON object per line, using the same `compiler-message` envelope of `cargo`'s JSON output.
+    Json,
+    /// A single SARIF v2.1.0 log.
+    Sarif,
+}
@@ -0,0 +1,20 @@
+use pavex::blueprint::Blueprint;
+use pavex::f;
+
+pub struct SessionStore;
+
+pub fn session_store() -> SessionStore {
+    todo!()
+}
+
+pub async fn delete_expired_sessions(_store: &SessionStore) {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.singleton(f!(crate::session_store));
+    // Every day at 03:30 UTC.
+    bp.cron("30 3 * * *", f!(crate::delete_expired_sessions));
+    bp
+}
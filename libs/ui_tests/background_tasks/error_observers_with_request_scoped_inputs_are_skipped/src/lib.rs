@@ -0,0 +1,40 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::request::RequestHead;
+use pavex::response::Response;
+use pavex::f;
+
+#[derive(Debug)]
+pub struct TaskError;
+
+impl std::fmt::Display for TaskError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The task failed")
+    }
+}
+
+impl std::error::Error for TaskError {}
+
+pub async fn task() -> Result<(), TaskError> {
+    todo!()
+}
+
+pub async fn request_observer(_head: &RequestHead, _e: &pavex::Error) {
+    todo!()
+}
+
+pub async fn singleton_observer(_e: &pavex::Error) {
+    todo!()
+}
+
+pub fn handler() -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.error_observer(f!(crate::request_observer));
+    bp.error_observer(f!(crate::singleton_observer));
+    bp.background_task(f!(crate::task));
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
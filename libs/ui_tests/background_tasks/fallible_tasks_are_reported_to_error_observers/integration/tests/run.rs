@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use app::Spy;
+use application::{ApplicationConfig, ApplicationState};
+
+#[tokio::test]
+async fn task_errors_are_reported_to_the_observers_in_scope() {
+    let spy = Spy::new();
+    let state = ApplicationState::new(ApplicationConfig {}, spy.clone())
+        .await
+        .unwrap();
+    let tasks = std::sync::Arc::new(state).start_background_tasks();
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while spy.get().len() < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("The task and its error observer were not invoked");
+    tasks.shutdown().await;
+
+    assert_eq!(spy.get(), vec!["task", "observer: The task failed"]);
+}
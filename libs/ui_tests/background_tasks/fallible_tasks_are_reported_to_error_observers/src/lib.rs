@@ -0,0 +1,54 @@
+use pavex::blueprint::Blueprint;
+use pavex::{f, t};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, Default)]
+pub struct Spy(Arc<Mutex<Vec<String>>>);
+
+impl Spy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, s: impl Into<String>) {
+        self.0.lock().unwrap().push(s.into());
+    }
+
+    pub fn get(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[derive(Debug)]
+pub struct TaskError;
+
+impl std::fmt::Display for TaskError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The task failed")
+    }
+}
+
+impl std::error::Error for TaskError {}
+
+pub async fn failing_task(spy: &Spy) -> Result<(), TaskError> {
+    spy.push("task");
+    Err(TaskError)
+}
+
+pub async fn observer(spy: &Spy, e: &pavex::Error) {
+    spy.push(format!("observer: {e}"));
+}
+
+pub async fn late_observer(spy: &Spy, _e: &pavex::Error) {
+    spy.push("late observer");
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.prebuilt(t!(crate::Spy));
+    bp.error_observer(f!(crate::observer));
+    bp.background_task(f!(crate::failing_task));
+    // Registered after the task: it isn't in scope for it.
+    bp.error_observer(f!(crate::late_observer));
+    bp
+}
@@ -0,0 +1,38 @@
+use pavex::background::ShutdownSignal;
+use pavex::blueprint::Blueprint;
+use pavex::f;
+use std::time::Duration;
+
+pub struct Cache;
+
+pub fn cache() -> Cache {
+    todo!()
+}
+
+#[derive(Debug)]
+pub struct RefreshError;
+
+impl std::fmt::Display for RefreshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to refresh the cache")
+    }
+}
+
+impl std::error::Error for RefreshError {}
+
+pub async fn refresh(_cache: &Cache) -> Result<(), RefreshError> {
+    todo!()
+}
+
+pub async fn listen(_cache: &Cache, _shutdown: ShutdownSignal) {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.singleton(f!(crate::cache));
+    bp.background_task(f!(crate::refresh))
+        .every(Duration::from_secs(60));
+    bp.background_task(f!(crate::listen));
+    bp
+}
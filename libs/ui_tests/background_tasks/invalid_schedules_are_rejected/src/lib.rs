@@ -0,0 +1,15 @@
+use pavex::blueprint::Blueprint;
+use pavex::f;
+use std::time::Duration;
+
+pub async fn task() {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.cron("61 * * * *", f!(crate::task));
+    bp.background_task(f!(crate::task))
+        .every(Duration::ZERO);
+    bp
+}
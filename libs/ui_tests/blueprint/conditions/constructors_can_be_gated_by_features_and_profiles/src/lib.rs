@@ -0,0 +1,41 @@
+use pavex::blueprint::{Blueprint, condition::Condition, router::GET};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct PaymentsClient(&'static str);
+
+pub fn payments_client() -> PaymentsClient {
+    PaymentsClient("real")
+}
+
+pub fn fake_payments_client() -> PaymentsClient {
+    PaymentsClient("fake")
+}
+
+pub struct Mailer(&'static str);
+
+pub fn mailer() -> Mailer {
+    Mailer("smtp")
+}
+
+pub fn test_mailer() -> Mailer {
+    Mailer("in-memory")
+}
+
+pub fn handler(client: &PaymentsClient, mailer: &Mailer) -> Response {
+    Response::ok().set_typed_body(format!("{} payments, {} mailer", client.0, mailer.0))
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    let fake = Condition::feature("fake-payments");
+    bp.request_scoped(f!(crate::payments_client))
+        .only_if(!fake.clone());
+    bp.request_scoped(f!(crate::fake_payments_client))
+        .only_if(fake);
+    let test = Condition::profile("test");
+    bp.request_scoped(f!(crate::mailer)).only_if(!test.clone());
+    bp.request_scoped(f!(crate::test_mailer)).only_if(test);
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,22 @@
+use pavex::blueprint::{Blueprint, condition::Condition};
+use pavex::f;
+
+pub struct Client;
+
+pub fn client() -> Client {
+    Client
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    // 2^5 feature combinations × (1 profile + the default one) = 64 variants.
+    bp.singleton(f!(crate::client)).only_if(Condition::all([
+        Condition::feature("a"),
+        Condition::feature("b"),
+        Condition::feature("c"),
+        Condition::feature("d"),
+        Condition::feature("e"),
+        Condition::profile("test"),
+    ]));
+    bp
+}
@@ -0,0 +1,17 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::response::Response;
+use pavex::{f, t};
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct LogConfig;
+
+pub fn handler() -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.config("log", t!(crate::LogConfig)).reloadable();
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
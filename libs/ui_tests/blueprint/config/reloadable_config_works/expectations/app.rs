@@ -0,0 +1,270 @@
+//! Do NOT edit this code.
+//! It was automatically generated by Pavex.
+//! All manual edits will be lost next time the code is generated.
+extern crate alloc;
+struct ServerState {
+    router: Router,
+    application_state: ApplicationState,
+}
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ApplicationConfig {
+    pub log: app::LogConfig,
+    pub rate_limits: app::RateLimits,
+    pub unused: app::Unused,
+}
+pub struct ApplicationState {
+    pub rate_limiter: app::RateLimiter,
+    pub reloadable_log_config: pavex::config::Reloadable<app::LogConfig>,
+    pub reloadable_rate_limits: pavex::config::Reloadable<app::RateLimits>,
+    pub reloadable_unused: pavex::config::Reloadable<app::Unused>,
+}
+impl ApplicationState {
+    pub async fn new(
+        app_config: crate::ApplicationConfig,
+    ) -> Result<crate::ApplicationState, crate::ApplicationStateError> {
+        Ok(
+            Self::_new(
+                    <pavex::config::Reloadable<
+                        app::LogConfig,
+                    >>::new(app_config.log),
+                    <pavex::config::Reloadable<
+                        app::RateLimits,
+                    >>::new(app_config.rate_limits),
+                    <pavex::config::Reloadable<
+                        app::Unused,
+                    >>::new(app_config.unused),
+                )
+                .await,
+        )
+    }
+    async fn _new(
+        v0: pavex::config::Reloadable<app::LogConfig>,
+        v1: pavex::config::Reloadable<app::RateLimits>,
+        v2: pavex::config::Reloadable<app::Unused>,
+    ) -> crate::ApplicationState {
+        let v3 = app::RateLimiter::new(&v1);
+        crate::ApplicationState {
+            rate_limiter: v3,
+            reloadable_log_config: v0,
+            reloadable_rate_limits: v1,
+            reloadable_unused: v2,
+        }
+    }
+}
+#[deprecated(note = "Use `ApplicationState::new` instead.")]
+pub async fn build_application_state(
+    app_config: crate::ApplicationConfig,
+) -> Result<crate::ApplicationState, crate::ApplicationStateError> {
+    crate::ApplicationState::new(app_config).await
+}
+/// Update the values of reloadable configuration types while the application is running.
+///
+/// Retrieve it via [`ApplicationState::config_reloader`].
+#[derive(Clone)]
+pub struct ConfigReloader {
+    reloadable_log_config: pavex::config::Reloadable<app::LogConfig>,
+    reloadable_rate_limits: pavex::config::Reloadable<app::RateLimits>,
+    reloadable_unused: pavex::config::Reloadable<app::Unused>,
+}
+impl ConfigReloader {
+    /// Replace the values of all reloadable configuration types with the ones
+    /// in `config`.
+    ///
+    /// Values for configuration types that are not reloadable are ignored.
+    pub fn reload(&self, config: crate::ApplicationConfig) {
+        self.reloadable_log_config.update(config.log);
+        self.reloadable_rate_limits.update(config.rate_limits);
+        self.reloadable_unused.update(config.unused);
+    }
+}
+impl ApplicationState {
+    /// Get a [`ConfigReloader`] for this application state.
+    ///
+    /// All reloaders share the same underlying values with the application state.
+    pub fn config_reloader(&self) -> ConfigReloader {
+        ConfigReloader {
+            reloadable_log_config: self.reloadable_log_config.clone(),
+            reloadable_rate_limits: self.reloadable_rate_limits.clone(),
+            reloadable_unused: self.reloadable_unused.clone(),
+        }
+    }
+}
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationStateError {}
+pub fn run(
+    server_builder: pavex::server::Server,
+    application_state: ApplicationState,
+) -> pavex::server::ServerHandle {
+    async fn handler(
+        request: http::Request<hyper::body::Incoming>,
+        connection_info: Option<pavex::connection::ConnectionInfo>,
+        server_state: std::sync::Arc<ServerState>,
+    ) -> pavex::response::Response {
+        let (router, state) = (&server_state.router, &server_state.application_state);
+        router.route(request, connection_info, state).await
+    }
+    let router = Router::new();
+    let server_state = std::sync::Arc::new(ServerState {
+        router,
+        application_state,
+    });
+    server_builder.serve(handler, server_state)
+}
+struct Router {
+    router: matchit::Router<u32>,
+}
+impl Router {
+    /// Create a new router instance.
+    ///
+    /// This method is invoked once, when the server starts.
+    pub fn new() -> Self {
+        Self { router: Self::router() }
+    }
+    fn router() -> matchit::Router<u32> {
+        let mut router = matchit::Router::new();
+        router.insert("/", 0u32).unwrap();
+        router
+    }
+    pub async fn route(
+        &self,
+        request: http::Request<hyper::body::Incoming>,
+        _connection_info: Option<pavex::connection::ConnectionInfo>,
+        #[allow(unused)]
+        state: &ApplicationState,
+    ) -> pavex::response::Response {
+        let (request_head, _) = request.into_parts();
+        let request_head: pavex::request::RequestHead = request_head.into();
+        let Ok(matched_route) = self.router.at(&request_head.target.path()) else {
+            let allowed_methods: pavex::router::AllowedMethods = pavex::router::MethodAllowList::from_iter(
+                    vec![],
+                )
+                .into();
+            return route_1::entrypoint(&allowed_methods).await;
+        };
+        match matched_route.value {
+            0u32 => {
+                match &request_head.method {
+                    &pavex::http::Method::GET => {
+                        route_0::entrypoint(
+                                &state.rate_limiter,
+                                &state.reloadable_log_config,
+                            )
+                            .await
+                    }
+                    _ => {
+                        let allowed_methods: pavex::router::AllowedMethods = pavex::router::MethodAllowList::from_iter([
+                                pavex::http::Method::GET,
+                            ])
+                            .into();
+                        route_1::entrypoint(&allowed_methods).await
+                    }
+                }
+            }
+            i => unreachable!("Unknown route id: {}", i),
+        }
+    }
+}
+pub mod route_0 {
+    pub async fn entrypoint<'a, 'b>(
+        s_0: &'a app::RateLimiter,
+        s_1: &'b pavex::config::Reloadable<app::LogConfig>,
+    ) -> pavex::response::Response {
+        let response = wrapping_0(s_0, s_1).await;
+        response
+    }
+    async fn stage_1<'a, 'b>(
+        s_0: &'a pavex::config::Reloadable<app::LogConfig>,
+        s_1: &'b app::RateLimiter,
+    ) -> pavex::response::Response {
+        let response = handler(s_0, s_1).await;
+        response
+    }
+    async fn wrapping_0(
+        v0: &app::RateLimiter,
+        v1: &pavex::config::Reloadable<app::LogConfig>,
+    ) -> pavex::response::Response {
+        let v2 = crate::route_0::Next0 {
+            s_0: v1,
+            s_1: v0,
+            next: stage_1,
+        };
+        let v3 = pavex::middleware::Next::new(v2);
+        let v4 = pavex::middleware::wrap_noop(v3).await;
+        <pavex::response::Response as pavex::response::IntoResponse>::into_response(v4)
+    }
+    async fn handler(
+        v0: &pavex::config::Reloadable<app::LogConfig>,
+        v1: &app::RateLimiter,
+    ) -> pavex::response::Response {
+        let v2 = pavex::config::Reloadable::current(v0);
+        let v3 = app::handler(&v2, v1);
+        <pavex::response::Response as pavex::response::IntoResponse>::into_response(v3)
+    }
+    struct Next0<'a, 'b, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        s_0: &'a pavex::config::Reloadable<app::LogConfig>,
+        s_1: &'b app::RateLimiter,
+        next: fn(
+            &'a pavex::config::Reloadable<app::LogConfig>,
+            &'b app::RateLimiter,
+        ) -> T,
+    }
+    impl<'a, 'b, T> std::future::IntoFuture for Next0<'a, 'b, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        type Output = pavex::response::Response;
+        type IntoFuture = T;
+        fn into_future(self) -> Self::IntoFuture {
+            (self.next)(self.s_0, self.s_1)
+        }
+    }
+}
+pub mod route_1 {
+    pub async fn entrypoint<'a>(
+        s_0: &'a pavex::router::AllowedMethods,
+    ) -> pavex::response::Response {
+        let response = wrapping_0(s_0).await;
+        response
+    }
+    async fn stage_1<'a>(
+        s_0: &'a pavex::router::AllowedMethods,
+    ) -> pavex::response::Response {
+        let response = handler(s_0).await;
+        response
+    }
+    async fn wrapping_0(
+        v0: &pavex::router::AllowedMethods,
+    ) -> pavex::response::Response {
+        let v1 = crate::route_1::Next0 {
+            s_0: v0,
+            next: stage_1,
+        };
+        let v2 = pavex::middleware::Next::new(v1);
+        let v3 = pavex::middleware::wrap_noop(v2).await;
+        <pavex::response::Response as pavex::response::IntoResponse>::into_response(v3)
+    }
+    async fn handler(v0: &pavex::router::AllowedMethods) -> pavex::response::Response {
+        let v1 = pavex::router::default_fallback(v0).await;
+        <pavex::response::Response as pavex::response::IntoResponse>::into_response(v1)
+    }
+    struct Next0<'a, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        s_0: &'a pavex::router::AllowedMethods,
+        next: fn(&'a pavex::router::AllowedMethods) -> T,
+    }
+    impl<'a, T> std::future::IntoFuture for Next0<'a, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        type Output = pavex::response::Response;
+        type IntoFuture = T;
+        fn into_future(self) -> Self::IntoFuture {
+            (self.next)(self.s_0)
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::config::Reloadable;
+use pavex::response::Response;
+use pavex::{f, t};
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct LogConfig;
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct RateLimits;
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Unused;
+
+pub struct RateLimiter;
+
+impl RateLimiter {
+    pub fn new(_limits: &Reloadable<RateLimits>) -> Self {
+        todo!()
+    }
+}
+
+pub fn handler(_log: &LogConfig, _limiter: &RateLimiter) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.config("log", t!(crate::LogConfig)).reloadable();
+    bp.config("rate_limits", t!(crate::RateLimits)).reloadable();
+    // Never used, but `ConfigReloader` can still update it.
+    bp.config("unused", t!(crate::Unused)).reloadable();
+    bp.singleton(f!(crate::RateLimiter::new));
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
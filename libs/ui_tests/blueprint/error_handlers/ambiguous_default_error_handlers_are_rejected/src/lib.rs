@@ -0,0 +1,41 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::response::Response;
+use pavex::{f, from};
+
+#[derive(Debug)]
+pub struct CustomError;
+
+pub mod errors {
+    use pavex::response::Response;
+
+    #[pavex::error_handler]
+    pub fn handle_error(_e: &super::CustomError) -> Response {
+        todo!()
+    }
+
+    #[pavex::error_handler]
+    pub fn handle_error_again(_e: &super::CustomError) -> Response {
+        todo!()
+    }
+}
+
+pub fn fallible_constructor() -> Result<String, CustomError> {
+    todo!()
+}
+
+pub fn another_fallible_constructor() -> Result<u64, CustomError> {
+    todo!()
+}
+
+pub fn handler(_s: String, _n: u64) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.import(from![crate::errors]);
+    bp.request_scoped(f!(crate::fallible_constructor));
+    bp.request_scoped(f!(crate::another_fallible_constructor));
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
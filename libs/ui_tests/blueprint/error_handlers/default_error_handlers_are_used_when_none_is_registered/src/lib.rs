@@ -0,0 +1,60 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::response::Response;
+use pavex::{f, from};
+
+#[derive(Debug)]
+pub struct CustomError;
+
+#[derive(Debug)]
+pub struct AnotherError;
+
+pub mod errors {
+    use pavex::response::Response;
+
+    #[pavex::error_handler]
+    pub fn handle_custom_error(_e: &super::CustomError) -> Response {
+        todo!()
+    }
+
+    #[pavex::error_handler]
+    pub fn handle_another_error(_e: &super::AnotherError) -> Response {
+        todo!()
+    }
+}
+
+pub mod routes {
+    use pavex::response::Response;
+
+    #[pavex::get(path = "/imported")]
+    pub fn imported(_s: String) -> Result<Response, super::CustomError> {
+        todo!()
+    }
+}
+
+pub fn fallible_constructor() -> Result<String, CustomError> {
+    todo!()
+}
+
+pub fn explicitly_handled() -> Result<u64, AnotherError> {
+    todo!()
+}
+
+pub fn explicit_error_handler(_e: &AnotherError) -> Response {
+    todo!()
+}
+
+pub fn handler(_s: String, _n: u64) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.import(from![crate::errors, crate::routes]);
+    // No error handler: the default one for `CustomError` is used.
+    bp.request_scoped(f!(crate::fallible_constructor));
+    // The explicit error handler takes precedence over the default one.
+    bp.request_scoped(f!(crate::explicitly_handled))
+        .error_handler(f!(crate::explicit_error_handler));
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
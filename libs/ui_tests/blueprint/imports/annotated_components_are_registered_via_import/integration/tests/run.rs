@@ -0,0 +1,28 @@
+use std::net::TcpListener;
+
+use application::{ApplicationConfig, ApplicationState, run};
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = ApplicationState::new(ApplicationConfig {}).await.unwrap();
+    tokio::task::spawn(async move { run(server, application_state).await });
+    port
+}
+
+#[tokio::test]
+async fn imported_components_are_wired_together() {
+    let port = spawn_test_server().await;
+    let response = reqwest::get(&format!("http://localhost:{port}/"))
+        .await
+        .expect("Failed to make request");
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["x-imported"], "yes");
+    assert_eq!(response.text().await.unwrap(), "Hello, world!");
+}
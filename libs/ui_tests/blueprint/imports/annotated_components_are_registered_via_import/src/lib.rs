@@ -0,0 +1,44 @@
+use pavex::blueprint::Blueprint;
+use pavex::from;
+use pavex::http::{HeaderName, HeaderValue};
+use pavex::response::Response;
+
+#[derive(Clone)]
+pub struct Greeter {
+    greeting: String,
+}
+
+impl Greeter {
+    #[pavex::singleton]
+    pub fn new() -> Self {
+        Self {
+            greeting: "Hello".into(),
+        }
+    }
+}
+
+pub struct Name(String);
+
+#[pavex::request_scoped]
+pub fn name() -> Name {
+    Name("world".into())
+}
+
+#[pavex::get(path = "/")]
+pub fn greet(greeter: &Greeter, name: &Name) -> Response {
+    Response::ok().set_typed_body(format!("{}, {}!", greeter.greeting, name.0))
+}
+
+#[pavex::post_process]
+pub fn tag(response: Response) -> Response {
+    response.insert_header(
+        HeaderName::from_static("x-imported"),
+        HeaderValue::from_static("yes"),
+    )
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.import(from![crate]);
+    bp
+}
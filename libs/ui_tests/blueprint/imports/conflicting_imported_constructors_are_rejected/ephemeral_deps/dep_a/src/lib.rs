@@ -0,0 +1,6 @@
+pub struct Logger;
+
+#[pavex::request_scoped]
+pub fn logger() -> Logger {
+    todo!()
+}
@@ -0,0 +1,6 @@
+use dep_a_8ebd28bf::Logger;
+
+#[pavex::request_scoped]
+pub fn another_logger() -> Logger {
+    todo!()
+}
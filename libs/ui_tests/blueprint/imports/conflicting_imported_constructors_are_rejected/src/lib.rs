@@ -0,0 +1,15 @@
+use dep_a_8ebd28bf::Logger;
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::response::Response;
+use pavex::{f, from};
+
+pub fn handler(_logger: Logger) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.import(from![dep_a_8ebd28bf, dep_b_8ebd28bf]);
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,6 @@
+use dep_a_a2c92c25::Logger;
+
+#[pavex::request_scoped]
+pub fn another_logger() -> Logger {
+    todo!()
+}
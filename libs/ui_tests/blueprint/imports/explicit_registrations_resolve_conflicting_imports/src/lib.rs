@@ -0,0 +1,17 @@
+use dep_a_a2c92c25::Logger;
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::response::Response;
+use pavex::{f, from};
+
+pub fn handler(_logger: Logger) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.import(from![dep_a_a2c92c25, dep_b_a2c92c25]);
+    // Explicit registrations take precedence over imported ones.
+    bp.request_scoped(f!(dep_b_a2c92c25::another_logger));
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
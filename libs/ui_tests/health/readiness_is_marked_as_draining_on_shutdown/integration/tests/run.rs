@@ -0,0 +1,40 @@
+use std::net::TcpListener;
+use std::time::Duration;
+
+use application::{ApplicationConfig, ApplicationState, run};
+use pavex::health::Readiness;
+use pavex::server::{ServerHandle, ShutdownMode};
+
+async fn spawn_test_server(readiness: Readiness) -> (ServerHandle, u16) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = ApplicationState::new(ApplicationConfig {}, readiness)
+        .await
+        .unwrap();
+    (run(server, application_state), port)
+}
+
+#[tokio::test]
+async fn readiness_is_marked_as_draining_on_shutdown() {
+    let readiness = Readiness::new();
+    let (handle, port) = spawn_test_server(readiness.clone()).await;
+
+    let response = reqwest::get(&format!("http://localhost:{port}/health/ready"))
+        .await
+        .expect("Failed to make request");
+    assert!(response.status().is_success());
+    assert!(!readiness.is_draining());
+
+    handle
+        .shutdown(ShutdownMode::Graceful {
+            timeout: Duration::from_secs(1),
+        })
+        .await;
+    assert!(readiness.is_draining());
+}
@@ -0,0 +1,18 @@
+use pavex::blueprint::{Blueprint, constructor::Constructor};
+use pavex::health::{HealthChecks, HealthKit};
+use pavex::{f, t};
+
+pub fn health_checks() -> HealthChecks {
+    HealthChecks::new().register("noop", || async { Ok::<_, std::io::Error>(()) })
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    let mut kit = HealthKit::new();
+    kit.health_checks = Some(Constructor::singleton(f!(crate::health_checks)));
+    // The test needs a handle on the `Readiness` instance used by the server.
+    kit.readiness = None;
+    kit.register(&mut bp);
+    bp.prebuilt(t!(pavex::health::Readiness));
+    bp
+}
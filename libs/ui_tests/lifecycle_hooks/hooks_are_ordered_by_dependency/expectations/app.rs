@@ -0,0 +1,199 @@
+//! Do NOT edit this code.
+//! It was automatically generated by Pavex.
+//! All manual edits will be lost next time the code is generated.
+extern crate alloc;
+struct ServerState {
+    router: Router,
+    application_state: ApplicationState,
+}
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ApplicationConfig {}
+pub struct ApplicationState {
+    pub pool: app::Pool,
+    pub repo: app::Repo,
+    pub spy: app::Spy,
+}
+impl ApplicationState {
+    pub async fn new(
+        _app_config: crate::ApplicationConfig,
+        v0: app::Spy,
+    ) -> Result<crate::ApplicationState, crate::ApplicationStateError> {
+        let state = Self::_new(v0).await;
+        state._on_startup().await?;
+        Ok(state)
+    }
+    async fn _new(v0: app::Spy) -> crate::ApplicationState {
+        let v1 = app::Pool::new();
+        let v2 = app::Repo::new(&v1);
+        crate::ApplicationState {
+            pool: v1,
+            repo: v2,
+            spy: v0,
+        }
+    }
+}
+#[deprecated(note = "Use `ApplicationState::new` instead.")]
+pub async fn build_application_state(
+    _app_config: crate::ApplicationConfig,
+    v0: app::Spy,
+) -> Result<crate::ApplicationState, crate::ApplicationStateError> {
+    crate::ApplicationState::new(_app_config, v0).await
+}
+impl ApplicationState {
+    async fn _on_startup(&self) -> Result<(), crate::ApplicationStateError> {
+        pavex::lifecycle::run_hook(
+                "app::ping_pool",
+                async { app::ping_pool(&self.pool, &self.spy).await },
+            )
+            .await;
+        pavex::lifecycle::run_hook(
+                "app::warm_repo",
+                async { app::warm_repo(&self.repo, &self.spy).await },
+            )
+            .await;
+        Ok(())
+    }
+    /// Invoke the shutdown hooks registered against the application blueprint.
+    ///
+    /// All hooks are invoked, even if some of them fail.
+    /// Failures are logged, and the first one is returned.
+    pub async fn shutdown(&self) -> Result<(), crate::ApplicationStateError> {
+        #[allow(unused_mut)]
+        let mut outcome = Ok(());
+        pavex::lifecycle::run_hook(
+                "app::flush_repo",
+                async { app::flush_repo(&self.repo, &self.spy).await },
+            )
+            .await;
+        if let Err(e) = async {
+            pavex::lifecycle::run_hook_with_timeout(
+                    "app::close_pool",
+                    std::time::Duration::new(1u64, 0u32),
+                    async { app::close_pool(&self.pool, &self.spy).await },
+                )
+                .await
+                .map_err(crate::ApplicationStateError::HookTimeout)?;
+            Ok::<(), crate::ApplicationStateError>(())
+        }
+            .await
+        {
+            pavex::lifecycle::log_shutdown_error("app::close_pool", &e);
+            if outcome.is_ok() {
+                outcome = Err(e);
+            }
+        }
+        outcome
+    }
+}
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationStateError {
+    #[error(transparent)]
+    HookTimeout(pavex::lifecycle::HookTimeoutError),
+}
+pub fn run(
+    server_builder: pavex::server::Server,
+    application_state: ApplicationState,
+) -> pavex::server::ServerHandle {
+    async fn handler(
+        request: http::Request<hyper::body::Incoming>,
+        connection_info: Option<pavex::connection::ConnectionInfo>,
+        server_state: std::sync::Arc<ServerState>,
+    ) -> pavex::response::Response {
+        let (router, state) = (&server_state.router, &server_state.application_state);
+        router.route(request, connection_info, state).await
+    }
+    let router = Router::new();
+    let server_state = std::sync::Arc::new(ServerState {
+        router,
+        application_state,
+    });
+    let server_builder = {
+        let server_state = server_state.clone();
+        server_builder
+            .on_shutdown(move || async move {
+                let _ = server_state.application_state.shutdown().await;
+            })
+    };
+    server_builder.serve(handler, server_state)
+}
+struct Router {
+    router: matchit::Router<u32>,
+}
+impl Router {
+    /// Create a new router instance.
+    ///
+    /// This method is invoked once, when the server starts.
+    pub fn new() -> Self {
+        Self { router: Self::router() }
+    }
+    fn router() -> matchit::Router<u32> {
+        let router = matchit::Router::new();
+        router
+    }
+    pub async fn route(
+        &self,
+        request: http::Request<hyper::body::Incoming>,
+        _connection_info: Option<pavex::connection::ConnectionInfo>,
+        #[allow(unused)]
+        state: &ApplicationState,
+    ) -> pavex::response::Response {
+        let (request_head, _) = request.into_parts();
+        let request_head: pavex::request::RequestHead = request_head.into();
+        let Ok(matched_route) = self.router.at(&request_head.target.path()) else {
+            let allowed_methods: pavex::router::AllowedMethods = pavex::router::MethodAllowList::from_iter(
+                    vec![],
+                )
+                .into();
+            return route_0::entrypoint(&allowed_methods).await;
+        };
+        match matched_route.value {
+            i => unreachable!("Unknown route id: {}", i),
+        }
+    }
+}
+pub mod route_0 {
+    pub async fn entrypoint<'a>(
+        s_0: &'a pavex::router::AllowedMethods,
+    ) -> pavex::response::Response {
+        let response = wrapping_0(s_0).await;
+        response
+    }
+    async fn stage_1<'a>(
+        s_0: &'a pavex::router::AllowedMethods,
+    ) -> pavex::response::Response {
+        let response = handler(s_0).await;
+        response
+    }
+    async fn wrapping_0(
+        v0: &pavex::router::AllowedMethods,
+    ) -> pavex::response::Response {
+        let v1 = crate::route_0::Next0 {
+            s_0: v0,
+            next: stage_1,
+        };
+        let v2 = pavex::middleware::Next::new(v1);
+        let v3 = pavex::middleware::wrap_noop(v2).await;
+        <pavex::response::Response as pavex::response::IntoResponse>::into_response(v3)
+    }
+    async fn handler(v0: &pavex::router::AllowedMethods) -> pavex::response::Response {
+        let v1 = pavex::router::default_fallback(v0).await;
+        <pavex::response::Response as pavex::response::IntoResponse>::into_response(v1)
+    }
+    struct Next0<'a, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        s_0: &'a pavex::router::AllowedMethods,
+        next: fn(&'a pavex::router::AllowedMethods) -> T,
+    }
+    impl<'a, T> std::future::IntoFuture for Next0<'a, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        type Output = pavex::response::Response;
+        type IntoFuture = T;
+        fn into_future(self) -> Self::IntoFuture {
+            (self.next)(self.s_0)
+        }
+    }
+}
\ No newline at end of file
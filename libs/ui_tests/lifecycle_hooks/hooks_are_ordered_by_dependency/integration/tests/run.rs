@@ -0,0 +1,17 @@
+use app::Spy;
+use application::{ApplicationConfig, ApplicationState};
+
+#[tokio::test]
+async fn hooks_are_invoked_in_dependency_order() {
+    let spy = Spy::new();
+    let state = ApplicationState::new(ApplicationConfig {}, spy.clone())
+        .await
+        .unwrap();
+    assert_eq!(spy.get(), vec!["ping_pool", "warm_repo"]);
+
+    state.shutdown().await.unwrap();
+    assert_eq!(
+        spy.get(),
+        vec!["ping_pool", "warm_repo", "flush_repo", "close_pool"]
+    );
+}
@@ -0,0 +1,70 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use pavex::blueprint::{Blueprint, constructor::Lifecycle};
+use pavex::{f, t};
+
+#[derive(Clone, Debug, Default)]
+pub struct Spy(Arc<Mutex<Vec<String>>>);
+
+impl Spy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, s: impl Into<String>) {
+        self.0.lock().unwrap().push(s.into());
+    }
+
+    pub fn get(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[derive(Clone)]
+pub struct Pool;
+
+impl Pool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Clone)]
+pub struct Repo;
+
+impl Repo {
+    pub fn new(_pool: &Pool) -> Self {
+        Self
+    }
+}
+
+pub async fn warm_repo(_repo: &Repo, spy: &Spy) {
+    spy.push("warm_repo");
+}
+
+pub async fn ping_pool(_pool: &Pool, spy: &Spy) {
+    spy.push("ping_pool");
+}
+
+pub async fn close_pool(_pool: &Pool, spy: &Spy) {
+    spy.push("close_pool");
+}
+
+pub async fn flush_repo(_repo: &Repo, spy: &Spy) {
+    spy.push("flush_repo");
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.prebuilt(t!(crate::Spy));
+    bp.constructor(f!(crate::Pool::new), Lifecycle::Singleton);
+    bp.constructor(f!(crate::Repo::new), Lifecycle::Singleton);
+    // Registered in the "wrong" order, on purpose.
+    bp.on_startup(f!(crate::warm_repo));
+    bp.on_startup(f!(crate::ping_pool));
+    bp.on_shutdown(f!(crate::close_pool))
+        .timeout(Duration::from_secs(1));
+    bp.on_shutdown(f!(crate::flush_repo));
+    bp
+}